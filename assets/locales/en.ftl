@@ -0,0 +1,445 @@
+# Kaspeak interface strings, English.
+# Format: key = value; "{ $name }" is replaced with an argument; indented lines continue the value.
+
+## Option lists
+ttl-forever = Keep forever
+ttl-days = Expire in { $n }d
+ttl-hours = Expire in { $n }h
+ttl-minutes = Expire in { $n }m
+fee-bump-never = Never
+bitrate-kbps = { $n } kbps
+rotation-keep = Keep address
+rotation-weeks = New every { $n }w
+rotation-days = New every { $n }d
+rotation-hours = New every { $n }h
+backfill-none = No history
+backfill-hours = Last { $n }h
+backfill-minutes = Last { $n } min
+
+## Wallet and backup status
+send-enter-amount = Enter an amount
+send-not-enough-funds = Not enough funds
+send-in-progress = Sending { $amount }…
+send-failed = Send failed
+copied-to-clipboard = Copied to clipboard
+saved-to = Saved to { $path }
+export-failed = Export failed
+
+## Toasts
+toast-incoming-funds = 💰 Received { $amount }
+toast-node-failover = 🔀 Node failed, switched to { $url }
+toast-rpc-timeout = ⏱ Node did not answer the { $request } request within { $secs } s
+toast-newer-protocol = ⬆ Clients with protocol v{ $version } are on the network, an upgrade may be needed
+toast-insufficient-funds = ⚠ Insufficient funds: top up the wallet or wait for the airdrop
+toast-tx-rejected = ⚠ Node rejected the transaction: { $reason }
+toast-send-failed = ⚠ Send failed: { $reason }
+toast-connect-failed = ⚠ Could not connect to the node: { $reason }
+toast-decode-failed = ⚠ Could not decode a received message (tx { $tx_id })
+
+## Developer console
+console-placeholder = Type 'help' and press Enter
+console-payload-inspector = Payload inspector
+console-waiting-payloads = Waiting for payloads…
+
+## Top bar
+select-input-device = Select Input Device
+node-address-placeholder = Enter node address (Optional)
+connect = Connect
+
+## Common actions
+block-sender = Block sender
+ignore-user = Hide messages in chat
+cancel = Cancel
+copy = Copy
+save = Save
+send = Send
+name-placeholder = Name
+claim-other = Name is claimed by another key
+
+## Clients and transactions
+clients-title = Clients ({ $count })
+last-seen = last seen { $ago } ago
+elapsed-seconds = { $value }s
+elapsed-minutes = { $value }m
+elapsed-hours = { $value }h
+elapsed-days = { $value }d
+request-avatar = Request avatar
+unmute-voice = Unmute voice
+unignore-user = Show messages in chat
+mute-voice = Mute voice
+dedupe-window = Dedupe window
+dedupe-window-hint = Transactions are remembered for this many DAA score units
+    (about 10 units per second on TN10)
+transactions = Transactions
+export-csv = Export CSV
+transaction-summary = { $amount } · fee { $fee } · { $size } B
+
+## Recorder
+stop-recording = Stop Recording
+start-recording = Start Recording
+listen-self = Listen to yourself
+mute-all = Mute All
+pause-incoming = Pause incoming
+pause-incoming-hint = Stays connected, but skips incoming messages and voice until resumed
+announce-speakers = Announce new speakers
+comfort-noise = Comfort noise
+comfort-noise-hint = Fills gaps between voice fragments with quiet noise
+voice-batching = Batch voice fragments
+voice-batching-hint = Sends up to { $n } fragments per transaction: lower fees, slightly more delay
+max-speakers = Max speakers
+over-limit-speakers = 🔇 Over limit: { $names }
+stream-stats = 📶 Stream stats
+stream-loss = { $name }: lost { $lost } ({ $percent }%)
+stream-details = Streams: { $streams }
+    Received: { $received }
+    Lost: { $lost }
+    Duplicates: { $duplicates }
+    Reordered: { $reordered }
+
+## Field labels
+label-address = 📍 Address:
+label-name = 🪪 Name:
+label-key = 🔑 Key:
+label-utxos = ↕️ UTXO's:
+label-balance = 💵 Balance:
+label-send-funds = 💸 Send funds:
+label-contacts = 📇 Contacts:
+label-blocked = 🚫 Blocked:
+label-ignored = 🙈 Ignored in chat:
+label-content-filter = 🧹 Content filter:
+label-fee = 🧾 Fee:
+label-session-cost = 💸 Session cost:
+label-network = 🌐 Network:
+label-derivation = 🧬 Derivation:
+label-faucet = 🚰 Faucet:
+label-nodes = 🖧 Nodes:
+label-profile = 👤 Profile:
+label-backup = 🔐 Backup:
+label-avatar = 🖼 Avatar:
+label-spending-limits = 🛑 Spending limits
+
+## Account
+new-address = New address
+new-address-hint = Receive on a fresh address so new activity is not linked to the old one
+incoming-funds-sound = Sound on incoming funds
+mute-notifications = Mute notifications
+open-permalink-placeholder = Open kaspeak:// link
+balance-details = Available: { $available }
+    Pending: { $pending }
+balance-outgoing = Outgoing: { $amount }
+balance-pending =  / { $amount } pending
+contact-placeholder = Contact
+address-placeholder = Address
+amount-placeholder = Amount ({ $unit })
+
+## Message signing
+sign-verify = ✍️ Sign / verify
+message-to-sign = Message to sign
+sign = Sign
+signed-by = Signed by { $address }
+signer-address = Signer address
+signed-message = Signed message
+signature-hex = Signature (hex)
+verify = Verify
+valid-signature = ✓ Valid signature
+
+## Contacts and blocklist
+contact-target-placeholder = Address or identity key
+contact-note-placeholder = Note
+nobody-blocked = Nobody is blocked
+unblock = Unblock
+nobody-ignored = Nobody is ignored
+unignore = Show
+filter-off = Off
+filter-mask = Mask
+filter-hide = Hide
+filter-inherit = As everywhere
+filter-channel = In channel #{ $channel }
+filter-pattern-placeholder = Word or /regex/
+filter-hint = Words match whole and ignore case. Your own messages are never filtered.
+
+## Fees and spending
+fee-bump = Bump stuck messages
+fee-bump-hint = Resends a message with a higher fee if it is not in a block after { $secs } s
+fee-placeholder = Fee ({ $unit })
+fee-per-message = ≈ { $amount } per message
+fee-node-rates = Node feerate: { $low } / { $normal } / { $priority } sompi per gram
+fee-no-estimate = No estimate from the node yet, using { $amount }
+compound-above = Compound above
+dust-below = Dust below
+dust-hint = Sweeps outputs under { $amount } once { $count } pile up
+dust-placeholder = Dust ({ $unit })
+per-hour = Per hour
+per-day = Per day
+spend-limit-row = { $label } ({ $spent } spent)
+no-limit-placeholder = No limit ({ $unit })
+session-cost-details = { $transactions } transaction(s), { $bytes } bytes of payload
+channel-cost = Channel { $channel }: { $fees } in { $transactions } transaction(s)
+
+## Network and node
+mainnet-warning = ⚠ Mainnet: every message and voice fragment spends real KAS
+mainnet-confirm = { $network } uses real funds. Each message is a transaction paid from your balance and stays on-chain forever. Make sure your recovery phrase is backed up. Switch and restart?
+switch-to-mainnet = Switch to mainnet
+network-switch-hint = Switching networks restarts the app
+account-index = Account #
+derivation-current = Current path: { $path }
+derivation-apply-hint = Addresses change, the app restarts
+apply = Apply
+account-index-invalid = Account index must be a whole number
+faucet-mnemonic-placeholder = Funding account recovery phrase
+faucet-no-builtin = No built-in faucet on { $network }
+faucet-saved = Faucet set to { $kind }
+nodes-empty = No nodes listed, the public resolver picks one
+add = Add
+auto-reconnect = Reconnect automatically
+backfill = Channel history on connect
+connect-timeout = Connect timeout, s
+request-timeout = Request timeout, s
+request-retries = Request retries
+request-retries-hint = Only reads are retried; transactions are never resubmitted
+scope-blocks = Receive new blocks
+scope-blocks-hint = Without blocks messages arrive only from the mempool. Applies on the next connection
+scope-chain = Wait for chain acceptance
+scope-chain-hint = Show messages once the chain accepts them and track reorgs; turn off for nodes without these notifications
+scope-mempool = Receive from mempool
+scope-mempool-hint = Messages arrive before they are in a block; the node's mempool is read twice a second
+
+## Profiles and backup
+new-profile-placeholder = New profile name
+create = Create
+profile-switch-hint = Switching profiles restarts the app
+show-recovery-phrase = Show recovery phrase
+show-recovery-phrase-confirm = Anyone who sees the recovery phrase can take your funds and identity. Show it?
+show = Show
+export = Export
+hide = Hide
+restore-recovery-phrase = Restore from recovery phrase
+restore-first-run-hint = A new wallet was created. Already have one? Restore it here.
+mnemonic-words-placeholder = 12 or 24 words
+restore-replace-warning = The current recovery phrase will be replaced. Back it up first.
+restore-and-restart = Restore and restart
+
+## Telemetry and protocol stats
+share-stats = Share anonymous stats
+telemetry-endpoint-placeholder = Telemetry endpoint (https://...)
+salvage-payloads = Show damaged messages
+salvage-payloads-hint = Keeps what can be recovered from truncated or corrupted messages
+protocol-stats-title = Seen on the network
+protocol-stats-empty = No messages yet
+protocol-stats-versions = Protocol: { $versions }
+protocol-stats-types = Types: { $types }
+protocol-newer = Protocol v{ $newest } is newer than v{ $current } of this client, an upgrade may be needed
+
+## Channels
+channel-number-placeholder = Channel number
+join = Join
+channel-title = 💬 Channel: { $channel }
+channels-favorites = Favorites
+channels-mentions = Mentions
+channels-recent = Recent
+focus-left = 🎯 Focus: { $time } left
+focus-end = End
+focus-allowlist-placeholder = Allowed channels
+focus-start = Focus
+
+## Name and avatar
+claim-mine = Name is claimed by your key
+claim = Claim
+avatar-file-placeholder = Image file
+avatar-set = Set
+avatar-broadcast = Broadcast
+avatar-broadcast-hint = Send the avatar to everyone (one transaction per 8 KB)
+
+## Chat
+add-to-contacts = Add to contacts
+clock-skew-hint = Sender clock is { $skew } off, block time is shown
+delivery-sent = Sent, waiting for the transaction to be included in a block
+delivery-confirmed = Confirmed: the transaction is in a block
+delivery-failed = Not confirmed: the transaction was not included in time
+delivery-reverted = Unconfirmed: the block with this message left the chain, it may be reverted
+copy-message-link = Copy message link
+chat-placeholder-direct = Type a direct message for { $peer }
+chat-placeholder-announcement = Type an announcement for channel #{ $channel }
+chat-placeholder = Type a message for channel #{ $channel }
+menu-copy-text = Copy text
+menu-copy-name = Copy name
+menu-copy-tx-id = Copy tx id
+menu-reply = Reply
+menu-pin = Pin
+menu-unpin = Unpin
+direct-messages = Direct messages
+link-previews = Link previews
+link-previews-hint = Loads page titles directly from the linked sites
+export-chat = Export chat
+announcement = Announcement
+announcement-hint = The next message is pinned above the chat for everyone in the channel
+direct-peer-placeholder = Recipient public key (hex)
+
+## Settings dialog
+settings-title = ⚙ Settings
+settings-profile = Profile
+username-hint = Up to { $max } chars; claim the new name to protect it
+settings-audio = Audio
+input-device = Input device
+voice-bitrate = Voice bitrate
+voice-bitrate-hint = Higher bitrate sounds better, but makes every voice transaction larger
+settings-appearance = Appearance
+language = Language
+theme = Theme
+number-format = Number format
+amounts-in = Amounts in
+settings-window = Window
+close-to-tray = Close to tray
+start-minimized = Start minimized
+tray-unavailable = System tray is not available on this desktop
+settings-node = Node
+settings-fees = Fees
+
+## Footer
+footer-recording = Recording:
+footer-recording-active = Active
+footer-recording-inactive = Inactive
+footer-status = Status:
+footer-connected = Connected
+footer-disconnected = Disconnected
+footer-away = { $status } (Away)
+footer-quality = Quality:
+footer-node = Node: { $node }
+footer-reconnecting = 🔄 Reconnecting (attempt { $attempt })
+footer-sending-paused = ⛔ Sending paused. { $reason }
+footer-retrying = ⏳ Retrying { $count } send(s)
+footer-tg-en = EN TG GROUP
+footer-tg-ru = RU TG GROUP
+
+## Option names
+faucet-builtin = Built-in
+faucet-url = Faucet URL
+faucet-mnemonic = Funding account
+faucet-off = Off
+fee-mode-manual = Manual
+fee-mode-low = Auto: low
+fee-mode-normal = Auto: normal
+fee-mode-priority = Auto: priority
+derivation-legacy = Legacy (KDX)
+derivation-bip44 = BIP44 account
+denomination-auto = Auto ({ $ticker } / sompi)
+
+## Tray menu
+tray-show-hide = Show / Hide
+tray-mute = Mute microphone
+tray-quit = Quit
+
+## Custom theme
+palette-background = Background
+palette-text = Text
+palette-primary = Accent
+
+## Diagnostics
+diag-node = Node:
+diag-network = Network:
+diag-server-version = Server version:
+diag-server-network = Server network:
+diag-synced = Synced:
+diag-latency = Latency:
+diag-latency-ms = { $ms } ms
+diag-listener = Listener:
+diag-broadcaster = Broadcaster:
+diag-last-error = Last error:
+diag-sub-blocks = blocks
+diag-sub-chain = virtual chain
+diag-sub-mempool = mempool
+diag-sub-account = account UTXOs
+diag-sub-none = no subscriptions
+diag-unknown = unknown
+diag-none = none
+diag-yes = yes
+diag-no = no
+
+## Transaction feed
+tx-feed-title = Channel transactions ({ $count })
+tx-feed-summary = { $size } B · fee { $fee }
+tx-feed-fee-unknown = unknown
+tx-feed-no-address = Sender address unknown
+tx-type-text = Text
+tx-type-voice = Voice
+tx-type-file = File
+tx-type-direct = Direct
+tx-type-claim = Name claim
+tx-type-announcement = Announcement
+tx-type-avatar = Avatar
+tx-type-avatar-request = Avatar request
+tx-type-unknown = Unknown
+
+## Recording
+recording-sent = { $fragments } fragments · { $transactions } transactions
+
+## Input meter
+input-meter = Show input level
+input-meter-hint = Keeps the input device open to show its level next to the device list
+
+## Shortcuts
+settings-shortcuts = Keyboard shortcuts
+shortcut-push-to-talk = Push to talk (hold)
+shortcut-push-to-talk-hint = Works even when the window is in the background. On macOS the app needs the Accessibility permission
+shortcut-toggle-mute = Mute all sound
+shortcut-next-channel = Next channel
+shortcut-previous-channel = Previous channel
+shortcut-send-message = Send message
+shortcut-none = None
+
+## Sync status
+diag-daa-score = DAA score:
+footer-synced = Node synced · DAA { $daa }
+footer-syncing = ⚠ Node is syncing, messages may lag · DAA { $daa }
+
+## Date separators and clock format
+date-today = Today
+date-yesterday = Yesterday
+clock-format = Time format
+clock-format-locale = As in number format
+clock-format-12h = 12-hour (02:05 PM)
+clock-format-24h = 24-hour (14:05)
+
+## Cost confirmation
+cost-confirm-recording = Recording is billed per voice fragment. Estimated cost of { $minutes } min:
+cost-confirm-avatar = Broadcasting your avatar takes several transactions. Estimated cost:
+cost-confirm-estimate = { $count } transactions, about { $amount } in fees
+cost-confirm-over = This is above your confirmation threshold of { $amount }, set under Spending limits.
+cost-confirm-continue = Continue
+cost-confirm-above = Confirm actions costing more than
+cost-confirm-hint = Before a recording or an avatar broadcast whose estimated fees exceed this amount, a confirmation with the transaction count and total fee is shown. A recording is confirmed once per launch.
+cost-confirm-placeholder = Never ask ({ $unit })
+
+## Wallet view
+wallet-open = 👛 Wallet
+wallet-title = Wallet
+wallet-no-address = Address is not available yet
+wallet-available = Available
+wallet-pending = Pending incoming
+wallet-outgoing = Outgoing
+wallet-total = Total
+wallet-explorer = Open in explorer
+wallet-request-airdrop = Request airdrop
+wallet-request-airdrop-hint = Ask the faucet selected below for funds now instead of waiting for the balance to run low
+airdrop-requested = Airdrop requested
+
+## Chat density
+chat-density = Chat density
+density-cozy = Cozy
+density-compact = Compact (one line)
+
+## Jump to latest
+jump-to-latest = ⬇ Jump to latest
+jump-to-latest-new = ⬇ Jump to latest ({ $count } new)
+
+## Follow OS theme
+theme-light = Theme for light system appearance
+theme-dark = Theme for dark system appearance
+theme-auto-hint = “Auto” follows the system light/dark setting and switches themes when it changes.
+
+## Channel info
+channel-info-edit = Name, topic and notes for this channel (stored only on this device)
+channel-name-placeholder = Channel name
+channel-topic-placeholder = Topic
+channel-notes-placeholder = Notes
+channel-info-hint = Visible only to you
@@ -0,0 +1,445 @@
+# Строки интерфейса Kaspeak, русский.
+# Формат: ключ = значение; "{ $name }" заменяется аргументом; строки с отступом продолжают значение.
+
+## Option lists
+ttl-forever = Хранить всегда
+ttl-days = Удалить через { $n } д
+ttl-hours = Удалить через { $n } ч
+ttl-minutes = Удалить через { $n } мин
+fee-bump-never = Никогда
+bitrate-kbps = { $n } кбит/с
+rotation-keep = Не менять адрес
+rotation-weeks = Новый каждые { $n } нед
+rotation-days = Новый каждые { $n } д
+rotation-hours = Новый каждые { $n } ч
+backfill-none = Без истории
+backfill-hours = За { $n } ч
+backfill-minutes = За { $n } мин
+
+## Wallet and backup status
+send-enter-amount = Введите сумму
+send-not-enough-funds = Недостаточно средств
+send-in-progress = Отправка { $amount }…
+send-failed = Не удалось отправить
+copied-to-clipboard = Скопировано в буфер обмена
+saved-to = Сохранено в { $path }
+export-failed = Не удалось экспортировать
+
+## Toasts
+toast-incoming-funds = 💰 Получено { $amount }
+toast-node-failover = 🔀 Нода не отвечает, переключились на { $url }
+toast-rpc-timeout = ⏱ Нода не ответила на запрос { $request } за { $secs } с
+toast-newer-protocol = ⬆ В сети есть клиенты с протоколом v{ $version }, возможно, нужно обновиться
+toast-insufficient-funds = ⚠ Недостаточно средств: пополните кошелёк или дождитесь эйрдропа
+toast-tx-rejected = ⚠ Нода отклонила транзакцию: { $reason }
+toast-send-failed = ⚠ Не удалось отправить: { $reason }
+toast-connect-failed = ⚠ Не удалось подключиться к ноде: { $reason }
+toast-decode-failed = ⚠ Не удалось разобрать полученное сообщение (tx { $tx_id })
+
+## Developer console
+console-placeholder = Введите 'help' и нажмите Enter
+console-payload-inspector = Инспектор payload
+console-waiting-payloads = Ожидание payload…
+
+## Top bar
+select-input-device = Выберите микрофон
+node-address-placeholder = Адрес ноды (необязательно)
+connect = Подключиться
+
+## Common actions
+block-sender = Заблокировать отправителя
+ignore-user = Скрыть сообщения в чате
+cancel = Отмена
+copy = Копировать
+save = Сохранить
+send = Отправить
+name-placeholder = Имя
+claim-other = Имя закреплено за другим ключом
+
+## Clients and transactions
+clients-title = Участники ({ $count })
+last-seen = активность { $ago } назад
+elapsed-seconds = { $value } с
+elapsed-minutes = { $value } мин
+elapsed-hours = { $value } ч
+elapsed-days = { $value } д
+request-avatar = Запросить аватар
+unmute-voice = Включить голос
+unignore-user = Показывать сообщения в чате
+mute-voice = Заглушить голос
+dedupe-window = Окно дедупликации
+dedupe-window-hint = Сколько единиц DAA score помнить транзакции
+    (около 10 единиц в секунду в TN10)
+transactions = Транзакции
+export-csv = Экспорт в CSV
+transaction-summary = { $amount } · комиссия { $fee } · { $size } Б
+
+## Recorder
+stop-recording = Остановить запись
+start-recording = Начать запись
+listen-self = Слышать себя
+mute-all = Заглушить всех
+pause-incoming = Пауза входящих
+pause-incoming-hint = Подключение остаётся, но входящие сообщения и голос пропускаются до снятия паузы
+announce-speakers = Сигнал о новых говорящих
+comfort-noise = Комфортный шум
+comfort-noise-hint = Заполняет паузы между голосовыми фрагментами тихим шумом
+voice-batching = Объединять голосовые фрагменты
+voice-batching-hint = До { $n } фрагментов в одной транзакции: комиссии ниже, задержка чуть больше
+max-speakers = Макс. говорящих
+over-limit-speakers = 🔇 Сверх лимита: { $names }
+stream-stats = 📶 Статистика потоков
+stream-loss = { $name }: потеряно { $lost } ({ $percent }%)
+stream-details = Потоков: { $streams }
+    Получено: { $received }
+    Потеряно: { $lost }
+    Дубликатов: { $duplicates }
+    Не по порядку: { $reordered }
+
+## Field labels
+label-address = 📍 Адрес:
+label-name = 🪪 Имя:
+label-key = 🔑 Ключ:
+label-utxos = ↕️ UTXO:
+label-balance = 💵 Баланс:
+label-send-funds = 💸 Перевод:
+label-contacts = 📇 Контакты:
+label-blocked = 🚫 Заблокированы:
+label-ignored = 🙈 Скрыты в чате:
+label-content-filter = 🧹 Фильтр сообщений:
+label-fee = 🧾 Комиссия:
+label-session-cost = 💸 Расходы за сессию:
+label-network = 🌐 Сеть:
+label-derivation = 🧬 Деривация:
+label-faucet = 🚰 Кран:
+label-nodes = 🖧 Ноды:
+label-profile = 👤 Профиль:
+label-backup = 🔐 Резервная копия:
+label-avatar = 🖼 Аватар:
+label-spending-limits = 🛑 Лимиты расходов
+
+## Account
+new-address = Новый адрес
+new-address-hint = Получать на новый адрес, чтобы новая активность не связывалась со старой
+incoming-funds-sound = Звук при поступлении средств
+mute-notifications = Без уведомлений
+open-permalink-placeholder = Открыть ссылку kaspeak://
+balance-details = Доступно: { $available }
+    Ожидает: { $pending }
+balance-outgoing = Исходящие: { $amount }
+balance-pending =  / { $amount } ожидает
+contact-placeholder = Контакт
+address-placeholder = Адрес
+amount-placeholder = Сумма ({ $unit })
+
+## Message signing
+sign-verify = ✍️ Подпись / проверка
+message-to-sign = Сообщение для подписи
+sign = Подписать
+signed-by = Подписано { $address }
+signer-address = Адрес подписавшего
+signed-message = Подписанное сообщение
+signature-hex = Подпись (hex)
+verify = Проверить
+valid-signature = ✓ Подпись верна
+
+## Contacts and blocklist
+contact-target-placeholder = Адрес или ключ
+contact-note-placeholder = Заметка
+nobody-blocked = Никто не заблокирован
+unblock = Разблокировать
+nobody-ignored = Никто не скрыт
+unignore = Показывать
+filter-off = Выключен
+filter-mask = Скрыть слова
+filter-hide = Скрыть сообщение
+filter-inherit = Как везде
+filter-channel = В канале #{ $channel }
+filter-pattern-placeholder = Слово или /regex/
+filter-hint = Слова ищутся целиком без учёта регистра. Свои сообщения не фильтруются.
+
+## Fees and spending
+fee-bump = Ускорять зависшие сообщения
+fee-bump-hint = Переотправляет сообщение с большей комиссией, если через { $secs } с оно не попало в блок
+fee-placeholder = Комиссия ({ $unit })
+fee-per-message = ≈ { $amount } за сообщение
+fee-node-rates = Ставки ноды: { $low } / { $normal } / { $priority } сомпи за грамм
+fee-no-estimate = Оценки от ноды пока нет, используется { $amount }
+compound-above = Объединять свыше
+dust-below = Пыль меньше
+dust-hint = Сметает выходы меньше { $amount }, когда их набирается { $count }
+dust-placeholder = Пыль ({ $unit })
+per-hour = В час
+per-day = В сутки
+spend-limit-row = { $label } (потрачено { $spent })
+no-limit-placeholder = Без лимита ({ $unit })
+session-cost-details = Транзакций: { $transactions }, байт payload: { $bytes }
+channel-cost = Канал { $channel }: { $fees }, транзакций: { $transactions }
+
+## Network and node
+mainnet-warning = ⚠ Mainnet: каждое сообщение и голосовой фрагмент тратят настоящие KAS
+mainnet-confirm = В { $network } тратятся настоящие средства. Каждое сообщение — транзакция, оплаченная с вашего баланса, и она навсегда остаётся в блокчейне. Убедитесь, что фраза восстановления сохранена. Переключиться и перезапустить?
+switch-to-mainnet = Перейти в mainnet
+network-switch-hint = Смена сети перезапускает приложение
+account-index = Аккаунт №
+derivation-current = Текущий путь: { $path }
+derivation-apply-hint = Адреса изменятся, приложение перезапустится
+apply = Применить
+account-index-invalid = Номер аккаунта должен быть целым числом
+faucet-mnemonic-placeholder = Фраза восстановления аккаунта-донора
+faucet-no-builtin = Для { $network } встроенного крана нет
+faucet-saved = Кран: { $kind }
+nodes-empty = Список пуст, ноду выберет публичный резолвер
+add = Добавить
+auto-reconnect = Переподключаться автоматически
+backfill = История канала при подключении
+connect-timeout = Таймаут подключения, с
+request-timeout = Таймаут запроса, с
+request-retries = Повторы запроса
+request-retries-hint = Повторяются только чтения, транзакции повторно не отправляются
+scope-blocks = Получать новые блоки
+scope-blocks-hint = Без блоков сообщения приходят только из мемпула. Применяется при следующем подключении
+scope-chain = Ждать принятия цепочкой
+scope-chain-hint = Показывать сообщения после принятия цепочкой и отслеживать реорганизации; выключите для нод без этих уведомлений
+scope-mempool = Получать из мемпула
+scope-mempool-hint = Сообщения приходят до попадания в блок; мемпул ноды читается дважды в секунду
+
+## Profiles and backup
+new-profile-placeholder = Имя нового профиля
+create = Создать
+profile-switch-hint = Смена профиля перезапускает приложение
+show-recovery-phrase = Показать фразу восстановления
+show-recovery-phrase-confirm = Любой, кто увидит фразу восстановления, получит ваши средства и личность. Показать?
+show = Показать
+export = Экспорт
+hide = Скрыть
+restore-recovery-phrase = Восстановить из фразы
+restore-first-run-hint = Создан новый кошелёк. Уже есть свой? Восстановите его здесь.
+mnemonic-words-placeholder = 12 или 24 слова
+restore-replace-warning = Текущая фраза восстановления будет заменена. Сначала сохраните её.
+restore-and-restart = Восстановить и перезапустить
+
+## Telemetry and protocol stats
+share-stats = Отправлять анонимную статистику
+telemetry-endpoint-placeholder = Адрес телеметрии (https://...)
+salvage-payloads = Показывать повреждённые сообщения
+salvage-payloads-hint = Сохраняет то, что удаётся восстановить из обрезанных или повреждённых сообщений
+protocol-stats-title = Замечено в сети
+protocol-stats-empty = Сообщений пока нет
+protocol-stats-versions = Протокол: { $versions }
+protocol-stats-types = Типы: { $types }
+protocol-newer = Протокол v{ $newest } новее v{ $current } этого клиента, возможно, нужно обновиться
+
+## Channels
+channel-number-placeholder = Номер канала
+join = Войти
+channel-title = 💬 Канал: { $channel }
+channels-favorites = Избранные
+channels-mentions = Упоминания
+channels-recent = Недавние
+focus-left = 🎯 Фокус: осталось { $time }
+focus-end = Завершить
+focus-allowlist-placeholder = Разрешённые каналы
+focus-start = Фокус
+
+## Name and avatar
+claim-mine = Имя закреплено за вашим ключом
+claim = Закрепить
+avatar-file-placeholder = Файл изображения
+avatar-set = Установить
+avatar-broadcast = Разослать
+avatar-broadcast-hint = Отправить аватар всем (одна транзакция на 8 КБ)
+
+## Chat
+add-to-contacts = Добавить в контакты
+clock-skew-hint = Часы отправителя отклоняются на { $skew }, показано время блока
+delivery-sent = Отправлено, транзакция ждёт попадания в блок
+delivery-confirmed = Подтверждено: транзакция в блоке
+delivery-failed = Не подтверждено: транзакция не попала в блок вовремя
+delivery-reverted = Не подтверждено: блок с сообщением вышел из цепочки, оно может быть отменено
+copy-message-link = Скопировать ссылку на сообщение
+chat-placeholder-direct = Личное сообщение для { $peer }
+chat-placeholder-announcement = Объявление для канала #{ $channel }
+chat-placeholder = Сообщение для канала #{ $channel }
+menu-copy-text = Копировать текст
+menu-copy-name = Копировать имя
+menu-copy-tx-id = Копировать id транзакции
+menu-reply = Ответить
+menu-pin = Закрепить
+menu-unpin = Открепить
+direct-messages = Личные сообщения
+link-previews = Превью ссылок
+link-previews-hint = Загружает заголовки страниц напрямую с сайтов по ссылкам
+export-chat = Выгрузить чат
+announcement = Объявление
+announcement-hint = Следующее сообщение закрепится над чатом у всех в канале
+direct-peer-placeholder = Публичный ключ получателя (hex)
+
+## Settings dialog
+settings-title = ⚙ Настройки
+settings-profile = Профиль
+username-hint = До { $max } символов; закрепите новое имя, чтобы его не заняли
+settings-audio = Звук
+input-device = Микрофон
+voice-bitrate = Битрейт голоса
+voice-bitrate-hint = Чем выше битрейт, тем лучше звук, но тем больше каждая голосовая транзакция
+settings-appearance = Оформление
+language = Язык
+theme = Тема
+number-format = Формат чисел
+amounts-in = Суммы в
+settings-window = Окно
+close-to-tray = Закрывать в трей
+start-minimized = Запускаться свёрнутым
+tray-unavailable = Системный трей недоступен в этом окружении
+settings-node = Нода
+settings-fees = Комиссии
+
+## Footer
+footer-recording = Запись:
+footer-recording-active = Идёт
+footer-recording-inactive = Выключена
+footer-status = Статус:
+footer-connected = Подключено
+footer-disconnected = Нет подключения
+footer-away = { $status } (Нет на месте)
+footer-quality = Качество:
+footer-node = Нода: { $node }
+footer-reconnecting = 🔄 Переподключение (попытка { $attempt })
+footer-sending-paused = ⛔ Отправка приостановлена. { $reason }
+footer-retrying = ⏳ Повторная отправка: { $count }
+footer-tg-en = EN TG ГРУППА
+footer-tg-ru = RU TG ГРУППА
+
+## Option names
+faucet-builtin = Встроенный
+faucet-url = URL крана
+faucet-mnemonic = Аккаунт-донор
+faucet-off = Выключен
+fee-mode-manual = Вручную
+fee-mode-low = Авто: низкая
+fee-mode-normal = Авто: обычная
+fee-mode-priority = Авто: приоритетная
+derivation-legacy = Legacy (KDX)
+derivation-bip44 = Аккаунт BIP44
+denomination-auto = Авто ({ $ticker } / сомпи)
+
+## Tray menu
+tray-show-hide = Показать / скрыть
+tray-mute = Выключить микрофон
+tray-quit = Выход
+
+## Custom theme
+palette-background = Фон
+palette-text = Текст
+palette-primary = Акцент
+
+## Diagnostics
+diag-node = Нода:
+diag-network = Сеть:
+diag-server-version = Версия сервера:
+diag-server-network = Сеть сервера:
+diag-synced = Синхронизирована:
+diag-latency = Задержка:
+diag-latency-ms = { $ms } мс
+diag-listener = Listener:
+diag-broadcaster = Broadcaster:
+diag-last-error = Последняя ошибка:
+diag-sub-blocks = блоки
+diag-sub-chain = виртуальная цепочка
+diag-sub-mempool = мемпул
+diag-sub-account = UTXO аккаунта
+diag-sub-none = нет подписок
+diag-unknown = неизвестно
+diag-none = нет
+diag-yes = да
+diag-no = нет
+
+## Transaction feed
+tx-feed-title = Транзакции канала ({ $count })
+tx-feed-summary = { $size } Б · комиссия { $fee }
+tx-feed-fee-unknown = неизвестна
+tx-feed-no-address = Адрес отправителя неизвестен
+tx-type-text = Текст
+tx-type-voice = Голос
+tx-type-file = Файл
+tx-type-direct = Личное
+tx-type-claim = Заявка на имя
+tx-type-announcement = Объявление
+tx-type-avatar = Аватар
+tx-type-avatar-request = Запрос аватара
+tx-type-unknown = Неизвестный
+
+## Recording
+recording-sent = фрагментов: { $fragments } · транзакций: { $transactions }
+
+## Input meter
+input-meter = Показывать уровень микрофона
+input-meter-hint = Держит устройство ввода открытым, чтобы показывать его уровень рядом со списком устройств
+
+## Shortcuts
+settings-shortcuts = Сочетания клавиш
+shortcut-push-to-talk = Рация (удерживать)
+shortcut-push-to-talk-hint = Работает, даже когда окно в фоне. На macOS приложению нужно разрешение «Универсальный доступ»
+shortcut-toggle-mute = Выключить весь звук
+shortcut-next-channel = Следующий канал
+shortcut-previous-channel = Предыдущий канал
+shortcut-send-message = Отправить сообщение
+shortcut-none = Нет
+
+## Sync status
+diag-daa-score = DAA score:
+footer-synced = Нода синхронизирована · DAA { $daa }
+footer-syncing = ⚠ Нода синхронизируется, сообщения могут запаздывать · DAA { $daa }
+
+## Date separators and clock format
+date-today = Сегодня
+date-yesterday = Вчера
+clock-format = Формат времени
+clock-format-locale = Как в формате чисел
+clock-format-12h = 12-часовой (02:05 PM)
+clock-format-24h = 24-часовой (14:05)
+
+## Cost confirmation
+cost-confirm-recording = Запись оплачивается за каждый голосовой фрагмент. Оценка на { $minutes } мин:
+cost-confirm-avatar = Рассылка аватара занимает несколько транзакций. Оценка расходов:
+cost-confirm-estimate = Транзакций: { $count }, комиссии около { $amount }
+cost-confirm-over = Это больше порога подтверждения { $amount }, заданного в разделе «Лимиты трат».
+cost-confirm-continue = Продолжить
+cost-confirm-above = Подтверждать действия дороже
+cost-confirm-hint = Перед записью или рассылкой аватара, оценка комиссий которых выше этой суммы, показывается подтверждение с числом транзакций и суммой комиссий. Запись подтверждается один раз за запуск.
+cost-confirm-placeholder = Не спрашивать ({ $unit })
+
+## Wallet view
+wallet-open = 👛 Кошелёк
+wallet-title = Кошелёк
+wallet-no-address = Адрес ещё не получен
+wallet-available = Доступно
+wallet-pending = Ожидает поступления
+wallet-outgoing = Исходящие
+wallet-total = Всего
+wallet-explorer = Открыть в обозревателе
+wallet-request-airdrop = Запросить эйрдроп
+wallet-request-airdrop-hint = Запросить средства у выбранного ниже крана сейчас, не дожидаясь низкого баланса
+airdrop-requested = Эйрдроп запрошен
+
+## Chat density
+chat-density = Плотность чата
+density-cozy = Просторно
+density-compact = Компактно (в одну строку)
+
+## Jump to latest
+jump-to-latest = ⬇ К последним
+jump-to-latest-new = ⬇ К последним (новых: { $count })
+
+## Follow OS theme
+theme-light = Тема при светлом оформлении системы
+theme-dark = Тема при тёмном оформлении системы
+theme-auto-hint = «Auto» следует светлому или тёмному оформлению системы и меняет тему при его смене.
+
+## Channel info
+channel-info-edit = Название, тема и заметки канала (хранятся только на этом устройстве)
+channel-name-placeholder = Название канала
+channel-topic-placeholder = Тема
+channel-notes-placeholder = Заметки
+channel-info-hint = Видно только вам
@@ -1,15 +1,51 @@
-use crate::constants::{DEFAULT_CHANNEL, DEFAULT_FEE_LEVEL, MAX_CHANNEL_CAPACITY};
-use crate::models::message::Message;
-use crate::settings::Settings;
+use crate::constants::{
+    AVATAR_CHUNK_SIZE, AVATAR_REANNOUNCE_COOLDOWN_SECS, COST_ESTIMATE_RECORDING_SECS, DEFAULT_CHANNEL, DEFAULT_CHAT_EXPORT_PATH,
+    DEFAULT_CONTACTS_PATH, DEFAULT_FEE_LEVEL, DEFAULT_MNEMONIC_BACKUP_PATH, DEFAULT_PINNED_MESSAGES_PATH, DEFAULT_RETRY_QUEUE_PATH,
+    DEFAULT_SEEN_USERS_PATH, DEFAULT_SPEND_LOG_PATH, DEFAULT_TX_EXPORT_PATH, DEFAULT_TX_HISTORY_PATH, DELIVERY_CONFIRM_TIMEOUT_SECS,
+    ESTIMATED_TX_BASE_MASS, FAILOVER_ERROR_WINDOW_SECS, FEE_BUMP_MAX_BUMPS, HEADER_SIZE, MAX_CHANNEL_CAPACITY, MAX_CONSOLE_LINES,
+    MAX_INSPECTED_PAYLOADS, MAX_NODE_URLS, MAX_PINNED_MESSAGES_PER_CHANNEL, MAX_RECENT_CHANNELS, MAX_SEEN_USERS_PER_CHANNEL,
+    MAX_TOASTS, MAX_TX_FEED, MAX_TX_HISTORY, MIC_LEVEL_HISTORY, MINIMUM_FEERATE, OVERFLOW_INDICATION_MS, PROTOCOL_VERSION,
+    QUALITY_NOTIFICATION_GAP_SECS, QUALITY_RPC_ERROR_PENALTY, QUALITY_SEND_FAILURE_PENALTY, QUALITY_WINDOW_SECS, RETRY_MAX_ATTEMPTS,
+    SPEAKING_INDICATION_MS, TOAST_DURATION_SECS, VOICE_BATCH_ENTRY_HEADER, VOICE_BATCH_MAX_FRAGMENTS, VOICE_FRAGMENT_MS,
+};
+use crate::core::identity::{parse_public_key, Identity};
+use crate::core::inspector::InspectedPayload;
+use crate::core::message_signing::SignedMessage;
+use crate::logs::telemetry::TelemetryReport;
+use crate::models::avatar::{check_avatar, AvatarChunk, CHUNK_HEADER_SIZE};
+use crate::models::contact::{BlockedSender, Contact};
+use crate::models::content_filter::{CompiledFilter, ContentFilter, FilterOutcome};
+use crate::models::derivation::AccountDerivation;
+use crate::models::faucet::FaucetSource;
+use crate::models::message::{DeliveryState, Message, PinnedMessage};
+use crate::models::network::{Network, NotificationScopes, RpcPolicy};
+use crate::models::payload::{MessageType, StatusFlag};
+use crate::models::retry::PendingSend;
+use crate::models::shortcut::{Shortcut, ShortcutAction, Shortcuts};
+use crate::models::transaction::{
+    sent_transactions_to_csv, CostEstimate, FeeMode, FeeRates, ObservedTransaction, SentTransaction, SessionCost, SpendLimits,
+    SpendLog,
+};
+use crate::models::transcript::{render_transcript, TranscriptFormat};
+use crate::models::user::{validate_username, SeenUser};
+use crate::settings::{active_profile, profile_file_path, validate_profile_name, ChannelInfo, ChatDensity, FocusMode, Settings};
+use crate::utils::format::{ClockFormat, Denomination, FormatLocale};
 use crate::utils::gather_audio_devices;
+use crate::utils::i18n::UiLanguage;
+use crate::utils::link_preview::LinkPreview;
+use crate::utils::store::{load_json, save_json};
 use config::ConfigError;
 use cpal::traits::{DeviceTrait, HostTrait};
 use cpal::Device;
 use dashmap::DashMap;
-use kaspa_wallet_core::prelude::Address;
+use kaspa_wallet_core::prelude::{Address, Language, Mnemonic};
 use lazy_static::lazy_static;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 pub(crate) struct RecorderSharedState {
     pub(crate) available_input_devices: Vec<String>,
@@ -19,20 +55,291 @@ pub(crate) struct RecorderSharedState {
 
 pub(crate) struct ChatSharedState {
     pub(crate) messages_by_channel: DashMap<u32, Vec<Message>>,
+    /// Личные переписки по hex-ключу собеседника
+    pub(crate) direct_messages_by_peer: DashMap<String, Vec<Message>>,
+    pub(crate) pending_permalink: Mutex<Option<String>>,
+    /// Превью по адресу ссылки; None — загрузка идёт или не удалась
+    pub(crate) link_previews: DashMap<String, Option<LinkPreview>>,
+    /// Последнее нескрытое объявление по каналам
+    pub(crate) announcements: DashMap<u32, Message>,
+    /// Недавние собеседники по каналам, новые в начале
+    pub(crate) seen_users: DashMap<u32, Vec<SeenUser>>,
+    seen_users_dirty: AtomicBool,
+    /// Реестр собеседников: последняя активность (голос или текст) по имени во всех каналах, unix-время
+    pub(crate) last_activity: DashMap<String, i64>,
+    /// Непрочитанные упоминания по каналам: значок в списке каналов
+    pub(crate) unread_mentions: DashMap<u32, u32>,
+    /// Локально закреплённые сообщения по каналам в порядке закрепления
+    pub(crate) pinned_messages: DashMap<u32, Vec<PinnedMessage>>,
+    /// Растёт при каждом изменении сообщений: GUI перечитывает ленту только после изменений
+    revision: AtomicU64,
 }
 
 impl ChatSharedState {
+    /// Номер последнего изменения сообщений
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::SeqCst)
+    }
+
+    fn touch(&self) {
+        self.revision.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Добавляет сообщение канала. Одна и та же транзакция может прийти дважды (мемпул и блок,
+    /// подгрузка истории после переподключения или перезапуска) — повтор по `Message::dedupe_key` отбрасывается
     pub fn add_message(&self, channel: u32, message: Message) {
         let mut messages = self.messages_by_channel.entry(channel).or_insert_with(Vec::new);
 
-        messages.push(message);
+        // Своё сообщение уже показано после отправки: транзакция попала в блок
+        if let Some(tx_id) = message.get_tx_id() {
+            if let Some(echo) = messages.iter_mut().find(|msg| msg.delivery.is_some() && msg.get_tx_id() == Some(tx_id)) {
+                if echo.delivery != Some(DeliveryState::Confirmed) {
+                    echo.delivery = Some(DeliveryState::Confirmed);
+                    echo.clock_skew_secs = message.clock_skew_secs;
+                    self.touch();
+                }
+                return;
+            }
+        }
+        if let Some(key) = message.dedupe_key() {
+            if messages.iter().any(|msg| msg.dedupe_key() == Some(key)) {
+                log::debug!("Skipped duplicate message (tx_id={}, fragment={})", key.0, key.1);
+                return;
+            }
+        }
+        self.touch();
+        insert_by_time(&mut messages, message);
+        if messages.len() > MAX_CHANNEL_CAPACITY {
+            messages.remove(0);
+        }
+    }
+
+    pub fn add_direct_message(&self, peer: &str, message: Message) {
+        self.touch();
+        let mut messages = self.direct_messages_by_peer.entry(peer.to_string()).or_insert_with(Vec::new);
+
+        insert_by_time(&mut messages, message);
         if messages.len() > MAX_CHANNEL_CAPACITY {
             messages.remove(0);
         }
     }
 
+    /// Своё сообщение отправлено заново транзакцией с большей комиссией
+    pub fn replace_tx_id(&self, tx_id: &str, replacement: &str) {
+        for mut entry in self.messages_by_channel.iter_mut() {
+            if let Some(message) = entry.value_mut().iter_mut().find(|msg| msg.delivery.is_some() && msg.get_tx_id() == Some(tx_id)) {
+                message.tx_id = Some(replacement.to_string());
+                self.touch();
+                return;
+            }
+        }
+    }
+
+    /// Отмечает сообщение транзакции `tx_id` как возможно отменённое реорганизацией цепочки или снимает отметку
+    pub fn set_possibly_reverted(&self, tx_id: &str, reverted: bool) {
+        for mut entry in self.messages_by_channel.iter_mut() {
+            if let Some(message) = entry.value_mut().iter_mut().find(|msg| msg.get_tx_id() == Some(tx_id)) {
+                message.possibly_reverted = reverted;
+                self.touch();
+                return;
+            }
+        }
+    }
+
+    /// Помечает неподтверждёнными свои сообщения, чья транзакция не попала в блок за DELIVERY_CONFIRM_TIMEOUT_SECS
+    pub fn fail_unconfirmed_messages(&self) {
+        let deadline = chrono::Local::now() - chrono::Duration::seconds(DELIVERY_CONFIRM_TIMEOUT_SECS);
+        for mut entry in self.messages_by_channel.iter_mut() {
+            for message in entry.value_mut().iter_mut() {
+                if message.delivery == Some(DeliveryState::Sent) && message.time < deadline {
+                    log::warn!("Message in transaction {:?} was not confirmed in time", message.get_tx_id());
+                    message.delivery = Some(DeliveryState::Failed);
+                    self.touch();
+                }
+            }
+        }
+    }
+
+    /// Ищет сообщение по транзакции: (канал, идентификатор сообщения)
+    pub fn find_by_tx_id(&self, tx_id: &str) -> Option<(u32, Uuid)> {
+        self.messages_by_channel
+            .iter()
+            .find_map(|entry| entry.value().iter().find(|msg| msg.get_tx_id() == Some(tx_id)).map(|msg| (*entry.key(), msg.get_id())))
+    }
+
+    /// Ссылка, переданная при запуске и ещё не открытая в GUI
+    pub fn set_pending_permalink(&self, link: String) {
+        if let Ok(mut pending) = self.pending_permalink.lock() {
+            *pending = Some(link);
+        }
+    }
+
+    pub fn take_pending_permalink(&self) -> Option<String> {
+        self.pending_permalink.lock().ok().and_then(|mut pending| pending.take())
+    }
+
+    /// Превью ссылки, если оно уже загружено
+    pub fn get_link_preview(&self, url: &str) -> Option<LinkPreview> {
+        self.link_previews.get(url).and_then(|preview| preview.clone())
+    }
+
+    /// Закрепляет объявление канала; новое объявление заменяет предыдущее
+    pub fn set_announcement(&self, channel: u32, message: Message) {
+        self.announcements.insert(channel, message);
+    }
+
+    pub fn get_announcement(&self, channel: u32) -> Option<Message> {
+        self.announcements.get(&channel).map(|message| message.clone()).filter(|message| !message.is_expired())
+    }
+
+    pub fn add_unread_mention(&self, channel: u32) {
+        *self.unread_mentions.entry(channel).or_insert(0) += 1;
+    }
+
+    pub fn get_unread_mentions(&self) -> HashMap<u32, u32> {
+        self.unread_mentions.iter().map(|entry| (*entry.key(), *entry.value())).collect()
+    }
+
+    pub fn clear_unread_mentions(&self, channel: u32) {
+        self.unread_mentions.remove(&channel);
+    }
+
+    /// Скрывает объявление, если за это время не пришло новое
+    pub fn dismiss_announcement(&self, channel: u32, id: Uuid) {
+        self.announcements.remove_if(&channel, |_, message| message.get_id() == id);
+    }
+
+    /// Запоминает собеседника в канале; адрес не затирается, если в этот раз он неизвестен
+    pub fn record_seen_user(&self, channel: u32, username: &str, address: Option<&str>, key: Option<&str>) {
+        let mut users = self.seen_users.entry(channel).or_default();
+        let now = chrono::Utc::now().timestamp();
+        match users.iter().position(|user| user.username == username) {
+            Some(idx) => {
+                let mut user = users.remove(idx);
+                user.last_seen = now;
+                if let Some(address) = address {
+                    user.address = Some(address.to_string());
+                }
+                if let Some(key) = key {
+                    user.key = Some(key.to_string());
+                }
+                users.insert(0, user);
+            }
+            None => {
+                users.insert(
+                    0,
+                    SeenUser {
+                        username: username.to_string(),
+                        address: address.map(str::to_string),
+                        key: key.map(str::to_string),
+                        last_seen: now,
+                    },
+                );
+                users.truncate(MAX_SEEN_USERS_PER_CHANNEL);
+            }
+        }
+        self.seen_users_dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Отмечает активность собеседника в момент `timestamp` (unix-время); более раннее время не затирает позднее
+    pub fn record_activity(&self, username: &str, timestamp: i64) {
+        let mut last = self.last_activity.entry(username.to_string()).or_insert(timestamp);
+        *last = (*last).max(timestamp);
+    }
+
+    /// Когда собеседник последний раз говорил или писал, unix-время
+    pub fn get_last_activity(&self, username: &str) -> Option<i64> {
+        self.last_activity.get(username).map(|last| *last)
+    }
+
+    pub fn get_seen_users(&self, channel: u32) -> Vec<SeenUser> {
+        self.seen_users.get(&channel).map(|users| users.clone()).unwrap_or_default()
+    }
+
+    /// Загружает недавних собеседников, сохранённые в прошлый запуск
+    pub fn load_seen_users(&self) -> Result<(), String> {
+        let stored: HashMap<u32, Vec<SeenUser>> = load_json(DEFAULT_SEEN_USERS_PATH)?.unwrap_or_default();
+        for (channel, users) in stored {
+            for user in &users {
+                self.record_activity(&user.username, user.last_seen);
+            }
+            self.seen_users.insert(channel, users);
+        }
+        Ok(())
+    }
+
+    /// Сохраняет недавних собеседников, если с прошлого сохранения что-то изменилось
+    pub fn save_seen_users(&self) -> Result<(), String> {
+        if !self.seen_users_dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let snapshot: HashMap<u32, Vec<SeenUser>> =
+            self.seen_users.iter().map(|entry| (*entry.key(), entry.value().clone())).collect();
+        save_json(DEFAULT_SEEN_USERS_PATH, &snapshot).inspect_err(|_| self.seen_users_dirty.store(true, Ordering::Relaxed))
+    }
+
+    pub fn get_pinned_messages(&self, channel: u32) -> Vec<PinnedMessage> {
+        self.pinned_messages.get(&channel).map(|pins| pins.clone()).unwrap_or_default()
+    }
+
+    /// Закрепляет сообщение канала или снимает его и сразу сохраняет список
+    pub fn set_message_pinned(&self, msg: &Message, pinned: bool) -> Result<(), String> {
+        {
+            let mut pins = self.pinned_messages.entry(msg.channel).or_default();
+            if pinned {
+                if pins.iter().any(|pin| pin.matches(msg)) {
+                    return Ok(());
+                }
+                if pins.len() >= MAX_PINNED_MESSAGES_PER_CHANNEL {
+                    return Err(format!("Channel #{} already has {} pinned messages", msg.channel, MAX_PINNED_MESSAGES_PER_CHANNEL));
+                }
+                pins.push(PinnedMessage::from_message(msg));
+            } else {
+                pins.retain(|pin| !pin.matches(msg));
+            }
+        }
+        self.save_pinned_messages()
+    }
+
+    /// Снимает закрепление по записи из списка: самого сообщения в ленте может уже не быть
+    pub fn unpin_message(&self, channel: u32, pin: &PinnedMessage) -> Result<(), String> {
+        if let Some(mut pins) = self.pinned_messages.get_mut(&channel) {
+            pins.retain(|existing| existing != pin);
+        }
+        self.save_pinned_messages()
+    }
+
+    /// Загружает закреплённые сообщения, сохранённые в прошлый запуск
+    pub fn load_pinned_messages(&self) -> Result<(), String> {
+        let stored: HashMap<u32, Vec<PinnedMessage>> = load_json(DEFAULT_PINNED_MESSAGES_PATH)?.unwrap_or_default();
+        for (channel, pins) in stored {
+            self.pinned_messages.insert(channel, pins);
+        }
+        Ok(())
+    }
+
+    fn save_pinned_messages(&self) -> Result<(), String> {
+        let snapshot: HashMap<u32, Vec<PinnedMessage>> = self
+            .pinned_messages
+            .iter()
+            .filter(|entry| !entry.value().is_empty())
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+        save_json(DEFAULT_PINNED_MESSAGES_PATH, &snapshot)
+    }
+
+    /// Список собеседников, с которыми есть личная переписка
+    pub fn get_direct_peers(&self) -> Vec<String> {
+        let mut peers: Vec<String> = self.direct_messages_by_peer.iter().map(|entry| entry.key().clone()).collect();
+        peers.sort();
+        peers
+    }
+
     pub fn clear(&self) {
+        self.touch();
         self.messages_by_channel.clear();
+        self.direct_messages_by_peer.clear();
+        self.announcements.clear();
     }
 }
 
@@ -41,14 +348,369 @@ pub(crate) struct ListenerSharedState {
     pub(crate) listen_self: Arc<AtomicBool>,
     pub(crate) mute_all: Arc<AtomicBool>,
     pub(crate) is_connected: Arc<AtomicBool>,
+    /// Входящие не обрабатываются, подключение к ноде остаётся
+    pub(crate) paused: Arc<AtomicBool>,
+    /// Отброшенные Listener payload по причинам
+    pub(crate) dropped_payloads: BTreeMap<&'static str, u64>,
 }
 
 pub(crate) struct BroadcasterSharedState {
     pub(crate) address: Option<Address>,
     pub(crate) fee_size: u64,
+    /// Последняя оценка комиссии от ноды
+    pub(crate) fee_rates: Option<FeeRates>,
     pub(crate) balance: u64,
+    /// Ещё не созревшие поступления (эйрдроп, сдача) и уходящие суммы, сомпи
+    pub(crate) pending_balance: u64,
+    pub(crate) outgoing_balance: u64,
     pub(crate) utxos: usize,
+    /// История отправленных транзакций, старые в начале
+    pub(crate) sent_transactions: VecDeque<SentTransaction>,
     pub(crate) is_connected: Arc<AtomicBool>,
+    /// Пользователь отошёл (система засыпала); сбрасывается при первом действии
+    pub(crate) is_away: Arc<AtomicBool>,
+    /// Когда Broadcaster в последний раз отправлял транзакцию с payload
+    pub(crate) last_send: Option<Instant>,
+    /// Расходы за текущий запуск
+    pub(crate) session_cost: SessionCost,
+    /// Траты за последние сутки для лимитов; хранятся в файле профиля
+    pub(crate) spend_log: SpendLog,
+    /// Отправка приостановлена превышенным лимитом трат: описание лимита
+    pub(crate) spend_pause: Option<String>,
+    /// Свои транзакции с payload, ещё не замеченные Listener'ом в блоке, по идентификатору
+    pub(crate) unconfirmed_sends: HashMap<String, UnconfirmedSend>,
+}
+
+/// Транзакция с payload в ожидании блока; зависшую заменяют транзакцией с большей комиссией
+pub(crate) struct UnconfirmedSend {
+    pub(crate) sent_at: Instant,
+    /// Сколько раз комиссию уже поднимали (транзакция — замена предыдущей)
+    pub(crate) bumps: u32,
+    /// Замена уже отправляется
+    pub(crate) in_flight: bool,
+}
+
+pub(crate) struct PlayerSharedState {
+    /// Собеседники, заглушённые из-за лимита одновременных потоков, и время последнего отброшенного фрагмента
+    pub(crate) overflow_speakers: HashMap<String, Instant>,
+    /// Статистика последовательности фрагментов по отправителям
+    pub(crate) stream_stats: HashMap<String, StreamStats>,
+    /// Время последнего голосового фрагмента по отправителям: индикатор «говорит» в списке собеседников
+    pub(crate) speaking: HashMap<String, Instant>,
+}
+
+/// Непрерывность номеров фрагментов одного отправителя за сессию.
+/// Пропуски считаются потерями, пока фрагмент не придёт с опозданием.
+#[derive(Debug, Clone, Default)]
+pub struct StreamStats {
+    pub streams: u32,
+    pub received: u32,
+    pub lost: u32,
+    pub duplicates: u32,
+    pub reordered: u32,
+    highest: Option<u32>,
+    seen: HashSet<u32>,
+}
+
+impl StreamStats {
+    fn record(&mut self, fragment_number: u32, status_flag: StatusFlag) {
+        if status_flag == StatusFlag::Start {
+            self.streams += 1;
+            self.highest = None;
+            self.seen.clear();
+        }
+        if !self.seen.insert(fragment_number) {
+            self.duplicates += 1;
+            return;
+        }
+        self.received += 1;
+
+        // Первый фрагмент передачи без Start: всё до него потеряно
+        let expected = self.highest.map_or(0, |highest| highest + 1);
+        if fragment_number >= expected {
+            self.lost += fragment_number - expected;
+            self.highest = Some(fragment_number);
+        } else {
+            // Опоздавший фрагмент закрывает уже учтённый пропуск
+            self.reordered += 1;
+            self.lost = self.lost.saturating_sub(1);
+        }
+    }
+
+    /// Доля потерянных фрагментов, %
+    pub fn loss_percent(&self) -> f64 {
+        let total = self.received + self.lost;
+        if total == 0 {
+            0.0
+        } else {
+            self.lost as f64 * 100.0 / total as f64
+        }
+    }
+}
+
+/// Инспектор payload (см. `core::inspector`)
+#[derive(Default)]
+pub(crate) struct InspectorState {
+    pub(crate) enabled: bool,
+    pub(crate) entries: VecDeque<InspectedPayload>,
+    pub(crate) revision: u64,
+}
+
+/// Идущая запись: начало, сколько фрагментов и транзакций голоса отправлено и недавние уровни микрофона
+#[derive(Debug, Clone, Default)]
+pub struct RecordingStats {
+    pub started_at: Option<Instant>,
+    /// Фрагменты, переданные на отправку
+    pub fragments_sent: u64,
+    /// Транзакции с голосом, принятые нодой
+    pub transactions_sent: u64,
+    /// Пиковые уровни входа от 0 до 1, от старых к новым
+    pub levels: VecDeque<f32>,
+}
+
+/// Лента транзакций текущего канала
+#[derive(Default)]
+pub(crate) struct TxFeedState {
+    pub(crate) entries: VecDeque<ObservedTransaction>,
+    pub(crate) revision: u64,
+}
+
+/// Таблица заявок на имена, наполняется Listener'ом. Первая подтверждённая заявка на имя выигрывает
+pub(crate) struct ClaimSharedState {
+    /// Имя → ключ идентичности владельца
+    pub(crate) claims: HashMap<String, String>,
+}
+
+/// Вставляет сообщение с учётом времени отправки: блоки приходят не строго по порядку.
+/// Обычно сообщение самое новое, поэтому ищем место с конца
+fn insert_by_time(messages: &mut Vec<Message>, message: Message) {
+    let position = messages.iter().rposition(|existing| existing.time <= message.time).map_or(0, |index| index + 1);
+    messages.insert(position, message);
+}
+
+/// Аватар, который ещё собирается из фрагментов
+pub(crate) struct PendingAvatar {
+    /// Подпись изображения: фрагменты с другой подписью относятся к другой версии аватара
+    pub(crate) signature: [u8; 64],
+    pub(crate) chunks: Vec<Option<Vec<u8>>>,
+}
+
+/// Всплывающее уведомление в углу окна
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub kind: ToastKind,
+    pub created: Instant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastKind {
+    /// Поступили средства, сомпи
+    IncomingFunds(u64),
+    /// Лимит трат: отправка приостановлена или перевод отклонён
+    SpendLimit(String),
+    /// Нода отказала, подключились к следующей из списка
+    NodeFailover(String),
+    /// Нода не ответила за отведённое время: что именно ждали и сколько секунд
+    RpcTimeout(String, u64),
+    /// В сети замечена версия протокола новее нашей
+    NewerProtocol(u8),
+    /// Ошибка, о которой нужно сказать пользователю, а не только записать в лог
+    Error(ErrorEvent),
+}
+
+/// Ошибки отправки, подключения и разбора сообщений, показываемые уведомлением
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorEvent {
+    /// Не хватает средств на транзакцию
+    InsufficientFunds,
+    /// Нода отклонила транзакцию: её ответ
+    TransactionRejected(String),
+    /// Отправка не удалась по другой причине
+    SendFailed(String),
+    /// Не удалось подключиться к ноде
+    ConnectFailed(String),
+    /// Не удалось разобрать полученное сообщение: id транзакции
+    DecodeFailed(String),
+}
+
+impl ErrorEvent {
+    /// Ошибка отправки: нехватку средств и отказ ноды выделяем, с ними пользователь может что-то сделать
+    pub fn from_send_error(error: &str) -> Self {
+        let lowercase = error.to_lowercase();
+        if lowercase.contains("insufficient") {
+            ErrorEvent::InsufficientFunds
+        } else if lowercase.contains("reject") {
+            ErrorEvent::TransactionRejected(error.to_string())
+        } else {
+            ErrorEvent::SendFailed(error.to_string())
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ToastSharedState {
+    pub(crate) toasts: VecDeque<Toast>,
+    next_id: u64,
+}
+
+/// Адресная книга профиля
+pub(crate) struct ContactSharedState {
+    pub(crate) contacts: Vec<Contact>,
+}
+
+/// Очередь повторов неудавшихся отправок профиля
+pub(crate) struct RetrySharedState {
+    pub(crate) queue: Vec<PendingSend>,
+    next_id: u64,
+}
+
+/// Кэш аватаров по ключу идентичности, наполняется Listener'ом
+pub(crate) struct AvatarSharedState {
+    /// Ключ идентичности (hex) → PNG
+    pub(crate) avatars: HashMap<String, Vec<u8>>,
+    /// Адрес, с которого пришёл аватар → ключ идентичности
+    pub(crate) keys_by_address: HashMap<String, String>,
+    pub(crate) pending: HashMap<String, PendingAvatar>,
+    /// Растёт при каждом изменении кэша, GUI по нему пересоздаёт картинки
+    pub(crate) revision: u64,
+    /// Когда свой аватар рассылался последний раз
+    pub(crate) last_announced: Option<Instant>,
+}
+
+/// Счётчики телеметрии с момента последней отправки
+#[derive(Debug, Clone)]
+pub struct TelemetryCounters {
+    pub period_start: Instant,
+    pub reconnects: u32,
+    pub audio_dropouts: u32,
+    /// Ошибки разбора входящих payload по видам (`ParseIssue::kind`)
+    pub parse_errors: BTreeMap<String, u32>,
+    /// Входящие payload по версиям протокола, включая неподдерживаемые
+    pub protocol_versions: BTreeMap<u8, u32>,
+    /// Входящие payload по типам сообщений
+    pub message_types: BTreeMap<String, u32>,
+    /// Самая новая версия протокола, замеченная в сети за сессию; с отправкой отчёта не сбрасывается
+    pub newest_protocol_version: Option<u8>,
+    /// Было ли уже хоть одно подключение: первое переподключением не считается
+    pub(crate) connected_once: bool,
+}
+
+impl Default for TelemetryCounters {
+    fn default() -> Self {
+        Self {
+            period_start: Instant::now(),
+            reconnects: 0,
+            audio_dropouts: 0,
+            parse_errors: BTreeMap::new(),
+            protocol_versions: BTreeMap::new(),
+            message_types: BTreeMap::new(),
+            newest_protocol_version: None,
+            connected_once: false,
+        }
+    }
+}
+
+/// Нода, к которой подключён клиент, и время последнего переключения
+pub(crate) struct NodeSharedState {
+    pub(crate) active_url: Option<String>,
+    /// Ошибки RPC до переключения к новой ноде не относятся
+    pub(crate) last_failover: Option<Instant>,
+    /// Номер текущей попытки автопереподключения; None — переподключение не идёт
+    pub(crate) reconnect_attempt: Option<u32>,
+}
+
+/// Оценка качества соединения (0–100) и факторы, которые её снизили
+#[derive(Debug, Clone)]
+pub struct ConnectionQuality {
+    pub score: u8,
+    pub factors: Vec<String>,
+}
+
+/// Сведения для панели диагностики соединения
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionDiagnostics {
+    /// Версия ноды, её сеть и синхронизация по ответу на последнее подключение
+    pub server_version: Option<String>,
+    pub server_network: Option<String>,
+    pub is_synced: Option<bool>,
+    /// DAA score виртуальной цепочки: по get_server_info и по новым блокам
+    pub daa_score: Option<u64>,
+    /// Время ответа на последний ping; None — замера ещё не было или нода не ответила
+    pub latency_ms: Option<u64>,
+    /// Подписки Listener на уведомления ноды; None — Listener не подписан
+    pub listener_scopes: Option<NotificationScopes>,
+    /// Последняя ошибка RPC или отправки: unix-время и текст
+    pub last_error: Option<(i64, String)>,
+}
+
+/// Недавние ошибки RPC, сбои отправки, время последнего уведомления от ноды и сведения для диагностики
+pub(crate) struct QualitySharedState {
+    pub(crate) rpc_errors: VecDeque<Instant>,
+    pub(crate) send_failures: VecDeque<Instant>,
+    pub(crate) last_notification: Option<Instant>,
+    pub(crate) diagnostics: ConnectionDiagnostics,
+}
+
+impl QualitySharedState {
+    /// Добавляет событие и отбрасывает вышедшие за окно наблюдения
+    fn push_event(events: &mut VecDeque<Instant>) {
+        let now = Instant::now();
+        events.push_back(now);
+        while let Some(oldest) = events.front() {
+            if now.duration_since(*oldest) > Duration::from_secs(QUALITY_WINDOW_SECS) {
+                events.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn count_recent(events: &VecDeque<Instant>) -> u32 {
+        events.iter().filter(|t| t.elapsed() <= Duration::from_secs(QUALITY_WINDOW_SECS)).count() as u32
+    }
+
+    /// Считает итоговую оценку. Без подключения оценка всегда 0.
+    pub fn evaluate(&self, is_connected: bool) -> ConnectionQuality {
+        if !is_connected {
+            return ConnectionQuality { score: 0, factors: vec!["Not connected to a node".to_string()] };
+        }
+
+        let mut penalty: u32 = 0;
+        let mut factors = Vec::new();
+
+        let rpc_errors = Self::count_recent(&self.rpc_errors);
+        if rpc_errors > 0 {
+            penalty += (rpc_errors * QUALITY_RPC_ERROR_PENALTY).min(40);
+            factors.push(format!("{} RPC error(s) in the last {} min", rpc_errors, QUALITY_WINDOW_SECS / 60));
+        }
+
+        let send_failures = Self::count_recent(&self.send_failures);
+        if send_failures > 0 {
+            penalty += (send_failures * QUALITY_SEND_FAILURE_PENALTY).min(45);
+            factors.push(format!("{} failed send(s) in the last {} min", send_failures, QUALITY_WINDOW_SECS / 60));
+        }
+
+        match self.last_notification {
+            Some(last) => {
+                let gap = last.elapsed().as_secs();
+                if gap > QUALITY_NOTIFICATION_GAP_SECS {
+                    penalty += (gap as u32 * 3).min(30);
+                    factors.push(format!("No block notifications for {} s", gap));
+                }
+            }
+            None => {
+                penalty += 30;
+                factors.push("No block notifications received yet".to_string());
+            }
+        }
+
+        if factors.is_empty() {
+            factors.push("No problems detected".to_string());
+        }
+
+        ConnectionQuality { score: 100u32.saturating_sub(penalty) as u8, factors }
+    }
 }
 
 /// Основное состояние приложения
@@ -57,8 +719,47 @@ pub struct AppState {
     pub(crate) recorder_state: Arc<RwLock<RecorderSharedState>>,
     pub(crate) broadcaster_state: Arc<RwLock<BroadcasterSharedState>>,
     pub(crate) chat_state: ChatSharedState,
+    pub(crate) quality_state: Arc<RwLock<QualitySharedState>>,
+    pub(crate) node_state: Arc<RwLock<NodeSharedState>>,
+    pub(crate) player_state: Arc<RwLock<PlayerSharedState>>,
+    pub(crate) claim_state: Arc<RwLock<ClaimSharedState>>,
+    pub(crate) avatar_state: Arc<RwLock<AvatarSharedState>>,
+    pub(crate) contact_state: Arc<RwLock<ContactSharedState>>,
+    pub(crate) retry_state: Arc<RwLock<RetrySharedState>>,
+    pub(crate) telemetry: Mutex<TelemetryCounters>,
+    /// Копия согласия на телеметрию: обработчик паники читает её без мьютекса настроек
+    telemetry_enabled: AtomicBool,
+    /// Падения, которые обработчик паники не смог сразу записать в настройки
+    unsaved_crashes: AtomicU32,
+    /// Вывод консоли разработчика
+    console_output: Mutex<VecDeque<String>>,
+    /// Инспектор payload: включён ли, последние записи и счётчик изменений для GUI
+    inspector: Mutex<InspectorState>,
+    /// Лента транзакций текущего канала и счётчик изменений для GUI
+    tx_feed: Mutex<TxFeedState>,
+    /// Показатели идущей записи для GUI
+    recording_stats: Mutex<RecordingStats>,
+    /// Нажатие (true) или отпускание глобальной клавиши push-to-talk, пока GUI его не забрал
+    push_to_talk: Mutex<Option<bool>>,
+    /// Пиковый уровень микрофона с последнего чтения GUI, биты f32
+    input_level: AtomicU32,
+    /// Результат последней подписи сообщения, пока GUI его не забрал
+    signed_message: Mutex<Option<Result<SignedMessage, String>>>,
+    toasts: Mutex<ToastSharedState>,
+    /// Фильтр входящих сообщений из настроек с собранными шаблонами
+    content_filter: RwLock<CompiledFilter>,
+    pub(crate) identity: Identity,
     pub(crate) mnemonic: String,
-    pub(crate) username: String,
+    /// Имя пользователя; меняется из GUI, поэтому за блокировкой
+    username: RwLock<String>,
+    /// Настройки созданы при этом запуске: GUI предлагает восстановить существующий кошелёк
+    first_run: bool,
+    /// Профиль настроек, выбранный при запуске
+    profile: String,
+    /// Сеть, с которой работает этот запуск; выбор в настройках применяется после перезапуска
+    network: Network,
+    /// Профиль, с которым перезапустить приложение после закрытия окна (сменилась мнемоника или профиль)
+    restart_profile: Mutex<Option<String>>,
     settings: Arc<Mutex<Settings>>,
 }
 
@@ -69,6 +770,7 @@ lazy_static! {
 impl AppState {
     pub(crate) fn new() -> Result<AppState, ConfigError> {
         let mut settings = Settings::new();
+        let mut first_run = false;
         match settings.load() {
             Ok(_) => {
                 log::info!("Settings loaded successfully");
@@ -76,6 +778,7 @@ impl AppState {
             Err(e) => {
                 if e == "NoFile" {
                     log::info!("No settings.kspk found. Creating new settings...");
+                    first_run = true;
                     settings.initialize_settings().map_err(|err| {
                         log::error!("Failed to init default settings: {}", err);
                         ConfigError::Message(err)
@@ -88,20 +791,59 @@ impl AppState {
 
         let mnemonic = settings.current.mnemonic.clone();
         let username = settings.current.username.clone();
+        let network = settings.current.network;
+        let identity = Identity::from_mnemonic(&mnemonic).map_err(ConfigError::Message)?;
 
-        let (available_input_devices, selected_input_device) = gather_audio_devices();
-        let listener_state = Self::create_listener_state();
+        let (available_input_devices, selected_input_device) = gather_audio_devices(settings.current.input_device.as_deref());
+        let channel = settings.current.recent_channels.first().copied().unwrap_or(DEFAULT_CHANNEL);
+        let listener_state = Self::create_listener_state(channel, settings.current.listen_self, settings.current.mute_all);
         let recorder_state = Self::create_recorder_state(available_input_devices, selected_input_device);
-        let broadcaster_state = Self::create_broadcaster_state();
+        let profile = active_profile();
+        let broadcaster_state = Self::create_broadcaster_state(&profile);
         let chat_state = Self::create_chat_state();
+        let quality_state = Self::create_quality_state();
+        let node_state = Arc::new(RwLock::new(NodeSharedState { active_url: None, last_failover: None, reconnect_attempt: None }));
+        let player_state = Self::create_player_state();
+        let claim_state = Arc::new(RwLock::new(ClaimSharedState { claims: HashMap::new() }));
+        let avatar_state = Self::create_avatar_state(&identity, &settings.current.avatar);
+        let contact_state = Self::create_contact_state(&profile);
+        let retry_state = Self::create_retry_state(&profile);
+        let content_filter = settings.current.content_filter.compile().unwrap_or_else(|err| {
+            log::error!("Error while loading content filter: {}", err);
+            CompiledFilter::default()
+        });
 
         Ok(Self {
             listener_state,
             recorder_state,
             broadcaster_state,
             chat_state,
+            quality_state,
+            node_state,
+            player_state,
+            claim_state,
+            avatar_state,
+            contact_state,
+            retry_state,
+            telemetry: Mutex::new(TelemetryCounters::default()),
+            telemetry_enabled: AtomicBool::new(settings.current.telemetry_enabled),
+            unsaved_crashes: AtomicU32::new(0),
+            console_output: Mutex::new(VecDeque::new()),
+            inspector: Mutex::new(InspectorState::default()),
+            tx_feed: Mutex::new(TxFeedState::default()),
+            recording_stats: Mutex::new(RecordingStats::default()),
+            push_to_talk: Mutex::new(None),
+            input_level: AtomicU32::new(0),
+            signed_message: Mutex::new(None),
+            toasts: Mutex::new(ToastSharedState::default()),
+            content_filter: RwLock::new(content_filter),
+            identity,
             mnemonic,
-            username,
+            username: RwLock::new(username),
+            first_run,
+            profile,
+            network,
+            restart_profile: Mutex::new(None),
             settings: Arc::new(Mutex::new(settings)),
         })
     }
@@ -117,46 +859,152 @@ impl AppState {
         }))
     }
 
-    fn create_listener_state() -> Arc<RwLock<ListenerSharedState>> {
+    fn create_listener_state(channel_number: u32, listen_self: bool, mute_all: bool) -> Arc<RwLock<ListenerSharedState>> {
         Arc::new(RwLock::new(ListenerSharedState {
-            channel_number: DEFAULT_CHANNEL,
-            listen_self: Arc::new(AtomicBool::new(false)),
-            mute_all: Arc::new(AtomicBool::new(false)),
+            channel_number,
+            listen_self: Arc::new(AtomicBool::new(listen_self)),
+            mute_all: Arc::new(AtomicBool::new(mute_all)),
             is_connected: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicBool::new(false)),
+            dropped_payloads: BTreeMap::new(),
         }))
     }
 
-    fn create_broadcaster_state() -> Arc<RwLock<BroadcasterSharedState>> {
+    fn create_broadcaster_state(profile: &str) -> Arc<RwLock<BroadcasterSharedState>> {
+        let sent_transactions = load_json(&Self::tx_history_path(profile)).unwrap_or_else(|err| {
+            log::error!("Error while loading transaction history: {}", err);
+            None
+        });
+        let spend_log = load_json(&Self::spend_log_path(profile)).unwrap_or_else(|err| {
+            log::error!("Error while loading spend log: {}", err);
+            None
+        });
         Arc::new(RwLock::new(BroadcasterSharedState {
             address: None,
             fee_size: DEFAULT_FEE_LEVEL,
+            fee_rates: None,
             balance: 0,
+            pending_balance: 0,
+            outgoing_balance: 0,
             utxos: 0,
+            sent_transactions: sent_transactions.unwrap_or_default(),
             is_connected: Arc::new(AtomicBool::new(false)),
+            is_away: Arc::new(AtomicBool::new(false)),
+            last_send: None,
+            session_cost: SessionCost::default(),
+            spend_log: spend_log.unwrap_or_default(),
+            spend_pause: None,
+            unconfirmed_sends: HashMap::new(),
         }))
     }
 
+    fn tx_history_path(profile: &str) -> String {
+        profile_file_path(DEFAULT_TX_HISTORY_PATH, profile).to_string_lossy().into_owned()
+    }
+
+    fn spend_log_path(profile: &str) -> String {
+        profile_file_path(DEFAULT_SPEND_LOG_PATH, profile).to_string_lossy().into_owned()
+    }
+
     fn create_chat_state() -> ChatSharedState {
-        ChatSharedState { messages_by_channel: DashMap::new() }
+        ChatSharedState {
+            messages_by_channel: DashMap::new(),
+            direct_messages_by_peer: DashMap::new(),
+            pending_permalink: Mutex::new(None),
+            link_previews: DashMap::new(),
+            announcements: DashMap::new(),
+            seen_users: DashMap::new(),
+            seen_users_dirty: AtomicBool::new(false),
+            last_activity: DashMap::new(),
+            unread_mentions: DashMap::new(),
+            pinned_messages: DashMap::new(),
+            revision: AtomicU64::new(0),
+        }
+    }
+
+    fn create_contact_state(profile: &str) -> Arc<RwLock<ContactSharedState>> {
+        let contacts = load_json(&Self::contacts_path(profile)).unwrap_or_else(|err| {
+            log::error!("Error while loading contacts: {}", err);
+            None
+        });
+        Arc::new(RwLock::new(ContactSharedState { contacts: contacts.unwrap_or_default() }))
+    }
+
+    fn create_retry_state(profile: &str) -> Arc<RwLock<RetrySharedState>> {
+        let queue: Vec<PendingSend> = load_json(&Self::retry_queue_path(profile))
+            .unwrap_or_else(|err| {
+                log::error!("Error while loading retry queue: {}", err);
+                None
+            })
+            .unwrap_or_default();
+        if !queue.is_empty() {
+            log::info!("Loaded {} pending send(s) to retry", queue.len());
+        }
+        let next_id = queue.iter().map(|entry| entry.id + 1).max().unwrap_or(0);
+        Arc::new(RwLock::new(RetrySharedState { queue, next_id }))
+    }
+
+    fn retry_queue_path(profile: &str) -> String {
+        profile_file_path(DEFAULT_RETRY_QUEUE_PATH, profile).to_string_lossy().into_owned()
+    }
+
+    fn contacts_path(profile: &str) -> String {
+        profile_file_path(DEFAULT_CONTACTS_PATH, profile).to_string_lossy().into_owned()
+    }
+
+    /// Свой аватар сразу кладём в кэш, чтобы видеть его рядом со своими сообщениями
+    fn create_avatar_state(identity: &Identity, own_avatar: &[u8]) -> Arc<RwLock<AvatarSharedState>> {
+        let mut avatars = HashMap::new();
+        if !own_avatar.is_empty() {
+            avatars.insert(identity.public_key_hex(), own_avatar.to_vec());
+        }
+        Arc::new(RwLock::new(AvatarSharedState {
+            avatars,
+            keys_by_address: HashMap::new(),
+            pending: HashMap::new(),
+            revision: 0,
+            last_announced: None,
+        }))
+    }
+
+    fn create_player_state() -> Arc<RwLock<PlayerSharedState>> {
+        Arc::new(RwLock::new(PlayerSharedState {
+            overflow_speakers: HashMap::new(),
+            stream_stats: HashMap::new(),
+            speaking: HashMap::new(),
+        }))
+    }
+
+    fn create_quality_state() -> Arc<RwLock<QualitySharedState>> {
+        Arc::new(RwLock::new(QualitySharedState {
+            rpc_errors: VecDeque::new(),
+            send_failures: VecDeque::new(),
+            last_notification: None,
+            diagnostics: ConnectionDiagnostics::default(),
+        }))
     }
 
     /// # todo
     pub fn update_selected_input_device(&self, device_name: &str) -> Result<(), String> {
-        let mut recorder_state = self.recorder_state.write().map_err(|_| "Lock poisoned")?;
+        {
+            let mut recorder_state = self.recorder_state.write().map_err(|_| "Lock poisoned")?;
 
-        // Поиск устройства по имени
-        let device = cpal::default_host()
-            .input_devices()
-            .map_err(|e| format!("Failed to get input devices list: {:?}", e))?
-            .find(|d| d.name().ok().as_deref() == Some(device_name));
+            // Поиск устройства по имени
+            let device = cpal::default_host()
+                .input_devices()
+                .map_err(|e| format!("Failed to get input devices list: {:?}", e))?
+                .find(|d| d.name().ok().as_deref() == Some(device_name))
+                .ok_or_else(|| format!("Input device '{}' not found.", device_name))?;
 
-        if let Some(device) = device {
             recorder_state.selected_input_device = Some(device);
             recorder_state.input_device_changed.store(true, Ordering::SeqCst);
-            Ok(())
-        } else {
-            Err(format!("Input device '{}' not found.", device_name))
         }
+
+        // Запоминаем выбор, чтобы при следующем запуске открыть то же устройство
+        self.with_settings_write(|settings| {
+            settings.current.input_device = Some(device_name.to_string());
+            settings.save()
+        })
     }
 
     /// Метод для чтения recorder_state
@@ -213,62 +1061,241 @@ impl AppState {
         f(&mut *guard)
     }
 
-    /// Единый метод для записи Settings.
-    pub fn with_settings_write<F, R>(&self, f: F) -> Result<R, String>
+    /// Метод для чтения quality_state
+    pub fn with_quality_state_read<F, R>(&self, f: F) -> Result<R, String>
     where
-        F: FnOnce(&mut Settings) -> Result<R, String>,
+        F: FnOnce(&QualitySharedState) -> R,
     {
-        let mut guard = self.settings.lock().map_err(|_| "Mutex (settings) poisoned")?;
+        let guard = self.quality_state.read().map_err(|_| "Lock poisoned")?;
+        Ok(f(&*guard))
+    }
+
+    /// Метод для записи в quality_state
+    pub fn with_quality_state_write<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut QualitySharedState) -> Result<R, String>,
+    {
+        let mut guard = self.quality_state.write().map_err(|_| "Lock poisoned")?;
         f(&mut *guard)
     }
 
-    // ------------------------------------------
-    // RecorderSharedState helpers
-    // ------------------------------------------
+    /// Метод для чтения node_state
+    pub fn with_node_state_read<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&NodeSharedState) -> R,
+    {
+        let guard = self.node_state.read().map_err(|_| "Lock poisoned")?;
+        Ok(f(&*guard))
+    }
 
-    /// Проверяет, был ли изменен input_device_changed
-    pub fn is_input_device_changed(&self) -> Result<bool, String> {
-        self.with_recorder_state_read(|recorder_state| {
-            let changed = recorder_state.input_device_changed.load(Ordering::SeqCst);
-            changed
-        })
+    /// Метод для записи в node_state
+    pub fn with_node_state_write<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut NodeSharedState) -> Result<R, String>,
+    {
+        let mut guard = self.node_state.write().map_err(|_| "Lock poisoned")?;
+        f(&mut *guard)
     }
 
-    /// Устанавливает флаг input_device_changed
-    pub fn set_input_device_changed(&self, value: bool) -> Result<(), String> {
-        self.with_recorder_state_write(|recorder_state| {
-            recorder_state.input_device_changed.store(value, Ordering::SeqCst);
-            Ok(())
-        })
+    /// Метод для чтения player_state
+    pub fn with_player_state_read<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&PlayerSharedState) -> R,
+    {
+        let guard = self.player_state.read().map_err(|_| "Lock poisoned")?;
+        Ok(f(&*guard))
     }
 
-    /// Возвращает текущее выбранное устройство ввода
-    pub fn get_selected_input_device(&self) -> Result<Option<Device>, String> {
-        self.with_recorder_state_read(|recorder_state| recorder_state.selected_input_device.clone())
+    /// Метод для записи в player_state
+    pub fn with_player_state_write<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut PlayerSharedState) -> Result<R, String>,
+    {
+        let mut guard = self.player_state.write().map_err(|_| "Lock poisoned")?;
+        f(&mut *guard)
     }
 
-    /// Устанавливает выбранное устройство ввода
-    pub fn set_selected_input_device(&self, device: Option<Device>) -> Result<(), String> {
-        self.with_recorder_state_write(|recorder_state| {
-            recorder_state.selected_input_device = device;
-            Ok(())
-        })
+    /// Метод для чтения claim_state
+    pub fn with_claim_state_read<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&ClaimSharedState) -> R,
+    {
+        let guard = self.claim_state.read().map_err(|_| "Lock poisoned")?;
+        Ok(f(&*guard))
     }
 
-    // ------------------------------------------
-    // ListenerSharedState helpers
-    // ------------------------------------------
+    /// Метод для записи в claim_state
+    pub fn with_claim_state_write<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut ClaimSharedState) -> Result<R, String>,
+    {
+        let mut guard = self.claim_state.write().map_err(|_| "Lock poisoned")?;
+        f(&mut *guard)
+    }
+
+    /// Метод для чтения avatar_state
+    pub fn with_avatar_state_read<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&AvatarSharedState) -> R,
+    {
+        let guard = self.avatar_state.read().map_err(|_| "Lock poisoned")?;
+        Ok(f(&*guard))
+    }
+
+    /// Метод для записи в avatar_state
+    pub fn with_avatar_state_write<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut AvatarSharedState) -> Result<R, String>,
+    {
+        let mut guard = self.avatar_state.write().map_err(|_| "Lock poisoned")?;
+        f(&mut *guard)
+    }
+
+    /// Метод для чтения contact_state
+    pub fn with_contact_state_read<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&ContactSharedState) -> R,
+    {
+        let guard = self.contact_state.read().map_err(|_| "Lock poisoned")?;
+        Ok(f(&*guard))
+    }
+
+    /// Метод для записи в contact_state
+    pub fn with_contact_state_write<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut ContactSharedState) -> Result<R, String>,
+    {
+        let mut guard = self.contact_state.write().map_err(|_| "Lock poisoned")?;
+        f(&mut *guard)
+    }
+
+    /// Метод для чтения retry_state
+    pub fn with_retry_state_read<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&RetrySharedState) -> R,
+    {
+        let guard = self.retry_state.read().map_err(|_| "Lock poisoned")?;
+        Ok(f(&*guard))
+    }
+
+    /// Метод для записи в retry_state
+    pub fn with_retry_state_write<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut RetrySharedState) -> Result<R, String>,
+    {
+        let mut guard = self.retry_state.write().map_err(|_| "Lock poisoned")?;
+        f(&mut *guard)
+    }
+
+    /// Единый метод для чтения Settings.
+    pub fn with_settings_read<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&Settings) -> R,
+    {
+        let guard = self.settings.lock().map_err(|_| "Mutex (settings) poisoned")?;
+        Ok(f(&*guard))
+    }
+
+    /// Единый метод для записи Settings.
+    pub fn with_settings_write<F, R>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut Settings) -> Result<R, String>,
+    {
+        let mut guard = self.settings.lock().map_err(|_| "Mutex (settings) poisoned")?;
+        f(&mut *guard)
+    }
+
+    // ------------------------------------------
+    // RecorderSharedState helpers
+    // ------------------------------------------
+
+    /// Проверяет, был ли изменен input_device_changed
+    pub fn is_input_device_changed(&self) -> Result<bool, String> {
+        self.with_recorder_state_read(|recorder_state| {
+            let changed = recorder_state.input_device_changed.load(Ordering::SeqCst);
+            changed
+        })
+    }
+
+    /// Устанавливает флаг input_device_changed
+    pub fn set_input_device_changed(&self, value: bool) -> Result<(), String> {
+        self.with_recorder_state_write(|recorder_state| {
+            recorder_state.input_device_changed.store(value, Ordering::SeqCst);
+            Ok(())
+        })
+    }
+
+    /// Возвращает текущее выбранное устройство ввода
+    pub fn get_selected_input_device(&self) -> Result<Option<Device>, String> {
+        self.with_recorder_state_read(|recorder_state| recorder_state.selected_input_device.clone())
+    }
+
+    /// Устанавливает выбранное устройство ввода
+    pub fn set_selected_input_device(&self, device: Option<Device>) -> Result<(), String> {
+        self.with_recorder_state_write(|recorder_state| {
+            recorder_state.selected_input_device = device;
+            Ok(())
+        })
+    }
+
+    // ------------------------------------------
+    // ListenerSharedState helpers
+    // ------------------------------------------
 
     /// Возвращает текущий канал
     pub fn get_channel_number(&self) -> Result<u32, String> {
         self.with_listener_state_read(|listener_state| listener_state.channel_number)
     }
 
-    /// Устанавливает текущий канал
+    /// Устанавливает текущий канал и поднимает его в начало недавних
     pub fn set_channel_number(&self, channel_number: u32) -> Result<(), String> {
         self.with_listener_state_write(|listener_state| {
             listener_state.channel_number = channel_number;
             Ok(())
+        })?;
+        self.clear_tx_feed();
+        self.with_settings_write(|settings| {
+            let recent = &mut settings.current.recent_channels;
+            recent.retain(|channel| *channel != channel_number);
+            recent.insert(0, channel_number);
+            recent.truncate(MAX_RECENT_CHANNELS);
+            settings.save()
+        })
+    }
+
+    pub fn get_recent_channels(&self) -> Result<Vec<u32>, String> {
+        self.with_settings_read(|settings| settings.current.recent_channels.clone())
+    }
+
+    pub fn get_favorite_channels(&self) -> Result<Vec<u32>, String> {
+        self.with_settings_read(|settings| settings.current.favorite_channels.clone())
+    }
+
+    pub fn set_channel_favorite(&self, channel_number: u32, favorite: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            let favorites = &mut settings.current.favorite_channels;
+            favorites.retain(|channel| *channel != channel_number);
+            if favorite {
+                favorites.push(channel_number);
+            }
+            settings.save()
+        })
+    }
+
+    /// Свои описания каналов по номеру
+    pub fn get_channel_info(&self) -> Result<BTreeMap<u32, ChannelInfo>, String> {
+        self.with_settings_read(|settings| settings.current.channel_info.clone())
+    }
+
+    /// Сохраняет описание канала; пустое описание удаляется
+    pub fn set_channel_info(&self, channel_number: u32, info: ChannelInfo) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            if info.is_empty() {
+                settings.current.channel_info.remove(&channel_number);
+            } else {
+                settings.current.channel_info.insert(channel_number, info);
+            }
+            settings.save()
         })
     }
 
@@ -282,6 +1309,10 @@ impl AppState {
         self.with_listener_state_write(|listener_state| {
             listener_state.listen_self.store(value, Ordering::SeqCst);
             Ok(())
+        })?;
+        self.with_settings_write(|settings| {
+            settings.current.listen_self = value;
+            settings.save()
         })
     }
 
@@ -295,6 +1326,22 @@ impl AppState {
         self.with_listener_state_write(|listener_state| {
             listener_state.mute_all.store(value, Ordering::SeqCst);
             Ok(())
+        })?;
+        self.with_settings_write(|settings| {
+            settings.current.mute_all = value;
+            settings.save()
+        })
+    }
+
+    /// Приостановлена ли обработка входящих
+    pub fn is_listening_paused(&self) -> Result<bool, String> {
+        self.with_listener_state_read(|state| state.paused.load(Ordering::SeqCst))
+    }
+
+    pub fn set_listening_paused(&self, paused: bool) -> Result<(), String> {
+        self.with_listener_state_write(|state| {
+            state.paused.store(paused, Ordering::SeqCst);
+            Ok(())
         })
     }
 
@@ -314,6 +1361,20 @@ impl AppState {
         })
     }
 
+    /// Учитывает payload, отброшенный Listener по причине `reason`
+    pub fn record_dropped_payload(&self, reason: &'static str) {
+        if let Err(err) = self.with_listener_state_write(|state| {
+            *state.dropped_payloads.entry(reason).or_insert(0) += 1;
+            Ok(())
+        }) {
+            log::error!("Error while counting dropped payload: {}", err);
+        }
+    }
+
+    pub fn get_dropped_payloads(&self) -> Result<BTreeMap<&'static str, u64>, String> {
+        self.with_listener_state_read(|state| state.dropped_payloads.clone())
+    }
+
     // ------------------------------------------
     // Методы для доступа к полям BroadcasterSharedState
     // ------------------------------------------
@@ -331,6 +1392,284 @@ impl AppState {
         })
     }
 
+    /// Запоминает оценку комиссии от ноды
+    pub fn set_fee_rates(&self, fee_rates: FeeRates) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            state.fee_rates = Some(fee_rates);
+            Ok(())
+        })
+    }
+
+    pub fn get_fee_rates(&self) -> Result<Option<FeeRates>, String> {
+        self.with_broadcaster_state_read(|state| state.fee_rates)
+    }
+
+    pub fn get_fee_mode(&self) -> Result<FeeMode, String> {
+        self.with_settings_read(|settings| settings.current.fee_mode)
+    }
+
+    /// Устанавливает режим комиссии и сохраняет настройки
+    pub fn set_fee_mode(&self, fee_mode: FeeMode) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.fee_mode = fee_mode;
+            settings.save()
+        })
+    }
+
+    /// Доплата к комиссии для транзакции с payload размером `payload_size`.
+    /// В автоматическом режиме считается по оценке ноды; пока оценки нет — берётся ручное значение
+    pub fn get_priority_fee(&self, payload_size: usize) -> Result<u64, String> {
+        let fee_mode = self.get_fee_mode()?;
+        self.with_broadcaster_state_read(|state| {
+            state.fee_rates.and_then(|rates| rates.priority_fee(fee_mode, payload_size)).unwrap_or(state.fee_size)
+        })
+    }
+
+    /// Ожидаемая комиссия транзакции с payload размером `payload_size`: минимальная по массе и доплата
+    pub fn estimate_transaction_fee(&self, payload_size: usize) -> Result<u64, String> {
+        let mass = ESTIMATED_TX_BASE_MASS + payload_size as u64;
+        Ok((mass as f64 * MINIMUM_FEERATE).ceil() as u64 + self.get_priority_fee(payload_size)?)
+    }
+
+    /// Оценка расходов на COST_ESTIMATE_RECORDING_SECS записи при текущих битрейте и пакетной отправке
+    pub fn estimate_recording_cost(&self) -> Result<CostEstimate, String> {
+        let fragment_bytes = self.get_opus_bitrate()?.max(0) as usize / 8 * VOICE_FRAGMENT_MS as usize / 1000;
+        let fragments_per_tx = if self.is_voice_batching_enabled()? { VOICE_BATCH_MAX_FRAGMENTS } else { 1 };
+        let payload_size = HEADER_SIZE + fragments_per_tx * (VOICE_BATCH_ENTRY_HEADER + fragment_bytes);
+        let fragments = (COST_ESTIMATE_RECORDING_SECS * 1000).div_ceil(VOICE_FRAGMENT_MS);
+        let transactions = fragments.div_ceil(fragments_per_tx as u64);
+        Ok(CostEstimate { transactions, fee: self.estimate_transaction_fee(payload_size)? * transactions })
+    }
+
+    /// Оценка расходов на рассылку своего аватара; None, если аватар не задан
+    pub fn estimate_avatar_cost(&self) -> Result<Option<CostEstimate>, String> {
+        let Some(avatar) = self.get_own_avatar()? else {
+            return Ok(None);
+        };
+        let mut estimate = CostEstimate::default();
+        for chunk in avatar.chunks(AVATAR_CHUNK_SIZE) {
+            estimate.add(self.estimate_transaction_fee(HEADER_SIZE + CHUNK_HEADER_SIZE + chunk.len())?);
+        }
+        Ok(Some(estimate))
+    }
+
+    /// Добавляет транзакцию в историю и сохраняет её на диск
+    pub fn record_sent_transaction(&self, transaction: SentTransaction) -> Result<(), String> {
+        let snapshot = self.with_broadcaster_state_write(|state| {
+            state.sent_transactions.push_back(transaction);
+            while state.sent_transactions.len() > MAX_TX_HISTORY {
+                state.sent_transactions.pop_front();
+            }
+            Ok(state.sent_transactions.clone())
+        })?;
+        save_json(&Self::tx_history_path(&self.profile), &snapshot)
+    }
+
+    /// Заменяет в истории транзакцию, вытесненную повышением комиссии, и сохраняет историю.
+    /// Если её в истории уже нет, замена добавляется как новая запись
+    pub fn replace_sent_transaction(&self, replaced_tx_id: &str, transaction: SentTransaction) -> Result<(), String> {
+        let snapshot = self.with_broadcaster_state_write(|state| {
+            match state.sent_transactions.iter_mut().find(|sent| sent.tx_id == replaced_tx_id) {
+                Some(sent) => *sent = transaction,
+                None => {
+                    state.sent_transactions.push_back(transaction);
+                    while state.sent_transactions.len() > MAX_TX_HISTORY {
+                        state.sent_transactions.pop_front();
+                    }
+                }
+            }
+            Ok(state.sent_transactions.clone())
+        })?;
+        save_json(&Self::tx_history_path(&self.profile), &snapshot)
+    }
+
+    /// Учитывает комиссию своей транзакции в расходах сессии; `channel` — канал payload, если он есть
+    pub fn record_session_cost(&self, channel: Option<u32>, fee: u64, payload_size: usize) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            state.session_cost.record(channel, fee, payload_size);
+            Ok(())
+        })
+    }
+
+    /// Учитывает в расходах сессии доплату за повышение комиссии
+    pub fn record_fee_bump(&self, channel: Option<u32>, extra_fee: u64) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            state.session_cost.record_fee_bump(channel, extra_fee);
+            Ok(())
+        })
+    }
+
+    pub fn get_session_cost(&self) -> Result<SessionCost, String> {
+        self.with_broadcaster_state_read(|state| state.session_cost.clone())
+    }
+
+    /// Учитывает трату (комиссию или перевод на чужой адрес) для лимитов и сохраняет журнал трат
+    pub fn record_spend(&self, sompi: u64) -> Result<(), String> {
+        let now = chrono::Utc::now().timestamp();
+        let snapshot = self.with_broadcaster_state_write(|state| {
+            state.spend_log.record(now, sompi);
+            Ok(state.spend_log.clone())
+        })?;
+        save_json(&Self::spend_log_path(&self.profile), &snapshot)
+    }
+
+    /// Проверяет лимиты трат перед отправкой, которая потратит `extra` сомпи сверх комиссии.
+    /// При превышении отправка приостанавливается (о начале паузы сообщает уведомление), а в ошибке — описание лимита.
+    /// Пауза снимается сама, когда траты выходят из окна лимита или лимит поднят
+    pub fn check_spend_limits(&self, extra: u64) -> Result<(), String> {
+        let limits = self.get_spend_limits()?;
+        let now = chrono::Utc::now().timestamp();
+        let (exceeded, newly_paused) = self.with_broadcaster_state_write(|state| {
+            let exceeded = limits.exceeded(&state.spend_log, now, extra);
+            // Перевод, который не влезает в лимит, отклоняется, но остальную отправку не останавливает
+            if extra > 0 && limits.exceeded(&state.spend_log, now, 0).is_none() {
+                return Ok((exceeded, false));
+            }
+            let newly_paused = exceeded.is_some() && state.spend_pause.is_none();
+            if state.spend_pause.is_some() && exceeded.is_none() {
+                log::info!("Spending is back under the limits, transmission resumed");
+            }
+            state.spend_pause = exceeded.clone();
+            Ok((exceeded, newly_paused))
+        })?;
+        match exceeded {
+            Some(reason) => {
+                if newly_paused {
+                    log::warn!("Transmission paused: {}", reason);
+                    self.push_toast(ToastKind::SpendLimit(reason.clone()));
+                }
+                Err(reason)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Описание лимита, из-за которого отправка приостановлена
+    pub fn get_spend_pause(&self) -> Result<Option<String>, String> {
+        self.with_broadcaster_state_read(|state| state.spend_pause.clone())
+    }
+
+    /// Начинает ждать попадания своей транзакции в блок
+    pub fn track_unconfirmed_send(&self, tx_id: &str) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            state.unconfirmed_sends.insert(tx_id.to_string(), UnconfirmedSend { sent_at: Instant::now(), bumps: 0, in_flight: false });
+            Ok(())
+        })
+    }
+
+    /// Listener увидел транзакцию в блоке
+    pub fn confirm_send(&self, tx_id: &str) {
+        let _ = self.with_broadcaster_state_write(|state| {
+            if state.unconfirmed_sends.remove(tx_id).is_some() {
+                log::debug!("Send {} confirmed", tx_id);
+            }
+            Ok(())
+        });
+    }
+
+    pub fn is_send_unconfirmed(&self, tx_id: &str) -> bool {
+        self.with_broadcaster_state_read(|state| state.unconfirmed_sends.contains_key(tx_id)).unwrap_or(false)
+    }
+
+    /// Транзакции, не попавшие в блок за `timeout`: (идентификатор, число замен). Помечаются как заменяемые.
+    /// Исчерпавшие FEE_BUMP_MAX_BUMPS больше не отслеживаются
+    pub fn take_stuck_sends(&self, timeout: Duration) -> Result<Vec<(String, u32)>, String> {
+        self.with_broadcaster_state_write(|state| {
+            state.unconfirmed_sends.retain(|tx_id, send| {
+                let keep = send.bumps < FEE_BUMP_MAX_BUMPS || send.sent_at.elapsed() < timeout;
+                if !keep {
+                    log::warn!("Send {} is still not confirmed after {} fee bumps, giving up", tx_id, send.bumps);
+                }
+                keep
+            });
+            Ok(state
+                .unconfirmed_sends
+                .iter_mut()
+                .filter(|(_, send)| !send.in_flight && send.sent_at.elapsed() >= timeout)
+                .map(|(tx_id, send)| {
+                    send.in_flight = true;
+                    (tx_id.clone(), send.bumps)
+                })
+                .collect())
+        })
+    }
+
+    /// Замена отправлена: старая транзакция больше не ждёт блока, новая ждёт вместо неё
+    pub fn finish_fee_bump(&self, tx_id: &str, replacement: Option<&str>) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            let Some(send) = state.unconfirmed_sends.remove(tx_id) else {
+                return Ok(());
+            };
+            if let Some(replacement) = replacement {
+                state.unconfirmed_sends.insert(
+                    replacement.to_string(),
+                    UnconfirmedSend { sent_at: Instant::now(), bumps: send.bumps + 1, in_flight: false },
+                );
+            }
+            Ok(())
+        })?;
+        if let Some(replacement) = replacement {
+            self.chat_state.replace_tx_id(tx_id, replacement);
+        }
+        Ok(())
+    }
+
+    /// Замена не удалась: попробуем ещё раз через FEE_BUMP_TIMEOUT_SECS, попытка засчитывается
+    pub fn fail_fee_bump(&self, tx_id: &str) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            if let Some(send) = state.unconfirmed_sends.get_mut(tx_id) {
+                send.bumps += 1;
+                send.sent_at = Instant::now();
+                send.in_flight = false;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn get_fee_bump_percent(&self) -> Result<Option<u32>, String> {
+        self.with_settings_read(|settings| settings.current.fee_bump_percent)
+    }
+
+    pub fn set_fee_bump_percent(&self, percent: Option<u32>) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.fee_bump_percent = percent;
+            settings.save()
+        })
+    }
+
+    /// Траты за последний час и за сутки, сомпи
+    pub fn get_recent_spend(&self) -> Result<(u64, u64), String> {
+        let now = chrono::Utc::now().timestamp();
+        self.with_broadcaster_state_read(|state| {
+            (state.spend_log.spent_since(now - 60 * 60), state.spend_log.spent_since(now - 24 * 60 * 60))
+        })
+    }
+
+    /// История отправленных транзакций, новые первыми
+    pub fn get_sent_transactions(&self) -> Result<Vec<SentTransaction>, String> {
+        self.with_broadcaster_state_read(|state| state.sent_transactions.iter().rev().cloned().collect())
+    }
+
+    /// Сохраняет историю отправок профиля в CSV (старые первыми) и возвращает путь к файлу
+    pub fn export_sent_transactions_csv(&self) -> Result<String, String> {
+        let csv = self.with_broadcaster_state_read(|state| sent_transactions_to_csv(&state.sent_transactions))?;
+        let path = profile_file_path(DEFAULT_TX_EXPORT_PATH, &self.profile);
+        std::fs::write(&path, csv).map_err(|e| format!("Error writing file {:?}: {}", path, e))?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    /// Сохраняет переписку канала (старые сообщения первыми, без истёкших) в файл
+    /// `kaspeak_channel_<канал>` профиля и возвращает путь к нему
+    pub fn export_chat_transcript(&self, channel: u32, format: TranscriptFormat) -> Result<String, String> {
+        let mut messages = self.chat_state.messages_by_channel.get(&channel).map(|messages| messages.clone()).unwrap_or_default();
+        messages.retain(|message| !message.is_expired());
+        let transcript = render_transcript(format, channel, &messages)?;
+        let file = Path::new(DEFAULT_CHAT_EXPORT_PATH).with_file_name(format!("kaspeak_channel_{}.{}", channel, format.extension()));
+        let path = profile_file_path(&file.to_string_lossy(), &self.profile);
+        std::fs::write(&path, transcript).map_err(|e| format!("Error writing file {:?}: {}", path, e))?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+
     /// Получить баланс
     pub fn get_balance(&self) -> Result<u64, String> {
         self.with_broadcaster_state_read(|state| state.balance)
@@ -344,6 +1683,19 @@ impl AppState {
         })
     }
 
+    /// Незрелые поступления и уходящие суммы: (pending, outgoing)
+    pub fn get_pending_balance(&self) -> Result<(u64, u64), String> {
+        self.with_broadcaster_state_read(|state| (state.pending_balance, state.outgoing_balance))
+    }
+
+    pub fn set_pending_balance(&self, pending: u64, outgoing: u64) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            state.pending_balance = pending;
+            state.outgoing_balance = outgoing;
+            Ok(())
+        })
+    }
+
     /// Получить адрес аккаунта
     pub fn get_account_address(&self) -> Result<Option<String>, String> {
         self.with_broadcaster_state_read(|state| state.address.clone().map_or(None, |addr| Some(addr.address_to_string())))
@@ -357,6 +1709,45 @@ impl AppState {
         })
     }
 
+    pub fn get_address_rotation(&self) -> Result<Option<u64>, String> {
+        self.with_settings_read(|settings| settings.current.address_rotation_secs)
+    }
+
+    /// Включённая смена отсчитывает период с момента включения, а не с последней смены адреса
+    pub fn set_address_rotation(&self, secs: Option<u64>) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            if settings.current.address_rotation_secs.is_none() {
+                settings.current.address_rotated_at = chrono::Utc::now().timestamp();
+            }
+            settings.current.address_rotation_secs = secs;
+            settings.save()
+        })
+    }
+
+    pub fn get_receive_address_index(&self) -> Result<u32, String> {
+        self.with_settings_read(|settings| settings.current.receive_address_index)
+    }
+
+    /// Запоминает новый адрес получения: его номер переживает перезапуск, а период смены отсчитывается заново
+    pub fn record_address_rotation(&self, address: Address) -> Result<(), String> {
+        self.set_account_address(Some(address))?;
+        self.with_settings_write(|settings| {
+            settings.current.receive_address_index += 1;
+            settings.current.address_rotated_at = chrono::Utc::now().timestamp();
+            settings.save()
+        })
+    }
+
+    /// Смена адреса включена и с прошлой смены прошёл период
+    pub fn is_address_rotation_due(&self) -> bool {
+        self.with_settings_read(|settings| {
+            settings.current.address_rotation_secs.is_some_and(|period| {
+                chrono::Utc::now().timestamp().saturating_sub(settings.current.address_rotated_at) >= period as i64
+            })
+        })
+        .unwrap_or(false)
+    }
+
     /// Получить количество UTXO
     pub fn get_utxos(&self) -> Result<usize, String> {
         self.with_broadcaster_state_read(|state| state.utxos)
@@ -386,21 +1777,1586 @@ impl AppState {
         })
     }
 
-    pub fn get_username(&self) -> String {
-        self.username.clone()
+    /// Фиксирует отправку транзакции с payload
+    pub fn mark_broadcaster_send(&self) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            state.last_send = Some(Instant::now());
+            Ok(())
+        })
     }
 
-    pub fn set_username(&mut self, new_username: &str) -> Result<(), String> {
-        self.username = new_username.to_string();
-        self.with_settings_write(|settings| {
-            settings.current.username = new_username.to_string();
-            settings.save()?;
-            Ok(())
-        })?;
-        Ok(())
+    /// Сколько Broadcaster ничего не отправлял; None, если отправок ещё не было
+    pub fn broadcaster_idle_for(&self) -> Result<Option<Duration>, String> {
+        self.with_broadcaster_state_read(|state| state.last_send.map(|at| at.elapsed()))
     }
 
-    pub fn get_mnemonic(&self) -> String {
-        self.mnemonic.clone()
+    /// Отошёл ли пользователь
+    pub fn is_away(&self) -> Result<bool, String> {
+        self.with_broadcaster_state_read(|state| state.is_away.load(Ordering::SeqCst))
+    }
+
+    /// Устанавливает статус «отошёл»
+    pub fn set_away(&self, away: bool) -> Result<(), String> {
+        self.with_broadcaster_state_write(|state| {
+            state.is_away.store(away, Ordering::SeqCst);
+            Ok(())
+        })
+    }
+
+    // ------------------------------------------
+    // QualitySharedState helpers
+    // ------------------------------------------
+
+    /// Фиксирует ошибку RPC (подключение, обработка уведомлений, ping)
+    pub fn record_rpc_error(&self, error: &str) {
+        let _ = self.with_quality_state_write(|state| {
+            QualitySharedState::push_event(&mut state.rpc_errors);
+            state.diagnostics.last_error = Some((chrono::Utc::now().timestamp(), error.to_string()));
+            Ok(())
+        });
+    }
+
+    /// Число ошибок RPC за окно отказа ноды, не считая случившихся до последнего переключения
+    pub fn count_failover_rpc_errors(&self) -> u32 {
+        let since = self.with_node_state_read(|state| state.last_failover).ok().flatten();
+        self.with_quality_state_read(|state| {
+            state
+                .rpc_errors
+                .iter()
+                .filter(|t| t.elapsed() <= Duration::from_secs(FAILOVER_ERROR_WINDOW_SECS) && since.map_or(true, |since| **t > since))
+                .count() as u32
+        })
+        .unwrap_or(0)
+    }
+
+    /// Нода не ответила вовремя: это ошибка RPC (в счёт качества связи и отказа ноды), о которой сообщаем отдельно
+    pub fn record_rpc_timeout(&self, what: &str, timeout: Duration) {
+        self.record_rpc_error(&format!("{} timed out after {} s", what, timeout.as_secs()));
+        self.push_toast(ToastKind::RpcTimeout(what.to_string(), timeout.as_secs()));
+    }
+
+    /// Фиксирует неудачную отправку транзакции
+    pub fn record_send_failure(&self, error: &str) {
+        let _ = self.with_quality_state_write(|state| {
+            QualitySharedState::push_event(&mut state.send_failures);
+            state.diagnostics.last_error = Some((chrono::Utc::now().timestamp(), error.to_string()));
+            Ok(())
+        });
+    }
+
+    /// Запоминает ответ ноды на get_server_info при подключении
+    pub fn set_server_info(&self, version: String, network: String, is_synced: bool, daa_score: u64) {
+        let _ = self.with_quality_state_write(|state| {
+            state.diagnostics.server_version = Some(version);
+            state.diagnostics.server_network = Some(network);
+            state.diagnostics.is_synced = Some(is_synced);
+            state.diagnostics.daa_score = Some(daa_score);
+            Ok(())
+        });
+    }
+
+    /// Синхронизация и DAA score по периодическому опросу ноды
+    pub fn set_sync_status(&self, is_synced: bool, daa_score: u64) {
+        let _ = self.with_quality_state_write(|state| {
+            state.diagnostics.is_synced = Some(is_synced);
+            state.diagnostics.daa_score = Some(state.diagnostics.daa_score.unwrap_or(0).max(daa_score));
+            Ok(())
+        });
+    }
+
+    /// DAA score нового блока; счёт не уменьшается, блоки приходят не по порядку
+    pub fn update_daa_score(&self, daa_score: u64) {
+        let _ = self.with_quality_state_write(|state| {
+            state.diagnostics.daa_score = Some(state.diagnostics.daa_score.unwrap_or(0).max(daa_score));
+            Ok(())
+        });
+    }
+
+    pub fn set_latency(&self, latency_ms: Option<u64>) {
+        let _ = self.with_quality_state_write(|state| {
+            state.diagnostics.latency_ms = latency_ms;
+            Ok(())
+        });
+    }
+
+    /// Подписки Listener: Some после подписки при подключении, None после отключения
+    pub fn set_listener_scopes(&self, scopes: Option<NotificationScopes>) {
+        let _ = self.with_quality_state_write(|state| {
+            state.diagnostics.listener_scopes = scopes;
+            Ok(())
+        });
+    }
+
+    pub fn get_connection_diagnostics(&self) -> Result<ConnectionDiagnostics, String> {
+        self.with_quality_state_read(|state| state.diagnostics.clone())
+    }
+
+    /// Фиксирует получение уведомления от ноды
+    pub fn record_notification(&self) {
+        let _ = self.with_quality_state_write(|state| {
+            state.last_notification = Some(Instant::now());
+            Ok(())
+        });
+    }
+
+    /// Сбрасывает время последнего уведомления (при отключении)
+    pub fn reset_notification_time(&self) {
+        let _ = self.with_quality_state_write(|state| {
+            state.last_notification = None;
+            Ok(())
+        });
+    }
+
+    /// Возвращает текущую оценку качества соединения
+    pub fn get_connection_quality(&self) -> Result<ConnectionQuality, String> {
+        let is_connected = self.is_listener_connected()? && self.is_broadcaster_connected()?;
+        self.with_quality_state_read(|state| state.evaluate(is_connected))
+    }
+
+    // ------------------------------------------
+    // PlayerSharedState helpers
+    // ------------------------------------------
+
+    /// Отмечает собеседника, чей фрагмент отброшен из-за лимита потоков
+    pub fn mark_overflow_speaker(&self, username: &str) {
+        let _ = self.with_player_state_write(|state| {
+            state.overflow_speakers.insert(username.to_string(), Instant::now());
+            Ok(())
+        });
+    }
+
+    /// Учитывает входящий фрагмент в статистике отправителя и возвращает её актуальное состояние
+    pub fn record_stream_fragment(
+        &self,
+        username: &str,
+        fragment_number: u32,
+        status_flag: StatusFlag,
+    ) -> Result<StreamStats, String> {
+        let (stats, lost_before) = self.with_player_state_write(|state| {
+            let stats = state.stream_stats.entry(username.to_string()).or_default();
+            let lost_before = stats.lost;
+            stats.record(fragment_number, status_flag);
+            Ok((stats.clone(), lost_before))
+        })?;
+        if stats.lost > lost_before {
+            self.record_audio_dropouts(stats.lost - lost_before);
+        }
+        Ok(stats)
+    }
+
+    /// Статистика потоков по отправителям, отсортированная по имени
+    pub fn get_stream_stats(&self) -> Result<Vec<(String, StreamStats)>, String> {
+        self.with_player_state_read(|state| {
+            let mut stats: Vec<(String, StreamStats)> =
+                state.stream_stats.iter().map(|(name, stats)| (name.clone(), stats.clone())).collect();
+            stats.sort_by(|a, b| a.0.cmp(&b.0));
+            stats
+        })
+    }
+
+    /// Отмечает голосовой фрагмент собеседника; фрагмент End снимает отметку сразу
+    pub fn mark_speaking(&self, username: &str, status_flag: StatusFlag) {
+        let _ = self.with_player_state_write(|state| {
+            if status_flag == StatusFlag::End {
+                state.speaking.remove(username);
+            } else {
+                state.speaking.insert(username.to_string(), Instant::now());
+            }
+            state.speaking.retain(|_, at| at.elapsed() <= Duration::from_millis(SPEAKING_INDICATION_MS));
+            Ok(())
+        });
+    }
+
+    /// Собеседники, от которых только что приходил голос
+    pub fn get_speaking_users(&self) -> Result<Vec<String>, String> {
+        self.with_player_state_read(|state| {
+            state
+                .speaking
+                .iter()
+                .filter(|(_, at)| at.elapsed() <= Duration::from_millis(SPEAKING_INDICATION_MS))
+                .map(|(name, _)| name.clone())
+                .collect()
+        })
+    }
+
+    /// Возвращает собеседников, недавно заглушённых из-за лимита потоков
+    pub fn get_overflow_speakers(&self) -> Result<Vec<String>, String> {
+        self.with_player_state_read(|state| {
+            let mut speakers: Vec<String> = state
+                .overflow_speakers
+                .iter()
+                .filter(|(_, at)| at.elapsed() <= Duration::from_millis(OVERFLOW_INDICATION_MS))
+                .map(|(name, _)| name.clone())
+                .collect();
+            speakers.sort();
+            speakers
+        })
+    }
+
+    // ------------------------------------------
+    // Console helpers
+    // ------------------------------------------
+
+    /// Добавляет строки в вывод консоли разработчика, старые строки вытесняются
+    pub fn push_console_output(&self, output: &str) {
+        if let Ok(mut lines) = self.console_output.lock() {
+            lines.extend(output.lines().map(str::to_string));
+            while lines.len() > MAX_CONSOLE_LINES {
+                lines.pop_front();
+            }
+        }
+    }
+
+    pub fn get_console_output(&self) -> Vec<String> {
+        self.console_output.lock().map(|lines| lines.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    pub fn is_payload_inspector_enabled(&self) -> bool {
+        self.inspector.lock().map(|state| state.enabled).unwrap_or(false)
+    }
+
+    /// Включает/выключает инспектор; при выключении записи удаляются
+    pub fn set_payload_inspector_enabled(&self, enabled: bool) {
+        if let Ok(mut state) = self.inspector.lock() {
+            state.enabled = enabled;
+            if !enabled {
+                state.entries.clear();
+                state.revision += 1;
+            }
+        }
+    }
+
+    pub fn record_inspected_payload(&self, inspected: InspectedPayload) {
+        if let Ok(mut state) = self.inspector.lock() {
+            state.entries.push_back(inspected);
+            while state.entries.len() > MAX_INSPECTED_PAYLOADS {
+                state.entries.pop_front();
+            }
+            state.revision += 1;
+        }
+    }
+
+    pub fn get_inspector_revision(&self) -> u64 {
+        self.inspector.lock().map(|state| state.revision).unwrap_or(0)
+    }
+
+    pub fn get_inspected_payloads(&self) -> Vec<InspectedPayload> {
+        self.inspector.lock().map(|state| state.entries.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    pub fn record_observed_transaction(&self, transaction: ObservedTransaction) {
+        if let Ok(mut feed) = self.tx_feed.lock() {
+            feed.entries.push_back(transaction);
+            while feed.entries.len() > MAX_TX_FEED {
+                feed.entries.pop_front();
+            }
+            feed.revision += 1;
+        }
+    }
+
+    /// Транзакция из ленты попала в блок
+    pub fn confirm_observed_transaction(&self, tx_id: &str) {
+        if let Ok(mut feed) = self.tx_feed.lock() {
+            if let Some(entry) = feed.entries.iter_mut().find(|entry| entry.tx_id == tx_id && !entry.confirmed) {
+                entry.confirmed = true;
+                feed.revision += 1;
+            }
+        }
+    }
+
+    fn clear_tx_feed(&self) {
+        if let Ok(mut feed) = self.tx_feed.lock() {
+            feed.entries.clear();
+            feed.revision += 1;
+        }
+    }
+
+    pub fn get_tx_feed_revision(&self) -> u64 {
+        self.tx_feed.lock().map(|feed| feed.revision).unwrap_or(0)
+    }
+
+    /// Лента от новых транзакций к старым
+    pub fn get_tx_feed(&self) -> Vec<ObservedTransaction> {
+        self.tx_feed.lock().map(|feed| feed.entries.iter().rev().cloned().collect()).unwrap_or_default()
+    }
+
+    pub fn start_recording_stats(&self) {
+        if let Ok(mut stats) = self.recording_stats.lock() {
+            *stats = RecordingStats { started_at: Some(Instant::now()), ..Default::default() };
+        }
+    }
+
+    pub fn stop_recording_stats(&self) {
+        if let Ok(mut stats) = self.recording_stats.lock() {
+            stats.started_at = None;
+            stats.levels.clear();
+        }
+    }
+
+    /// Вызывается из потока захвата звука, поэтому только короткая блокировка
+    pub fn push_mic_level(&self, level: f32) {
+        if let Ok(mut stats) = self.recording_stats.lock() {
+            if stats.started_at.is_none() {
+                return;
+            }
+            stats.levels.push_back(level.clamp(0.0, 1.0));
+            while stats.levels.len() > MIC_LEVEL_HISTORY {
+                stats.levels.pop_front();
+            }
+        }
+    }
+
+    pub fn record_fragment_sent(&self) {
+        if let Ok(mut stats) = self.recording_stats.lock() {
+            stats.fragments_sent += 1;
+        }
+    }
+
+    /// Пакет, досланный после остановки записи, уже не считается
+    pub fn record_voice_transaction_sent(&self) {
+        if let Ok(mut stats) = self.recording_stats.lock() {
+            if stats.started_at.is_some() {
+                stats.transactions_sent += 1;
+            }
+        }
+    }
+
+    pub fn get_recording_stats(&self) -> RecordingStats {
+        self.recording_stats.lock().map(|stats| stats.clone()).unwrap_or_default()
+    }
+
+    /// Вызывается из потока захвата индикатора. Биты неотрицательных f32 упорядочены как числа,
+    /// поэтому пик копится через fetch_max без блокировок
+    pub fn record_input_level(&self, level: f32) {
+        self.input_level.fetch_max(level.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Вызывается из потока глобального перехвата клавиатуры
+    pub fn request_push_to_talk(&self, pressed: bool) {
+        if let Ok(mut request) = self.push_to_talk.lock() {
+            *request = Some(pressed);
+        }
+    }
+
+    pub fn take_push_to_talk(&self) -> Option<bool> {
+        self.push_to_talk.lock().ok().and_then(|mut request| request.take())
+    }
+
+    /// Пик с прошлого вызова; счётчик обнуляется
+    pub fn take_input_level(&self) -> f32 {
+        f32::from_bits(self.input_level.swap(0, Ordering::Relaxed))
+    }
+
+    pub fn set_signed_message(&self, result: Result<SignedMessage, String>) {
+        if let Ok(mut signed) = self.signed_message.lock() {
+            *signed = Some(result);
+        }
+    }
+
+    pub fn take_signed_message(&self) -> Option<Result<SignedMessage, String>> {
+        self.signed_message.lock().ok().and_then(|mut signed| signed.take())
+    }
+
+    // ------------------------------------------
+    // Toast helpers
+    // ------------------------------------------
+
+    /// Уведомление о поступлении средств. Поступления подряд (эйрдроп приходит 20 транзакциями)
+    /// складываются в одно уведомление; возвращает true, если появилось новое
+    pub fn notify_incoming_funds(&self, amount: u64) -> bool {
+        let Ok(mut state) = self.toasts.lock() else {
+            return false;
+        };
+        let is_fresh = |toast: &Toast| toast.created.elapsed() < Duration::from_secs(TOAST_DURATION_SECS);
+        if let Some(toast) = state.toasts.back_mut().filter(|toast| is_fresh(toast)) {
+            if let ToastKind::IncomingFunds(total) = &mut toast.kind {
+                *total += amount;
+                toast.created = Instant::now();
+                return false;
+            }
+        }
+        Self::push_toast_locked(&mut state, ToastKind::IncomingFunds(amount));
+        true
+    }
+
+    /// Показывает уведомление
+    pub fn push_toast(&self, kind: ToastKind) {
+        if let Ok(mut state) = self.toasts.lock() {
+            Self::push_toast_locked(&mut state, kind);
+        }
+    }
+
+    /// Показывает ошибку. Та же ошибка подряд (повторы отправки, поток битых payload) не плодит
+    /// уведомления, а обновляет уже показанное
+    pub fn report_error(&self, event: ErrorEvent) {
+        let Ok(mut state) = self.toasts.lock() else {
+            return;
+        };
+        let is_fresh = |toast: &Toast| toast.created.elapsed() < Duration::from_secs(TOAST_DURATION_SECS);
+        let same_kind = |toast: &Toast| matches!(&toast.kind, ToastKind::Error(shown) if std::mem::discriminant(shown) == std::mem::discriminant(&event));
+        if let Some(toast) = state.toasts.iter_mut().find(|toast| is_fresh(toast) && same_kind(toast)) {
+            toast.kind = ToastKind::Error(event);
+            toast.created = Instant::now();
+            return;
+        }
+        Self::push_toast_locked(&mut state, ToastKind::Error(event));
+    }
+
+    fn push_toast_locked(state: &mut ToastSharedState, kind: ToastKind) {
+        let id = state.next_id;
+        state.next_id += 1;
+        state.toasts.push_back(Toast { id, kind, created: Instant::now() });
+        while state.toasts.len() > MAX_TOASTS {
+            state.toasts.pop_front();
+        }
+    }
+
+    /// Уведомления, которые ещё не пора скрывать
+    pub fn get_toasts(&self) -> Vec<Toast> {
+        let Ok(mut state) = self.toasts.lock() else {
+            return Vec::new();
+        };
+        state.toasts.retain(|toast| toast.created.elapsed() < Duration::from_secs(TOAST_DURATION_SECS));
+        state.toasts.iter().cloned().collect()
+    }
+
+    pub fn dismiss_toast(&self, id: u64) {
+        if let Ok(mut state) = self.toasts.lock() {
+            state.toasts.retain(|toast| toast.id != id);
+        }
+    }
+
+    // ------------------------------------------
+    // Telemetry helpers
+    // ------------------------------------------
+
+    /// Учитывает подключение к ноде; все, кроме первого, — переподключения
+    pub fn record_connect(&self) {
+        if let Ok(mut counters) = self.telemetry.lock() {
+            if counters.connected_once {
+                counters.reconnects += 1;
+            }
+            counters.connected_once = true;
+        }
+    }
+
+    /// Учитывает новые пропуски во входящих голосовых потоках.
+    /// Опоздавшие фрагменты уменьшают статистику потока, но не телеметрию: пропуск всё равно был слышен
+    fn record_audio_dropouts(&self, count: u32) {
+        if let Ok(mut counters) = self.telemetry.lock() {
+            counters.audio_dropouts += count;
+        }
+    }
+
+    /// Учитывает несоответствие, найденное при разборе входящего payload
+    pub fn record_parse_issue(&self, kind: &str) {
+        if let Ok(mut counters) = self.telemetry.lock() {
+            *counters.parse_errors.entry(kind.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// Учитывает версию протокола и тип входящего payload. О версии новее нашей предупреждает один раз:
+    /// в сети появились клиенты, чьи сообщения мы можем не понять
+    pub fn record_payload_kind(&self, version: u8, message_type: MessageType) {
+        let Ok(mut counters) = self.telemetry.lock() else {
+            return;
+        };
+        *counters.protocol_versions.entry(version).or_insert(0) += 1;
+        *counters.message_types.entry(format!("{:?}", message_type)).or_insert(0) += 1;
+        let newest = counters.newest_protocol_version.unwrap_or(PROTOCOL_VERSION);
+        counters.newest_protocol_version = Some(newest.max(version));
+        drop(counters);
+        if version > newest {
+            log::warn!("Payload of protocol version {} seen, this client supports up to {}", version, PROTOCOL_VERSION);
+            self.push_toast(ToastKind::NewerProtocol(version));
+        }
+    }
+
+    pub fn get_telemetry_counters(&self) -> Result<TelemetryCounters, String> {
+        self.telemetry.lock().map(|counters| counters.clone()).map_err(|_| "Mutex (telemetry) poisoned".to_string())
+    }
+
+    /// Падение приложения, вызывается из обработчика паники; без согласия на телеметрию не считается.
+    /// Упавший поток может держать мьютекс настроек, поэтому он не ждётся:
+    /// если занят, падение остаётся в `unsaved_crashes` до `save_unsaved_crashes`
+    pub fn record_crash(&self) -> Result<(), String> {
+        if !self.telemetry_enabled.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        self.unsaved_crashes.fetch_add(1, Ordering::Relaxed);
+        let Ok(mut settings) = self.settings.try_lock() else {
+            return Ok(());
+        };
+        settings.current.pending_crashes += self.unsaved_crashes.swap(0, Ordering::Relaxed);
+        settings.save()
+    }
+
+    /// Сохраняет падения, которые обработчик паники не смог записать сразу
+    pub fn save_unsaved_crashes(&self) -> Result<(), String> {
+        if self.unsaved_crashes.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        self.with_settings_write(|settings| {
+            settings.current.pending_crashes += self.unsaved_crashes.swap(0, Ordering::Relaxed);
+            settings.save()
+        })
+    }
+
+    pub fn get_pending_crashes(&self) -> Result<u32, String> {
+        let unsaved = self.unsaved_crashes.load(Ordering::Relaxed);
+        self.with_settings_read(|settings| settings.current.pending_crashes + unsaved)
+    }
+
+    /// Вычитает отправленное из счётчиков и начинает новый период
+    pub fn reset_telemetry(&self, sent: &TelemetryReport) -> Result<(), String> {
+        if let Ok(mut counters) = self.telemetry.lock() {
+            counters.period_start = Instant::now();
+            counters.reconnects = counters.reconnects.saturating_sub(sent.reconnects);
+            counters.audio_dropouts = counters.audio_dropouts.saturating_sub(sent.audio_dropouts);
+            for (kind, sent_count) in &sent.parse_errors {
+                if let Some(count) = counters.parse_errors.get_mut(kind) {
+                    *count = count.saturating_sub(*sent_count);
+                }
+            }
+            counters.parse_errors.retain(|_, count| *count > 0);
+            for (version, sent_count) in &sent.protocol_versions {
+                if let Some(count) = counters.protocol_versions.get_mut(version) {
+                    *count = count.saturating_sub(*sent_count);
+                }
+            }
+            counters.protocol_versions.retain(|_, count| *count > 0);
+            for (message_type, sent_count) in &sent.message_types {
+                if let Some(count) = counters.message_types.get_mut(message_type) {
+                    *count = count.saturating_sub(*sent_count);
+                }
+            }
+            counters.message_types.retain(|_, count| *count > 0);
+        }
+        self.with_settings_write(|settings| {
+            settings.current.pending_crashes = settings.current.pending_crashes.saturating_sub(sent.crashes);
+            settings.save()
+        })
+    }
+
+    /// Адрес сервера телеметрии, если она включена и адрес задан
+    pub fn get_telemetry_endpoint(&self) -> Result<Option<String>, String> {
+        self.with_settings_read(|settings| {
+            let endpoint = settings.current.telemetry_endpoint.trim();
+            (settings.current.telemetry_enabled && !endpoint.is_empty()).then(|| endpoint.to_string())
+        })
+    }
+
+    pub fn is_telemetry_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.telemetry_enabled)
+    }
+
+    pub fn get_telemetry_endpoint_input(&self) -> Result<String, String> {
+        self.with_settings_read(|settings| settings.current.telemetry_endpoint.clone())
+    }
+
+    /// Включает/выключает телеметрию и сохраняет настройки
+    pub fn set_telemetry(&self, enabled: bool, endpoint: &str) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.telemetry_enabled = enabled;
+            settings.current.telemetry_endpoint = endpoint.trim().to_string();
+            self.telemetry_enabled.store(enabled, Ordering::Relaxed);
+            settings.save()
+        })
+    }
+
+    // ------------------------------------------
+    // ClaimSharedState helpers
+    // ------------------------------------------
+
+    /// Регистрирует проверенную заявку. Возвращает false, если имя уже закреплено за другим ключом
+    pub fn register_claim(&self, username: &str, public_key: &str) -> Result<bool, String> {
+        self.with_claim_state_write(|state| {
+            let owner = state.claims.entry(username.to_string()).or_insert_with(|| public_key.to_string());
+            Ok(owner == public_key)
+        })
+    }
+
+    /// Ключ, за которым закреплено имя
+    pub fn get_claim_owner(&self, username: &str) -> Result<Option<String>, String> {
+        self.with_claim_state_read(|state| state.claims.get(username).cloned())
+    }
+
+    /// Имя закреплено, но сообщение не подписано ключом владельца
+    pub fn is_claim_mismatch(&self, username: &str, sender_key: Option<&str>) -> bool {
+        self.with_claim_state_read(|state| match state.claims.get(username) {
+            Some(owner) => sender_key != Some(owner.as_str()),
+            None => false,
+        })
+        .unwrap_or(false)
+    }
+
+    // ------------------------------------------
+    // Blocklist helpers
+    // ------------------------------------------
+
+    pub fn get_blocklist(&self) -> Result<Vec<BlockedSender>, String> {
+        self.with_settings_read(|settings| settings.current.blocklist.clone())
+    }
+
+    pub fn block_sender(&self, sender: BlockedSender) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            if !settings.current.blocklist.contains(&sender) {
+                settings.current.blocklist.push(sender);
+            }
+            settings.save()
+        })
+    }
+
+    pub fn unblock_sender(&self, sender: &BlockedSender) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.blocklist.retain(|blocked| blocked != sender);
+            settings.save()
+        })
+    }
+
+    pub fn get_muted_users(&self) -> Result<Vec<String>, String> {
+        self.with_settings_read(|settings| settings.current.muted_users.clone())
+    }
+
+    /// Заглушён ли голос собеседника
+    pub fn is_user_muted(&self, username: &str) -> bool {
+        self.with_settings_read(|settings| settings.current.muted_users.iter().any(|muted| muted == username)).unwrap_or(false)
+    }
+
+    pub fn set_user_muted(&self, username: &str, muted: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.muted_users.retain(|name| name != username);
+            if muted {
+                settings.current.muted_users.push(username.to_string());
+            }
+            settings.save()
+        })
+    }
+
+    pub fn get_content_filter(&self) -> Result<ContentFilter, String> {
+        self.with_settings_read(|settings| settings.current.content_filter.clone())
+    }
+
+    /// Сохраняет фильтр; при неверном шаблоне ничего не меняется
+    pub fn set_content_filter(&self, filter: ContentFilter) -> Result<(), String> {
+        let compiled = filter.compile()?;
+        self.with_settings_write(|settings| {
+            settings.current.content_filter = filter;
+            settings.save()
+        })?;
+        *self.content_filter.write().map_err(|_| "Content filter lock poisoned".to_string())? = compiled;
+        Ok(())
+    }
+
+    /// Проверяет входящее сообщение канала фильтром содержимого
+    pub fn filter_content(&self, channel: u32, content: &str) -> FilterOutcome {
+        self.content_filter.read().map(|filter| filter.apply(channel, content)).unwrap_or(FilterOutcome::Pass)
+    }
+
+    pub fn get_ignored_users(&self) -> Result<Vec<String>, String> {
+        self.with_settings_read(|settings| settings.current.ignored_users.clone())
+    }
+
+    /// Скрыты ли сообщения собеседника в ленте чата
+    pub fn is_user_ignored(&self, username: &str) -> bool {
+        self.with_settings_read(|settings| settings.current.ignored_users.iter().any(|ignored| ignored == username)).unwrap_or(false)
+    }
+
+    pub fn set_user_ignored(&self, username: &str, ignored: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.ignored_users.retain(|name| name != username);
+            if ignored {
+                settings.current.ignored_users.push(username.to_string());
+            }
+            settings.save()
+        })
+    }
+
+    /// Заблокирован ли отправитель по имени или по ключу идентичности, которым подписан payload
+    pub fn is_sender_blocked(&self, username: &str, sender_key: Option<&str>) -> bool {
+        self.get_blocklist().unwrap_or_default().iter().any(|blocked| match blocked {
+            BlockedSender::Username(name) => name == username,
+            BlockedSender::IdentityKey(key) => sender_key == Some(key.as_str()),
+        })
+    }
+
+    /// Заблокирован ли ключ идентичности (отправитель личного сообщения)
+    pub fn is_identity_key_blocked(&self, identity_key: &str) -> bool {
+        self.get_blocklist()
+            .unwrap_or_default()
+            .iter()
+            .any(|blocked| matches!(blocked, BlockedSender::IdentityKey(key) if key == identity_key))
+    }
+
+    // ------------------------------------------
+    // ContactSharedState helpers
+    // ------------------------------------------
+
+    /// Контакты по имени
+    pub fn get_contacts(&self) -> Result<Vec<Contact>, String> {
+        self.with_contact_state_read(|state| {
+            let mut contacts = state.contacts.clone();
+            contacts.sort_by_key(|contact| contact.name.to_lowercase());
+            contacts
+        })
+    }
+
+    /// Контакт с этим адресом или ключом идентичности
+    pub fn find_contact(&self, address: Option<&str>, identity_key: Option<&str>) -> Option<Contact> {
+        self.with_contact_state_read(|state| state.contacts.iter().find(|contact| contact.matches(address, identity_key)).cloned())
+            .ok()
+            .flatten()
+    }
+
+    /// Добавляет контакт или заменяет записи с тем же адресом или ключом и сохраняет адресную книгу
+    pub fn save_contact(&self, contact: Contact) -> Result<(), String> {
+        if contact.name.trim().is_empty() {
+            return Err("Contact name is empty".to_string());
+        }
+        if contact.address.is_none() && contact.identity_key.is_none() {
+            return Err("Contact needs an address or an identity key".to_string());
+        }
+        if let Some(address) = &contact.address {
+            let prefix = self.network.address_prefix();
+            if !address.starts_with(prefix) || Address::try_from(address.as_str()).is_err() {
+                return Err(format!("Invalid address for {}: {}", self.network, address));
+            }
+        }
+        if let Some(identity_key) = &contact.identity_key {
+            parse_public_key(identity_key)?;
+        }
+        let snapshot = self.with_contact_state_write(|state| {
+            state.contacts.retain(|existing| !existing.matches(contact.address.as_deref(), contact.identity_key.as_deref()));
+            state.contacts.push(contact);
+            Ok(state.contacts.clone())
+        })?;
+        save_json(&Self::contacts_path(&self.profile), &snapshot)
+    }
+
+    pub fn remove_contact(&self, contact: &Contact) -> Result<(), String> {
+        let snapshot = self.with_contact_state_write(|state| {
+            state.contacts.retain(|existing| existing != contact);
+            Ok(state.contacts.clone())
+        })?;
+        save_json(&Self::contacts_path(&self.profile), &snapshot)
+    }
+
+    // ------------------------------------------
+    // RetrySharedState helpers
+    // ------------------------------------------
+
+    /// Ставит payload, который не удалось отправить, в очередь повторов
+    pub fn enqueue_send_retry(&self, payload: &[u8], error: &str) -> Result<(), String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let snapshot = self.with_retry_state_write(|state| {
+            let entry = PendingSend::new(state.next_id, payload, error, now);
+            state.next_id += 1;
+            log::info!("Send #{} queued for retry in {} ms", entry.id, entry.next_attempt_at - now);
+            state.queue.push(entry);
+            Ok(state.queue.clone())
+        })?;
+        save_json(&Self::retry_queue_path(&self.profile), &snapshot)
+    }
+
+    /// Забирает записи, время повтора которых пришло, и помечает их отправляемыми. Истёкшие записи отбрасываются
+    pub fn take_due_retries(&self) -> Result<Vec<PendingSend>, String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let (due, snapshot) = self.with_retry_state_write(|state| {
+            let before = state.queue.len();
+            state.queue.retain(|entry| {
+                let expired = !entry.in_flight && entry.is_expired();
+                if expired {
+                    log::info!("Dropped retry of send #{}: payload expired", entry.id);
+                }
+                !expired
+            });
+            let mut due = Vec::new();
+            for entry in state.queue.iter_mut().filter(|entry| !entry.in_flight && entry.next_attempt_at <= now) {
+                entry.in_flight = true;
+                due.push(entry.clone());
+            }
+            let snapshot = (state.queue.len() != before).then(|| state.queue.clone());
+            Ok((due, snapshot))
+        })?;
+        if let Some(snapshot) = snapshot {
+            save_json(&Self::retry_queue_path(&self.profile), &snapshot)?;
+        }
+        Ok(due)
+    }
+
+    /// Повтор удался — запись больше не нужна
+    pub fn complete_send_retry(&self, id: u64) -> Result<(), String> {
+        let snapshot = self.with_retry_state_write(|state| {
+            state.queue.retain(|entry| entry.id != id);
+            Ok(state.queue.clone())
+        })?;
+        save_json(&Self::retry_queue_path(&self.profile), &snapshot)
+    }
+
+    /// Повтор не удался: переносит следующую попытку или, после RETRY_MAX_ATTEMPTS, удаляет запись.
+    /// Возвращает false, если попытки закончились
+    pub fn fail_send_retry(&self, id: u64, error: &str) -> Result<bool, String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let (will_retry, snapshot) = self.with_retry_state_write(|state| {
+            let Some(index) = state.queue.iter().position(|entry| entry.id == id) else {
+                return Ok((false, state.queue.clone()));
+            };
+            let entry = &mut state.queue[index];
+            entry.reschedule(error, now);
+            let will_retry = entry.attempts < RETRY_MAX_ATTEMPTS;
+            if !will_retry {
+                state.queue.remove(index);
+            }
+            Ok((will_retry, state.queue.clone()))
+        })?;
+        save_json(&Self::retry_queue_path(&self.profile), &snapshot)?;
+        Ok(will_retry)
+    }
+
+    /// Сколько отправок ждёт повтора
+    pub fn get_pending_retries(&self) -> Result<usize, String> {
+        self.with_retry_state_read(|state| state.queue.len())
+    }
+
+    // ------------------------------------------
+    // AvatarSharedState helpers
+    // ------------------------------------------
+
+    /// Добавляет фрагмент чужого аватара. Возвращает ключ владельца, когда изображение собрано и проверено
+    pub fn add_avatar_chunk(&self, chunk: AvatarChunk, index: u32, address: &str) -> Result<Option<String>, String> {
+        let index = index as usize;
+        let total = chunk.total as usize;
+        if index >= total {
+            return Err(format!("Avatar chunk {} is out of {}", index, total));
+        }
+        let key = chunk.public_key_hex();
+        let signature = chunk.signature_bytes();
+        self.with_avatar_state_write(|state| {
+            let pending = state.pending.entry(key.clone()).or_insert_with(|| PendingAvatar { signature, chunks: vec![None; total] });
+            // Пришла новая версия аватара: начинаем сборку заново
+            if pending.signature != signature || pending.chunks.len() != total {
+                *pending = PendingAvatar { signature, chunks: vec![None; total] };
+            }
+            pending.chunks[index] = Some(chunk.bytes.clone());
+            if pending.chunks.iter().any(Option::is_none) {
+                return Ok(None);
+            }
+
+            let chunks = state.pending.remove(&key).map(|pending| pending.chunks).unwrap_or_default();
+            let image: Vec<u8> = chunks.into_iter().flatten().flatten().collect();
+            chunk.verify(&image, address)?;
+            check_avatar(&image)?;
+            state.avatars.insert(key.clone(), image);
+            state.keys_by_address.insert(address.to_string(), key.clone());
+            state.revision += 1;
+            Ok(Some(key))
+        })
+    }
+
+    pub fn get_avatar_revision(&self) -> u64 {
+        self.with_avatar_state_read(|state| state.revision).unwrap_or(0)
+    }
+
+    pub fn get_avatars(&self) -> HashMap<String, Vec<u8>> {
+        self.with_avatar_state_read(|state| state.avatars.clone()).unwrap_or_default()
+    }
+
+    /// Есть ли аватар у владельца адреса
+    pub fn has_avatar_for_address(&self, address: &str) -> bool {
+        self.with_avatar_state_read(|state| state.keys_by_address.contains_key(address)).unwrap_or(false)
+    }
+
+    /// Свой аватар; None — не задан
+    pub fn get_own_avatar(&self) -> Result<Option<Vec<u8>>, String> {
+        self.with_settings_read(|settings| Some(settings.current.avatar.clone()).filter(|avatar| !avatar.is_empty()))
+    }
+
+    /// Сохраняет свой аватар в настройках и в кэше
+    pub fn set_own_avatar(&self, avatar: Vec<u8>) -> Result<(), String> {
+        let key = self.identity.public_key_hex();
+        self.with_avatar_state_write(|state| {
+            state.avatars.insert(key, avatar.clone());
+            state.revision += 1;
+            Ok(())
+        })?;
+        self.with_settings_write(|settings| {
+            settings.current.avatar = avatar;
+            settings.save()
+        })
+    }
+
+    /// Можно ли повторить рассылку своего аватара по запросу
+    pub fn is_avatar_reannounce_due(&self) -> bool {
+        self.with_avatar_state_read(|state| {
+            state.last_announced.map_or(true, |at| at.elapsed() >= Duration::from_secs(AVATAR_REANNOUNCE_COOLDOWN_SECS))
+        })
+        .unwrap_or(false)
+    }
+
+    pub fn mark_avatar_announced(&self) -> Result<(), String> {
+        self.with_avatar_state_write(|state| {
+            state.last_announced = Some(Instant::now());
+            Ok(())
+        })
+    }
+
+    // ------------------------------------------
+    // Settings helpers
+    // ------------------------------------------
+
+    /// Максимальное число одновременно воспроизводимых собеседников
+    pub fn get_max_playback_streams(&self) -> Result<usize, String> {
+        self.with_settings_read(|settings| settings.current.max_playback_streams.max(1))
+    }
+
+    /// Устанавливает лимит одновременно воспроизводимых собеседников и сохраняет настройки
+    pub fn set_max_playback_streams(&self, value: usize) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.max_playback_streams = value.max(1);
+            settings.save()
+        })
+    }
+
+    pub fn is_mempool_listening_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.mempool_listening)
+    }
+
+    pub fn set_mempool_listening_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.mempool_listening = enabled;
+            settings.save()
+        })
+    }
+
+    pub fn get_backfill_minutes(&self) -> Result<Option<u64>, String> {
+        self.with_settings_read(|settings| settings.current.backfill_minutes)
+    }
+
+    pub fn set_backfill_minutes(&self, minutes: Option<u64>) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.backfill_minutes = minutes;
+            settings.save()
+        })
+    }
+
+    /// Принимать частично повреждённые payload (см. `Payload::from_bytes_lenient`)
+    pub fn is_salvage_payloads_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.salvage_payloads)
+    }
+
+    pub fn set_salvage_payloads_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.salvage_payloads = enabled;
+            settings.save()
+        })
+    }
+
+    /// Окно дедупликации транзакций в единицах DAA score
+    pub fn get_dedupe_window_daa(&self) -> Result<u64, String> {
+        self.with_settings_read(|settings| settings.current.dedupe_window_daa.max(1))
+    }
+
+    /// Устанавливает окно дедупликации транзакций и сохраняет настройки
+    pub fn set_dedupe_window_daa(&self, value: u64) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.dedupe_window_daa = value.max(1);
+            settings.save()
+        })
+    }
+
+    /// Срок жизни отправляемых текстовых сообщений
+    pub fn get_message_ttl(&self) -> Result<Option<u64>, String> {
+        self.with_settings_read(|settings| settings.current.message_ttl_secs)
+    }
+
+    pub fn set_message_ttl(&self, ttl_secs: Option<u64>) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.message_ttl_secs = ttl_secs;
+            settings.save()
+        })
+    }
+
+    pub fn is_input_meter_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.input_meter_enabled)
+    }
+
+    pub fn set_input_meter_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.input_meter_enabled = enabled;
+            settings.save()
+        })
+    }
+
+    pub fn is_comfort_noise_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.comfort_noise_enabled)
+    }
+
+    /// Включает/выключает шум в паузах между фрагментами и сохраняет настройки
+    pub fn set_comfort_noise_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.comfort_noise_enabled = enabled;
+            settings.save()
+        })
+    }
+
+    pub fn is_incoming_funds_sound_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.incoming_funds_sound)
+    }
+
+    /// Включает/выключает звук при поступлении средств и сохраняет настройки
+    pub fn set_incoming_funds_sound_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.incoming_funds_sound = enabled;
+            settings.save()
+        })
+    }
+
+    pub fn get_utxo_max_count(&self) -> Result<usize, String> {
+        self.with_settings_read(|settings| settings.current.utxo_max_count)
+    }
+
+    pub fn set_utxo_max_count(&self, count: usize) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.utxo_max_count = count;
+            settings.save()
+        })
+    }
+
+    /// Порог пыли, сомпи
+    pub fn get_utxo_dust_threshold(&self) -> Result<u64, String> {
+        self.with_settings_read(|settings| settings.current.utxo_dust_threshold)
+    }
+
+    pub fn set_utxo_dust_threshold(&self, threshold: u64) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.utxo_dust_threshold = threshold;
+            settings.save()
+        })
+    }
+
+    // ------------------------------------------
+    // NodeSharedState helpers
+    // ------------------------------------------
+
+    pub fn get_node_urls(&self) -> Result<Vec<String>, String> {
+        self.with_settings_read(|settings| settings.current.node_urls.clone())
+    }
+
+    /// Сохраняет список нод в порядке приоритета; адреса проверяются и очищаются от повторов
+    pub fn set_node_urls(&self, urls: Vec<String>) -> Result<(), String> {
+        let mut cleaned: Vec<String> = Vec::with_capacity(urls.len());
+        for url in urls {
+            let url = url.trim().to_string();
+            if !url.starts_with("ws://") && !url.starts_with("wss://") {
+                return Err(format!("Node URL must start with ws:// or wss://: {url}"));
+            }
+            if !cleaned.contains(&url) {
+                cleaned.push(url);
+            }
+        }
+        if cleaned.len() > MAX_NODE_URLS {
+            return Err(format!("At most {} nodes can be listed", MAX_NODE_URLS));
+        }
+        self.with_settings_write(|settings| {
+            settings.current.node_urls = cleaned;
+            settings.save()
+        })
+    }
+
+    /// Адрес ноды, к которой подключён клиент
+    pub fn get_active_node(&self) -> Option<String> {
+        self.with_node_state_read(|state| state.active_url.clone()).ok().flatten()
+    }
+
+    pub fn set_active_node(&self, url: Option<String>) {
+        let _ = self.with_node_state_write(|state| {
+            state.active_url = url;
+            Ok(())
+        });
+    }
+
+    /// Ноды для переключения: следующие за активной по приоритету, активная — последней.
+    /// Пусто, если активная нода не из списка (адрес введён вручную или выбран резолвером) или переключаться не на что.
+    pub fn get_failover_nodes(&self) -> Vec<String> {
+        let urls = self.get_node_urls().unwrap_or_default();
+        let Some(position) = self.get_active_node().and_then(|active| urls.iter().position(|url| *url == active)) else {
+            return Vec::new();
+        };
+        if urls.len() < 2 {
+            return Vec::new();
+        }
+        urls[position + 1..].iter().chain(urls[..=position].iter()).cloned().collect()
+    }
+
+    pub fn get_reconnect_attempt(&self) -> Option<u32> {
+        self.with_node_state_read(|state| state.reconnect_attempt).ok().flatten()
+    }
+
+    pub fn set_reconnect_attempt(&self, attempt: Option<u32>) {
+        let _ = self.with_node_state_write(|state| {
+            state.reconnect_attempt = attempt;
+            Ok(())
+        });
+    }
+
+    pub fn is_auto_reconnect_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.auto_reconnect)
+    }
+
+    /// Включает/выключает автопереподключение; уже идущие попытки прекращаются на следующем шаге
+    pub fn set_auto_reconnect_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.auto_reconnect = enabled;
+            settings.save()
+        })
+    }
+
+    pub fn get_rpc_policy(&self) -> Result<RpcPolicy, String> {
+        self.with_settings_read(|settings| settings.current.rpc_policy)
+    }
+
+    /// Новые таймауты действуют со следующего запроса и следующего подключения
+    pub fn set_rpc_policy(&self, policy: RpcPolicy) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.rpc_policy = policy;
+            settings.save()
+        })
+    }
+
+    pub fn get_notification_scopes(&self) -> Result<NotificationScopes, String> {
+        self.with_settings_read(|settings| settings.current.notification_scopes)
+    }
+
+    /// Новые подписки действуют со следующего подключения
+    pub fn set_notification_scopes(&self, scopes: NotificationScopes) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.notification_scopes = scopes;
+            settings.save()
+        })
+    }
+
+    pub fn is_start_minimized(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.start_minimized)
+    }
+
+    pub fn set_start_minimized(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.start_minimized = enabled;
+            settings.save()
+        })
+    }
+
+    /// Прятать окно в трей при закрытии
+    pub fn is_minimize_to_tray(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.minimize_to_tray)
+    }
+
+    pub fn set_minimize_to_tray(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.minimize_to_tray = enabled;
+            settings.save()
+        })
+    }
+
+    pub fn get_pane_ratios(&self) -> Result<[f32; 2], String> {
+        self.with_settings_read(|settings| settings.current.pane_ratios)
+    }
+
+    pub fn set_pane_ratios(&self, ratios: [f32; 2]) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.pane_ratios = ratios;
+            settings.save()
+        })
+    }
+
+    /// Язык интерфейса
+    pub fn get_language(&self) -> Result<UiLanguage, String> {
+        self.with_settings_read(|settings| settings.current.language)
+    }
+
+    /// Устанавливает язык интерфейса и сохраняет настройки
+    pub fn set_language(&self, language: UiLanguage) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.language = language;
+            settings.save()
+        })
+    }
+
+    /// Имя сохранённой темы оформления; пустое — тема по умолчанию
+    pub fn get_theme_name(&self) -> Result<String, String> {
+        self.with_settings_read(|settings| settings.current.theme.clone())
+    }
+
+    pub fn set_theme_name(&self, name: &str) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.theme = name.to_string();
+            settings.save()
+        })
+    }
+
+    /// Темы для светлого и тёмного оформления ОС (для темы «Auto»)
+    pub fn get_auto_theme_names(&self) -> Result<(String, String), String> {
+        self.with_settings_read(|settings| (settings.current.light_theme.clone(), settings.current.dark_theme.clone()))
+    }
+
+    /// Сохраняет тему для светлого (`dark` = false) или тёмного оформления ОС
+    pub fn set_auto_theme_name(&self, dark: bool, name: &str) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            if dark {
+                settings.current.dark_theme = name.to_string();
+            } else {
+                settings.current.light_theme = name.to_string();
+            }
+            settings.save()
+        })
+    }
+
+    /// Назначенные сочетания клавиш
+    pub fn get_shortcuts(&self) -> Result<Shortcuts, String> {
+        self.with_settings_read(|settings| settings.current.shortcuts.clone())
+    }
+
+    pub fn get_shortcut(&self, action: ShortcutAction) -> Result<Option<Shortcut>, String> {
+        self.with_settings_read(|settings| settings.current.shortcuts.get(action).cloned())
+    }
+
+    pub fn set_shortcut(&self, action: ShortcutAction, shortcut: Option<Shortcut>) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.shortcuts.set(action, shortcut);
+            settings.save()
+        })
+    }
+
+    /// Цвета своей темы оформления
+    pub fn get_custom_palette(&self) -> Result<CustomPalette, String> {
+        self.with_settings_read(|settings| settings.current.custom_palette)
+    }
+
+    pub fn set_custom_palette(&self, palette: CustomPalette) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.custom_palette = palette;
+            settings.save()
+        })
+    }
+
+    /// Битрейт Opus-энкодера, бит/с
+    pub fn get_opus_bitrate(&self) -> Result<i32, String> {
+        self.with_settings_read(|settings| settings.current.opus_bitrate)
+    }
+
+    /// Сохраняет битрейт и просит Recorder пересоздать энкодер перед следующим фрагментом
+    pub fn set_opus_bitrate(&self, bitrate: i32) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.opus_bitrate = bitrate;
+            settings.save()
+        })?;
+        self.set_input_device_changed(true)
+    }
+
+    /// Отмечает переключение ноды: прежние ошибки RPC больше не учитываются
+    pub fn mark_failover(&self) {
+        let _ = self.with_node_state_write(|state| {
+            state.last_failover = Some(Instant::now());
+            Ok(())
+        });
+    }
+
+    pub fn get_spend_limits(&self) -> Result<SpendLimits, String> {
+        self.with_settings_read(|settings| settings.current.spend_limits)
+    }
+
+    /// Меняет лимиты трат и сохраняет настройки; пауза пересчитывается при следующей отправке
+    pub fn set_spend_limits(&self, limits: SpendLimits) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.spend_limits = limits;
+            settings.save()
+        })?;
+        self.with_broadcaster_state_write(|state| {
+            state.spend_pause = None;
+            Ok(())
+        })
+    }
+
+    pub fn get_cost_confirm_threshold(&self) -> Result<Option<u64>, String> {
+        self.with_settings_read(|settings| settings.current.cost_confirm_threshold)
+    }
+
+    /// Меняет порог подтверждения расходов и сохраняет настройки
+    pub fn set_cost_confirm_threshold(&self, threshold: Option<u64>) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.cost_confirm_threshold = threshold;
+            settings.save()
+        })
+    }
+
+    pub fn get_faucet_source(&self) -> Result<FaucetSource, String> {
+        self.with_settings_read(|settings| settings.current.faucet.clone())
+    }
+
+    /// Меняет источник пополнения и сохраняет настройки; мнемоника аккаунта пополнения проверяется сразу
+    pub fn set_faucet_source(&self, source: FaucetSource) -> Result<(), String> {
+        match &source {
+            FaucetSource::Url(url) if !url.trim().starts_with("http://") && !url.trim().starts_with("https://") => {
+                return Err(format!("Faucet URL must start with http:// or https://: {url}"));
+            }
+            FaucetSource::Mnemonic(phrase) => {
+                Mnemonic::new(phrase.trim(), Language::English).map_err(|e| format!("Invalid funding mnemonic: {e}"))?;
+            }
+            _ => {}
+        }
+        self.with_settings_write(|settings| {
+            settings.current.faucet = source;
+            settings.save()
+        })
+    }
+
+    /// Пополнять ли кошелёк при низком балансе в текущей сети
+    pub fn is_faucet_available(&self) -> bool {
+        self.get_faucet_source().is_ok_and(|source| source.is_available(self.network))
+    }
+
+    pub fn is_voice_batching_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.voice_batching)
+    }
+
+    /// Включает/выключает пакетную отправку голосовых фрагментов и сохраняет настройки
+    pub fn set_voice_batching_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.voice_batching = enabled;
+            settings.save()
+        })
+    }
+
+    /// Включён ли сигнал о начале голосовой передачи в канале
+    pub fn is_voice_announce_enabled(&self, channel: u32) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.voice_announce_channels.contains(&channel))
+    }
+
+    /// Включает/выключает сигнал о начале голосовой передачи в канале и сохраняет настройки
+    pub fn set_voice_announce_enabled(&self, channel: u32, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            let channels = &mut settings.current.voice_announce_channels;
+            channels.retain(|c| *c != channel);
+            if enabled {
+                channels.push(channel);
+            }
+            settings.save()
+        })
+    }
+
+    /// Выключены ли уведомления канала: явно или режимом фокусировки
+    pub fn is_channel_muted(&self, channel: u32) -> Result<bool, String> {
+        let now = chrono::Utc::now().timestamp();
+        self.with_settings_read(|settings| match &settings.current.focus_mode {
+            Some(focus) if focus.until > now && !focus.allowlist.contains(&channel) => true,
+            _ => settings.current.muted_channels.contains(&channel),
+        })
+    }
+
+    /// Включает/выключает уведомления канала и сохраняет настройки
+    pub fn set_channel_muted(&self, channel: u32, muted: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            let channels = &mut settings.current.muted_channels;
+            channels.retain(|c| *c != channel);
+            if muted {
+                channels.push(channel);
+            }
+            settings.save()
+        })
+    }
+
+    /// Режим фокусировки, если он включён
+    pub fn get_focus_mode(&self) -> Result<Option<FocusMode>, String> {
+        self.with_settings_read(|settings| settings.current.focus_mode.clone())
+    }
+
+    /// Включает режим фокусировки, запоминая текущие настройки уведомлений
+    pub fn start_focus_mode(&self, allowlist: Vec<u32>, duration: Duration) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            // Повторное включение продлевает фокус, но не перезаписывает исходный снимок
+            let saved_muted_channels = match settings.current.focus_mode.take() {
+                Some(previous) => previous.saved_muted_channels,
+                None => settings.current.muted_channels.clone(),
+            };
+            let until = chrono::Utc::now().timestamp() + duration.as_secs() as i64;
+            log::info!("Focus mode enabled for {} min, allowed channels: {:?}", duration.as_secs() / 60, allowlist);
+            settings.current.focus_mode = Some(FocusMode { until, allowlist, saved_muted_channels });
+            settings.save()
+        })
+    }
+
+    /// Выключает режим фокусировки и восстанавливает настройки уведомлений
+    pub fn end_focus_mode(&self) -> Result<(), String> {
+        self.with_settings_write(|settings| match settings.current.focus_mode.take() {
+            Some(focus) => {
+                log::info!("Focus mode ended, notification settings restored");
+                settings.current.muted_channels = focus.saved_muted_channels;
+                settings.save()
+            }
+            None => Ok(()),
+        })
+    }
+
+    /// Завершает режим фокусировки, если его время вышло
+    pub fn expire_focus_mode(&self) -> Result<(), String> {
+        let now = chrono::Utc::now().timestamp();
+        let expired =
+            self.with_settings_read(|settings| settings.current.focus_mode.as_ref().is_some_and(|focus| focus.until <= now))?;
+        if expired {
+            self.end_focus_mode()?;
+        }
+        Ok(())
+    }
+
+    /// Включена ли загрузка превью ссылок
+    pub fn is_link_previews_enabled(&self) -> Result<bool, String> {
+        self.with_settings_read(|settings| settings.current.link_previews_enabled)
+    }
+
+    /// Включает/выключает загрузку превью ссылок и сохраняет настройки
+    pub fn set_link_previews_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.link_previews_enabled = enabled;
+            settings.save()
+        })
+    }
+
+    /// Локаль для форматирования чисел и времени
+    pub fn get_locale(&self) -> Result<FormatLocale, String> {
+        self.with_settings_read(|settings| settings.current.locale)
+    }
+
+    /// Устанавливает локаль форматирования и сохраняет настройки
+    pub fn set_locale(&self, locale: FormatLocale) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.locale = locale;
+            settings.save()
+        })
+    }
+
+    /// Формат часов в сообщениях и подсказках
+    pub fn get_clock_format(&self) -> Result<ClockFormat, String> {
+        self.with_settings_read(|settings| settings.current.clock_format)
+    }
+
+    /// Устанавливает формат часов и сохраняет настройки
+    pub fn set_clock_format(&self, clock_format: ClockFormat) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.clock_format = clock_format;
+            settings.save()
+        })
+    }
+
+    pub fn get_chat_density(&self) -> Result<ChatDensity, String> {
+        self.with_settings_read(|settings| settings.current.chat_density)
+    }
+
+    /// Меняет плотность ленты чата и сохраняет настройки
+    pub fn set_chat_density(&self, density: ChatDensity) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.chat_density = density;
+            settings.save()
+        })
+    }
+
+    /// Единица отображения и ввода сумм
+    pub fn get_denomination(&self) -> Result<Denomination, String> {
+        self.with_settings_read(|settings| settings.current.denomination)
+    }
+
+    /// Устанавливает единицу сумм и сохраняет настройки
+    pub fn set_denomination(&self, denomination: Denomination) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.denomination = denomination;
+            settings.save()
+        })
+    }
+
+    pub fn get_username(&self) -> String {
+        self.username.read().map(|username| username.clone()).unwrap_or_default()
+    }
+
+    /// Проверяет и сохраняет новое имя; следующие сообщения уходят уже под ним
+    pub fn set_username(&self, new_username: &str) -> Result<(), String> {
+        let new_username = new_username.trim();
+        validate_username(new_username)?;
+        self.with_settings_write(|settings| {
+            settings.current.username = new_username.to_string();
+            settings.save()
+        })?;
+        *self.username.write().map_err(|_| "Lock (username) poisoned")? = new_username.to_string();
+        Ok(())
+    }
+
+    /// Публичный ключ идентичности в hex (передаётся собеседникам для личных сообщений)
+    pub fn get_identity_public_key(&self) -> String {
+        self.identity.public_key_hex()
+    }
+
+    pub fn get_mnemonic(&self) -> String {
+        self.mnemonic.clone()
+    }
+
+    pub fn is_first_run(&self) -> bool {
+        self.first_run
+    }
+
+    /// Заменяет мнемонику и имя в настройках. Кошелёк и ключ идентичности создаются при запуске,
+    /// поэтому новый аккаунт начинает работать после перезапуска
+    pub fn import_mnemonic(&self, phrase: &str) -> Result<(), String> {
+        self.with_settings_write(|settings| settings.import_mnemonic(phrase))?;
+        self.request_restart(&self.profile)
+    }
+
+    pub fn get_profile(&self) -> String {
+        self.profile.clone()
+    }
+
+    /// Сеть текущего запуска
+    pub fn get_network(&self) -> Network {
+        self.network
+    }
+
+    /// Сохраняет выбранную сеть и перезапускает приложение: RPC-клиент и кошелёк создаются при запуске
+    pub fn set_network(&self, network: Network) -> Result<(), String> {
+        self.with_settings_write(|settings| {
+            settings.current.network = network;
+            settings.save()
+        })?;
+        log::info!("Network changed to {}, restarting", network);
+        self.request_restart(&self.profile)
+    }
+
+    pub fn get_account_derivation(&self) -> Result<AccountDerivation, String> {
+        self.with_settings_read(|settings| settings.current.account_derivation)
+    }
+
+    /// Меняет путь вывода ключей и перезапускает приложение: личный аккаунт пересоздаётся с другими адресами
+    pub fn set_account_derivation(&self, derivation: AccountDerivation) -> Result<(), String> {
+        derivation.validate()?;
+        self.with_settings_write(|settings| {
+            settings.current.account_derivation = derivation;
+            settings.save()
+        })?;
+        log::info!("Account derivation changed to {}, restarting", derivation);
+        self.request_restart(&self.profile)
+    }
+
+    /// Перезапуск с другим профилем; новый профиль создаётся при первом запуске с ним
+    pub fn request_restart(&self, profile: &str) -> Result<(), String> {
+        validate_profile_name(profile)?;
+        *self.restart_profile.lock().map_err(|_| "Mutex (restart_profile) poisoned")? = Some(profile.to_string());
+        Ok(())
+    }
+
+    pub fn take_restart_profile(&self) -> Option<String> {
+        self.restart_profile.lock().ok().and_then(|mut profile| profile.take())
+    }
+
+    /// Сохраняет мнемонику в файл резервной копии профиля и возвращает его путь
+    pub fn export_mnemonic(&self) -> Result<String, String> {
+        let path = profile_file_path(DEFAULT_MNEMONIC_BACKUP_PATH, &self.profile);
+        self.with_settings_read(|settings| settings.export_mnemonic(&path))??;
+        Ok(path.to_string_lossy().into_owned())
     }
 }
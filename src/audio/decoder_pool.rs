@@ -0,0 +1,78 @@
+use crate::constants::{CHANNELS, DECODER_POOL_MAX_IDLE, DECODER_POOL_WARM, SAMPLE_RATE};
+use opus::Decoder as OpusDecoder;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Пул декодеров Opus.
+/// Декодер закреплён за отправителем и переживает поток воспроизведения, поэтому следующая передача
+/// того же собеседника начинается без создания декодера. Для новых собеседников держим несколько
+/// заранее созданных «тёплых» декодеров. Состояние сбрасывается явно — на фрагменте со `StatusFlag::Start`.
+pub struct DecoderPool {
+    inner: Mutex<DecoderPoolInner>,
+}
+
+struct DecoderPoolInner {
+    /// Свободные декодеры по отправителю и время их возврата
+    by_sender: HashMap<String, (OpusDecoder, Instant)>,
+    /// Сброшенные декодеры без владельца
+    spare: Vec<OpusDecoder>,
+}
+
+impl DecoderPool {
+    pub fn new() -> Self {
+        let spare = (0..DECODER_POOL_WARM).filter_map(|_| Self::create_decoder()).collect();
+        Self { inner: Mutex::new(DecoderPoolInner { by_sender: HashMap::new(), spare }) }
+    }
+
+    fn create_decoder() -> Option<OpusDecoder> {
+        match OpusDecoder::new(SAMPLE_RATE, CHANNELS) {
+            Ok(decoder) => Some(decoder),
+            Err(err) => {
+                log::error!("Error while creating Opus decoder: {:?}", err);
+                None
+            }
+        }
+    }
+
+    /// Забирает декодер отправителя; если его нет — тёплый из запаса или новый
+    pub fn acquire(&self, sender: &str) -> Option<OpusDecoder> {
+        let pooled = self.inner.lock().ok().and_then(|mut inner| {
+            if let Some((decoder, _)) = inner.by_sender.remove(sender) {
+                log::debug!("Reusing Opus decoder of {}", sender);
+                return Some(decoder);
+            }
+            inner.spare.pop()
+        });
+        pooled.or_else(Self::create_decoder)
+    }
+
+    /// Возвращает декодер в пул. Сверх лимита вытесняется декодер, дольше всех не использовавшийся:
+    /// он сбрасывается и уходит в запас, если там есть место
+    pub fn release(&self, sender: &str, decoder: OpusDecoder) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        inner.by_sender.insert(sender.to_string(), (decoder, Instant::now()));
+        if inner.by_sender.len() <= DECODER_POOL_MAX_IDLE {
+            return;
+        }
+        let oldest = inner.by_sender.iter().min_by_key(|(_, (_, released))| *released).map(|(name, _)| name.clone());
+        if let Some((mut decoder, _)) = oldest.and_then(|name| inner.by_sender.remove(&name)) {
+            if inner.spare.len() < DECODER_POOL_WARM && Self::reset(&mut decoder) {
+                inner.spare.push(decoder);
+            }
+        }
+    }
+
+    /// Сбрасывает состояние декодера перед новой передачей
+    pub fn reset(decoder: &mut OpusDecoder) -> bool {
+        match decoder.reset_state() {
+            Ok(_) => true,
+            Err(err) => {
+                log::error!("Error while resetting Opus decoder: {:?}", err);
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use rand::Rng;
+
+/// Линейно затухающий хвост: убирает щелчок при резком окончании передачи
+pub fn apply_fade_out(samples: &mut [i16], fade_samples: usize) {
+    let fade_samples = fade_samples.min(samples.len());
+    if fade_samples == 0 {
+        return;
+    }
+    let start = samples.len() - fade_samples;
+    for (i, sample) in samples[start..].iter_mut().enumerate() {
+        let gain = 1.0 - (i + 1) as f32 / fade_samples as f32;
+        *sample = (*sample as f32 * gain) as i16;
+    }
+}
+
+/// Тихий белый шум, заполняющий паузы между фрагментами активной передачи
+pub fn comfort_noise(sample_count: usize, amplitude: i16) -> Vec<i16> {
+    let mut rng = rand::thread_rng();
+    (0..sample_count).map(|_| rng.gen_range(-amplitude..=amplitude)).collect()
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, StreamTrait};
+use cpal::{Device, Stream, StreamConfig};
+
+use crate::app_state::APP_STATE;
+use crate::constants::LEVEL_METER_POLL_MS;
+
+/// Индикатор уровня микрофона в верхней панели.
+/// Поток захвата cpal нельзя передавать между потоками, поэтому он живёт в отдельном потоке,
+/// который раз в LEVEL_METER_POLL_MS сверяет выбранное устройство и включённость индикатора.
+/// Устройство, которое не удалось открыть, повторно не открывается, пока выбор не сменится
+pub fn spawn_level_meter() {
+    std::thread::spawn(|| {
+        let mut current: Option<String> = None;
+        // Держится ради Drop: пока поток захвата жив, уровень обновляется
+        let mut _stream: Option<Stream> = None;
+        loop {
+            let device = if APP_STATE.is_input_meter_enabled().unwrap_or(false) {
+                APP_STATE.get_selected_input_device().ok().flatten()
+            } else {
+                None
+            };
+            let name = device.as_ref().and_then(|device| device.name().ok());
+            if name != current {
+                // Drop останавливает прежний поток захвата
+                _stream = None;
+                APP_STATE.take_input_level();
+                current = name;
+                if let Some(device) = device {
+                    match open_stream(&device) {
+                        Ok(opened) => _stream = Some(opened),
+                        Err(err) => log::warn!("Level meter could not open {:?}: {}", current, err),
+                    }
+                }
+            }
+            std::thread::sleep(Duration::from_millis(LEVEL_METER_POLL_MS));
+        }
+    });
+}
+
+fn open_stream(device: &Device) -> Result<Stream, String> {
+    let config: StreamConfig = device.default_input_config().map_err(|e| e.to_string())?.into();
+    let stream = device
+        .build_input_stream(
+            &config,
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                APP_STATE.record_input_level(data.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs())));
+            },
+            move |err| {
+                log::error!("Level meter input error: {}", err);
+            },
+            None,
+        )
+        .map_err(|e| e.to_string())?;
+    stream.play().map_err(|e| e.to_string())?;
+    Ok(stream)
+}
@@ -1,2 +1,5 @@
+pub(crate) mod decoder_pool;
+pub(crate) mod effects;
+pub(crate) mod level_meter;
 pub(crate) mod player;
 pub(crate) mod recorder;
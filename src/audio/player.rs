@@ -1,6 +1,13 @@
-use crate::constants::{CHANNELS, FRAME_SIZE, SAMPLE_RATE};
-
-use crate::models::payload::{Payload, StatusFlag};
+use crate::app_state::APP_STATE;
+use crate::constants::{
+    COMFORT_NOISE_AMPLITUDE, COMFORT_NOISE_CHUNK_MS, DEFAULT_MAX_PLAYBACK_STREAMS, FADE_OUT_MS, FRAME_SIZE, PLAYBACK_IDLE_TIMEOUT_MS,
+    SAMPLE_RATE,
+};
+
+use crate::audio::decoder_pool::DecoderPool;
+use crate::audio::effects::{apply_fade_out, comfort_noise};
+use crate::models::payload::{Payload, StatusFlag, VoiceCodec};
+use crate::utils::play_announcement_tone;
 use cpal::traits::{DeviceTrait, HostTrait};
 use dashmap::DashMap;
 use opus::Decoder as OpusDecoder;
@@ -19,21 +26,25 @@ pub struct UserBuffer {
 
 impl UserBuffer {
     pub fn new() -> Self {
-        UserBuffer { buffer: BTreeMap::new(), is_stream_active: true }
+        UserBuffer { buffer: BTreeMap::new(), is_stream_active: false }
     }
 
-    /// Добавление фрагмента в буфер
-    pub fn add_fragment(&mut self, fragment: Payload) {
+    /// Добавление фрагмента в буфер.
+    /// Возвращает `true`, если фрагмент открывает новую передачу отправителя.
+    pub fn add_fragment(&mut self, fragment: Payload) -> bool {
         self.buffer.insert(fragment.get_fragment_number(), (fragment.clone(), Instant::now()));
 
+        let stream_started = !self.is_stream_active || fragment.get_status_flag() == StatusFlag::Start;
         match fragment.get_status_flag() {
-            StatusFlag::Start => {}
-            StatusFlag::Continue => {}
+            StatusFlag::Start | StatusFlag::Continue => {
+                self.is_stream_active = true;
+            }
             StatusFlag::End => {
                 self.is_stream_active = false;
             }
             _ => {}
         }
+        stream_started
     }
 
     /// Получение следующего фрагмента по порядку
@@ -54,6 +65,7 @@ impl UserBuffer {
 
 pub struct Player {
     buffers: Arc<DashMap<String, UserBuffer>>,
+    decoders: Arc<DecoderPool>,
 }
 
 impl Player {
@@ -75,7 +87,7 @@ impl Player {
             log::info!("No default output device found");
         }
 
-        Player { buffers: Arc::new(DashMap::new()) }
+        Player { buffers: Arc::new(DashMap::new()), decoders: Arc::new(DecoderPool::new()) }
     }
 
     /// Запускает блокирующий цикл обработки входящих данных и воспроизведения.
@@ -87,7 +99,7 @@ impl Player {
         self.spawn_cleanup_thread();
 
         // Отдельный поток для считывания и воспроизведения буферов
-        Self::start_dynamic_playback(Arc::clone(&self.buffers))
+        Self::start_dynamic_playback(Arc::clone(&self.buffers), Arc::clone(&self.decoders))
     }
 
     /// Поток для чтения/обработки входящих фрагментов
@@ -95,15 +107,88 @@ impl Player {
         let buffers = Arc::clone(&self.buffers);
         thread::spawn(move || {
             log::info!("Incoming fragment processing thread started");
-            for fragment in rx {
-                let mut user_buffer = buffers.entry(fragment.get_username().to_string()).or_insert_with(UserBuffer::new);
-                user_buffer.add_fragment(fragment.clone());
-                log::debug!("Current sender buffer size id={} : {} fragments", fragment.get_username(), user_buffer.buffer.len());
+            for payload in rx {
+                // Пакет фрагментов разворачивается здесь, дальше всё работает с обычными фрагментами
+                let fragments = match payload.unpack_voice_batch() {
+                    Ok(fragments) => fragments,
+                    Err(err) => {
+                        log::warn!("Dropped malformed voice batch from {}: {}", payload.get_username(), err);
+                        continue;
+                    }
+                };
+                for fragment in fragments {
+                    if fragment.is_expired() {
+                        log::debug!(
+                            "Dropped stale fragment {} from {} (expired)",
+                            fragment.get_fragment_number(),
+                            fragment.get_username()
+                        );
+                        continue;
+                    }
+                    Self::record_stream_stats(&fragment);
+                    APP_STATE.mark_speaking(fragment.get_username(), fragment.get_status_flag());
+                    APP_STATE.chat_state.record_activity(fragment.get_username(), chrono::Utc::now().timestamp());
+                    // Заглушённый собеседник виден как говорящий, но не воспроизводится
+                    if APP_STATE.is_user_muted(fragment.get_username()) {
+                        continue;
+                    }
+                    let mut user_buffer = buffers.entry(fragment.get_username().to_string()).or_insert_with(UserBuffer::new);
+                    if user_buffer.add_fragment(fragment.clone()) {
+                        Self::announce_stream_start(&fragment);
+                        APP_STATE.chat_state.record_seen_user(
+                            fragment.get_channel(),
+                            fragment.get_username(),
+                            fragment.get_sender_address(),
+                            fragment.get_sender_key(),
+                        );
+                    }
+                    log::debug!("Current sender buffer size id={} : {} fragments", fragment.get_username(), user_buffer.buffer.len());
+                }
             }
             log::info!("Incoming fragment processing thread finished (channel closed)");
         });
     }
 
+    /// Учитывает фрагмент в статистике потерь; по окончании передачи пишет итог в лог
+    fn record_stream_stats(fragment: &Payload) {
+        let stats = match APP_STATE.record_stream_fragment(
+            fragment.get_username(),
+            fragment.get_fragment_number(),
+            fragment.get_status_flag(),
+        ) {
+            Ok(stats) => stats,
+            Err(err) => {
+                log::error!("Error while updating stream stats: {}", err);
+                return;
+            }
+        };
+        if fragment.get_status_flag() == StatusFlag::End {
+            log::info!(
+                "Stream stats for {}: received {}, lost {} ({:.1}%), duplicates {}, reordered {}",
+                fragment.get_username(),
+                stats.received,
+                stats.lost,
+                stats.loss_percent(),
+                stats.duplicates,
+                stats.reordered
+            );
+        }
+    }
+
+    /// Проигрывает сигнал о начале передачи, если он включён для канала фрагмента
+    fn announce_stream_start(fragment: &Payload) {
+        let channel = fragment.get_channel();
+        if !APP_STATE.is_voice_announce_enabled(channel).unwrap_or(false) || APP_STATE.is_channel_muted(channel).unwrap_or(false) {
+            return;
+        }
+        log::info!("Voice stream from {} started buffering", fragment.get_username());
+        thread::spawn(|| {
+            if let Err(err) = play_announcement_tone() {
+                log::error!("Error while playing announcement tone: {}", err);
+            }
+        });
+    }
+
     /// Поток для уборки устаревших данных (пока заглушка)
     fn spawn_cleanup_thread(&self) {
         let buffers_for_cleanup = Arc::clone(&self.buffers);
@@ -119,9 +204,10 @@ impl Player {
         });
     }
 
-    /// Стартует потоки для воспроизведения буферов
-    /// TODO проверить, логика выглядит чуть странной
-    fn start_dynamic_playback(buffers: Arc<DashMap<String, UserBuffer>>) {
+    /// Стартует потоки для воспроизведения буферов.
+    /// Одновременно воспроизводится не больше `max_playback_streams` собеседников,
+    /// фрагменты остальных отбрасываются, а сами собеседники отмечаются как заглушённые.
+    fn start_dynamic_playback(buffers: Arc<DashMap<String, UserBuffer>>, decoders: Arc<DecoderPool>) {
         let active_threads = Arc::new(DashMap::new());
 
         thread::spawn({
@@ -129,44 +215,29 @@ impl Player {
             let active_threads = Arc::clone(&active_threads);
 
             move || loop {
-                // Проверяем текущие буферы
-                for user_id in buffers.iter().map(|entry| entry.key().clone()) {
-                    let active_threads_inner = Arc::clone(&active_threads);
-
-                    // Запускаем поток, если его ещё нет
-                    if !active_threads_inner.contains_key(&user_id) {
-                        let buffer = Arc::clone(&buffers);
-                        let user_id_inner = user_id.clone();
-
-                        thread::spawn(move || {
-                            log::info!("Playback thread for sender buffer id={} started", &user_id_inner);
-                            let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-                            let mut opus_decoder = OpusDecoder::new(SAMPLE_RATE, CHANNELS).unwrap();
-
-                            loop {
-                                if let Some(mut buffer) = buffer.get_mut(&user_id_inner) {
-                                    if let Some(fragment) = buffer.next_fragment() {
-                                        match Player::handle_fragment(&fragment, &mut opus_decoder, &stream_handle) {
-                                            Ok(_) => (),
-                                            Err(e) => {
-                                                log::error!("Error processing fragment from {}: {:?}", fragment.get_username(), e)
-                                            }
-                                        }
-                                    }
-                                } else {
-                                    log::info!("Playback thread for id={} finished (buffer removed)", &user_id_inner);
-                                    break;
-                                }
-
-                                thread::sleep(Duration::from_millis(10));
-                            }
+                let max_streams = APP_STATE.get_max_playback_streams().unwrap_or(DEFAULT_MAX_PLAYBACK_STREAMS);
 
-                            // Удаляем поток из списка активных
-                            active_threads_inner.remove(&user_id_inner);
-                        });
+                // Отправители, у которых есть что воспроизводить
+                let pending: Vec<String> =
+                    buffers.iter().filter(|entry| !entry.value().buffer.is_empty()).map(|entry| entry.key().clone()).collect();
 
-                        active_threads.insert(user_id, ());
+                for user_id in pending {
+                    if active_threads.contains_key(&user_id) {
+                        continue;
                     }
+
+                    // Лимит потоков исчерпан: отбрасываем фрагменты и отмечаем собеседника
+                    if active_threads.len() >= max_streams {
+                        if let Some(mut buffer) = buffers.get_mut(&user_id) {
+                            buffer.buffer.clear();
+                        }
+                        APP_STATE.mark_overflow_speaker(&user_id);
+                        log::debug!("Playback limit ({}) reached, fragments from {} dropped", max_streams, &user_id);
+                        continue;
+                    }
+
+                    active_threads.insert(user_id.clone(), ());
+                    Self::spawn_playback_thread(Arc::clone(&buffers), Arc::clone(&active_threads), Arc::clone(&decoders), user_id);
                 }
 
                 // Очищаем active_threads от тех, чьи буферы были удалены
@@ -178,6 +249,68 @@ impl Player {
         });
     }
 
+    /// Поток воспроизведения одного отправителя. Завершается после окончания передачи
+    /// или долгого простоя, освобождая место в лимите одновременных потоков.
+    /// Декодер берётся из пула и возвращается в него при завершении потока.
+    fn spawn_playback_thread(
+        buffers: Arc<DashMap<String, UserBuffer>>,
+        active_threads: Arc<DashMap<String, ()>>,
+        decoders: Arc<DecoderPool>,
+        user_id: String,
+    ) {
+        thread::spawn(move || {
+            log::info!("Playback thread for sender buffer id={} started", &user_id);
+            let (_stream, stream_handle) = OutputStream::try_default().unwrap();
+            let Some(mut opus_decoder) = decoders.acquire(&user_id) else {
+                active_threads.remove(&user_id);
+                return;
+            };
+            let mut last_activity = Instant::now();
+
+            loop {
+                // Забираем фрагмент и сразу отпускаем блокировку буфера на время воспроизведения
+                let (fragment, is_stream_active) = match buffers.get_mut(&user_id) {
+                    Some(mut buffer) => (buffer.next_fragment(), buffer.is_stream_active),
+                    None => {
+                        log::info!("Playback thread for id={} finished (buffer removed)", &user_id);
+                        break;
+                    }
+                };
+
+                match fragment {
+                    Some(fragment) => {
+                        last_activity = Instant::now();
+                        // Новая передача не должна наследовать состояние предыдущей
+                        if fragment.get_status_flag() == StatusFlag::Start {
+                            DecoderPool::reset(&mut opus_decoder);
+                        }
+                        if let Err(e) = Player::handle_fragment(&fragment, &mut opus_decoder, &stream_handle) {
+                            log::error!("Error processing fragment from {}: {:?}", fragment.get_username(), e)
+                        }
+                    }
+                    None if !is_stream_active || last_activity.elapsed() > Duration::from_millis(PLAYBACK_IDLE_TIMEOUT_MS) => {
+                        log::info!("Playback thread for id={} finished (stream is over)", &user_id);
+                        break;
+                    }
+                    // Передача ещё идёт, следующий фрагмент не пришёл: заполняем паузу шумом
+                    None => {
+                        if APP_STATE.is_comfort_noise_enabled().unwrap_or(false) {
+                            if let Err(e) = Player::play_comfort_noise(&stream_handle) {
+                                log::error!("Error while playing comfort noise: {:?}", e);
+                            }
+                        }
+                    }
+                }
+
+                thread::sleep(Duration::from_millis(10));
+            }
+
+            decoders.release(&user_id, opus_decoder);
+            // Удаляем поток из списка активных
+            active_threads.remove(&user_id);
+        });
+    }
+
     /// Раскодировать и воспроизвести один фрагмент.
     fn handle_fragment(
         fragment: &Payload,
@@ -185,16 +318,35 @@ impl Player {
         stream_handle: &OutputStreamHandle,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // todo decode_audio_fragment тоже можно вынести в utils
-        let decoded_audio = Self::decode_audio_fragment(opus_decoder, &fragment.get_data())?;
+        let mut decoded_audio = match fragment.get_voice_codec() {
+            VoiceCodec::OpusMono48k => Self::decode_audio_fragment(opus_decoder, &fragment.get_data())?,
+            VoiceCodec::Unknown(codec) => return Err(format!("Unsupported voice codec {codec}").into()),
+        };
         if decoded_audio.is_empty() {
             log::warn!("Decoded audio is empty for fragment: {}", fragment.get_fragment_number());
             return Ok(());
         }
+        if fragment.get_status_flag() == StatusFlag::End {
+            apply_fade_out(&mut decoded_audio, Self::samples_for_ms(FADE_OUT_MS));
+        }
 
         Player::play_audio(decoded_audio, stream_handle)?;
         Ok(())
     }
 
+    /// Короткая вставка шума; без логирования, чтобы не засорять лог каждые 20 мс
+    fn play_comfort_noise(stream_handle: &OutputStreamHandle) -> Result<(), PlayError> {
+        let noise = comfort_noise(Self::samples_for_ms(COMFORT_NOISE_CHUNK_MS), COMFORT_NOISE_AMPLITUDE);
+        let sink = Sink::try_new(stream_handle)?;
+        sink.append(rodio::buffer::SamplesBuffer::new(1, SAMPLE_RATE, noise));
+        sink.sleep_until_end();
+        Ok(())
+    }
+
+    fn samples_for_ms(ms: u64) -> usize {
+        (SAMPLE_RATE as u64 * ms / 1000) as usize
+    }
+
     fn decode_audio_fragment(opus_decoder: &mut OpusDecoder, opus_data: &[u8]) -> Result<Vec<i16>, opus::Error> {
         let mut decoded_audio = Vec::new();
         let mut offset = 0;
@@ -1,5 +1,5 @@
 use crate::app_state::APP_STATE;
-use crate::constants::{FRAME_DURATION_MS, OPUS_BITRATE, OPUS_MAX_PACKET_SIZE};
+use crate::constants::{FRAME_DURATION_MS, OPUS_BITRATE, OPUS_MAX_PACKET_SIZE, VOICE_FRAGMENT_MS};
 use crate::models::payload::StatusFlag;
 use crate::models::recording::Recording;
 use cpal::traits::{DeviceTrait, StreamTrait};
@@ -47,6 +47,7 @@ impl Recorder {
             return;
         }
         self.recording.store(true, Ordering::SeqCst);
+        APP_STATE.start_recording_stats();
 
         // Проверка на смену устройства перед началом записи
         self.check_and_update_device();
@@ -93,6 +94,7 @@ impl Recorder {
                 }
             }
         }
+        APP_STATE.stop_recording_stats();
     }
 
     /// Останавливает запись (run_blocking() завершится после следующей итерации цикла)
@@ -126,7 +128,7 @@ impl Recorder {
         let clamped_ms = duration_ms.min(max_duration);*/
         let duration = match fragment_num {
             num if num < 1 => 800,
-            _ => 1200,
+            _ => VOICE_FRAGMENT_MS,
         };
 
         Duration::from_millis(duration)
@@ -173,6 +175,7 @@ impl Recorder {
             &*config,
             move |data: &[f32], _: &cpal::InputCallbackInfo| {
                 audio_data_clone.lock().unwrap().extend_from_slice(data);
+                APP_STATE.push_mic_level(data.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs())));
             },
             move |err| {
                 log::error!("Audio input error: {}", err);
@@ -235,6 +238,7 @@ impl Recorder {
         } else {
             tx.send(arc_recording.clone()).map_err(|_| Box::<dyn Error>::from("Error sending audio data (Continue fragment)"))?;
         }
+        APP_STATE.record_fragment_sent();
 
         Ok(())
     }
@@ -253,15 +257,11 @@ impl Recorder {
         if let Some(selected_device) = selected_device {
             let (config, sample_rate, channels, channels_count) = Self::get_device_config(&selected_device)?;
 
+            let bitrate = APP_STATE.get_opus_bitrate().unwrap_or(OPUS_BITRATE);
             let mut opus_encoder = OpusEncoder::new(sample_rate, channels, Application::Audio)?;
-            opus_encoder.set_bitrate(Bitrate::Bits(OPUS_BITRATE))?;
-
-            log::info!(
-                "Encoder configured: Sample Rate = {}, Channels = {:?}, Bitrate = {} bits/s",
-                sample_rate,
-                channels,
-                OPUS_BITRATE
-            );
+            opus_encoder.set_bitrate(Bitrate::Bits(bitrate))?;
+
+            log::info!("Encoder configured: Sample Rate = {}, Channels = {:?}, Bitrate = {} bits/s", sample_rate, channels, bitrate);
             Ok(Some((opus_encoder, config, sample_rate, channels_count)))
         } else {
             log::info!("No input device selected, encoder not created.");
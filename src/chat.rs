@@ -1,10 +1,15 @@
 use crate::app_state::APP_STATE;
-use crate::models::message::Message;
-use crate::models::payload::Payload;
+use crate::constants::{DELIVERY_CHECK_INTERVAL_SECS, SEEN_USERS_SAVE_INTERVAL_SECS};
+use crate::models::content_filter::FilterOutcome;
+use crate::models::direct_message::DirectEnvelope;
+use crate::models::message::{mentions, Message};
+use crate::models::payload::{MessageType, Payload};
 use crate::models::user::User;
-use crate::utils::play_notification_sound;
+use crate::utils::link_preview::{extract_first_url, fetch_preview};
+use crate::utils::{play_mention_tone, play_notification_sound};
 use std::sync::mpsc::Receiver;
 use std::thread;
+use std::time::Duration;
 
 pub(crate) struct Chat {}
 
@@ -35,11 +40,37 @@ IMPORTANT:
         ];
         APP_STATE.chat_state.add_message(0, example_messages[0].clone());
         APP_STATE.chat_state.add_message(0, example_messages[1].clone());
+        if let Err(err) = APP_STATE.chat_state.load_seen_users() {
+            log::error!("Error while loading seen users: {}", err);
+        }
+        if let Err(err) = APP_STATE.chat_state.load_pinned_messages() {
+            log::error!("Error while loading pinned messages: {}", err);
+        }
         Self {}
     }
 
     pub fn subscribe_to_channel(&self, chat_rx: Receiver<Payload>) {
         self.spawn_incoming_messages_thread(chat_rx);
+        self.spawn_seen_users_saver_thread();
+        self.spawn_delivery_watch_thread();
+    }
+
+    /// Периодически помечает свои сообщения, так и не попавшие в блок
+    fn spawn_delivery_watch_thread(&self) {
+        thread::spawn(|| loop {
+            thread::sleep(Duration::from_secs(DELIVERY_CHECK_INTERVAL_SECS));
+            APP_STATE.chat_state.fail_unconfirmed_messages();
+        });
+    }
+
+    /// Периодически сохраняет недавних собеседников на диск
+    fn spawn_seen_users_saver_thread(&self) {
+        thread::spawn(|| loop {
+            thread::sleep(Duration::from_secs(SEEN_USERS_SAVE_INTERVAL_SECS));
+            if let Err(err) = APP_STATE.chat_state.save_seen_users() {
+                log::error!("Error while saving seen users: {}", err);
+            }
+        });
     }
 
     /// Поток для чтения/обработки входящих сообщений
@@ -48,9 +79,45 @@ IMPORTANT:
             log::info!("Incoming message processing thread started");
             for payload in rx {
                 //todo обновить список юзеров если юзера из сообщения не было
-                log::info!("Received text message: {}", String::from_utf8_lossy(payload.get_data()));
-                let message = Message::from_payload(payload);
-                if let Err(err) = Chat::handle_incoming_message(message) {
+                let result = match payload.get_message_type() {
+                    MessageType::Direct => Chat::handle_incoming_direct_message(payload),
+                    _ => {
+                        log::info!("Received text message: {}", String::from_utf8_lossy(payload.get_data()));
+                        let claim_mismatch = APP_STATE.is_claim_mismatch(payload.get_username(), payload.get_sender_key());
+                        APP_STATE.chat_state.record_seen_user(
+                            payload.get_channel(),
+                            payload.get_username(),
+                            payload.get_sender_address(),
+                            payload.get_sender_key(),
+                        );
+                        let is_announcement = payload.get_message_type() == MessageType::Announcement;
+                        // История, подгруженная при подключении, не звенит
+                        let notify = !payload.is_backfilled();
+                        let mut message = Message::from_payload(payload);
+                        APP_STATE.chat_state.record_activity(message.get_username(), message.time.timestamp());
+                        // Фильтр содержимого не трогает свои сообщения: они уже показаны после отправки
+                        if message.get_username() != APP_STATE.get_username() {
+                            match APP_STATE.filter_content(message.channel, message.get_content()) {
+                                FilterOutcome::Pass => {}
+                                FilterOutcome::Masked(content) => message.content = content,
+                                FilterOutcome::Hidden => {
+                                    log::info!("Hid filtered message from '{}'", message.get_username());
+                                    continue;
+                                }
+                            }
+                        }
+                        if claim_mismatch {
+                            log::warn!("Message from '{}' was not sent by the owner of this name", message.user.get_username());
+                            message.claim_mismatch = true;
+                        }
+                        if is_announcement {
+                            log::info!("Announcement in channel {} from '{}'", message.channel, message.get_username());
+                            APP_STATE.chat_state.set_announcement(message.channel, message.clone());
+                        }
+                        Chat::handle_incoming_message(message, notify)
+                    }
+                };
+                if let Err(err) = result {
                     log::error!("Error while processing message: {}", err);
                 }
             }
@@ -58,13 +125,80 @@ IMPORTANT:
         });
     }
 
-    fn handle_incoming_message(message: Message) -> Result<(), String> {
+    /// Расшифровывает личное сообщение и кладёт его в переписку с собеседником
+    fn handle_incoming_direct_message(payload: Payload) -> Result<(), String> {
+        let envelope = DirectEnvelope::from_bytes(payload.get_data())?;
+        let content = envelope.open(&APP_STATE.identity)?;
+        let peer = hex::encode(envelope.peer(&APP_STATE.identity).serialize());
+        log::info!("Received direct message (peer={})", peer);
+
+        let mut message = Message::new(&mut User::new(payload.get_username()), content.trim(), payload.get_channel());
+        message.sender_key = payload.get_sender_key().map(str::to_string);
+        APP_STATE.chat_state.add_direct_message(&peer, message);
+
+        // О входящих личных сообщениях уведомляем всегда
+        if envelope.sender != APP_STATE.identity.public_key() {
+            thread::spawn(|| {
+                if let Err(err) = play_notification_sound() {
+                    log::error!("Error while playing notification sound: {}", err)
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Загружает превью ссылки в фоне, если это разрешено и оно ещё не запрашивалось
+    fn request_link_preview(url: &str) {
+        if !APP_STATE.is_link_previews_enabled().unwrap_or(false) || APP_STATE.chat_state.link_previews.contains_key(url) {
+            return;
+        }
+        APP_STATE.chat_state.link_previews.insert(url.to_string(), None);
+
+        let url = url.to_string();
+        thread::spawn(move || match fetch_preview(&url) {
+            Ok(preview) => {
+                APP_STATE.chat_state.link_previews.insert(url, Some(preview));
+            }
+            Err(err) => log::warn!("Could not load link preview for {}: {}", url, err),
+        });
+    }
+
+    fn handle_incoming_message(mut message: Message, notify: bool) -> Result<(), String> {
+        if message.is_expired() {
+            log::info!("Skipped expired message from '{}'", message.get_username());
+            return Ok(());
+        }
         let channel = message.channel;
+        message.link_url = extract_first_url(message.get_content());
+        if let Some(url) = &message.link_url {
+            Chat::request_link_preview(url);
+        }
+        let username = APP_STATE.get_username();
+        // Сообщения игнорируемых попадают в историю, но не звучат и не упоминают
+        let ignored = APP_STATE.is_user_ignored(message.get_username());
+        message.mentions_me = !ignored && message.get_username() != username && mentions(message.get_content(), &username);
+        let mentions_me = message.mentions_me;
         APP_STATE.chat_state.add_message(channel, message);
+        if !notify || ignored {
+            return Ok(());
+        }
+
+        // Упоминание слышно и видно, даже если канал заглушён или открыт другой
+        if mentions_me {
+            if APP_STATE.get_channel_number().unwrap_or(0) != channel {
+                APP_STATE.chat_state.add_unread_mention(channel);
+            }
+            thread::spawn(|| {
+                if let Err(err) = play_mention_tone() {
+                    log::error!("Error while playing mention tone: {}", err)
+                }
+            });
+            return Ok(());
+        }
 
         APP_STATE.with_listener_state_read(|state| {
-            // Уведомления только для текущего канала
-            if state.channel_number == channel {
+            // Уведомления только для текущего канала, если он не заглушён
+            if state.channel_number == channel && !APP_STATE.is_channel_muted(channel).unwrap_or(false) {
                 thread::spawn(|| match play_notification_sound() {
                     Ok(_) => {}
                     Err(err) => {
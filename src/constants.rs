@@ -1,4 +1,3 @@
-use kaspa_consensus_core::network::{NetworkId, NetworkType};
 use opus::Channels;
 
 /// Список прилагательных для генерации имен пользователей. Их длина не превышает 7 символов.
@@ -75,57 +74,367 @@ pub const EMOJIS: [&str; 70] = [
 pub const DEFAULT_SETTINGS_PATH: &'static str = "settings.kspk";
 #[cfg(not(target_os = "macos"))]
 pub const DEFAULT_LOGS_PATH: &'static str = "kaspeak.log";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_SEEN_USERS_PATH: &'static str = "seen_users.json";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_ARCHIVE_EXPORT_PATH: &'static str = "archive_scan.json";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_MNEMONIC_BACKUP_PATH: &'static str = "kaspeak_recovery_phrase.txt";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_PROFILES_DIR: &'static str = "profiles";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_TX_HISTORY_PATH: &'static str = "tx_history.json";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_CONTACTS_PATH: &'static str = "contacts.json";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_RETRY_QUEUE_PATH: &'static str = "retry_queue.json";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_SPEND_LOG_PATH: &'static str = "spend_log.json";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_TX_EXPORT_PATH: &'static str = "kaspeak_transactions.csv";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_PROCESSED_TXS_PATH: &'static str = "processed_txs.json";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_CHAT_EXPORT_PATH: &'static str = "kaspeak_channel.txt";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_PINNED_MESSAGES_PATH: &'static str = "pinned_messages.json";
 
 #[cfg(target_os = "macos")]
 pub const DEFAULT_SETTINGS_PATH: &'static str = "/Library/Caches/Kaspeak/settings.kspk";
 #[cfg(target_os = "macos")]
 pub const DEFAULT_LOGS_PATH: &'static str = "/Library/Caches/Kaspeak/logs/kaspeak.log";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_SEEN_USERS_PATH: &'static str = "/Library/Caches/Kaspeak/seen_users.json";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_ARCHIVE_EXPORT_PATH: &'static str = "/Library/Caches/Kaspeak/archive_scan.json";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_PROFILES_DIR: &'static str = "/Library/Caches/Kaspeak/profiles";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_TX_HISTORY_PATH: &'static str = "/Library/Caches/Kaspeak/tx_history.json";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_CONTACTS_PATH: &'static str = "/Library/Caches/Kaspeak/contacts.json";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_RETRY_QUEUE_PATH: &'static str = "/Library/Caches/Kaspeak/retry_queue.json";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_SPEND_LOG_PATH: &'static str = "/Library/Caches/Kaspeak/spend_log.json";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_TX_EXPORT_PATH: &'static str = "/Library/Caches/Kaspeak/kaspeak_transactions.csv";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_MNEMONIC_BACKUP_PATH: &'static str = "/Library/Caches/Kaspeak/kaspeak_recovery_phrase.txt";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_PROCESSED_TXS_PATH: &'static str = "/Library/Caches/Kaspeak/processed_txs.json";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_CHAT_EXPORT_PATH: &'static str = "/Library/Caches/Kaspeak/kaspeak_channel.txt";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_PINNED_MESSAGES_PATH: &'static str = "/Library/Caches/Kaspeak/pinned_messages.json";
+// ПРОФИЛИ: основной профиль живёт в DEFAULT_SETTINGS_PATH, остальные — <DEFAULT_PROFILES_DIR>/<имя>.kspk
+pub const DEFAULT_PROFILE: &str = "default";
+pub const PROFILE_ARG: &str = "--profile";
+pub const MAX_PROFILE_NAME_CHARS: usize = 32;
 //todo
 pub const KSPK_ENCRYPTION_KEY: [u8; 32] = *b"E31CCF4FDF6446A2712294C6C757398F";
+// Расшифрованный settings.kspk начинается с метки и версии формата; файлы v0.1.x метки не имеют
+pub const SETTINGS_MAGIC: &[u8; 4] = b"KSPK";
+pub const SETTINGS_FORMAT_VERSION: u8 = 2;
 
 // PLAYER
 pub const SAMPLE_RATE: u32 = 48000; // Частота дискретизации аудио
 pub const CHANNELS: Channels = Channels::Mono;
 pub const FRAME_SIZE: usize = (SAMPLE_RATE as f32 * FRAME_DURATION_MS as f32 / 1000.0) as usize;
+pub const DEFAULT_MAX_PLAYBACK_STREAMS: usize = 4; // Одновременно воспроизводимые собеседники
+pub const PLAYBACK_STREAM_OPTIONS: [usize; 7] = [1, 2, 3, 4, 6, 8, 16];
+pub const PLAYBACK_IDLE_TIMEOUT_MS: u64 = 5000; // Простой, после которого поток воспроизведения завершается
+pub const OVERFLOW_INDICATION_MS: u64 = 3000; // Сколько показывать заглушённого из-за лимита собеседника
+pub const SPEAKING_INDICATION_MS: u64 = 1500; // Сколько после последнего фрагмента собеседник считается говорящим
+pub const DECODER_POOL_WARM: usize = 2; // Заранее созданные декодеры для новых собеседников
+pub const DECODER_POOL_MAX_IDLE: usize = 16; // Сколько свободных декодеров хранить за отправителями
+pub const FADE_OUT_MS: u64 = 30; // Затухание в конце передачи
+pub const COMFORT_NOISE_CHUNK_MS: u64 = 20; // Длина вставки шума в паузе между фрагментами
+pub const COMFORT_NOISE_AMPLITUDE: i16 = 40; // Уровень шума (из 32767)
+
+// Сигнал о начале чужой голосовой передачи
+pub const ANNOUNCEMENT_TONE_HZ: f32 = 880.0;
+pub const ANNOUNCEMENT_TONE_MS: u64 = 120;
+pub const ANNOUNCEMENT_TONE_VOLUME: f32 = 0.15;
+// Сигнал упоминания: два тона подряд, чтобы отличался от обычного уведомления
+pub const MENTION_TONES_HZ: [f32; 2] = [660.0, 990.0];
+pub const MENTION_TONE_MS: u64 = 90;
 
 // RECORDER
 pub const FRAME_DURATION_MS: usize = 20; // Длительность одного аудиофрейма в миллисекундах
-pub const OPUS_BITRATE: i32 = 32000; // Битрейт для Opus-энкодера
+pub const VOICE_FRAGMENT_MS: u64 = 1_200; // Длительность голосового фрагмента (кроме первого, он короче)
+pub const OPUS_BITRATE: i32 = 32000; // Битрейт для Opus-энкодера по умолчанию
+pub const OPUS_BITRATE_OPTIONS: [i32; 5] = [16000, 24000, 32000, 48000, 64000]; // Выбор битрейта в настройках
 pub const OPUS_MAX_PACKET_SIZE: usize = 4000; // Максимальный размер пакета Opus
+pub const MIC_LEVEL_HISTORY: usize = 120; // Уровни микрофона для осциллограммы во время записи (по одному на буфер захвата)
+pub const WAVEFORM_HEIGHT: f32 = 32.0; // Высота осциллограммы в панели записи
+pub const LEVEL_METER_POLL_MS: u64 = 250; // Как часто индикатор уровня сверяет выбранное устройство
+pub const INPUT_LEVEL_DECAY: f32 = 0.8; // Спад индикатора уровня за тик GUI, когда звук тише
 
 // РАЗМЕР 1 КАСПЫ В СОМПИ
-pub const UNIT: f64 = 100_000_000.0;
+pub const SOMPI_PER_KAS: u64 = 100_000_000;
+// Знаков после запятой у KAS
+pub const KAS_DECIMALS: usize = 8;
+// В режиме «Auto» суммы меньше этой (0.001 KAS) показываются в сомпи
+pub const AUTO_DENOMINATION_SOMPI_BELOW: u64 = 100_000;
 
 // НАЧАЛЬНЫЙ РАЗМЕР КОМИССИИ
 pub const DEFAULT_FEE_LEVEL: u64 = 1_000_000;
+// Оценка комиссии по ноде: период обновления, минимальная ставка (сомпи за грамм)
+// и масса транзакции без payload (1 вход, 2 выхода)
+pub const FEE_ESTIMATE_REFRESH_SECS: u64 = 30;
+pub const MINIMUM_FEERATE: f64 = 1.0;
+pub const ESTIMATED_TX_BASE_MASS: u64 = 2_000;
+// ПОВЫШЕНИЕ КОМИССИИ: через сколько секунд без попадания в блок транзакция с payload считается зависшей,
+// во сколько раз (в процентах) растёт комиссия при замене, сколько замен допускается и период проверки
+pub const FEE_BUMP_TIMEOUT_SECS: u64 = 20;
+pub const FEE_BUMP_PERCENT_OPTIONS: [u32; 3] = [150, 200, 300];
+pub const DEFAULT_FEE_BUMP_PERCENT: u32 = 200;
+pub const FEE_BUMP_MAX_BUMPS: u32 = 3;
+pub const FEE_BUMP_CHECK_INTERVAL_SECS: u64 = 5;
+// ПОДТВЕРЖДЕНИЕ РАСХОДОВ: порог по умолчанию (0.1 KAS), выше которого действие требует подтверждения,
+// и длительность записи, для которой считается оценка
+pub const DEFAULT_COST_CONFIRM_THRESHOLD: u64 = 10_000_000;
+pub const COST_ESTIMATE_RECORDING_SECS: u64 = 60;
 
 // НАЧАЛЬНЫЙ КАНАЛ
 pub const DEFAULT_CHANNEL: u32 = 0;
+// Наибольший номер канала: поле ввода принимает до 7 цифр
+pub const MAX_CHANNEL_NUMBER: u32 = 9_999_999;
+// Сколько недавних каналов показывать в списке каналов
+pub const MAX_RECENT_CHANNELS: usize = 8;
+// Свои названия, темы и заметки каналов: наибольшая длина в символах
+pub const MAX_CHANNEL_NAME_CHARS: usize = 32;
+pub const MAX_CHANNEL_TOPIC_CHARS: usize = 120;
+pub const MAX_CHANNEL_NOTES_CHARS: usize = 1000;
+// ЗАКРЕПЛЁННЫЕ СООБЩЕНИЯ: сколько можно закрепить в канале и сколько символов текста видно в списке над чатом
+pub const MAX_PINNED_MESSAGES_PER_CHANNEL: usize = 10;
+pub const PINNED_PREVIEW_CHARS: usize = 80;
+
+// СРОК ЖИЗНИ СООБЩЕНИЙ: варианты для текста (секунды) и окно воспроизведения голосового фрагмента
+pub const MESSAGE_TTL_OPTIONS_SECS: [u64; 4] = [10 * 60, 60 * 60, 24 * 60 * 60, 7 * 24 * 60 * 60];
+pub const VOICE_FRAGMENT_TTL_SECS: i64 = 30;
+
+// ПАКЕТНАЯ ОТПРАВКА ГОЛОСА: фрагментов в одной транзакции и сколько ждать следующий, прежде чем отправить неполный пакет
+pub const VOICE_BATCH_MAX_FRAGMENTS: usize = 2;
+pub const VOICE_BATCH_MAX_WAIT_MS: u64 = 1500;
+/// Заголовок фрагмента внутри пакета: 1 (STATUS_FLAG) + 2 (LENGTH, BE)
+pub const VOICE_BATCH_ENTRY_HEADER: usize = 3;
+
+// КОНСОЛЬ РАЗРАБОТЧИКА: сколько строк вывода хранить
+pub const MAX_CONSOLE_LINES: usize = 200;
+// Инспектор payload: сколько последних payload хранить
+pub const MAX_INSPECTED_PAYLOADS: usize = 200;
+// Лента транзакций канала: сколько последних транзакций хранить
+pub const MAX_TX_FEED: usize = 200;
+// Архивное сканирование: прогресс в консоль раз в столько блоков
+pub const ARCHIVE_SCAN_PROGRESS_BLOCKS: u64 = 10_000;
+
+// НЕДАВНИЕ СОБЕСЕДНИКИ: сколько помнить на канал и как часто сохранять
+pub const MAX_SEEN_USERS_PER_CHANNEL: usize = 200;
+// История своих транзакций
+pub const MAX_TX_HISTORY: usize = 500;
+pub const SEEN_USERS_SAVE_INTERVAL_SECS: u64 = 30;
+
+// ДОСТАВКА СВОИХ СООБЩЕНИЙ: сколько ждать попадания транзакции в блок и как часто проверять
+pub const DELIVERY_CONFIRM_TIMEOUT_SECS: i64 = 60;
+pub const DELIVERY_CHECK_INTERVAL_SECS: u64 = 5;
 
 // ОГРАНИЧЕНИЕ РАЗМЕРА КАНАЛА
 pub const MAX_CHANNEL_CAPACITY: usize = 250;
+// ЛЕНТА ЧАТА: расчётная высота однострочного сообщения, каждой следующей строки текста, превью ссылки
+// и разделителя дня; запас сообщений вокруг видимой области и высота ленты до первой прокрутки
+pub const CHAT_ROW_ESTIMATE_PX: f32 = 72.0;
+pub const CHAT_COMPACT_ROW_ESTIMATE_PX: f32 = 28.0;
+pub const CHAT_LINE_ESTIMATE_PX: f32 = 20.0;
+pub const CHAT_CHARS_PER_LINE_ESTIMATE: usize = 80;
+pub const CHAT_LINK_PREVIEW_ESTIMATE_PX: f32 = 56.0;
+pub const CHAT_DAY_SEPARATOR_ESTIMATE_PX: f32 = 24.0;
+pub const CHAT_RENDER_OVERSCAN: usize = 10;
+pub const CHAT_DEFAULT_VIEWPORT_PX: f32 = 800.0;
+// ПАНЕЛИ ОКНА: доля левой панели и доля чата в оставшемся месте; пределы перетаскивания и задержка сохранения
+pub const DEFAULT_PANE_RATIOS: [f32; 2] = [1.0 / 6.0, 0.8];
+pub const MIN_PANE_RATIO: f32 = 0.1;
+pub const MAX_PANE_RATIO: f32 = 0.9;
+pub const PANE_RATIOS_SAVE_DELAY_MS: u64 = 1000;
+// IDENTICON: клеток по стороне и размер клетки в пикселях
+pub const IDENTICON_GRID: usize = 5;
+pub const IDENTICON_CELL_PX: u32 = 8;
+// СВОЯ ТЕМА: имя в списке тем (под ним же сохраняется выбор)
+pub const CUSTOM_THEME_NAME: &str = "Custom";
+// ТЕМА ПО СИСТЕМЕ: имя в списке тем, темы по умолчанию для светлого и тёмного оформления ОС
+// и как часто сверяться с оформлением ОС
+pub const AUTO_THEME_NAME: &str = "Auto";
+pub const DEFAULT_LIGHT_THEME: &str = "Light";
+pub const DEFAULT_DARK_THEME: &str = "Oxocarbon";
+pub const OS_THEME_POLL_SECS: u64 = 5;
 
 // МИНИМАЛЬНЫЙ РАЗМЕР БАЛАНСА ДЛЯ НАЧАЛА ЭЙРДРОПА
-pub const MINIMUM_AIRDROP_BALANCE_TKAS: f64 = 10.0f64;
+pub const MINIMUM_AIRDROP_BALANCE: u64 = 10 * SOMPI_PER_KAS;
+// Энтропия мнемоники встроенного аккаунта эйрдропа (testnet-10)
+#[cfg(feature = "builtin-faucet")]
+pub const BUILTIN_FAUCET_ENTROPY: &str = "8ee7277ab57bb6cae8c5bec2cf530459069c5a2e4ff7dc00c523a2ef0e42f97a";
+// Таймаут запроса к внешнему крану
+pub const FAUCET_REQUEST_TIMEOUT_SECS: u64 = 15;
+
+// РЕЖИМ ФОКУСИРОВКИ: варианты длительности в минутах
+pub const FOCUS_DURATION_OPTIONS_MIN: [u64; 5] = [15, 30, 60, 120, 240];
+pub const DEFAULT_FOCUS_DURATION_MIN: u64 = 60;
+
+// ОБЪЕДИНЕНИЕ UTXO: порог числа UTXO по умолчанию и варианты, период проверки и сколько простоя ждать перед объединением
+pub const DEFAULT_UTXO_MAX_COUNT: usize = 50;
+pub const UTXO_MAX_COUNT_OPTIONS: [usize; 4] = [20, 50, 100, 200];
+// Пыль: выходы дешевле порога (сомпи); объединяем, когда их набирается UTXO_DUST_SWEEP_MIN_COUNT, иначе комиссия дороже пользы
+pub const DEFAULT_UTXO_DUST_THRESHOLD: u64 = 10_000_000;
+pub const MAX_UTXO_DUST_THRESHOLD: u64 = 10 * SOMPI_PER_KAS;
+pub const UTXO_DUST_SWEEP_MIN_COUNT: usize = 10;
+pub const UTXO_COMPOUND_CHECK_INTERVAL_SECS: u64 = 60;
+pub const UTXO_COMPOUND_IDLE_SECS: u64 = 120;
+
+// ПОВТОР НЕУДАВШИХСЯ ОТПРАВОК: число попыток (включая первую), задержки экспоненциальной паузы и период проверки очереди
+pub const RETRY_MAX_ATTEMPTS: u32 = 5;
+pub const RETRY_BASE_DELAY_MS: i64 = 1_000;
+pub const RETRY_MAX_DELAY_MS: i64 = 60_000;
+pub const RETRY_CHECK_INTERVAL_MS: u64 = 500;
+
+// ВСПЛЫВАЮЩИЕ УВЕДОМЛЕНИЯ: сколько показывать и сколько держать на экране одновременно
+pub const TOAST_DURATION_SECS: u64 = 6;
+pub const MAX_TOASTS: usize = 5;
+
+// КАЧЕСТВО СОЕДИНЕНИЯ
+pub const QUALITY_WINDOW_SECS: u64 = 300; // Окно учёта ошибок
+pub const QUALITY_NOTIFICATION_GAP_SECS: u64 = 5; // Допустимая пауза между уведомлениями о блоках
+pub const QUALITY_RPC_ERROR_PENALTY: u32 = 10;
+pub const QUALITY_SEND_FAILURE_PENALTY: u32 = 15;
+// Как часто опрашивать ноду: задержка ответа, синхронизация и DAA score
+pub const DIAGNOSTICS_PROBE_INTERVAL_SECS: u64 = 5;
 
-// АЙДИ СЕТИ
-pub const NETWORK_ID: NetworkId = NetworkId::with_suffix(NetworkType::Testnet, 10);
+// СОН СИСТЕМЫ: период проверки и допустимое отставание таймера
+pub const SLEEP_CHECK_INTERVAL_SECS: u64 = 2;
+pub const SLEEP_DETECT_THRESHOLD_SECS: u64 = 10;
 
-// ПРЕФИКС АДРЕСА
-pub const PREFIX: &str = "kaspatest:";
+// ПЕРЕКЛЮЧЕНИЕ НОД: размер списка, сколько ошибок RPC за окно считаются отказом ноды и период проверки
+pub const MAX_NODE_URLS: usize = 8;
+pub const FAILOVER_RPC_ERROR_THRESHOLD: u32 = 3;
+pub const FAILOVER_ERROR_WINDOW_SECS: u64 = 60;
+pub const FAILOVER_CHECK_INTERVAL_SECS: u64 = 5;
+// АВТОПЕРЕПОДКЛЮЧЕНИЕ: экспоненциальная пауза между попытками; к ней добавляется случайная доля до RECONNECT_JITTER_PERCENT
+pub const RECONNECT_BASE_DELAY_MS: u64 = 1_000;
+pub const RECONNECT_MAX_DELAY_MS: u64 = 60_000;
+pub const RECONNECT_JITTER_PERCENT: u64 = 30;
+// Пауза после отключения: за это время соединение могли восстановить без нас (сон системы, консоль)
+pub const FAILOVER_DISCONNECT_GRACE_MS: u64 = 1_500;
+// ТАЙМАУТЫ RPC: ожидание подключения и ответа ноды, повторы запросов на чтение и пауза между ними
+pub const RPC_TIMEOUT_OPTIONS_SECS: [u64; 5] = [5, 10, 15, 30, 60];
+pub const RPC_RETRY_OPTIONS: [u32; 5] = [0, 1, 2, 3, 5];
+pub const DEFAULT_RPC_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub const DEFAULT_RPC_REQUEST_TIMEOUT_SECS: u64 = 15;
+pub const DEFAULT_RPC_REQUEST_RETRIES: u32 = 2;
+pub const RPC_RETRY_DELAY_MS: u64 = 500;
+
+// СМЕНА АДРЕСА ПОЛУЧЕНИЯ: варианты периода и как часто проверять, не пора ли
+pub const ADDRESS_ROTATION_OPTIONS_SECS: [u64; 3] = [3_600, 86_400, 604_800];
+pub const ADDRESS_ROTATION_CHECK_INTERVAL_SECS: u64 = 60;
+
+// ПРОСЛУШИВАНИЕ МЕМПУЛА: как часто читать мемпул ноды
+pub const MEMPOOL_POLL_INTERVAL_MS: u64 = 500;
+
+// ПОДГРУЗКА ИСТОРИИ при подключении: за сколько последних минут читать сообщения канала
+pub const BACKFILL_OPTIONS_MIN: [u64; 3] = [5, 15, 60];
+// Сколько единиц DAA score нарастает за секунду (10 BPS)
+pub const DAA_SCORE_PER_SECOND: u64 = 10;
+// Сколько ждать, пока Broadcaster очистит чат после подключения, прежде чем показывать историю
+pub const BACKFILL_CHAT_READY_TIMEOUT_SECS: u64 = 30;
+
+// ОКНО ДЕДУПЛИКАЦИИ ТРАНЗАКЦИЙ в единицах DAA score (в TN10 ~10 единиц в секунду).
+// Повторы внутри DAG отсекает виртуальная цепочка, окно нужно только для мемпула и переподключений
+pub const DEDUPE_WINDOW_OPTIONS: [u64; 4] = [6_000, 36_000, 216_000, 864_000];
+pub const DEFAULT_DEDUPE_WINDOW_DAA: u64 = 6_000;
+// Как часто сохранять окно дедупликации на диск, чтобы после перезапуска не показать сообщения повторно
+pub const PROCESSED_TXS_SAVE_INTERVAL_SECS: u64 = 30;
+
+// ОБРАБОТЧИКИ PAYLOAD: сколько их и сколько транзакций может ждать в очереди каждого
+pub const PAYLOAD_WORKERS: usize = 4;
+pub const PAYLOAD_WORKER_QUEUE: usize = 256;
 
 pub static NOTIFICATION_SOUND_FILE_INLINED: &'static [u8] = include_bytes!("../assets/notification.wav");
 pub static APP_ICON_FILE_INLINED: &'static [u8] = include_bytes!("../assets/256x256_1.png");
+// Каталоги строк интерфейса (см. utils::i18n)
+pub static LOCALE_EN_INLINED: &'static str = include_str!("../assets/locales/en.ftl");
+pub static LOCALE_RU_INLINED: &'static str = include_str!("../assets/locales/ru.ftl");
+
+// Наибольший номер BIP44-аккаунта (усиленный индекс)
+pub const MAX_ACCOUNT_INDEX: u64 = 0x7FFF_FFFF;
+
+// Домен для вывода ключа идентичности из мнемоники
+pub const IDENTITY_KEY_DOMAIN: &[u8] = b"kaspeak-identity";
+// Домен подписи заявки на имя пользователя
+pub const CLAIM_DOMAIN: &[u8] = b"kaspeak-claim";
+// Домен подписи отправителя над payload
+pub const SENDER_SIGNATURE_DOMAIN: &[u8] = b"kaspeak-sender";
+// Домен подписи аватара
+pub const AVATAR_DOMAIN: &[u8] = b"kaspeak-avatar";
+
+// АВАТАРЫ: картинка уменьшается до 64x64 и рассылается фрагментами
+pub const AVATAR_SIZE_PX: u32 = 64;
+pub const AVATAR_CHUNK_SIZE: usize = 8_000;
+pub const MAX_AVATAR_CHUNKS: usize = 4;
+// Повторная рассылка по запросу не чаще раза в 10 минут
+pub const AVATAR_REANNOUNCE_COOLDOWN_SECS: u64 = 600;
+
+// ТЕЛЕМЕТРИЯ: проверка раз в минуту, отправка не чаще раза в сутки
+pub const TELEMETRY_CHECK_INTERVAL_SECS: u64 = 60;
+pub const TELEMETRY_UPLOAD_INTERVAL_SECS: u64 = 24 * 60 * 60;
+pub const TELEMETRY_TIMEOUT_SECS: u64 = 10;
+
+// ПРЕВЬЮ ССЫЛОК
+pub const LINK_PREVIEW_TIMEOUT_SECS: u64 = 5;
+pub const LINK_PREVIEW_MAX_BYTES: u64 = 256 * 1024;
+pub const LINK_PREVIEW_MAX_REDIRECTS: usize = 3;
+pub const MAX_PREVIEW_DESCRIPTION_CHARS: usize = 200;
+
+// Ссылка на сообщение: kaspeak://msg/<txid>
+pub const PERMALINK_PREFIX: &str = "kaspeak://msg/";
 
 // PAYLOADS
 pub const ZSTD_COMPRESSION_LEVEL: i32 = 3;
 pub const MARKER: &[u8] = b"KSPK";
-pub const PROTOCOL_VERSION: u8 = 0;
+/// Версии протокола:
+///   0 — исходный формат
+///   1 — после данных добавлена контрольная сумма CRC32 (4 байта, big-endian)
+///   2 — после имени пользователя добавлена область расширений TLV
+pub const PROTOCOL_VERSION: u8 = 2;
+/// Минимальная версия, которую ещё принимаем от старых клиентов
+pub const MIN_PROTOCOL_VERSION: u8 = 0;
+/// Размер контрольной суммы данных (с версии 1)
+pub const CHECKSUM_SIZE: usize = 4;
+/// Размер поля длины области расширений (с версии 2)
+pub const EXTENSIONS_LEN_SIZE: usize = 2;
+/// Заголовок одного расширения: 1 (TAG) + 2 (LENGTH)
+pub const EXTENSION_HEADER_SIZE: usize = 3;
+pub const MAX_EXTENSIONS_BYTES: usize = 1024;
+/// Теги расширений:
+///   0x01 — время истечения сообщения, unix-время в секундах (8 байт, BE)
+///   0x02 — время отправки по часам отправителя, unix-время в миллисекундах (8 байт, BE)
+///   0x03 — кодек голосового фрагмента (1 байт, см. `VoiceCodec`)
+///   0x04 — текст после каждого `@` в сообщении, байт длины перед каждым (см. `mention_candidates`)
+pub const EXT_EXPIRES_AT: u8 = 0x01;
+pub const EXT_SENT_AT: u8 = 0x02;
+pub const EXT_VOICE_CODEC: u8 = 0x03;
+pub const EXT_MENTIONS: u8 = 0x04;
+/// Сколько `@` из одного сообщения попадает в расширение упоминаний
+pub const MAX_MENTIONS_PER_MESSAGE: usize = 8;
+/// Допустимое расхождение времени отправителя с временем блока; сверх него показываем время блока
+pub const MAX_CLOCK_SKEW_SECS: i64 = 300;
 
 /// Полный размер «жёсткой» части заголовка (17 байт):
 ///   4 (MARKER) + 1 (VERSION) + 3 (CHANNEL) + 1 (MESSAGE_TYPE)
 /// + 1 (STATUS_FLAG) + 3 (FRAGMENT) + 1 (USERNAME_LEN) + 3 (MESSAGE_SIZE)
+/// С версии 2 после имени идёт область расширений переменной длины, в HEADER_SIZE она не входит
 pub const HEADER_SIZE: usize = 17;
+/// Подпись отправителя после данных: 33 (PUBLIC_KEY) + 64 (SIGNATURE), см. `SenderSignature`.
+/// Старые клиенты не читают байты после данных, поэтому подпись им не мешает
+pub const SENDER_SIGNATURE_SIZE: usize = 97;
 
 pub const MAX_USERNAME_CHARS: usize = 18;
 pub const MAX_USERNAME_BYTES: usize = 255;
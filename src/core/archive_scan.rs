@@ -0,0 +1,201 @@
+//! Архивное сканирование: проход по историческому диапазону DAA score через RPC `get_blocks`
+//! и выгрузка всех KSPK payload одного канала в JSON-файл.
+//!
+//! Запускается из консоли разработчика (`archive-scan`), прогресс пишется туда же.
+
+use std::sync::Arc;
+
+use kaspa_consensus_core::Hash;
+use kaspa_wrpc_client::prelude::*;
+use kaspa_wrpc_client::KaspaRpcClient;
+use serde::Serialize;
+
+use crate::app_state::APP_STATE;
+use crate::constants::{ARCHIVE_SCAN_PROGRESS_BLOCKS, DEFAULT_ARCHIVE_EXPORT_PATH, MARKER};
+use crate::models::payload::{MessageType, Payload};
+use crate::utils::rpc::call_with_policy;
+use crate::utils::store::save_json;
+
+/// Что и где искать
+#[derive(Debug, Clone)]
+pub struct ArchiveScanRange {
+    pub from_daa: u64,
+    pub to_daa: u64,
+    pub channel: u32,
+    /// Блок, с которого начинать обход; по умолчанию — точка отсечения (pruning point) ноды
+    pub low_hash: Option<Hash>,
+}
+
+/// Одна найденная запись в файле выгрузки
+#[derive(Debug, Serialize)]
+struct ArchiveRecord {
+    tx_id: String,
+    daa_score: u64,
+    block_time: u64,
+    message_type: String,
+    username: String,
+    sender_address: Option<String>,
+    /// Ключ идентичности, если payload подписан отправителем
+    sender_key: Option<String>,
+    /// Текст для текстовых сообщений и объявлений
+    text: Option<String>,
+    data_hex: String,
+}
+
+/// Запускает сканирование в фоне; результат и прогресс выводятся в консоль разработчика
+pub fn spawn_archive_scan(client: Arc<KaspaRpcClient>, range: ArchiveScanRange) {
+    tokio::spawn(async move {
+        match scan(&client, &range).await {
+            Ok(found) => APP_STATE.push_console_output(&format!(
+                "archive-scan: {} payload(s) of channel {} saved to {}",
+                found, range.channel, DEFAULT_ARCHIVE_EXPORT_PATH
+            )),
+            Err(err) => {
+                log::error!("Archive scan failed: {}", err);
+                APP_STATE.push_console_output(&format!("archive-scan failed: {err}"));
+            }
+        }
+    });
+}
+
+/// Сканирует диапазон и сохраняет найденные payload канала в файл выгрузки
+async fn scan(client: &KaspaRpcClient, range: &ArchiveScanRange) -> Result<usize, String> {
+    let low_hash = match range.low_hash {
+        Some(hash) => hash,
+        None => call_with_policy("DAG info", || client.get_block_dag_info()).await.map_err(|e| e.to_string())?.pruning_point_hash,
+    };
+    log::info!("Archive scan of channel {} for DAA {}..={} from {}", range.channel, range.from_daa, range.to_daa, low_hash);
+
+    let mut records = Vec::new();
+    let mut scanned_blocks: u64 = 0;
+    walk_blocks(client, low_hash, range.to_daa, |block| {
+        let header = &block.header;
+        scanned_blocks += 1;
+        if scanned_blocks % ARCHIVE_SCAN_PROGRESS_BLOCKS == 0 {
+            APP_STATE.push_console_output(&format!(
+                "archive-scan: {} blocks scanned, DAA {}, {} payload(s) found",
+                scanned_blocks,
+                header.daa_score,
+                records.len()
+            ));
+        }
+        if header.daa_score < range.from_daa || header.daa_score > range.to_daa {
+            return;
+        }
+        for tx in block.transactions.iter().filter(|tx| tx.payload.starts_with(MARKER)) {
+            if let Some(record) = extract_record(tx, header.daa_score, header.timestamp, range.channel) {
+                records.push(record);
+            }
+        }
+    })
+    .await?;
+
+    // Блоки одной страницы идут не строго по DAA score, а одна транзакция может попасть в несколько блоков
+    records.sort_by_key(|record| record.daa_score);
+    let mut seen = std::collections::HashSet::new();
+    records.retain(|record| seen.insert(record.tx_id.clone()));
+
+    save_json(DEFAULT_ARCHIVE_EXPORT_PATH, &records)?;
+    log::info!("Archive scan finished: {} blocks, {} payload(s)", scanned_blocks, records.len());
+    Ok(records.len())
+}
+
+/// Обходит блоки страницами `get_blocks` от `low_hash`, пока DAA score не превысит `to_daa`
+/// или не кончится DAG. Блоки страницы приходят не строго по DAA score
+pub(crate) async fn walk_blocks(
+    client: &KaspaRpcClient,
+    mut low_hash: Hash,
+    to_daa: u64,
+    mut visit: impl FnMut(&RpcBlock),
+) -> Result<(), String> {
+    loop {
+        let response = call_with_policy("blocks", || client.get_blocks(Some(low_hash), true, true))
+            .await
+            .map_err(|e| format!("Failed to fetch blocks from {low_hash}: {e}"))?;
+        let mut next_low_hash = low_hash;
+        let mut highest_daa = 0;
+        for block in &response.blocks {
+            if block.header.daa_score >= highest_daa {
+                highest_daa = block.header.daa_score;
+                next_low_hash = block.header.hash;
+            }
+            visit(block);
+        }
+        // Дошли до конца диапазона или до вершины DAG
+        if highest_daa > to_daa || next_low_hash == low_hash {
+            return Ok(());
+        }
+        low_hash = next_low_hash;
+    }
+}
+
+/// Ищет блок цепочки с DAA score не выше `target_daa`, спускаясь от вершины DAG по родителям верхних уровней:
+/// родитель уровня k в среднем в 2^k блоках позади, поэтому хватает десятков запросов заголовков.
+/// Если нода уже не хранит нужного предка, возвращается самый ранний из найденных
+pub(crate) async fn find_block_before_daa(client: &KaspaRpcClient, target_daa: u64) -> Result<Hash, String> {
+    let sink = call_with_policy("DAG info", || client.get_block_dag_info()).await.map_err(|e| e.to_string())?.sink;
+    let mut current = call_with_policy("block", || client.get_block(sink, false)).await.map_err(|e| e.to_string())?.header;
+    let mut level = current.parents_by_level.len().saturating_sub(1);
+    while current.daa_score > target_daa {
+        let Some(parent_hash) = current.parents_by_level.get(level).and_then(|parents| parents.first()).copied() else {
+            if level == 0 {
+                break;
+            }
+            level -= 1;
+            continue;
+        };
+        let parent = match call_with_policy("block", || client.get_block(parent_hash, false)).await {
+            Ok(block) => block.header,
+            Err(err) => {
+                log::warn!("Block {} is not available ({}), starting from DAA {}", parent_hash, err, current.daa_score);
+                break;
+            }
+        };
+        // Перескочили через цель — пробуем шаг поменьше
+        if parent.daa_score < target_daa && level > 0 {
+            level -= 1;
+            continue;
+        }
+        level = level.min(parent.parents_by_level.len().saturating_sub(1));
+        current = parent;
+    }
+    Ok(current.hash)
+}
+
+/// Разбирает payload транзакции; чужие каналы и повреждённые payload пропускаются
+fn extract_record(tx: &RpcTransaction, daa_score: u64, block_time: u64, channel: u32) -> Option<ArchiveRecord> {
+    let tx_id = tx.verbose_data.as_ref()?.transaction_id.to_string();
+    let mut payload = match Payload::from_bytes(&tx.payload) {
+        Ok(payload) => payload,
+        Err(err) => {
+            log::debug!("Archive scan skipped payload in {}: {}", tx_id, err);
+            return None;
+        }
+    };
+    if payload.get_channel() != channel {
+        return None;
+    }
+    let text = match payload.get_message_type() {
+        MessageType::Text | MessageType::Announcement => {
+            payload.decompress_zstd().ok()?;
+            Some(String::from_utf8_lossy(payload.get_data()).into_owned())
+        }
+        _ => None,
+    };
+    let sender_key = payload.verify_sender(&tx.payload);
+    // Kaspeak отправляет транзакции на свой адрес, первый выход — адрес отправителя
+    let sender_address =
+        tx.outputs.first().and_then(|out| out.verbose_data.as_ref()).map(|vd| vd.script_public_key_address.address_to_string());
+
+    Some(ArchiveRecord {
+        tx_id,
+        daa_score,
+        block_time,
+        message_type: format!("{:?}", payload.get_message_type()),
+        username: payload.get_username().to_string(),
+        sender_address,
+        sender_key,
+        text,
+        data_hex: hex::encode(payload.get_data()),
+    })
+}
@@ -2,18 +2,29 @@ use futures::stream::FuturesUnordered;
 use futures::{select_biased, FutureExt, StreamExt};
 use kaspa_wallet_core::rpc::RpcApi;
 use kaspa_wrpc_client::prelude::RpcState;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::Semaphore;
+use tokio::task::AbortHandle;
 use workflow_core::{
     channel::{Channel, DuplexChannel, SendError},
     task::spawn,
 };
 
-use crate::app_state::APP_STATE;
-use crate::constants::{MINIMUM_AIRDROP_BALANCE_TKAS, UNIT};
+use crate::app_state::{ErrorEvent, ToastKind, APP_STATE};
+use crate::constants::{
+    ADDRESS_ROTATION_CHECK_INTERVAL_SECS, DEFAULT_UTXO_DUST_THRESHOLD, DEFAULT_UTXO_MAX_COUNT, FEE_BUMP_CHECK_INTERVAL_SECS,
+    FEE_BUMP_TIMEOUT_SECS, FEE_ESTIMATE_REFRESH_SECS, MINIMUM_AIRDROP_BALANCE, RETRY_CHECK_INTERVAL_MS, RETRY_MAX_ATTEMPTS,
+    UTXO_COMPOUND_CHECK_INTERVAL_SECS, UTXO_COMPOUND_IDLE_SECS, UTXO_DUST_SWEEP_MIN_COUNT,
+};
 use crate::core::wallet::WalletService;
 use crate::models::instruction::Instruction;
-use crate::models::instruction::Instruction::{Airdrop, SendTx};
+use crate::models::instruction::Instruction::{Airdrop, BumpFee, Compound, Retry, RotateAddress, SendTx, Transfer};
+use crate::models::instruction::{BumpFeeInstruction, RetryInstruction};
+use crate::models::payload::{MessageType, Payload};
+use crate::models::transaction::FeeRates;
+use crate::utils::rpc::call_with_policy;
 use kaspa_wrpc_client::{result::Result as KaspaResult, KaspaRpcClient};
 
 pub struct BroadcasterInner {
@@ -21,6 +32,10 @@ pub struct BroadcasterInner {
     client: Arc<KaspaRpcClient>,
     instruction_channel: Channel<Instruction>,
     wallet_service: Arc<WalletService>,
+    /// Объединение UTXO уже запущено — повторное не ставим
+    is_compounding: AtomicBool,
+    /// Фоновые циклы, запущенные в `start`; `stop` их останавливает
+    background_tasks: Mutex<Vec<AbortHandle>>,
 }
 
 #[derive(Clone)]
@@ -30,17 +45,34 @@ pub struct Broadcaster {
 
 impl Broadcaster {
     pub fn try_new(client: Arc<KaspaRpcClient>, wallet_service: Arc<WalletService>) -> KaspaResult<Self> {
-        let inner =
-            BroadcasterInner { task_ctl: DuplexChannel::oneshot(), client, instruction_channel: Channel::unbounded(), wallet_service };
+        let inner = BroadcasterInner {
+            task_ctl: DuplexChannel::oneshot(),
+            client,
+            instruction_channel: Channel::unbounded(),
+            wallet_service,
+            is_compounding: AtomicBool::new(false),
+            background_tasks: Mutex::new(Vec::new()),
+        };
         Ok(Self { inner: Arc::new(inner) })
     }
 
     pub async fn start(&self) -> KaspaResult<()> {
         self.spawn_event_loop().await?;
+        let tasks = [
+            self.spawn_compound_watch(),
+            self.spawn_fee_estimate_watch(),
+            self.spawn_retry_watch(),
+            self.spawn_fee_bump_watch(),
+            self.spawn_address_rotation_watch(),
+        ];
+        self.inner.background_tasks.lock().unwrap().extend(tasks);
         Ok(())
     }
 
     pub async fn stop(&self) -> KaspaResult<()> {
+        for task in self.inner.background_tasks.lock().unwrap().drain(..) {
+            task.abort();
+        }
         self.client().disconnect().await?;
         self.stop_event_loop().await?;
         Ok(())
@@ -55,6 +87,10 @@ impl Broadcaster {
         &self.inner.client
     }
 
+    pub fn wallet_service(&self) -> &Arc<WalletService> {
+        &self.inner.wallet_service
+    }
+
     async fn spawn_event_loop(&self) -> KaspaResult<()> {
         let broadcaster = self.clone();
         let rpc_ctl_channel = self.client().rpc_ctl().multiplexer().channel();
@@ -76,6 +112,8 @@ impl Broadcaster {
                                 RpcState::Connected => {
                                     if let Err(err) = broadcaster.handle_connect().await {
                                         log::error!("Error while connecting: {err}");
+                                        APP_STATE.record_rpc_error(&format!("Broadcaster connect: {err}"));
+                                        APP_STATE.report_error(ErrorEvent::ConnectFailed(err.to_string()));
                                     } else {
                                         while let Some(instr) = deferred_instructions.pop() {
                                             let permit = semaphore.clone().acquire_owned().await.unwrap();
@@ -126,6 +164,177 @@ impl Broadcaster {
         Ok(())
     }
 
+    /// Периодически проверяет число UTXO и пыль и, пока отправок нет, ставит инструкцию на их объединение.
+    /// Частые отправки payload и эйрдроп дробят баланс на мелкие UTXO, из-за чего каждая следующая отправка медленнее.
+    fn spawn_compound_watch(&self) -> AbortHandle {
+        let broadcaster = self.clone();
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(UTXO_COMPOUND_CHECK_INTERVAL_SECS);
+            loop {
+                tokio::time::sleep(interval).await;
+                if broadcaster.is_compound_due().await {
+                    broadcaster.inner.is_compounding.store(true, Ordering::SeqCst);
+                    if let Err(err) = broadcaster.send_instruction(Compound).await {
+                        log::error!("Error while sending compound instruction: {}", err);
+                        broadcaster.inner.is_compounding.store(false, Ordering::SeqCst);
+                    }
+                }
+            }
+        })
+        .abort_handle()
+    }
+
+    /// Периодически забирает из очереди повторов отправки, время которых пришло.
+    /// Пока Broadcaster отключён, очередь не трогаем: попытки тратились бы впустую
+    fn spawn_retry_watch(&self) -> AbortHandle {
+        let broadcaster = self.clone();
+        tokio::spawn(async move {
+            let interval = Duration::from_millis(RETRY_CHECK_INTERVAL_MS);
+            loop {
+                tokio::time::sleep(interval).await;
+                if !Self::is_connected() {
+                    continue;
+                }
+                let due = match APP_STATE.take_due_retries() {
+                    Ok(due) => due,
+                    Err(err) => {
+                        log::error!("Error while reading retry queue: {}", err);
+                        continue;
+                    }
+                };
+                for entry in due {
+                    let instruction = match entry.payload() {
+                        Ok(tx_payload) => Retry(RetryInstruction { id: entry.id, tx_payload }),
+                        Err(err) => {
+                            log::error!("Dropped retry of send #{}: {}", entry.id, err);
+                            APP_STATE.complete_send_retry(entry.id).map_err(|e| log::error!("Error complete_send_retry: {}", e)).ok();
+                            continue;
+                        }
+                    };
+                    if let Err(err) = broadcaster.send_instruction(instruction).await {
+                        log::error!("Error while sending retry instruction: {}", err);
+                    }
+                }
+            }
+        })
+        .abort_handle()
+    }
+
+    /// Периодически ищет свои транзакции с payload, не попавшие в блок за FEE_BUMP_TIMEOUT_SECS,
+    /// и ставит их замену с большей комиссией, чтобы голос не застревал при росте комиссий в сети
+    fn spawn_fee_bump_watch(&self) -> AbortHandle {
+        let broadcaster = self.clone();
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(FEE_BUMP_CHECK_INTERVAL_SECS);
+            loop {
+                tokio::time::sleep(interval).await;
+                if !Self::is_connected() || APP_STATE.get_fee_bump_percent().ok().flatten().is_none() {
+                    continue;
+                }
+                let stuck = match APP_STATE.take_stuck_sends(Duration::from_secs(FEE_BUMP_TIMEOUT_SECS)) {
+                    Ok(stuck) => stuck,
+                    Err(err) => {
+                        log::error!("Error while reading unconfirmed sends: {}", err);
+                        continue;
+                    }
+                };
+                for (tx_id, bumps) in stuck {
+                    log::info!("Send {} is not confirmed after {} s (bumped {} times)", tx_id, FEE_BUMP_TIMEOUT_SECS, bumps);
+                    if let Err(err) = broadcaster.send_instruction(BumpFee(BumpFeeInstruction { tx_id })).await {
+                        log::error!("Error while sending fee bump instruction: {}", err);
+                    }
+                }
+            }
+        })
+        .abort_handle()
+    }
+
+    /// Переходит на новый адрес получения, когда истекает период из настроек
+    fn spawn_address_rotation_watch(&self) -> AbortHandle {
+        let broadcaster = self.clone();
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(ADDRESS_ROTATION_CHECK_INTERVAL_SECS);
+            loop {
+                tokio::time::sleep(interval).await;
+                if Self::is_connected() && APP_STATE.is_address_rotation_due() {
+                    if let Err(err) = broadcaster.send_instruction(RotateAddress).await {
+                        log::error!("Error while sending address rotation instruction: {}", err);
+                    }
+                }
+            }
+        })
+        .abort_handle()
+    }
+
+    /// Периодически запрашивает у ноды оценку комиссии для автоматического режима
+    fn spawn_fee_estimate_watch(&self) -> AbortHandle {
+        let broadcaster = self.clone();
+        tokio::spawn(async move {
+            let interval = Duration::from_secs(FEE_ESTIMATE_REFRESH_SECS);
+            loop {
+                if Self::is_connected() {
+                    broadcaster.refresh_fee_estimate().await;
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+        .abort_handle()
+    }
+
+    async fn refresh_fee_estimate(&self) {
+        let estimate = match call_with_policy("fee estimate", || self.client().get_fee_estimate()).await {
+            Ok(estimate) => estimate,
+            Err(err) => {
+                log::warn!("Error while requesting fee estimate: {}", err);
+                return;
+            }
+        };
+        let priority = estimate.priority_bucket.feerate;
+        let normal = estimate.normal_buckets.first().map_or(priority, |bucket| bucket.feerate);
+        let low = estimate.low_buckets.first().map_or(normal, |bucket| bucket.feerate);
+        log::debug!("Fee estimate: low={:.3}, normal={:.3}, priority={:.3} sompi/gram", low, normal, priority);
+        if let Err(err) = APP_STATE.set_fee_rates(FeeRates { low, normal, priority }) {
+            log::error!("Error while saving fee estimate: {}", err);
+        }
+    }
+
+    /// Объединять стоит, если Broadcaster подключён, давно ничего не отправлял,
+    /// а UTXO больше настроенного предела или накопилось достаточно пыли
+    async fn is_compound_due(&self) -> bool {
+        if self.inner.is_compounding.load(Ordering::SeqCst) || !Self::is_connected() {
+            return false;
+        }
+        let is_idle = APP_STATE
+            .broadcaster_idle_for()
+            .unwrap_or_default()
+            .map_or(true, |idle| idle >= Duration::from_secs(UTXO_COMPOUND_IDLE_SECS));
+        if !is_idle {
+            return false;
+        }
+        let utxos = APP_STATE.get_utxos().unwrap_or(0);
+        let max_utxos = APP_STATE.get_utxo_max_count().unwrap_or(DEFAULT_UTXO_MAX_COUNT);
+        if utxos > max_utxos {
+            log::info!("{} UTXOs exceed the limit of {}, compounding", utxos, max_utxos);
+            return true;
+        }
+        // Пыли меньше, чем UTXO вообще, быть не может — к ноде ходим, только если её может набраться достаточно
+        if utxos < UTXO_DUST_SWEEP_MIN_COUNT {
+            return false;
+        }
+        let threshold = APP_STATE.get_utxo_dust_threshold().unwrap_or(DEFAULT_UTXO_DUST_THRESHOLD);
+        match self.inner.wallet_service.count_dust_utxos(threshold).await {
+            Ok(dust) if dust >= UTXO_DUST_SWEEP_MIN_COUNT => {
+                log::info!("{} dust UTXOs below {} sompi, compounding", dust, threshold);
+                true
+            }
+            Ok(_) => false,
+            Err(err) => {
+                log::warn!("Error while counting dust UTXOs: {}", err);
+                false
+            }
+        }
+    }
+
     async fn stop_event_loop(&self) -> KaspaResult<()> {
         self.inner.task_ctl.signal(()).await.expect("Failed to stop the event loop");
         Ok(())
@@ -140,25 +349,22 @@ impl Broadcaster {
 
     async fn handle_connect(&self) -> KaspaResult<()> {
         log::info!("Connected to {:?}", self.client().url());
-        let server_info = self.client().get_server_info().await?;
+        let server_info = call_with_policy("server info", || self.client().get_server_info())
+            .await
+            .map_err(|e| kaspa_wrpc_client::error::Error::from(e.to_string()))?;
         log::info!("Server info: {:?}", server_info);
 
         match self.inner.wallet_service.handle_connect_to_node().await {
-            Ok(curr_balance) => {
-                let balance_tkas = (curr_balance.mature as f64) / UNIT;
-                if balance_tkas < MINIMUM_AIRDROP_BALANCE_TKAS {
-                    if let Err(err) = self.send_instruction(Airdrop).await {
-                        log::error!("Error while sending airdrop instruction: {}", err);
-                    }
-                }
-            }
+            Ok(curr_balance) => self.check_airdrop_balance(curr_balance.mature).await,
             Err(err) => {
                 return Err(kaspa_wrpc_client::error::Error::from(err.to_string()));
             }
         }
 
-        APP_STATE.set_broadcaster_connected(true).map_err(|e| log::error!("Error set_broadcaster_connected: {}", e)).ok();
+        // Сначала очищаем чат: подгрузка истории в Listener ждёт флага подключения
         APP_STATE.chat_state.clear();
+        APP_STATE.set_broadcaster_connected(true).map_err(|e| log::error!("Error set_broadcaster_connected: {}", e)).ok();
+        self.refresh_fee_estimate().await;
 
         Ok(())
     }
@@ -173,27 +379,127 @@ impl Broadcaster {
         let wallet_service = &self.inner.wallet_service;
 
         match instruction {
-            SendTx(send_tx) => match wallet_service.send_transaction_to_self(None, send_tx.tx_payload).await {
-                Ok(current_balance) => {
-                    let balance_tkas = (current_balance.mature as f64) / UNIT;
-                    if balance_tkas < MINIMUM_AIRDROP_BALANCE_TKAS {
-                        if let Err(err) = self.send_instruction(Airdrop).await {
-                            log::error!("Error sending airdrop instruction: {}", err);
+            SendTx(send_tx) => {
+                // Лимит трат превышен: отправка на паузе, сообщение не ставим и в очередь повторов
+                if let Err(reason) = APP_STATE.check_spend_limits(0) {
+                    log::debug!("Dropped payload while paused: {}", reason);
+                    return Ok(());
+                }
+                APP_STATE.mark_broadcaster_send().map_err(|e| log::error!("Error mark_broadcaster_send: {}", e)).ok();
+                let is_voice = send_tx
+                    .tx_payload
+                    .as_deref()
+                    .and_then(Payload::peek_message_type)
+                    .is_some_and(|message_type| matches!(message_type, MessageType::Voice | MessageType::VoiceBatch));
+                match wallet_service.send_transaction_to_self(None, send_tx.tx_payload.clone()).await {
+                    Ok(current_balance) => {
+                        if is_voice {
+                            APP_STATE.record_voice_transaction_sent();
+                        }
+                        self.check_airdrop_balance(current_balance.mature).await
+                    }
+                    Err(err) => {
+                        log::error!("Error while sending transaction: {}", err);
+                        APP_STATE.record_send_failure(&format!("Send: {err}"));
+                        APP_STATE.report_error(ErrorEvent::from_send_error(&err.to_string()));
+                        if let Some(payload) = send_tx.tx_payload {
+                            APP_STATE
+                                .enqueue_send_retry(&payload, &err.to_string())
+                                .map_err(|e| log::error!("Error enqueue_send_retry: {}", e))
+                                .ok();
+                        }
+                    }
+                }
+            }
+            Retry(retry) => {
+                if let Err(reason) = APP_STATE.check_spend_limits(0) {
+                    APP_STATE.fail_send_retry(retry.id, &reason).map_err(|e| log::error!("Error fail_send_retry: {}", e)).ok();
+                    return Ok(());
+                }
+                APP_STATE.mark_broadcaster_send().map_err(|e| log::error!("Error mark_broadcaster_send: {}", e)).ok();
+                match wallet_service.send_transaction_to_self(None, Some(retry.tx_payload)).await {
+                    Ok(current_balance) => {
+                        log::info!("Send #{} succeeded on retry", retry.id);
+                        APP_STATE.complete_send_retry(retry.id).map_err(|e| log::error!("Error complete_send_retry: {}", e)).ok();
+                        self.check_airdrop_balance(current_balance.mature).await;
+                    }
+                    Err(err) => {
+                        APP_STATE.record_send_failure(&format!("Send retry: {err}"));
+                        match APP_STATE.fail_send_retry(retry.id, &err.to_string()) {
+                            Ok(true) => log::warn!("Retry of send #{} failed: {}", retry.id, err),
+                            Ok(false) => {
+                                log::error!("Giving up on send #{} after {} attempts: {}", retry.id, RETRY_MAX_ATTEMPTS, err);
+                                APP_STATE.report_error(ErrorEvent::from_send_error(&err.to_string()));
+                            }
+                            Err(e) => log::error!("Error fail_send_retry: {}", e),
                         }
                     }
                 }
-                Err(err) => {
-                    log::error!("Error while sending transaction: {}", err);
+            }
+            BumpFee(bump) => {
+                let percent = match APP_STATE.get_fee_bump_percent() {
+                    Ok(Some(percent)) => percent,
+                    _ => {
+                        APP_STATE.fail_fee_bump(&bump.tx_id).map_err(|e| log::error!("Error fail_fee_bump: {}", e)).ok();
+                        return Ok(());
+                    }
+                };
+                if let Err(reason) = APP_STATE.check_spend_limits(0) {
+                    log::debug!("Fee bump of {} skipped while paused: {}", bump.tx_id, reason);
+                    APP_STATE.fail_fee_bump(&bump.tx_id).map_err(|e| log::error!("Error fail_fee_bump: {}", e)).ok();
+                    return Ok(());
                 }
-            },
+                match wallet_service.bump_fee(&bump.tx_id, percent).await {
+                    Ok(replacement) => APP_STATE
+                        .finish_fee_bump(&bump.tx_id, replacement.as_deref())
+                        .map_err(|e| log::error!("Error finish_fee_bump: {}", e))
+                        .ok(),
+                    Err(err) => {
+                        log::warn!("Error while bumping fee of {}: {}", bump.tx_id, err);
+                        APP_STATE.fail_fee_bump(&bump.tx_id).map_err(|e| log::error!("Error fail_fee_bump: {}", e)).ok()
+                    }
+                };
+            }
+            RotateAddress => {
+                if let Err(err) = wallet_service.new_receive_address().await {
+                    log::error!("Error while deriving a new receive address: {}", err);
+                }
+            }
             Airdrop => {
                 if let Err(err) = wallet_service.handle_airdrop().await {
                     log::error!("Error while performing Airdrop instruction: {}", err);
                 }
             }
+            Transfer(transfer) => {
+                if let Err(reason) = APP_STATE.check_spend_limits(transfer.amount) {
+                    log::warn!("Transfer of {} sompi to {} refused: {}", transfer.amount, transfer.address, reason);
+                    APP_STATE.push_toast(ToastKind::SpendLimit(reason));
+                    return Ok(());
+                }
+                if let Err(err) = wallet_service.send_funds(&transfer.address, transfer.amount).await {
+                    log::error!("Error while sending funds to {}: {}", transfer.address, err);
+                    APP_STATE.record_send_failure(&format!("Transfer: {err}"));
+                    APP_STATE.report_error(ErrorEvent::from_send_error(&err.to_string()));
+                }
+            }
+            Compound => {
+                if let Err(err) = wallet_service.compound_utxos().await {
+                    log::error!("Error while compounding UTXOs: {}", err);
+                }
+                self.inner.is_compounding.store(false, Ordering::SeqCst);
+            }
         }
         Ok(())
     }
+
+    /// Просит эйрдроп, если баланс упал ниже минимума и источник пополнения доступен в этой сети
+    async fn check_airdrop_balance(&self, mature: u64) {
+        if mature < MINIMUM_AIRDROP_BALANCE && APP_STATE.is_faucet_available() {
+            if let Err(err) = self.send_instruction(Airdrop).await {
+                log::error!("Error sending airdrop instruction: {}", err);
+            }
+        }
+    }
 }
 
 async fn spawn_task(broadcaster: Broadcaster, instruction: Instruction, _permit: tokio::sync::OwnedSemaphorePermit) {
@@ -0,0 +1,121 @@
+//! Скрытая консоль разработчика (Ctrl+Shift+D в GUI).
+//!
+//! GUI отправляет строку команды событием `GuiEvent::ConsoleCommand`, команда выполняется здесь,
+//! а ответ построчно складывается в `APP_STATE` и забирается GUI на очередном тике.
+
+use std::sync::Arc;
+
+use kaspa_wrpc_client::KaspaRpcClient;
+use log::LevelFilter;
+use tokio::sync::broadcast::Sender;
+
+use crate::app_state::APP_STATE;
+use crate::core::archive_scan::{spawn_archive_scan, ArchiveScanRange};
+use crate::models::payload::{MessageType, Payload};
+use crate::utils::format::format_amount;
+use crate::utils::try_connect_to_node;
+
+const HELP: &str = "Commands:
+  help                      this list
+  stats                     connection, wallet and telemetry counters
+  drop-connection           disconnect from the node
+  reconnect                 connect to the current node again
+  fake-payload <hex>        inject a payload as if it came from the network
+  archive-scan <from-daa> <to-daa> <channel> [low-hash]
+                            export channel payloads from a DAA range to a file
+  set-loglevel <level>      off | error | warn | info | debug | trace";
+
+/// Выполняет команду и возвращает ответ для вывода в консоль
+pub async fn execute(command: &str, client: &Arc<KaspaRpcClient>, payload_tx: &Sender<Payload>) -> Result<String, String> {
+    let mut parts = command.split_whitespace();
+    let name = parts.next().unwrap_or_default();
+    let args: Vec<&str> = parts.collect();
+    match (name, args.as_slice()) {
+        ("help", []) => Ok(HELP.to_string()),
+        ("stats", []) => Ok(stats()),
+        ("drop-connection", []) => {
+            client.disconnect().await.map_err(|e| format!("Disconnect failed: {e}"))?;
+            Ok("Disconnected".to_string())
+        }
+        ("reconnect", []) => {
+            try_connect_to_node(client.clone(), client.url()).await;
+            Ok(format!("Connecting to {}", client.url().unwrap_or_else(|| "resolver".to_string())))
+        }
+        ("fake-payload", [hex_payload]) => fake_payload(hex_payload, payload_tx),
+        ("archive-scan", [from_daa, to_daa, channel, rest @ ..]) if rest.len() <= 1 => {
+            let range = parse_archive_range(from_daa, to_daa, channel, rest.first().copied())?;
+            let response = format!("Scanning DAA {}..={} of channel {} in background", range.from_daa, range.to_daa, range.channel);
+            spawn_archive_scan(client.clone(), range);
+            Ok(response)
+        }
+        ("set-loglevel", [level]) => {
+            let level: LevelFilter = level.parse().map_err(|_| format!("Unknown log level '{level}'"))?;
+            log::set_max_level(level);
+            Ok(format!("Log level set to {level}"))
+        }
+        ("", _) => Ok(String::new()),
+        _ => Err(format!("Unknown command or wrong arguments: '{command}'. Type 'help'")),
+    }
+}
+
+fn stats() -> String {
+    let quality = APP_STATE.get_connection_quality().map(|quality| quality.score.to_string()).unwrap_or_else(|e| e);
+    let counters = APP_STATE.get_telemetry_counters().unwrap_or_default();
+    let mut lines = vec![
+        format!("listener connected: {}", APP_STATE.is_listener_connected().unwrap_or(false)),
+        format!("broadcaster connected: {}", APP_STATE.is_broadcaster_connected().unwrap_or(false)),
+        format!("connection quality: {quality}"),
+        format!("balance: {}", format_amount(APP_STATE.get_balance().unwrap_or(0), 8)),
+        format!("utxos: {}", APP_STATE.get_utxos().unwrap_or(0)),
+        format!("reconnects: {}, audio dropouts: {}", counters.reconnects, counters.audio_dropouts),
+    ];
+    for (kind, count) in &counters.parse_errors {
+        lines.push(format!("parse errors [{kind}]: {count}"));
+    }
+    for (version, count) in &counters.protocol_versions {
+        lines.push(format!("protocol v{version}: {count}"));
+    }
+    for (message_type, count) in &counters.message_types {
+        lines.push(format!("message type {message_type}: {count}"));
+    }
+    for (reason, count) in APP_STATE.get_dropped_payloads().unwrap_or_default() {
+        lines.push(format!("dropped payloads [{reason}]: {count}"));
+    }
+    for (name, stats) in APP_STATE.get_stream_stats().unwrap_or_default() {
+        lines.push(format!("stream {name}: received {}, lost {}", stats.received, stats.lost));
+    }
+    lines.join("\n")
+}
+
+fn parse_archive_range(from_daa: &str, to_daa: &str, channel: &str, low_hash: Option<&str>) -> Result<ArchiveScanRange, String> {
+    let from_daa: u64 = from_daa.parse().map_err(|_| format!("Invalid DAA score '{from_daa}'"))?;
+    let to_daa: u64 = to_daa.parse().map_err(|_| format!("Invalid DAA score '{to_daa}'"))?;
+    if from_daa > to_daa {
+        return Err(format!("Empty DAA range {from_daa}..={to_daa}"));
+    }
+    let channel: u32 = channel.parse().map_err(|_| format!("Invalid channel '{channel}'"))?;
+    let low_hash = low_hash.map(|hash| hash.parse().map_err(|_| format!("Invalid block hash '{hash}'"))).transpose()?;
+    Ok(ArchiveScanRange { from_daa, to_daa, channel, low_hash })
+}
+
+/// Разбирает payload в режиме восстановления и отправляет потребителям как пришедший из сети.
+/// Сжатые текст и голос распаковываются, как это делает Listener; несжатые данные передаются как есть
+fn fake_payload(hex_payload: &str, payload_tx: &Sender<Payload>) -> Result<String, String> {
+    let bytes = hex::decode(hex_payload).map_err(|e| format!("Invalid hex: {e}"))?;
+    let (mut payload, issues) = Payload::from_bytes_lenient(&bytes).map_err(|issue| issue.to_string())?;
+    if matches!(
+        payload.get_message_type(),
+        MessageType::Text | MessageType::Voice | MessageType::VoiceBatch | MessageType::Announcement
+    ) {
+        let mut decompressed = payload.clone();
+        if decompressed.decompress_zstd().is_ok() {
+            payload = decompressed;
+        }
+    }
+    let mut response = format!("Injected: {}", payload.debug_string());
+    for issue in issues {
+        response.push_str(&format!("\nissue [{}]: {}", issue.kind(), issue));
+    }
+    payload_tx.send(payload).map_err(|e| format!("Broadcast failed: {e}"))?;
+    Ok(response)
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use kaspa_wallet_core::rpc::RpcApi;
+use kaspa_wrpc_client::KaspaRpcClient;
+
+use crate::app_state::APP_STATE;
+use crate::constants::DIAGNOSTICS_PROBE_INTERVAL_SECS;
+
+/// Опрашивает ноду для панели диагностики и строки состояния: get_server_info даёт и задержку ответа,
+/// и синхронизацию с DAA score. Запрос, не уложившийся в таймаут, считается ошибкой RPC, но без уведомления:
+/// при зависшей ноде оно появлялось бы каждые несколько секунд
+pub fn spawn_latency_probe(kaspa_rpc_client: Arc<KaspaRpcClient>) {
+    tokio::spawn(async move {
+        let interval = Duration::from_secs(DIAGNOSTICS_PROBE_INTERVAL_SECS);
+        loop {
+            tokio::time::sleep(interval).await;
+            if !kaspa_rpc_client.is_connected() {
+                APP_STATE.set_latency(None);
+                continue;
+            }
+            let timeout = APP_STATE.get_rpc_policy().unwrap_or_default().request_timeout();
+            let started = Instant::now();
+            match tokio::time::timeout(timeout, kaspa_rpc_client.get_server_info()).await {
+                Ok(Ok(server_info)) => {
+                    APP_STATE.set_latency(Some(started.elapsed().as_millis() as u64));
+                    APP_STATE.set_sync_status(server_info.is_synced, server_info.virtual_daa_score);
+                }
+                Ok(Err(err)) => {
+                    log::warn!("Node probe failed: {}", err);
+                    APP_STATE.set_latency(None);
+                    APP_STATE.record_rpc_error(&format!("Server info: {err}"));
+                }
+                Err(_) => {
+                    log::warn!("Node probe timed out after {} s", timeout.as_secs());
+                    APP_STATE.set_latency(None);
+                    APP_STATE.record_rpc_error(&format!("Server info timed out after {} s", timeout.as_secs()));
+                }
+            }
+        }
+    });
+}
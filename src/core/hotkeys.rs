@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rdev::{listen, EventType, Key};
+
+use crate::app_state::APP_STATE;
+use crate::models::shortcut::ShortcutAction;
+
+/// Глобальный перехват уже запущен: rdev::listen не останавливается до выхода
+static LISTENER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Запускает глобальный перехват клавиатуры для push-to-talk, если клавиша назначена.
+/// Вызывается при старте и после изменения сочетаний; перехват запускается один раз,
+/// а назначенное сочетание читается из настроек при каждом нажатии.
+/// Нажатие и отпускание передаются в GUI через APP_STATE, чтобы запись шла тем же путём, что и по кнопке
+pub fn ensure_push_to_talk_listener() {
+    let bound = APP_STATE.get_shortcut(ShortcutAction::PushToTalk).ok().flatten().is_some();
+    if !bound || LISTENER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    std::thread::spawn(|| {
+        let (mut ctrl, mut shift, mut alt) = (false, false, false);
+        let mut held: Option<&'static str> = None;
+        let result = listen(move |event| match event.event_type {
+            EventType::KeyPress(key) => {
+                set_modifier(key, true, &mut ctrl, &mut shift, &mut alt);
+                let Some(name) = key_name(key) else {
+                    return;
+                };
+                let shortcut = APP_STATE.get_shortcut(ShortcutAction::PushToTalk).ok().flatten();
+                if held.is_none() && shortcut.is_some_and(|shortcut| shortcut.matches(name, ctrl, shift, alt)) {
+                    held = Some(name);
+                    APP_STATE.request_push_to_talk(true);
+                }
+            }
+            EventType::KeyRelease(key) => {
+                set_modifier(key, false, &mut ctrl, &mut shift, &mut alt);
+                // Запись останавливает отпускание самой клавиши, модификаторы можно отпустить раньше
+                if held.is_some() && held == key_name(key) {
+                    held = None;
+                    APP_STATE.request_push_to_talk(false);
+                }
+            }
+            _ => {}
+        });
+        if let Err(err) = result {
+            log::error!("Global keyboard listener failed: {:?}", err);
+        }
+    });
+}
+
+fn set_modifier(key: Key, pressed: bool, ctrl: &mut bool, shift: &mut bool, alt: &mut bool) {
+    match key {
+        Key::ControlLeft | Key::ControlRight => *ctrl = pressed,
+        Key::ShiftLeft | Key::ShiftRight => *shift = pressed,
+        Key::Alt | Key::AltGr => *alt = pressed,
+        _ => {}
+    }
+}
+
+/// Имя клавиши из SHORTCUT_KEYS
+fn key_name(key: Key) -> Option<&'static str> {
+    Some(match key {
+        Key::KeyA => "A",
+        Key::KeyB => "B",
+        Key::KeyC => "C",
+        Key::KeyD => "D",
+        Key::KeyE => "E",
+        Key::KeyF => "F",
+        Key::KeyG => "G",
+        Key::KeyH => "H",
+        Key::KeyI => "I",
+        Key::KeyJ => "J",
+        Key::KeyK => "K",
+        Key::KeyL => "L",
+        Key::KeyM => "M",
+        Key::KeyN => "N",
+        Key::KeyO => "O",
+        Key::KeyP => "P",
+        Key::KeyQ => "Q",
+        Key::KeyR => "R",
+        Key::KeyS => "S",
+        Key::KeyT => "T",
+        Key::KeyU => "U",
+        Key::KeyV => "V",
+        Key::KeyW => "W",
+        Key::KeyX => "X",
+        Key::KeyY => "Y",
+        Key::KeyZ => "Z",
+        Key::Num0 => "0",
+        Key::Num1 => "1",
+        Key::Num2 => "2",
+        Key::Num3 => "3",
+        Key::Num4 => "4",
+        Key::Num5 => "5",
+        Key::Num6 => "6",
+        Key::Num7 => "7",
+        Key::Num8 => "8",
+        Key::Num9 => "9",
+        Key::F1 => "F1",
+        Key::F2 => "F2",
+        Key::F3 => "F3",
+        Key::F4 => "F4",
+        Key::F5 => "F5",
+        Key::F6 => "F6",
+        Key::F7 => "F7",
+        Key::F8 => "F8",
+        Key::F9 => "F9",
+        Key::F10 => "F10",
+        Key::F11 => "F11",
+        Key::F12 => "F12",
+        Key::Tab => "Tab",
+        Key::Space => "Space",
+        Key::Return => "Enter",
+        Key::Escape => "Escape",
+        Key::Backspace => "Backspace",
+        Key::Insert => "Insert",
+        Key::Delete => "Delete",
+        Key::Home => "Home",
+        Key::End => "End",
+        Key::PageUp => "PageUp",
+        Key::PageDown => "PageDown",
+        Key::UpArrow => "Up",
+        Key::DownArrow => "Down",
+        Key::LeftArrow => "Left",
+        Key::RightArrow => "Right",
+        Key::BackQuote => "Backquote",
+        _ => return None,
+    })
+}
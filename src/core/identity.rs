@@ -0,0 +1,86 @@
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Key, Nonce,
+};
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::ecdsa::Signature;
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+use sha2::{Digest, Sha256};
+
+use crate::constants::IDENTITY_KEY_DOMAIN;
+
+/// Ключ идентичности пользователя (secp256k1).
+/// Выводится из мнемоники, поэтому совпадает на всех устройствах с тем же кошельком,
+/// но не раскрывает ключи самого кошелька.
+#[derive(Clone)]
+pub struct Identity {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+}
+
+impl Identity {
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self, String> {
+        let mut hasher = Sha256::new();
+        hasher.update(IDENTITY_KEY_DOMAIN);
+        hasher.update(mnemonic.as_bytes());
+        let seed = hasher.finalize();
+
+        let secret_key = SecretKey::from_slice(&seed).map_err(|e| format!("Failed to derive identity key: {e}"))?;
+        let public_key = PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+        Ok(Self { secret_key, public_key })
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.public_key.serialize())
+    }
+
+    /// Шифрует данные для собеседника (ECDH + AES-256-GCM). Формат: nonce (12 байт) + шифртекст.
+    pub fn encrypt_for(&self, peer: &PublicKey, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+        let cipher = self.cipher_for(peer);
+        let nonce_bytes: [u8; 12] = rand::random();
+        let mut ciphertext =
+            cipher.encrypt(Nonce::from_slice(&nonce_bytes), plaintext).map_err(|_| "Failed to encrypt direct message".to_string())?;
+
+        let mut combined = nonce_bytes.to_vec();
+        combined.append(&mut ciphertext);
+        Ok(combined)
+    }
+
+    /// Расшифровывает данные собеседника, зашифрованные `encrypt_for`.
+    pub fn decrypt_from(&self, peer: &PublicKey, combined: &[u8]) -> Result<Vec<u8>, String> {
+        if combined.len() < 12 {
+            return Err("Encrypted data is too short".to_string());
+        }
+        let (nonce_bytes, ciphertext) = combined.split_at(12);
+        self.cipher_for(peer)
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| "Failed to decrypt direct message".to_string())
+    }
+
+    /// Подпись ECDSA над 32-байтным хешем
+    pub fn sign_digest(&self, digest: [u8; 32]) -> Signature {
+        Secp256k1::signing_only().sign_ecdsa(&Message::from_digest(digest), &self.secret_key)
+    }
+
+    /// Общий симметричный ключ: одинаков для отправителя и получателя
+    fn cipher_for(&self, peer: &PublicKey) -> Aes256Gcm {
+        let shared = SharedSecret::new(peer, &self.secret_key);
+        let key: [u8; 32] = Sha256::digest(shared.secret_bytes()).into();
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+    }
+}
+
+/// Проверка подписи, сделанной `Identity::sign_digest`
+pub fn verify_digest(public_key: &PublicKey, digest: [u8; 32], signature: &Signature) -> bool {
+    Secp256k1::verification_only().verify_ecdsa(&Message::from_digest(digest), signature, public_key).is_ok()
+}
+
+/// Разбор публичного ключа из hex-строки (33 байта в сжатом виде)
+pub fn parse_public_key(hex_str: &str) -> Result<PublicKey, String> {
+    let bytes = hex::decode(hex_str.trim()).map_err(|e| format!("Invalid public key hex: {e}"))?;
+    PublicKey::from_slice(&bytes).map_err(|e| format!("Invalid public key: {e}"))
+}
@@ -0,0 +1,54 @@
+//! Инспектор payload для отладки протокола: пока он включён в консоли разработчика,
+//! обработчики Listener'а записывают каждый увиденный KSPK payload — разбор заголовка,
+//! поля, размеры до и после распаковки и чем закончилась обработка.
+
+use chrono::Local;
+
+/// Поля заголовка по порядку и их длина в байтах (см. `Payload::to_bytes`)
+const HEADER_FIELDS: [(&str, usize); 7] =
+    [("marker", 4), ("ver", 1), ("channel", 3), ("type", 1), ("status", 1), ("fragment", 3), ("username_len", 1)];
+
+/// Один увиденный payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectedPayload {
+    pub tx_id: String,
+    /// Время получения, ЧЧ:ММ:СС.мс
+    pub seen_at: String,
+    /// Заголовок в hex по полям
+    pub header: String,
+    /// Разобранные поля или ошибка разбора
+    pub fields: String,
+    pub compressed_size: usize,
+    /// Размер данных после распаковки zstd, если до неё дошло
+    pub raw_size: Option<usize>,
+    pub outcome: String,
+}
+
+impl InspectedPayload {
+    pub fn new(tx_id: &str, bytes: &[u8]) -> Self {
+        Self {
+            tx_id: tx_id.to_string(),
+            seen_at: Local::now().format("%H:%M:%S%.3f").to_string(),
+            header: header_breakdown(bytes),
+            fields: String::new(),
+            compressed_size: bytes.len(),
+            raw_size: None,
+            outcome: String::new(),
+        }
+    }
+}
+
+/// Раскладывает заголовок по полям в hex, не полагаясь на разбор: видно и повреждённые payload
+pub fn header_breakdown(bytes: &[u8]) -> String {
+    let mut parts = Vec::with_capacity(HEADER_FIELDS.len());
+    let mut pos = 0;
+    for (name, len) in HEADER_FIELDS {
+        let Some(field) = bytes.get(pos..pos + len) else {
+            parts.push(format!("{name}=<missing>"));
+            break;
+        };
+        parts.push(format!("{name}={}", hex::encode(field)));
+        pos += len;
+    }
+    parts.join(" ")
+}
@@ -1,17 +1,44 @@
 use futures::{select_biased, FutureExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{Hash as _, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use workflow_core::{
     channel::{Channel, DuplexChannel},
     task::spawn,
 };
 
-use crate::app_state::APP_STATE;
-use crate::constants::{MARKER, MAX_TEXT_CHARS};
+use crate::app_state::{ErrorEvent, APP_STATE};
+use crate::constants::{
+    BACKFILL_CHAT_READY_TIMEOUT_SECS, DAA_SCORE_PER_SECOND, DEFAULT_DEDUPE_WINDOW_DAA, DEFAULT_PROCESSED_TXS_PATH, MARKER,
+    MAX_TEXT_CHARS, MEMPOOL_POLL_INTERVAL_MS, PAYLOAD_WORKERS, PAYLOAD_WORKER_QUEUE, PROCESSED_TXS_SAVE_INTERVAL_SECS,
+};
+use crate::core::archive_scan::{find_block_before_daa, walk_blocks};
+use crate::core::inspector::InspectedPayload;
+use crate::models::avatar::AvatarChunk;
+use crate::models::claim::Claim;
+use crate::models::direct_message::DirectEnvelope;
+use crate::models::network::{Network, NotificationScopes};
 use crate::models::payload::{MessageType, Payload};
-use crate::utils::LimitedHashSet;
+use crate::models::transaction::ObservedTransaction;
+use crate::utils::rpc::call_with_policy;
+use crate::utils::store::{load_json, save_json};
+use crate::utils::DaaWindowSet;
 use kaspa_wallet_core::prelude::*;
 use kaspa_wrpc_client::{prelude::*, result::Result};
 use tokio::sync::broadcast::Sender;
+use tokio::sync::mpsc;
+
+// Причины, по которым Listener отбрасывает payload (счётчики в `stats` консоли)
+const DROP_OTHER_CHANNEL: &str = "other channel";
+const DROP_MUTED: &str = "muted";
+const DROP_OWN_VOICE: &str = "own voice";
+const DROP_INVALID_TEXT: &str = "invalid text";
+const DROP_NOT_FOR_US: &str = "not for us";
+const DROP_BLOCKED: &str = "blocked sender";
+const DROP_PAUSED: &str = "paused";
 
 /// Структура для хранения внутреннего состояния листнера
 pub struct ListenerInner {
@@ -23,12 +50,57 @@ pub struct ListenerInner {
     notification_channel: Channel<Notification>,
     // Идентификатор листнера для Kaspa RPC
     listener_id: Mutex<Option<ListenerId>>,
-    // Хранилище обработанных транзакций для уникальности пейлоадов
-    processed_transactions: Mutex<LimitedHashSet<String>>,
+    // Обработанные транзакции в окне DAA score: защита от повторов из мемпула, после переподключения и перезапуска
+    processed_transactions: Mutex<DaaWindowSet<String>>,
+    // С последнего сохранения на диск обработаны новые транзакции
+    processed_dirty: AtomicBool,
+    // DAA score последнего блока: им помечаются транзакции из мемпула в окне дедупликации
+    last_daa_score: AtomicU64,
+    // Подписки текущего подключения
+    scopes: Mutex<NotificationScopes>,
+    // Транзакции с payload из добавленных блоков, ещё не принятые виртуальной цепочкой
+    pending_payloads: Mutex<HashMap<String, PendingPayloadTx>>,
+    // Транзакции с payload по блокам цепочки, которые их приняли: нужны, если блок уйдёт из цепочки
+    accepted_payloads: Mutex<HashMap<RpcHash, AcceptedPayloads>>,
+    // Транзакции, чей принявший блок ушёл из цепочки, с DAA score; ждут принятия новым блоком
+    reverted_payloads: Mutex<HashMap<String, u64>>,
+    // Очереди обработчиков payload; получатели забираются при запуске
+    payload_workers: Vec<mpsc::Sender<PayloadJob>>,
+    payload_worker_receivers: Mutex<Vec<mpsc::Receiver<PayloadJob>>>,
     // Броадкаст часть канала для потребителей пейлоадов
     broadcast_sender: Sender<Payload>,
 }
 
+/// Транзакция с payload для обработчика из пула
+struct PayloadJob {
+    tx_id: String,
+    bytes: Vec<u8>,
+    sender_address: Option<String>,
+    block_time: Option<i64>,
+    fee: Option<u64>,
+    backfilled: bool,
+}
+
+/// Окно дедупликации на диске. DAA score разных сетей несравнимы, поэтому файл другой сети не читается
+#[derive(Serialize, Deserialize)]
+struct ProcessedTransactions {
+    network: Network,
+    entries: Vec<(String, u64)>,
+}
+
+/// Транзакция с payload из блока: обрабатывается, когда виртуальная цепочка её примет
+struct PendingPayloadTx {
+    tx: RpcTransaction,
+    daa_score: u64,
+    block_time: i64,
+}
+
+/// Транзакции с payload, принятые одним блоком цепочки
+struct AcceptedPayloads {
+    daa_score: u64,
+    tx_ids: Vec<String>,
+}
+
 #[derive(Clone)]
 pub struct Listener {
     pub inner: Arc<ListenerInner>,
@@ -36,18 +108,67 @@ pub struct Listener {
 
 impl Listener {
     pub fn try_new(client: Arc<KaspaRpcClient>, broadcast_sender: Sender<Payload>) -> Result<Self> {
+        let (payload_workers, payload_worker_receivers) = (0..PAYLOAD_WORKERS).map(|_| mpsc::channel(PAYLOAD_WORKER_QUEUE)).unzip();
         let inner = ListenerInner {
             task_ctl: DuplexChannel::oneshot(),
             client,
             notification_channel: Channel::unbounded(),
             listener_id: Mutex::new(None),
-            processed_transactions: Mutex::new(LimitedHashSet::new(100000)),
+            processed_transactions: Mutex::new(Self::load_processed_transactions()),
+            processed_dirty: AtomicBool::new(false),
+            last_daa_score: AtomicU64::new(0),
+            scopes: Mutex::new(NotificationScopes::default()),
+            pending_payloads: Mutex::new(HashMap::new()),
+            accepted_payloads: Mutex::new(HashMap::new()),
+            reverted_payloads: Mutex::new(HashMap::new()),
+            payload_workers,
+            payload_worker_receivers: Mutex::new(payload_worker_receivers),
             broadcast_sender,
         };
 
         Ok(Self { inner: Arc::new(inner) })
     }
 
+    /// Окно дедупликации с прошлого запуска, если оно сохранено для той же сети
+    fn load_processed_transactions() -> DaaWindowSet<String> {
+        let mut processed = DaaWindowSet::new(APP_STATE.get_dedupe_window_daa().unwrap_or(DEFAULT_DEDUPE_WINDOW_DAA));
+        match load_json::<ProcessedTransactions>(DEFAULT_PROCESSED_TXS_PATH) {
+            Ok(Some(stored)) if stored.network == APP_STATE.get_network() => {
+                log::info!("Restored {} processed transaction(s)", stored.entries.len());
+                processed.extend(stored.entries);
+            }
+            Ok(_) => {}
+            Err(err) => log::warn!("Error while loading processed transactions: {}", err),
+        }
+        processed
+    }
+
+    /// Сохраняет окно дедупликации, если с прошлого сохранения оно изменилось
+    fn save_processed_transactions(&self) {
+        if !self.inner.processed_dirty.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        let stored = ProcessedTransactions {
+            network: APP_STATE.get_network(),
+            entries: self.inner.processed_transactions.lock().unwrap().entries(),
+        };
+        if let Err(err) = save_json(DEFAULT_PROCESSED_TXS_PATH, &stored) {
+            log::error!("Error while saving processed transactions: {}", err);
+            self.inner.processed_dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Периодически сохраняет окно дедупликации на диск
+    fn spawn_processed_saver(&self) {
+        let listener = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(PROCESSED_TXS_SAVE_INTERVAL_SECS)).await;
+                listener.save_processed_transactions();
+            }
+        });
+    }
+
     /// Проверка текущего состояния подключения к ноде
     fn is_connected() -> bool {
         APP_STATE.is_listener_connected().unwrap_or_else(|err| {
@@ -59,7 +180,10 @@ impl Listener {
     /// Запуск листнера
     pub async fn start(&self) -> Result<()> {
         // Запуск задачи обработки событий
+        self.spawn_payload_workers();
         self.start_event_task().await?;
+        self.spawn_mempool_poll();
+        self.spawn_processed_saver();
 
         Ok(())
     }
@@ -68,6 +192,7 @@ impl Listener {
     pub async fn stop(&self) -> Result<()> {
         self.client().disconnect().await?;
         self.stop_event_task().await?;
+        self.save_processed_transactions();
         Ok(())
     }
 
@@ -87,8 +212,22 @@ impl Listener {
 
         *self.inner.listener_id.lock().unwrap() = Some(listener_id);
 
-        // Подписка на уведомления о добавленных блоках
-        self.client().rpc_api().start_notify(listener_id, Scope::BlockAdded(BlockAddedScope {})).await?;
+        // Подписка на уведомления о добавленных блоках (содержимое транзакций)
+        // и об изменениях виртуальной цепочки (какие транзакции приняты)
+        let scopes = APP_STATE.get_notification_scopes().unwrap_or_else(|err| {
+            log::error!("Error while reading notification scopes: {}", err);
+            NotificationScopes::default()
+        });
+        *self.inner.scopes.lock().unwrap() = scopes;
+        if scopes.block_added {
+            self.client().rpc_api().start_notify(listener_id, Scope::BlockAdded(BlockAddedScope {})).await?;
+        } else {
+            log::warn!("Not subscribed to blocks, messages arrive only from the mempool");
+        }
+        if scopes.uses_virtual_chain() {
+            self.client().rpc_api().start_notify(listener_id, Scope::VirtualChainChanged(VirtualChainChangedScope::new(true))).await?;
+        }
+        APP_STATE.set_listener_scopes(Some(scopes));
         Ok(())
     }
 
@@ -104,91 +243,378 @@ impl Listener {
         Ok(())
     }
 
-    /// Обработка уведомлений от ноды
+    /// Обработка уведомлений от ноды. Одна транзакция может лежать в нескольких блоках DAG,
+    /// но виртуальная цепочка принимает её ровно один раз: блоки только поставляют содержимое
+    /// транзакций с payload, а обрабатываются они по списку принятых
     async fn handle_notification(&self, notification: Notification) -> Result<()> {
-        if let Notification::BlockAdded(not) = notification {
-            // Обработка полезных данных транзакций
-            for _tx in not.block.transactions.clone() {
-                if !_tx.payload.starts_with(MARKER) {
-                    continue;
+        match notification {
+            Notification::BlockAdded(not) => {
+                APP_STATE.record_notification();
+                let daa_score = not.block.header.daa_score;
+                let block_time = not.block.header.timestamp as i64;
+                let window = match APP_STATE.get_dedupe_window_daa() {
+                    Ok(window) => window,
+                    Err(err) => {
+                        log::error!("Error while reading dedupe window: {}", err);
+                        DEFAULT_DEDUPE_WINDOW_DAA
+                    }
+                };
+                self.inner.processed_transactions.lock().unwrap().set_window(window);
+                self.inner.last_daa_score.fetch_max(daa_score, Ordering::SeqCst);
+                APP_STATE.update_daa_score(daa_score);
+
+                // Реорганизации глубже окна дедупликации не отслеживаются
+                self.inner.accepted_payloads.lock().unwrap().retain(|_, entry| entry.daa_score.saturating_add(window) >= daa_score);
+                self.inner.reverted_payloads.lock().unwrap().retain(|_, reverted_at| reverted_at.saturating_add(window) >= daa_score);
+                let payload_txs = not.block.transactions.iter().filter(|tx| tx.payload.starts_with(MARKER));
+                // Без изменений цепочки блок — единственное, что известно о транзакции
+                if !self.inner.scopes.lock().unwrap().uses_virtual_chain() {
+                    for tx in payload_txs {
+                        self.process_transaction(tx, daa_score, Some(block_time), None, false).await?;
+                    }
+                    return Ok(());
                 }
-                let tx_verbose = match &_tx.verbose_data {
-                    Some(vd) => vd.clone(),
-                    None => {
+                let mut pending = self.inner.pending_payloads.lock().unwrap();
+                // Транзакции красных блоков цепочка не примет никогда
+                pending.retain(|_, entry| entry.daa_score.saturating_add(window) >= daa_score);
+                for tx in payload_txs {
+                    let Some(tx_id) = tx.verbose_data.as_ref().map(|vd| vd.transaction_id.to_string()) else {
                         log::error!("No verbose_data in this transaction");
                         continue;
-                    }
-                };
-                let tx_id: String = tx_verbose.transaction_id.to_string();
+                    };
+                    pending.entry(tx_id).or_insert_with(|| PendingPayloadTx { tx: tx.clone(), daa_score, block_time });
+                }
+            }
+            Notification::VirtualChainChanged(not) => {
+                self.revert_removed_blocks(&not.removed_chain_block_hashes);
+                let daa_score = self.inner.last_daa_score.load(Ordering::SeqCst);
+                let mut accepted = Vec::new();
                 {
-                    let mut processed = self.inner.processed_transactions.lock().unwrap();
-                    if processed.contains(&tx_id) {
-                        continue;
+                    let mut pending = self.inner.pending_payloads.lock().unwrap();
+                    let mut reverted = self.inner.reverted_payloads.lock().unwrap();
+                    let mut accepted_payloads = self.inner.accepted_payloads.lock().unwrap();
+                    for block in not.accepted_transaction_ids.iter() {
+                        let mut tx_ids = Vec::new();
+                        for tx_id in block.accepted_transaction_ids.iter().map(|tx_id| tx_id.to_string()) {
+                            if let Some(entry) = pending.remove(&tx_id) {
+                                accepted.push(entry);
+                                tx_ids.push(tx_id);
+                            } else if reverted.remove(&tx_id).is_some() {
+                                log::info!("Transaction {} is accepted again by {}", tx_id, block.accepting_block_hash);
+                                APP_STATE.chat_state.set_possibly_reverted(&tx_id, false);
+                                tx_ids.push(tx_id);
+                            }
+                        }
+                        if !tx_ids.is_empty() {
+                            accepted_payloads.insert(block.accepting_block_hash, AcceptedPayloads { daa_score, tx_ids });
+                        }
                     }
-                    processed.insert(tx_id.clone());
                 }
+                for entry in accepted {
+                    self.process_transaction(&entry.tx, entry.daa_score, Some(entry.block_time), None, false).await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
 
-                /*let sender_id = _tx
-                .outputs
-                .iter()
-                .max_by_key(|out| out.value)
-                .map_or(None, |out| Some(out.verbose_data.clone().unwrap()))
-                .map_or(None, |vd| Some(vd.script_public_key_address));*/
-
-                let mut payload = match Payload::from_bytes(&_tx.payload) {
-                    Ok(payload) => {
-                        log::info!("Received payload: {} (tx_id={})", payload.debug_string(), tx_id);
-                        payload
-                    }
+    /// Блоки ушли из выбранной цепочки: принятые ими сообщения отмечаются как возможно отменённые,
+    /// пока новая цепочка не примет их транзакции снова (обычно в том же уведомлении)
+    fn revert_removed_blocks(&self, removed: &[RpcHash]) {
+        let mut accepted_payloads = self.inner.accepted_payloads.lock().unwrap();
+        let mut reverted = self.inner.reverted_payloads.lock().unwrap();
+        for hash in removed {
+            let Some(entry) = accepted_payloads.remove(hash) else {
+                continue;
+            };
+            for tx_id in entry.tx_ids {
+                log::warn!("Block {} left the selected chain, transaction {} may be reverted", hash, tx_id);
+                APP_STATE.chat_state.set_possibly_reverted(&tx_id, true);
+                reverted.insert(tx_id, entry.daa_score);
+            }
+        }
+    }
+
+    /// Опрашивает мемпул ноды, пока это включено в настройках: сообщения приходят сразу после отправки,
+    /// не дожидаясь блока. Уведомлений о мемпуле у ноды нет, поэтому мемпул читается целиком.
+    /// Транзакция, позже попавшая в блок, второй раз не обрабатывается (см. `processed_transactions`)
+    fn spawn_mempool_poll(&self) {
+        let listener = self.clone();
+        tokio::spawn(async move {
+            let interval = Duration::from_millis(MEMPOOL_POLL_INTERVAL_MS);
+            loop {
+                tokio::time::sleep(interval).await;
+                if !Self::is_connected() || !APP_STATE.is_mempool_listening_enabled().unwrap_or(false) {
+                    continue;
+                }
+                let entries = match listener.client().get_mempool_entries(false, false).await {
+                    Ok(entries) => entries,
                     Err(err) => {
-                        log::error!("Error while parsing payload: {} (tx_id={})", err, tx_id);
+                        log::warn!("Error while reading mempool: {}", err);
                         continue;
                     }
                 };
-                match payload.get_message_type() {
-                    MessageType::Voice => {
-                        if self.filter_incoming_voice(&payload).await {
-                            if let Err(err) = payload.decompress_zstd() {
-                                log::error!("Error while decompressing audio: {}", err);
-                                continue;
-                            }
-                            self.broadcast_payload(payload).await?;
-                        }
+                let daa_score = listener.inner.last_daa_score.load(Ordering::SeqCst);
+                for entry in entries {
+                    if let Err(err) = listener.process_transaction(&entry.transaction, daa_score, None, Some(entry.fee), false).await {
+                        log::error!("Error while processing mempool transaction: {err}");
                     }
-                    MessageType::Text => {
-                        if let Err(err) = payload.decompress_zstd() {
-                            log::error!("Error while decompressing text: {}", err);
-                            continue;
-                        }
-                        if self.filter_incoming_text(&payload).await {
-                            self.broadcast_payload(payload).await?;
+                }
+            }
+        });
+    }
+
+    /// Подгружает текстовые сообщения и объявления текущего канала за последние `minutes` минут,
+    /// чтобы чат не был пустым до прихода новых блоков
+    fn spawn_backfill(&self, minutes: u64) {
+        let listener = self.clone();
+        tokio::spawn(async move {
+            if let Err(err) = listener.backfill(minutes).await {
+                log::error!("Error while loading channel history: {}", err);
+            }
+        });
+    }
+
+    async fn backfill(&self, minutes: u64) -> std::result::Result<(), String> {
+        let channel = APP_STATE.get_channel_number()?;
+        let virtual_daa =
+            call_with_policy("DAG info", || self.client().get_block_dag_info()).await.map_err(|e| e.to_string())?.virtual_daa_score;
+        let from_daa = virtual_daa.saturating_sub(minutes * 60 * DAA_SCORE_PER_SECOND);
+        let low_hash = find_block_before_daa(self.client(), from_daa).await?;
+        log::info!("Loading history of channel {} since DAA {} from {}", channel, from_daa, low_hash);
+
+        let mut found = Vec::new();
+        walk_blocks(self.client(), low_hash, u64::MAX, |block| {
+            let header = &block.header;
+            if header.daa_score < from_daa {
+                return;
+            }
+            for tx in block.transactions.iter().filter(|tx| is_channel_text(tx, channel)) {
+                found.push((tx.clone(), header.daa_score, header.timestamp as i64));
+            }
+        })
+        .await?;
+        found.sort_by_key(|(_, daa_score, _)| *daa_score);
+
+        // Broadcaster очищает чат при подключении: история должна прийти после этого
+        let deadline = std::time::Instant::now() + Duration::from_secs(BACKFILL_CHAT_READY_TIMEOUT_SECS);
+        while !APP_STATE.is_broadcaster_connected().unwrap_or(false) {
+            if std::time::Instant::now() >= deadline || !Self::is_connected() {
+                return Err("Wallet did not connect, history was not shown".to_string());
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+
+        log::info!("Found {} history message(s) of channel {}", found.len(), channel);
+        // Повторы из нескольких блоков и уже полученные сообщения отсекает `ChatSharedState::add_message`
+        for (tx, daa_score, block_time) in found {
+            self.process_transaction(&tx, daa_score, Some(block_time), None, true).await.map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Отсеивает повторы и передаёт транзакцию обработчику payload. `block_time` — время блока;
+    /// None — транзакция из мемпула и в блок ещё не попала. `fee` известна только в мемпуле.
+    /// `backfilled` — транзакция из подгружаемой истории
+    async fn process_transaction(
+        &self,
+        tx: &RpcTransaction,
+        daa_score: u64,
+        block_time: Option<i64>,
+        fee: Option<u64>,
+        backfilled: bool,
+    ) -> Result<()> {
+        if !tx.payload.starts_with(MARKER) {
+            return Ok(());
+        }
+        let tx_verbose = match &tx.verbose_data {
+            Some(vd) => vd.clone(),
+            None => {
+                log::error!("No verbose_data in this transaction");
+                return Ok(());
+            }
+        };
+        let tx_id: String = tx_verbose.transaction_id.to_string();
+        if block_time.is_some() {
+            APP_STATE.confirm_send(&tx_id);
+            APP_STATE.confirm_observed_transaction(&tx_id);
+        } else if APP_STATE.is_send_unconfirmed(&tx_id) {
+            // Своё сообщение уже показано; доставленным его отметит блок
+            return Ok(());
+        }
+        // На паузе payload не разбираются и не распаковываются; доставка своих сообщений отмечается выше
+        if APP_STATE.is_listening_paused().unwrap_or(false) {
+            APP_STATE.record_dropped_payload(DROP_PAUSED);
+            return Ok(());
+        }
+        {
+            let mut processed = self.inner.processed_transactions.lock().unwrap();
+            // Историю после переподключения нужно показать снова: чат при подключении очищается.
+            // Повторы в ленте отсекает `ChatSharedState::add_message` по транзакции и фрагменту
+            if !processed.insert(tx_id.clone(), daa_score) && !backfilled {
+                return Ok(());
+            }
+            self.inner.processed_dirty.store(true, Ordering::Relaxed);
+        }
+
+        // Kaspeak отправляет транзакции на свой адрес, первый выход — адрес отправителя.
+        // Его выбирает сам отправитель, поэтому адрес только показывается,
+        // а кто отправил payload, решает подпись ключом идентичности (`Payload::verify_sender`)
+        let sender_address =
+            tx.outputs.first().and_then(|out| out.verbose_data.as_ref()).map(|vd| vd.script_public_key_address.address_to_string());
+
+        // Сообщения одного отправителя всегда попадают к одному обработчику и не обгоняют друг друга
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        sender_address.as_deref().unwrap_or(&tx_id).hash(&mut hasher);
+        let worker = &self.inner.payload_workers[hasher.finish() as usize % self.inner.payload_workers.len()];
+        let job = PayloadJob { tx_id, bytes: tx.payload.clone(), sender_address, block_time, fee, backfilled };
+        if worker.send(job).await.is_err() {
+            log::error!("Payload worker has stopped");
+        }
+        Ok(())
+    }
+
+    /// Запускает обработчиков payload: разбор и распаковка zstd идут в пуле блокирующих потоков,
+    /// а цикл событий Listener'а только раздаёт транзакции
+    fn spawn_payload_workers(&self) {
+        let receivers = std::mem::take(&mut *self.inner.payload_worker_receivers.lock().unwrap());
+        for mut receiver in receivers {
+            let listener = self.clone();
+            tokio::spawn(async move {
+                while let Some(job) = receiver.recv().await {
+                    let tx_id = job.tx_id.clone();
+                    match tokio::task::spawn_blocking(move || decode_payload(job)).await {
+                        Ok(Some(payload)) => {
+                            if let Err(err) = listener.dispatch_payload(payload).await {
+                                log::error!("Error while dispatching payload (tx_id={}): {}", tx_id, err);
+                            }
                         }
+                        Ok(None) => {}
+                        Err(err) => log::error!("Payload worker failed (tx_id={}): {}", tx_id, err),
                     }
-                    MessageType::File | MessageType::Unknown(_) => {
-                        log::warn!("Unsupported message type");
-                    }
                 }
+            });
+        }
+    }
+
+    /// Передаёт разобранный и распакованный payload потребителям
+    async fn dispatch_payload(&self, payload: Payload) -> Result<()> {
+        match payload.get_message_type() {
+            MessageType::Voice | MessageType::VoiceBatch => self.broadcast_payload(payload).await?,
+            MessageType::Text | MessageType::Announcement => {
+                if self.filter_incoming_text(&payload).await {
+                    self.broadcast_payload(payload).await?;
+                } else {
+                    APP_STATE.record_dropped_payload(DROP_INVALID_TEXT);
+                }
+            }
+            MessageType::Direct => {
+                if self.filter_incoming_direct(&payload).await {
+                    self.broadcast_payload(payload).await?;
+                } else {
+                    APP_STATE.record_dropped_payload(DROP_NOT_FOR_US);
+                }
+            }
+            MessageType::Claim => self.register_claim(&payload),
+            MessageType::Avatar => self.register_avatar_chunk(&payload),
+            MessageType::AvatarRequest => {
+                if self.filter_avatar_request(&payload) {
+                    self.broadcast_payload(payload).await?;
+                }
+            }
+            MessageType::File | MessageType::Unknown(_) => {
+                log::warn!("Unsupported message type");
             }
         }
         Ok(())
     }
 
-    async fn filter_incoming_voice(&self, payload: &Payload) -> bool {
-        let self_username = APP_STATE.get_username();
-        let listen_self = APP_STATE.is_listen_self().unwrap_or(false);
-        let mute_all = APP_STATE.is_mute_all().unwrap_or(false);
-        let channel_number = APP_STATE.get_channel_number().unwrap_or(0);
-        if mute_all {
-            return false;
+    /// Проверяет подпись заявки на имя и заносит её в таблицу заявок
+    fn register_claim(&self, payload: &Payload) {
+        let username = payload.get_username();
+        let Some(address) = payload.get_sender_address() else {
+            log::warn!("Claim for '{}' has no sender address, ignoring", username);
+            return;
+        };
+        let claim = match Claim::from_bytes(payload.get_data()).and_then(|claim| claim.verify(username, address).map(|_| claim)) {
+            Ok(claim) => claim,
+            Err(err) => {
+                log::warn!("Rejected claim for '{}': {}", username, err);
+                return;
+            }
+        };
+        // Чужую заявку можно переотправить в своей транзакции; принимаем только подписанную самим владельцем
+        if payload.get_sender_key() != Some(claim.public_key_hex().as_str()) {
+            log::warn!("Rejected claim for '{}': payload is not signed by the claimed key", username);
+            return;
         }
-        if payload.get_username() == self_username && !listen_self {
-            return false;
+        match APP_STATE.register_claim(username, &claim.public_key_hex()) {
+            Ok(true) => log::info!("Username '{}' claimed by {} ({})", username, claim.public_key_hex(), address),
+            Ok(false) => log::warn!("Username '{}' is already claimed by another key", username),
+            Err(err) => log::error!("Error while registering claim: {}", err),
         }
-        if payload.get_channel() != channel_number {
-            return false;
+    }
+
+    /// Добавляет фрагмент аватара в кэш; подпись проверяется, когда изображение собрано целиком
+    fn register_avatar_chunk(&self, payload: &Payload) {
+        let Some(address) = payload.get_sender_address() else {
+            log::warn!("Avatar chunk from '{}' has no sender address, ignoring", payload.get_username());
+            return;
+        };
+        let result = AvatarChunk::from_bytes(payload.get_data())
+            .and_then(|chunk| match payload.get_sender_key() == Some(chunk.public_key_hex().as_str()) {
+                true => Ok(chunk),
+                false => Err("payload is not signed by the avatar key".to_string()),
+            })
+            .and_then(|chunk| APP_STATE.add_avatar_chunk(chunk, payload.get_fragment_number(), address));
+        match result {
+            Ok(Some(key)) => log::info!("Avatar of {} received ({})", key, address),
+            Ok(None) => {}
+            Err(err) => log::warn!("Rejected avatar chunk from {}: {}", address, err),
         }
+    }
 
-        true
+    /// Пропускаем только запросы нашего аватара, если он задан и давно не рассылался
+    fn filter_avatar_request(&self, payload: &Payload) -> bool {
+        let requested = String::from_utf8_lossy(payload.get_data());
+        let is_own_address = APP_STATE.get_account_address().ok().flatten().is_some_and(|address| address == requested);
+        is_own_address && APP_STATE.get_own_avatar().ok().flatten().is_some() && APP_STATE.is_avatar_reannounce_due()
+    }
+
+    /// Причина отбросить payload, видная уже по заголовку: голос и текст нужны только из текущего канала,
+    /// голос — ещё и если не заглушён весь звук и это не наш собственный голос.
+    /// Текст из другого канала нужен, если в нём упомянут локальный пользователь: по нему в списке каналов
+    /// появляется значок упоминания. Упоминания видны в расширениях заголовка (`Payload::is_mentioning`)
+    fn drop_reason(payload: &Payload) -> Option<&'static str> {
+        let is_voice = matches!(payload.get_message_type(), MessageType::Voice | MessageType::VoiceBatch);
+        let is_text = matches!(payload.get_message_type(), MessageType::Text | MessageType::Announcement);
+        if !is_voice && !is_text {
+            return None;
+        }
+        if payload.get_channel() != APP_STATE.get_channel_number().unwrap_or(0) {
+            let username = APP_STATE.get_username();
+            if is_voice || payload.get_username() == username || !payload.is_mentioning(&username) {
+                return Some(DROP_OTHER_CHANNEL);
+            }
+        }
+        if is_voice && APP_STATE.is_mute_all().unwrap_or(false) {
+            return Some(DROP_MUTED);
+        }
+        if is_voice && payload.get_username() == APP_STATE.get_username() && !APP_STATE.is_listen_self().unwrap_or(false) {
+            return Some(DROP_OWN_VOICE);
+        }
+        None
+    }
+
+    /// Причина отбросить payload от заблокированного отправителя. Вызывается после проверки подписи,
+    /// потому что блокировка по ключу смотрит на ключ, которым подписан payload
+    fn sender_drop_reason(payload: &Payload) -> Option<&'static str> {
+        let is_incoming = matches!(
+            payload.get_message_type(),
+            MessageType::Voice | MessageType::VoiceBatch | MessageType::Text | MessageType::Announcement | MessageType::Direct
+        );
+        (is_incoming && APP_STATE.is_sender_blocked(payload.get_username(), payload.get_sender_key())).then_some(DROP_BLOCKED)
     }
 
     async fn filter_incoming_text(&self, payload: &Payload) -> bool {
@@ -204,16 +630,44 @@ impl Listener {
         true
     }
 
+    /// Пропускаем только личные сообщения, где мы отправитель или получатель, и не от заблокированных ключей
+    async fn filter_incoming_direct(&self, payload: &Payload) -> bool {
+        match DirectEnvelope::from_bytes(payload.get_data()) {
+            Ok(envelope) => {
+                envelope.involves(&APP_STATE.identity) && !APP_STATE.is_identity_key_blocked(&hex::encode(envelope.sender.serialize()))
+            }
+            Err(err) => {
+                log::warn!("Malformed direct message: {}", err);
+                false
+            }
+        }
+    }
+
     // Обработка события подключения
     async fn handle_connect(&self) -> Result<()> {
         log::info!("Connected to {:?}", self.client().url());
 
         // Получение информации о сервере
-        let server_info = self.client().get_server_info().await?;
+        let server_info = call_with_policy("server info", || self.client().get_server_info())
+            .await
+            .map_err(|e| kaspa_wrpc_client::error::Error::from(e.to_string()))?;
         log::info!("Server info: {server_info:?}");
+        APP_STATE.set_server_info(
+            server_info.server_version.clone(),
+            server_info.network_id.to_string(),
+            server_info.is_synced,
+            server_info.virtual_daa_score,
+        );
 
         // Регистрация уведомлений
         self.register_notification_listeners().await?;
+        APP_STATE.record_connect();
+
+        match APP_STATE.get_backfill_minutes() {
+            Ok(Some(minutes)) => self.spawn_backfill(minutes),
+            Ok(None) => {}
+            Err(err) => log::error!("Error while reading history backfill setting: {}", err),
+        }
 
         // Обновление состояния подключения
         if let Err(e) = APP_STATE.set_listener_connected(true) {
@@ -233,6 +687,9 @@ impl Listener {
         if let Err(e) = APP_STATE.set_listener_connected(false) {
             log::error!("Error while setting is_connected flag: {}", &e);
         }
+        APP_STATE.reset_notification_time();
+        APP_STATE.set_listener_scopes(None);
+        APP_STATE.set_latency(None);
 
         Ok(())
     }
@@ -254,6 +711,8 @@ impl Listener {
                                 RpcState::Connected => {
                                     if let Err(err) = listener.handle_connect().await {
                                         log::error!("Error while connecting: {err}");
+                                        APP_STATE.record_rpc_error(&format!("Listener connect: {err}"));
+                                        APP_STATE.report_error(ErrorEvent::ConnectFailed(err.to_string()));
                                     }
                                 },
                                 RpcState::Disconnected => {
@@ -271,6 +730,7 @@ impl Listener {
                         if let Ok(notification) = notification {
                             if let Err(err) = listener.handle_notification(notification).await {
                                 log::error!("Error while processing notification: {err}");
+                                APP_STATE.record_rpc_error(&format!("Notification: {err}"));
                             }
                         } else {
                             log::error!("Notification channel error");
@@ -311,3 +771,144 @@ impl Listener {
         }
     }
 }
+
+/// Текстовое сообщение или объявление канала `channel`: только такие подгружаются в историю
+fn is_channel_text(tx: &RpcTransaction, channel: u32) -> bool {
+    if !tx.payload.starts_with(MARKER) {
+        return false;
+    }
+    Payload::from_bytes(&tx.payload).is_ok_and(|payload| {
+        payload.get_channel() == channel && matches!(payload.get_message_type(), MessageType::Text | MessageType::Announcement)
+    })
+}
+
+/// Заносит транзакцию в ленту, если она из текущего канала и не от заблокированного отправителя
+fn observe_transaction(payload: &Payload, tx_id: &str, payload_size: usize, fee: Option<u64>) {
+    if payload.get_channel() != APP_STATE.get_channel_number().unwrap_or(0)
+        || APP_STATE.is_sender_blocked(payload.get_username(), payload.get_sender_key())
+    {
+        return;
+    }
+    let block_time = payload.get_block_time();
+    APP_STATE.record_observed_transaction(ObservedTransaction {
+        tx_id: tx_id.to_string(),
+        username: payload.get_username().to_string(),
+        sender_address: payload.get_sender_address().map(str::to_string),
+        message_type: payload.get_message_type(),
+        payload_size,
+        fee,
+        timestamp: block_time.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+        confirmed: block_time.is_some(),
+    });
+}
+
+/// Разбирает payload и распаковывает zstd; вызывается в блокирующем потоке обработчика.
+/// None — payload повреждён или отброшен по заголовку. Включённый инспектор получает запись о каждом payload
+fn decode_payload(job: PayloadJob) -> Option<Payload> {
+    let mut inspected = APP_STATE.is_payload_inspector_enabled().then(|| InspectedPayload::new(&job.tx_id, &job.bytes));
+    let payload = decode(job, &mut inspected);
+    if let Some(inspected) = inspected {
+        APP_STATE.record_inspected_payload(inspected);
+    }
+    payload
+}
+
+fn decode(job: PayloadJob, inspected: &mut Option<InspectedPayload>) -> Option<Payload> {
+    let tx_id = job.tx_id;
+    // Версия и тип читаются прямо из байтов заголовка: считаются и payload, которые мы не умеем разобрать
+    if let (Some(&version), Some(&message_type)) = (job.bytes.get(MARKER.len()), job.bytes.get(MARKER.len() + 4)) {
+        APP_STATE.record_payload_kind(version, MessageType::from_byte(message_type));
+    }
+    let mut note = |fields: Option<String>, raw_size: Option<usize>, outcome: String| {
+        if let Some(inspected) = inspected.as_mut() {
+            if let Some(fields) = fields {
+                inspected.fields = fields;
+            }
+            inspected.raw_size = raw_size;
+            inspected.outcome = outcome;
+        }
+    };
+    let mut payload = match Payload::from_bytes_lenient(&job.bytes) {
+        Ok((payload, issues)) => {
+            if !issues.is_empty() {
+                for issue in &issues {
+                    log::warn!(
+                        "Payload parse issue: kind={} version={} type={:?} user='{}' tx_id={}: {}",
+                        issue.kind(),
+                        payload.get_protocol_version(),
+                        payload.get_message_type(),
+                        payload.get_username(),
+                        tx_id,
+                        issue
+                    );
+                    APP_STATE.record_parse_issue(issue.kind());
+                }
+                if !APP_STATE.is_salvage_payloads_enabled().unwrap_or(false) {
+                    let kinds: Vec<&str> = issues.iter().map(|issue| issue.kind()).collect();
+                    note(Some(payload.debug_string()), None, format!("rejected: {}", kinds.join(", ")));
+                    return None;
+                }
+                log::warn!("Salvaged payload with {} issue(s) (tx_id={})", issues.len(), tx_id);
+            }
+            log::info!("Received payload: {} (tx_id={})", payload.debug_string(), tx_id);
+            payload
+        }
+        Err(issue) => {
+            log::error!("Error while parsing payload: kind={} tx_id={}: {}", issue.kind(), tx_id, issue);
+            APP_STATE.record_parse_issue(issue.kind());
+            APP_STATE.report_error(ErrorEvent::DecodeFailed(tx_id.clone()));
+            note(Some(issue.to_string()), None, format!("parse error: {}", issue.kind()));
+            return None;
+        }
+    };
+    payload.set_tx_id(tx_id.clone());
+    if let Some(block_time) = job.block_time {
+        payload.set_block_time(block_time);
+    }
+    if job.backfilled {
+        payload.set_backfilled();
+    }
+    if let Some(address) = job.sender_address {
+        payload.set_sender_address(address);
+    }
+    let fields = Some(payload.debug_string());
+
+    // Всё, что можно отсечь по заголовку, отсекается до распаковки zstd.
+    // Подпись отправителя проверяется только у payload, которые не отброшены по заголовку
+    let mut drop_reason = Listener::drop_reason(&payload);
+    if drop_reason.is_none() {
+        if let Some(key) = payload.verify_sender(&job.bytes) {
+            payload.set_sender_key(key);
+        }
+        drop_reason = Listener::sender_drop_reason(&payload);
+    }
+    // Лента видит и отброшенные по заголовку payload своего канала, например заглушённый голос
+    if !job.backfilled {
+        observe_transaction(&payload, &tx_id, job.bytes.len(), job.fee);
+    }
+    if let Some(reason) = drop_reason {
+        log::debug!(
+            "Dropped {:?} payload from '{}': {} (tx_id={})",
+            payload.get_message_type(),
+            payload.get_username(),
+            reason,
+            tx_id
+        );
+        APP_STATE.record_dropped_payload(reason);
+        note(fields, None, format!("dropped: {reason}"));
+        return None;
+    }
+    if matches!(
+        payload.get_message_type(),
+        MessageType::Voice | MessageType::VoiceBatch | MessageType::Text | MessageType::Announcement
+    ) {
+        if let Err(err) = payload.decompress_zstd() {
+            log::error!("Error while decompressing {:?} payload (tx_id={}): {}", payload.get_message_type(), tx_id, err);
+            APP_STATE.report_error(ErrorEvent::DecodeFailed(tx_id.clone()));
+            note(fields, None, format!("decompression failed: {err}"));
+            return None;
+        }
+    }
+    note(fields, Some(payload.get_data().len()), "decoded".to_string());
+    Some(payload)
+}
@@ -0,0 +1,33 @@
+use kaspa_wallet_core::message::{sign_message, verify_message, PersonalMessage, SignMessageOptions};
+use kaspa_wallet_core::prelude::Address;
+use secp256k1::{Secp256k1, SecretKey, XOnlyPublicKey};
+
+/// Сообщение, подписанное ключом адреса кошелька. Чтобы проверить подпись, достаточно адреса:
+/// в адресе Kaspa (Schnorr, P2PK) лежит сам публичный ключ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedMessage {
+    pub address: String,
+    pub message: String,
+    /// Подпись Schnorr в hex
+    pub signature: String,
+}
+
+/// Подписывает `message` ключом `secret_key`, который должен принадлежать `address`
+pub fn sign_with_key(address: &Address, message: &str, secret_key: &SecretKey) -> Result<SignedMessage, String> {
+    let (public_key, _) = secret_key.x_only_public_key(&Secp256k1::signing_only());
+    if address.payload.as_slice() != public_key.serialize().as_slice() {
+        return Err(format!("The derived key does not belong to {}", address));
+    }
+    let signature = sign_message(&PersonalMessage(message), &secret_key.secret_bytes(), &SignMessageOptions { no_aux_rand: false })
+        .map_err(|e| format!("Failed to sign message: {e}"))?;
+    Ok(SignedMessage { address: address.to_string(), message: message.to_string(), signature: hex::encode(signature) })
+}
+
+/// Проверяет, что `signature_hex` — подпись `message` владельцем `address`
+pub fn verify_signed_message(address: &str, message: &str, signature_hex: &str) -> Result<(), String> {
+    let address = Address::try_from(address.trim()).map_err(|e| format!("Invalid address: {e}"))?;
+    let public_key = XOnlyPublicKey::from_slice(address.payload.as_slice())
+        .map_err(|_| "Only Schnorr (P2PK) addresses can verify messages".to_string())?;
+    let signature = hex::decode(signature_hex.trim()).map_err(|e| format!("Invalid signature hex: {e}"))?;
+    verify_message(&PersonalMessage(message), &signature, &public_key).map_err(|_| "Signature does not match".to_string())
+}
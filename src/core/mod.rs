@@ -1,3 +1,12 @@
+pub mod archive_scan;
 pub mod broadcaster;
+pub mod console;
+pub mod diagnostics;
+pub mod hotkeys;
+pub mod identity;
+pub mod inspector;
 pub mod listener;
+pub mod message_signing;
+pub mod node_failover;
+pub mod sleep_watch;
 pub mod wallet;
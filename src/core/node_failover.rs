@@ -0,0 +1,141 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::{select_biased, FutureExt};
+use kaspa_wrpc_client::prelude::RpcState;
+use kaspa_wrpc_client::KaspaRpcClient;
+use rand::Rng;
+
+use crate::app_state::{ToastKind, APP_STATE};
+use crate::constants::{
+    FAILOVER_CHECK_INTERVAL_SECS, FAILOVER_DISCONNECT_GRACE_MS, FAILOVER_ERROR_WINDOW_SECS, FAILOVER_RPC_ERROR_THRESHOLD,
+    RECONNECT_BASE_DELAY_MS, RECONNECT_JITTER_PERCENT, RECONNECT_MAX_DELAY_MS,
+};
+use crate::utils::try_connect_to_node;
+
+/// Подключается к первой доступной ноде из списка, а при пустом списке — через резолвер.
+pub async fn connect_preferred_node(kaspa_rpc_client: Arc<KaspaRpcClient>) {
+    let urls = APP_STATE.get_node_urls().unwrap_or_default();
+    if urls.is_empty() {
+        try_connect_to_node(kaspa_rpc_client, None).await;
+        return;
+    }
+    if connect_first_reachable(&kaspa_rpc_client, urls).await.is_none() {
+        log::error!("None of the listed nodes is reachable");
+    }
+}
+
+/// Следит за активной нодой из списка и переключается на следующую по приоритету,
+/// когда соединение обрывается или ошибки RPC идут одна за другой.
+/// Ноды, введённые вручную или выбранные резолвером, не переключаются.
+/// Если после обрыва переключиться не удалось, переподключается с растущей паузой (если это не выключено в настройках).
+pub fn spawn_node_failover(kaspa_rpc_client: Arc<KaspaRpcClient>) {
+    let rpc_ctl_channel = kaspa_rpc_client.rpc_ctl().multiplexer().channel();
+    tokio::spawn(async move {
+        let interval = Duration::from_secs(FAILOVER_CHECK_INTERVAL_SECS);
+        loop {
+            select_biased! {
+                msg = rpc_ctl_channel.receiver.recv().fuse() => match msg {
+                    Ok(RpcState::Disconnected) => {
+                        // Сон системы и консоль переподключаются сами
+                        tokio::time::sleep(Duration::from_millis(FAILOVER_DISCONNECT_GRACE_MS)).await;
+                        if kaspa_rpc_client.is_connected() {
+                            continue;
+                        }
+                        if !fail_over(&kaspa_rpc_client, "connection lost").await
+                            && APP_STATE.is_auto_reconnect_enabled().unwrap_or(false)
+                        {
+                            reconnect_with_backoff(&kaspa_rpc_client).await;
+                        }
+                    }
+                    Ok(RpcState::Connected) => {}
+                    Err(_) => {
+                        log::error!("RPC CTL channel error");
+                        break;
+                    }
+                },
+                _ = tokio::time::sleep(interval).fuse() => {
+                    let errors = APP_STATE.count_failover_rpc_errors();
+                    if errors >= FAILOVER_RPC_ERROR_THRESHOLD {
+                        let reason = format!("{} RPC errors in the last {} s", errors, FAILOVER_ERROR_WINDOW_SECS);
+                        fail_over(&kaspa_rpc_client, &reason).await;
+                    }
+                },
+            }
+        }
+    });
+}
+
+/// Переключается на следующую ноду списка; true — подключение установлено
+async fn fail_over(kaspa_rpc_client: &Arc<KaspaRpcClient>, reason: &str) -> bool {
+    let candidates = APP_STATE.get_failover_nodes();
+    if candidates.is_empty() {
+        return false;
+    }
+    APP_STATE.mark_failover();
+    log::warn!("Node {:?} failed ({}), failing over", APP_STATE.get_active_node(), reason);
+    if kaspa_rpc_client.is_connected() {
+        if let Err(err) = kaspa_rpc_client.disconnect().await {
+            log::error!("Error while disconnecting from failed node: {}", err);
+        }
+    }
+    match connect_first_reachable(kaspa_rpc_client, candidates).await {
+        Some(url) => {
+            log::info!("Failed over to {}", url);
+            APP_STATE.push_toast(ToastKind::NodeFailover(url));
+            true
+        }
+        None => {
+            log::error!("None of the listed nodes is reachable");
+            false
+        }
+    }
+}
+
+/// Подключается к первой доступной ноде из `urls` и возвращает её адрес
+async fn connect_first_reachable(kaspa_rpc_client: &Arc<KaspaRpcClient>, urls: Vec<String>) -> Option<String> {
+    for url in urls {
+        try_connect_to_node(kaspa_rpc_client.clone(), Some(url.clone())).await;
+        if kaspa_rpc_client.is_connected() {
+            return Some(url);
+        }
+        log::warn!("Node {} is unreachable", url);
+    }
+    None
+}
+
+/// Повторяет подключение к последней активной ноде (или к нодам списка), пока не получится.
+/// Случайная добавка к паузе не даёт клиентам, потерявшим одну ноду, возвращаться к ней одновременно.
+async fn reconnect_with_backoff(kaspa_rpc_client: &Arc<KaspaRpcClient>) {
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+        APP_STATE.set_reconnect_attempt(Some(attempt));
+        let delay = reconnect_delay(attempt);
+        log::info!("Reconnecting in {} ms (attempt {})", delay.as_millis(), attempt);
+        tokio::time::sleep(delay).await;
+        // Пока ждали, пользователь мог подключиться сам или выключить переподключение
+        if kaspa_rpc_client.is_connected() || !APP_STATE.is_auto_reconnect_enabled().unwrap_or(false) {
+            break;
+        }
+        let candidates = APP_STATE.get_failover_nodes();
+        if candidates.is_empty() {
+            try_connect_to_node(kaspa_rpc_client.clone(), APP_STATE.get_active_node()).await;
+        } else {
+            connect_first_reachable(kaspa_rpc_client, candidates).await;
+        }
+        if kaspa_rpc_client.is_connected() {
+            log::info!("Reconnected to {:?} after {} attempt(s)", kaspa_rpc_client.url(), attempt);
+            break;
+        }
+    }
+    APP_STATE.set_reconnect_attempt(None);
+}
+
+/// Пауза перед попыткой `attempt`: 1, 2, 4... секунды, но не больше RECONNECT_MAX_DELAY_MS, и случайная добавка сверху
+fn reconnect_delay(attempt: u32) -> Duration {
+    let factor = 1u64.checked_shl(attempt.saturating_sub(1)).unwrap_or(u64::MAX);
+    let base = RECONNECT_BASE_DELAY_MS.saturating_mul(factor).min(RECONNECT_MAX_DELAY_MS);
+    let jitter = rand::thread_rng().gen_range(0..=base * RECONNECT_JITTER_PERCENT / 100);
+    Duration::from_millis(base + jitter)
+}
@@ -0,0 +1,58 @@
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use kaspa_wrpc_client::KaspaRpcClient;
+
+use crate::app_state::APP_STATE;
+use crate::audio::recorder::Recorder;
+use crate::constants::{SLEEP_CHECK_INTERVAL_SECS, SLEEP_DETECT_THRESHOLD_SECS};
+use crate::utils::try_connect_to_node;
+
+/// Следит за сном системы.
+/// Пока система спит, таймеры стоят, а системные часы идут, поэтому после пробуждения
+/// очередная проверка видит разрыв системного времени намного больше интервала.
+/// Момент засыпания кроссплатформенно не поймать, поэтому реакция происходит сразу после пробуждения:
+/// запись останавливается, статус становится «Away», а соединение с нодой переустанавливается —
+/// wRPC-соединения сон почти никогда не переживают, а флаги при этом остаются «Connected».
+pub fn spawn_sleep_watch(kaspa_rpc_client: Arc<KaspaRpcClient>, recorder: Option<Arc<RwLock<Recorder>>>) {
+    tokio::spawn(async move {
+        let interval = Duration::from_secs(SLEEP_CHECK_INTERVAL_SECS);
+        let threshold = Duration::from_secs(SLEEP_DETECT_THRESHOLD_SECS);
+        let mut last_check = SystemTime::now();
+        loop {
+            tokio::time::sleep(interval).await;
+            let now = SystemTime::now();
+            // Перевод часов назад — не сон
+            let elapsed = now.duration_since(last_check).unwrap_or_default();
+            last_check = now;
+            if elapsed > interval + threshold {
+                log::warn!("System resumed after ~{}s of sleep", elapsed.as_secs());
+                handle_resume(&kaspa_rpc_client, recorder.as_ref()).await;
+            }
+        }
+    });
+}
+
+async fn handle_resume(kaspa_rpc_client: &Arc<KaspaRpcClient>, recorder: Option<&Arc<RwLock<Recorder>>>) {
+    if let Some(recorder) = recorder {
+        match recorder.read() {
+            Ok(recorder) => recorder.stop_recording(),
+            Err(_) => log::error!("Recorder lock poisoned, cannot stop recording"),
+        }
+    }
+    if let Err(err) = APP_STATE.set_away(true) {
+        log::error!("Error while setting away flag: {}", err);
+    }
+
+    // Переподключаемся, только если до сна были подключены
+    let was_connected = APP_STATE.is_listener_connected().unwrap_or(false) || APP_STATE.is_broadcaster_connected().unwrap_or(false);
+    if !was_connected {
+        return;
+    }
+    let node_url = kaspa_rpc_client.url();
+    log::info!("Forcing reconnect after resume ({:?})", node_url);
+    if let Err(err) = kaspa_rpc_client.disconnect().await {
+        log::error!("Error while disconnecting after resume: {}", err);
+    }
+    try_connect_to_node(kaspa_rpc_client.clone(), node_url).await;
+}
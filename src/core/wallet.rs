@@ -1,18 +1,34 @@
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+use futures::TryStreamExt;
 use kaspa_wallet_core::prelude::*;
-use kaspa_wallet_core::{rpc::DynRpcApi, rpc::Rpc, wallet::Wallet};
+use kaspa_wallet_core::tx::{Generator, GeneratorSettings, PendingTransaction, Signer, SignerT};
+use kaspa_wallet_core::{rpc::DynRpcApi, rpc::Rpc, rpc::RpcApi, wallet::Wallet};
 use kaspa_wrpc_client::KaspaRpcClient;
 use workflow_core::prelude::Abortable;
 
 use crate::app_state::APP_STATE;
-use crate::constants::{NETWORK_ID, UNIT};
-
-use kaspa_wallet_core::account::Account;
+use crate::constants::{DEFAULT_PROFILE, FAUCET_REQUEST_TIMEOUT_SECS, SOMPI_PER_KAS};
+use crate::core::message_signing::{sign_with_key, SignedMessage};
+use crate::models::derivation::AccountDerivation;
+use crate::models::faucet::FaucetSource;
+use crate::models::message::Message;
+use crate::models::payload::{MessageType, Payload};
+use crate::models::transaction::SentTransaction;
+use crate::utils::format::format_log_amount;
+use crate::utils::play_notification_sound;
+use crate::utils::rpc::call_with_policy;
+
+use kaspa_wallet_core::account::{create_private_keys, Account};
+use kaspa_wallet_core::deterministic::AccountId;
 use kaspa_wallet_core::error::Error;
+use kaspa_wallet_core::events::Events;
 use kaspa_wallet_core::prelude::{Language, Mnemonic, Secret};
 use kaspa_wallet_core::result::Result as KaspaResult;
 use kaspa_wallet_core::storage::keydata::PrvKeyDataVariantKind;
+use kaspa_wallet_core::storage::{Binding, TransactionKind};
+use kaspa_wallet_core::utxo::UtxoEntryReference;
 
 /// Пример «сервиса» для кошелька.
 /// Хранит ссылку на `kaspa_wallet_core::Wallet`, `account`,
@@ -21,7 +37,19 @@ pub struct WalletService {
     wallet: Arc<Wallet>,
     wallet_secret: Secret,
     personal_account: Mutex<Option<Arc<dyn Account>>>,
-    airdrop_account: Mutex<Option<Arc<dyn Account>>>,
+    /// Аккаунт пополнения и мнемоника, из которой он создан: при смене источника создаётся заново
+    airdrop_account: Mutex<Option<(String, Arc<dyn Account>)>>,
+    /// Свои транзакции с payload, ещё не попавшие в блок: из них собирается замена с большей комиссией
+    unconfirmed_payload_txs: Mutex<HashMap<String, UnconfirmedPayloadTx>>,
+}
+
+/// Всё, что нужно, чтобы пересобрать транзакцию с payload на тех же входах
+#[derive(Clone)]
+struct UnconfirmedPayloadTx {
+    utxo_entries: Vec<UtxoEntryReference>,
+    amount: u64,
+    fee: u64,
+    payload: Vec<u8>,
 }
 
 impl WalletService {
@@ -31,11 +59,15 @@ impl WalletService {
         let rpc = Rpc::new(rpc_api, client.ctl().clone());
 
         let storage = Wallet::local_store().expect("Failed to initialize local wallet storage");
-        let wallet = Arc::new(Wallet::try_with_rpc(Some(rpc), storage, Some(NETWORK_ID)).expect("Failed to create a Wallet with RPC"));
+        let wallet = Arc::new(
+            Wallet::try_with_rpc(Some(rpc), storage, Some(APP_STATE.get_network().network_id()))
+                .expect("Failed to create a Wallet with RPC"),
+        );
         let wallet_service = Self {
             wallet,
             personal_account: Mutex::new(None),
             airdrop_account: Mutex::new(None),
+            unconfirmed_payload_txs: Mutex::new(HashMap::new()),
             wallet_secret: Secret::new(wallet_mnemonic.clone().into_bytes()),
         };
         wallet_service.setup_wallet_and_account(&wallet_mnemonic).await?;
@@ -49,11 +81,13 @@ impl WalletService {
         let wallet = self.wallet.clone();
         self.wallet.load_settings().await?;
 
-        // 1) Переопределяем кошелек
+        // 1) Переопределяем кошелек; у каждого профиля свой файл, основной остаётся в файле по умолчанию
+        let profile = APP_STATE.get_profile();
+        let filename = (profile != DEFAULT_PROFILE).then(|| format!("kaspeak-{profile}"));
         let _ = wallet
             .create_wallet(
                 &wallet_secret,
-                WalletCreateArgs::new(Some("Application Wallet".to_string()), None, EncryptionKind::XChaCha20Poly1305, None, true),
+                WalletCreateArgs::new(Some("Application Wallet".to_string()), filename, EncryptionKind::XChaCha20Poly1305, None, true),
             )
             .await?;
 
@@ -63,11 +97,24 @@ impl WalletService {
         let personal_account = {
             let mnemonic = Mnemonic::new(wallet_mnemonic, Language::English).expect("Failed to create Mnemonic");
             log::info!("Created mnemonic: {}", mnemonic.phrase_string());
-            init_account_from_mnemonic("Personal Account", mnemonic, wallet.clone(), &wallet_secret).await
+            let derivation = APP_STATE.get_account_derivation().map_err(Error::from)?;
+            log::info!("Personal account derivation path: {}", derivation);
+            init_account_from_mnemonic("Personal Account", mnemonic, derivation, wallet.clone(), &wallet_secret).await
         }?;
 
         // Запоминаем локально
         self.personal_account.lock()?.replace(personal_account.clone());
+        self.spawn_balance_watch(*personal_account.id());
+
+        // Кошелёк создаётся заново при каждом запуске: доводим аккаунт до адреса, на который перешли в прошлый раз
+        let address_index = APP_STATE.get_receive_address_index().map_err(Error::from)?;
+        if address_index > 0 {
+            let derivation = personal_account.clone().as_derivation_capable()?;
+            for _ in 0..address_index {
+                derivation.new_receive_address().await?;
+            }
+            log::info!("Restored receive address #{}", address_index);
+        }
 
         // Устанавливаем адрес в стейт приложения
         if let Ok(address) = personal_account.receive_address() {
@@ -77,6 +124,72 @@ impl WalletService {
         Ok(())
     }
 
+    /// Выводит следующий адрес получения и переходит на него: новые сообщения и пополнения
+    /// не связываются с прежней активностью. Средства на старых адресах остаются в балансе аккаунта
+    pub async fn new_receive_address(&self) -> KaspaResult<Address> {
+        let account = match self.personal_account.lock()?.clone() {
+            Some(account) => account,
+            None => return Err(Error::from("Personal account is not initialized")),
+        };
+        let address = account.as_derivation_capable()?.new_receive_address().await?;
+        APP_STATE.record_address_rotation(address.clone()).map_err(Error::from)?;
+        log::info!("Switched to a new receive address: {}", address);
+        Ok(address)
+    }
+
+    /// Подписывает произвольный текст ключом текущего адреса получения: собеседник проверит подпись
+    /// по адресу (см. `verify_signed_message`) и убедится, что пишет владелец кошелька
+    pub async fn sign_message(&self, message: &str) -> KaspaResult<SignedMessage> {
+        let account = match self.personal_account.lock()?.clone() {
+            Some(account) => account,
+            None => return Err(Error::from("Personal account is not initialized")),
+        };
+        let address = account.receive_address()?;
+        let address_index = APP_STATE.get_receive_address_index().map_err(Error::from)?;
+        let account_index = account.clone().as_derivation_capable()?.account_index();
+        let xprv = account.prv_key_data(self.wallet_secret.clone()).await?.create_xprv(None)?;
+        let keys = create_private_keys(&account.account_kind(), 0, account_index, &xprv, &[(&address, address_index)], &[])?;
+        let Some((_, secret_key)) = keys.into_iter().next() else {
+            return Err(Error::from("Failed to derive the address key"));
+        };
+        let signed = sign_with_key(&address, message, &secret_key).map_err(Error::from)?;
+        log::info!("Signed a {}-byte message with {}", message.len(), address);
+        Ok(signed)
+    }
+
+    /// Следит за событиями кошелька: баланс личного аккаунта обновляется сразу,
+    /// а о созревших входящих транзакциях (эйрдроп, чужой перевод) показывается уведомление.
+    /// Сдача от своих отправок приходит как `Change` и уведомлений не вызывает
+    fn spawn_balance_watch(&self, account_id: AccountId) {
+        let channel = self.wallet.multiplexer().channel();
+        tokio::spawn(async move {
+            while let Ok(event) = channel.receiver.recv().await {
+                match &*event {
+                    Events::Balance { balance: Some(balance), id } if *id == account_id => {
+                        let _ = APP_STATE.set_balance(balance.mature);
+                        let _ = APP_STATE.set_pending_balance(balance.pending, balance.outgoing);
+                        let _ = APP_STATE.set_utxos(balance.mature_utxo_count);
+                    }
+                    Events::Maturity { record } if *record.binding() == Binding::Account(account_id) => {
+                        if !matches!(record.transaction_type(), TransactionKind::Incoming | TransactionKind::TransferIncoming) {
+                            continue;
+                        }
+                        log::info!("Incoming funds matured: {} in {}", format_log_amount(record.value()), record.id());
+                        let is_new = APP_STATE.notify_incoming_funds(record.value());
+                        if is_new && APP_STATE.is_incoming_funds_sound_enabled().unwrap_or(false) {
+                            std::thread::spawn(|| {
+                                if let Err(err) = play_notification_sound() {
+                                    log::error!("Error while playing notification sound: {}", err)
+                                }
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+    }
+
     /// Отправка транзакции
     async fn send_transaction(
         &self,
@@ -85,6 +198,7 @@ impl WalletService {
         amount: Option<u64>,
         payload: Option<Vec<u8>>,
     ) -> KaspaResult<Balance> {
+        let is_personal = from.is_none();
         let account = match from {
             None => match self.personal_account.lock()?.clone() {
                 Some(account) => account,
@@ -94,20 +208,45 @@ impl WalletService {
         };
 
         let payload_size = payload.as_ref().map_or(0, |p| p.len());
-        let default_amount = (5.0 * UNIT) as u64;
+        // Канал нужен для счётчика расходов; сжатые данные для этого распаковывать не нужно
+        let parsed_payload = payload.as_deref().and_then(|bytes| {
+            let mut parsed = Payload::from_bytes(bytes).ok()?;
+            // Подпись отправителя проверяется здесь: сами байты уходят в транзакцию
+            if let Some(key) = parsed.verify_sender(bytes) {
+                parsed.set_sender_key(key);
+            }
+            Some(parsed)
+        });
+        let channel = parsed_payload.as_ref().map(|payload| payload.get_channel());
+        let default_amount = 5 * SOMPI_PER_KAS;
         let final_amount = amount.unwrap_or(default_amount);
-
+        // Перевод на чужой адрес — трата; отправка на свой адрес стоит только комиссию
+        let outgoing = destination != account.receive_address()?;
+
+        let payment_destination = PaymentDestination::PaymentOutputs(PaymentOutputs::from((destination, final_amount)));
+        let priority_fee = Fees::SenderPays(APP_STATE.get_priority_fee(payload_size)?);
+        // Входы отправленных транзакций: если транзакция с payload зависнет, замену соберём на них же
+        let generated = Arc::new(Mutex::new(Vec::new()));
+        let notifier: Arc<dyn Fn(&PendingTransaction) + Send + Sync> = {
+            let generated = generated.clone();
+            Arc::new(move |pending: &PendingTransaction| {
+                if let Ok(mut generated) = generated.lock() {
+                    generated.push((pending.id().to_string(), pending.utxo_entries().values().cloned().collect::<Vec<_>>()));
+                }
+            })
+        };
+        let payload_copy = if is_personal { payload.clone() } else { None };
         let tx_result = account
             .clone()
             .send(
-                PaymentDestination::PaymentOutputs(PaymentOutputs::from((destination, final_amount))),
+                payment_destination,
                 Option::None,
-                Fees::SenderPays(APP_STATE.get_fee_size()?),
+                priority_fee,
                 payload,
                 self.wallet_secret.clone(),
                 None,
                 &Abortable::new(),
-                None,
+                Some(notifier),
             )
             .await;
 
@@ -123,28 +262,165 @@ impl WalletService {
                 // final_transaction_id: Option<TransactionId>
                 let final_txid = summary.final_transaction_id().unwrap_or_default();
 
-                let fees_kas = fees_atomic as f64 / UNIT;
-                let amount_kas = final_amount_atomic as f64 / UNIT;
-
                 log::info!(
-                    "Transaction successfully sent: utxos={}, fee={:.8} TKAS, tx_count={}, amount={:.8} TKAS, final_txid={}, payload_size={}",
+                    "Transaction successfully sent: utxos={}, fee={}, tx_count={}, amount={}, final_txid={}, payload_size={}",
                     utxos,
-                    fees_kas,
+                    format_log_amount(fees_atomic),
                     tx_count,
-                    amount_kas,
+                    format_log_amount(final_amount_atomic),
                     final_txid,
                     payload_size,
                 );
+                // Airdrop отправляется с чужого аккаунта, в историю попадают только свои транзакции
+                if is_personal {
+                    self.record_sent_transaction(
+                        SentTransaction {
+                            tx_id: final_txid.to_string(),
+                            amount: final_amount_atomic,
+                            fee: fees_atomic,
+                            payload_size,
+                            timestamp: chrono::Utc::now().timestamp(),
+                            channel,
+                        },
+                        outgoing,
+                    );
+                    if let Some(parsed_payload) = parsed_payload {
+                        show_outgoing_message(parsed_payload, final_txid.to_string());
+                    }
+                    if let Some(payload) = payload_copy {
+                        let utxo_entries = generated
+                            .lock()
+                            .ok()
+                            .and_then(|mut generated| generated.drain(..).find(|(tx_id, _)| *tx_id == final_txid.to_string()))
+                            .map(|(_, utxo_entries)| utxo_entries)
+                            .unwrap_or_default();
+                        let stuck = UnconfirmedPayloadTx { utxo_entries, amount: final_amount_atomic, fee: fees_atomic, payload };
+                        if let Err(err) = self.track_payload_tx(final_txid.to_string(), stuck) {
+                            log::error!("Error while tracking payload transaction: {}", err);
+                        }
+                    }
+                }
             }
-            Err(err) => {
-                log::error!("Error while sending transaction: {:?}", err);
-            }
+            // Ошибку разбирает вызывающий: Broadcaster ставит сообщение в очередь повторов и считает неудачу
+            Err(err) => return Err(err),
         }
 
         let current_balance = account.balance().unwrap_or_default();
         Ok(current_balance)
     }
 
+    /// Начинает ждать попадания транзакции с payload в блок
+    fn track_payload_tx(&self, tx_id: String, transaction: UnconfirmedPayloadTx) -> KaspaResult<()> {
+        if transaction.utxo_entries.is_empty() {
+            return Ok(());
+        }
+        APP_STATE.track_unconfirmed_send(&tx_id).map_err(Error::from)?;
+        let mut unconfirmed = self.unconfirmed_payload_txs.lock()?;
+        // Попавшие в блок и заброшенные транзакции больше не нужны
+        unconfirmed.retain(|tx_id, _| APP_STATE.is_send_unconfirmed(tx_id));
+        unconfirmed.insert(tx_id, transaction);
+        Ok(())
+    }
+
+    /// Заменяет зависшую транзакцию с payload такой же, но с приоритетной комиссией в `percent`% от прежней.
+    /// Замена тратит те же входы, поэтому нода вытесняет старую транзакцию из мемпула (RBF) и в блок попадёт одна из них.
+    /// None — транзакция уже не ждёт замены
+    pub async fn bump_fee(&self, tx_id: &str, percent: u32) -> KaspaResult<Option<String>> {
+        let Some(stuck) = self.unconfirmed_payload_txs.lock()?.get(tx_id).cloned() else {
+            return Ok(None);
+        };
+        let account = match self.personal_account.lock()?.clone() {
+            Some(account) => account,
+            None => return Err(Error::from("Personal account is not initialized")),
+        };
+        let priority_fee = stuck.fee.saturating_mul(percent as u64) / 100;
+        let destination = PaymentDestination::PaymentOutputs(PaymentOutputs::from((account.receive_address()?, stuck.amount)));
+        let settings = GeneratorSettings::try_new_with_iterator(
+            self.wallet.network_id()?,
+            Box::new(stuck.utxo_entries.clone().into_iter()),
+            None,
+            account.change_address()?,
+            1,
+            1,
+            destination,
+            None,
+            Fees::SenderPays(priority_fee),
+            Some(stuck.payload.clone()),
+            None,
+        )?;
+        let signer: Arc<dyn SignerT> =
+            Arc::new(Signer::new(account.clone(), account.prv_key_data(self.wallet_secret.clone()).await?, None));
+        let abortable = Abortable::new();
+        let generator = Generator::try_new(settings, Some(signer), Some(&abortable))?;
+        let rpc_api = self.wallet.rpc_api();
+        let mut stream = generator.stream();
+        let mut replacement = None;
+        while let Some(transaction) = stream.try_next().await? {
+            transaction.try_sign()?;
+            let response = rpc_api.submit_transaction_replacement((&transaction.transaction()).into()).await?;
+            replacement = Some(response.transaction_id.to_string());
+        }
+        let Some(replacement) = replacement else {
+            return Err(Error::from("Fee bump generated no transaction"));
+        };
+
+        let summary = generator.summary();
+        let fees_atomic = summary.aggregate_fees();
+        log::info!(
+            "Fee bumped: {} replaced by {}, fee {} -> {}",
+            tx_id,
+            replacement,
+            format_log_amount(stuck.fee),
+            format_log_amount(fees_atomic),
+        );
+        self.record_replaced_transaction(
+            tx_id,
+            SentTransaction {
+                tx_id: replacement.clone(),
+                amount: stuck.amount,
+                fee: fees_atomic,
+                payload_size: stuck.payload.len(),
+                timestamp: chrono::Utc::now().timestamp(),
+                channel: Payload::from_bytes(&stuck.payload).ok().map(|payload| payload.get_channel()),
+            },
+            stuck.fee,
+        );
+        let mut unconfirmed = self.unconfirmed_payload_txs.lock()?;
+        unconfirmed.remove(tx_id);
+        unconfirmed.insert(replacement.clone(), UnconfirmedPayloadTx { fee: fees_atomic, ..stuck });
+        Ok(Some(replacement))
+    }
+
+    /// Запоминает отправленную транзакцию в истории кошелька (см. `AppState::get_sent_transactions`)
+    /// и учитывает её комиссию в расходах сессии, а комиссию и сумму перевода на чужой адрес — в лимитах трат
+    fn record_sent_transaction(&self, transaction: SentTransaction, outgoing: bool) {
+        if let Err(err) = APP_STATE.record_session_cost(transaction.channel, transaction.fee, transaction.payload_size) {
+            log::error!("Error while updating session cost: {}", err);
+        }
+        let spent = transaction.fee + if outgoing { transaction.amount } else { 0 };
+        if let Err(err) = APP_STATE.record_spend(spent) {
+            log::error!("Error while updating spend log: {}", err);
+        }
+        if let Err(err) = APP_STATE.record_sent_transaction(transaction) {
+            log::error!("Error while saving transaction history: {}", err);
+        }
+    }
+
+    /// Ставит замену на место вытесненной транзакции в истории кошелька.
+    /// Вытесненная в блок не попадёт, поэтому в расходах и лимитах трат учитывается только доплата
+    fn record_replaced_transaction(&self, replaced_tx_id: &str, transaction: SentTransaction, replaced_fee: u64) {
+        let extra_fee = transaction.fee.saturating_sub(replaced_fee);
+        if let Err(err) = APP_STATE.record_fee_bump(transaction.channel, extra_fee) {
+            log::error!("Error while updating session cost: {}", err);
+        }
+        if let Err(err) = APP_STATE.record_spend(extra_fee) {
+            log::error!("Error while updating spend log: {}", err);
+        }
+        if let Err(err) = APP_STATE.replace_sent_transaction(replaced_tx_id, transaction) {
+            log::error!("Error while saving transaction history: {}", err);
+        }
+    }
+
     pub async fn send_transaction_to_self(&self, amount: Option<u64>, payload: Option<Vec<u8>>) -> KaspaResult<Balance> {
         let account = match self.personal_account.lock()?.clone() {
             Some(account) => account,
@@ -154,38 +430,148 @@ impl WalletService {
         Ok(self.update_app_state_balance(account.clone()).await)
     }
 
+    /// Перевод с личного аккаунта на чужой адрес
+    pub async fn send_funds(&self, address: &str, amount: u64) -> KaspaResult<Balance> {
+        let account = match self.personal_account.lock()?.clone() {
+            Some(account) => account,
+            None => return Err(Error::from("Personal account is not initialized")),
+        };
+        let destination = Address::try_from(address).map_err(|e| Error::from(format!("Invalid address {address}: {e}")))?;
+        self.send_transaction(None, destination, Some(amount), None).await?;
+        Ok(self.update_app_state_balance(account).await)
+    }
+
+    /// Объединяет все UTXO личного аккаунта в один выход на свой адрес (sweep)
+    pub async fn compound_utxos(&self) -> KaspaResult<Balance> {
+        let account = match self.personal_account.lock()?.clone() {
+            Some(account) => account,
+            None => return Err(Error::from("Personal account is not initialized")),
+        };
+        let utxos_before = account.balance().unwrap_or_default().mature_utxo_count;
+
+        let (summary, _tx_ids) = account.clone().sweep(self.wallet_secret.clone(), None, &Abortable::new(), None).await?;
+        let fees_atomic = summary.aggregate_fees();
+        let final_txid = summary.final_transaction_id().unwrap_or_default();
+        log::info!(
+            "UTXOs compounded: utxos={}, fee={}, tx_count={}, final_txid={}",
+            utxos_before,
+            format_log_amount(fees_atomic),
+            summary.number_of_generated_transactions(),
+            final_txid,
+        );
+        self.record_sent_transaction(
+            SentTransaction {
+                tx_id: final_txid.to_string(),
+                amount: summary.final_transaction_amount().unwrap_or(0),
+                fee: fees_atomic,
+                payload_size: 0,
+                timestamp: chrono::Utc::now().timestamp(),
+                channel: None,
+            },
+            false,
+        );
+
+        Ok(self.update_app_state_balance(account).await)
+    }
+
+    /// Сколько UTXO на адресах личного аккаунта дешевле `threshold` (сомпи).
+    /// Кошелёк отдаёт только количество UTXO, поэтому суммы спрашиваем у ноды
+    pub async fn count_dust_utxos(&self, threshold: u64) -> KaspaResult<usize> {
+        let account = match self.personal_account.lock()?.clone() {
+            Some(account) => account,
+            None => return Err(Error::from("Personal account is not initialized")),
+        };
+        let mut addresses = vec![account.receive_address()?];
+        let change_address = account.change_address()?;
+        if !addresses.contains(&change_address) {
+            addresses.push(change_address);
+        }
+        let rpc_api = self.wallet.rpc_api();
+        let entries = call_with_policy("UTXOs", || rpc_api.get_utxos_by_addresses(addresses.clone()))
+            .await
+            .map_err(|e| Error::from(e.to_string()))?;
+        Ok(entries.iter().filter(|entry| entry.utxo_entry.amount < threshold).count())
+    }
+
     /// Обновить баланс в AppState
     pub async fn update_app_state_balance(&self, account: Arc<dyn Account>) -> Balance {
         let balance_info = account.balance().unwrap_or_default();
         let balance = balance_info.mature;
         let utxos = balance_info.mature_utxo_count;
         let _ = APP_STATE.set_balance(balance);
+        let _ = APP_STATE.set_pending_balance(balance_info.pending, balance_info.outgoing);
         let _ = APP_STATE.set_utxos(utxos);
 
-        let balance_tkas = (balance as f64) / UNIT;
-        log::info!("Current balance: {:.8} TKAS | {} UTXOs", balance_tkas, utxos);
+        log::info!(
+            "Current balance: {} | pending {} | {} UTXOs",
+            format_log_amount(balance),
+            format_log_amount(balance_info.pending),
+            utxos
+        );
 
         balance_info
     }
 
-    /// Airdrop
+    /// Airdrop: пополнение из источника, выбранного в настройках (см. `FaucetSource`)
     pub async fn handle_airdrop(&self) -> KaspaResult<()> {
-        log::info!("Starting airdrop");
+        let source = APP_STATE.get_faucet_source().map_err(Error::from)?;
+        log::info!("Starting airdrop from {}", source.kind());
+        match source {
+            FaucetSource::BuiltIn => self.airdrop_from_mnemonic(builtin_faucet_mnemonic()?).await,
+            FaucetSource::Mnemonic(phrase) => {
+                let mnemonic = Mnemonic::new(phrase.trim(), Language::English)
+                    .map_err(|e| Error::from(format!("Invalid funding mnemonic: {e}")))?;
+                self.airdrop_from_mnemonic(mnemonic).await
+            }
+            FaucetSource::Url(url) => self.request_external_faucet(&url).await,
+            FaucetSource::Disabled => {
+                log::info!("Faucet is disabled, skipping airdrop");
+                Ok(())
+            }
+        }
+    }
 
-        //Init airdrop account or use initialized if already have been done
-        let airdrop_account_initialized = { self.airdrop_account.lock()?.is_some() };
-        let airdrop_account = match airdrop_account_initialized {
-            true => self.airdrop_account.lock()?.clone().unwrap(),
-            false => {
+    /// Просит внешний кран пополнить адрес кошелька
+    async fn request_external_faucet(&self, url: &str) -> KaspaResult<()> {
+        let target_account = match self.personal_account.lock()?.clone() {
+            Some(account) => account,
+            None => return Err(Error::from("Personal account is not initialized")),
+        };
+        let address = target_account.receive_address()?.to_string();
+        let request_url = FaucetSource::request_url(url, &address);
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(FAUCET_REQUEST_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| Error::from(format!("Failed to build HTTP client: {e}")))?;
+        let response = client
+            .post(&request_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::json!({ "address": address }).to_string())
+            .send()
+            .await
+            .map_err(|e| Error::from(format!("Faucet request failed: {e}")))?;
+        if !response.status().is_success() {
+            return Err(Error::from(format!("Faucet answered with status {}", response.status())));
+        }
+        log::info!("Faucet accepted the request for {}", address);
+        Ok(())
+    }
+
+    /// Переводит средства с аккаунта пополнения на личный
+    async fn airdrop_from_mnemonic(&self, airdrop_mnemonic: Mnemonic) -> KaspaResult<()> {
+        let phrase = airdrop_mnemonic.phrase_string();
+        // Аккаунт создаём один раз на источник; при смене мнемоники в настройках — заново
+        let cached = self.airdrop_account.lock()?.clone().filter(|(cached_phrase, _)| *cached_phrase == phrase);
+        let airdrop_account = match cached {
+            Some((_, account)) => account,
+            None => {
                 let wallet_secret = self.wallet_secret.clone();
                 let wallet = self.wallet.clone();
-                let airdrop_mnemonic_entropy = hex::decode("8ee7277ab57bb6cae8c5bec2cf530459069c5a2e4ff7dc00c523a2ef0e42f97a")
-                    .expect("Failed to decode the airdrop wallet");
-                let airdrop_mnemonic = Mnemonic::from_entropy(airdrop_mnemonic_entropy, Language::English)
-                    .expect("Failed to create the airdrop mnemonic");
-                let airdrop_account = init_account_from_mnemonic("Airdrop Account", airdrop_mnemonic, wallet, &wallet_secret).await?;
+                let airdrop_account =
+                    init_account_from_mnemonic("Airdrop Account", airdrop_mnemonic, AccountDerivation::Legacy, wallet, &wallet_secret)
+                        .await?;
                 airdrop_account.clone().start().await?;
-                self.airdrop_account.lock()?.replace(airdrop_account.clone());
+                self.airdrop_account.lock()?.replace((phrase, airdrop_account.clone()));
                 airdrop_account
             }
         };
@@ -199,8 +585,7 @@ impl WalletService {
 
         // Шлём 20 транзакций по 10 TKAS
         for _ in 0..20 {
-            let ten_kas_atomic = (10.0f64 * UNIT) as u64;
-            self.send_transaction(Some(airdrop_account.clone()), destination.clone(), Some(ten_kas_atomic), None).await?;
+            self.send_transaction(Some(airdrop_account.clone()), destination.clone(), Some(10 * SOMPI_PER_KAS), None).await?;
             self.update_app_state_balance(target_account.clone()).await;
         }
 
@@ -223,9 +608,39 @@ impl WalletService {
     }
 }
 
+/// Показывает своё текстовое сообщение в чате сразу после отправки; подтверждение придёт через Listener
+fn show_outgoing_message(mut payload: Payload, tx_id: String) {
+    if !matches!(payload.get_message_type(), MessageType::Text | MessageType::Announcement) {
+        return;
+    }
+    if let Err(err) = payload.decompress_zstd() {
+        log::error!("Error while decompressing sent message: {}", err);
+        return;
+    }
+    // Listener мог успеть увидеть транзакцию раньше
+    if APP_STATE.chat_state.find_by_tx_id(&tx_id).is_some() {
+        return;
+    }
+    let channel = payload.get_channel();
+    APP_STATE.chat_state.add_message(channel, Message::outgoing(payload, tx_id));
+}
+
+/// Мнемоника встроенного аккаунта эйрдропа; в сборках без фичи `builtin-faucet` его нет
+#[cfg(feature = "builtin-faucet")]
+fn builtin_faucet_mnemonic() -> KaspaResult<Mnemonic> {
+    let entropy = hex::decode(crate::constants::BUILTIN_FAUCET_ENTROPY).expect("Failed to decode the airdrop wallet");
+    Ok(Mnemonic::from_entropy(entropy, Language::English).expect("Failed to create the airdrop mnemonic"))
+}
+
+#[cfg(not(feature = "builtin-faucet"))]
+fn builtin_faucet_mnemonic() -> KaspaResult<Mnemonic> {
+    Err(Error::from("This build has no built-in faucet, choose a faucet in the settings"))
+}
+
 async fn init_account_from_mnemonic(
     account_name: &str,
     account_mnemonic: Mnemonic,
+    derivation: AccountDerivation,
     wallet: Arc<Wallet>,
     wallet_secret: &Secret,
 ) -> kaspa_wallet_core::result::Result<Arc<dyn Account>> {
@@ -239,5 +654,11 @@ async fn init_account_from_mnemonic(
     let wallet_guard = wallet.guard();
     let account_name = Some(account_name.to_string());
     let guard = wallet_guard.lock().await;
-    wallet.create_account(&wallet_secret, AccountCreateArgs::Legacy { prv_key_data_id, account_name }, false, &guard).await
+    let create_args = match derivation {
+        AccountDerivation::Legacy => AccountCreateArgs::Legacy { prv_key_data_id, account_name },
+        AccountDerivation::Bip44 { account_index } => {
+            AccountCreateArgs::new_bip32(prv_key_data_id, None, account_name, Some(account_index))
+        }
+    };
+    wallet.create_account(&wallet_secret, create_args, false, &guard).await
 }
@@ -1,16 +1,57 @@
-use crate::app_state::APP_STATE;
-use crate::constants::{MAX_TEXT_CHARS, UNIT};
-use crate::models::message::Message as ChatMessage;
-use crate::models::user::User;
-use crate::utils::shorten_address;
+use crate::app_state::{
+    ConnectionDiagnostics, ConnectionQuality, ErrorEvent, RecordingStats, StreamStats, Toast, ToastKind, APP_STATE,
+};
+use crate::constants::{
+    ADDRESS_ROTATION_OPTIONS_SECS, AUTO_THEME_NAME, BACKFILL_OPTIONS_MIN, CHAT_CHARS_PER_LINE_ESTIMATE,
+    CHAT_DAY_SEPARATOR_ESTIMATE_PX, CHAT_DEFAULT_VIEWPORT_PX, CHAT_LINE_ESTIMATE_PX, CHAT_LINK_PREVIEW_ESTIMATE_PX,
+    CHAT_RENDER_OVERSCAN, COST_ESTIMATE_RECORDING_SECS, CUSTOM_THEME_NAME, DEDUPE_WINDOW_OPTIONS, DEFAULT_DARK_THEME,
+    DEFAULT_DEDUPE_WINDOW_DAA, DEFAULT_FOCUS_DURATION_MIN, DEFAULT_LIGHT_THEME, DEFAULT_MAX_PLAYBACK_STREAMS, DEFAULT_PANE_RATIOS,
+    DEFAULT_UTXO_DUST_THRESHOLD, DEFAULT_UTXO_MAX_COUNT, FEE_BUMP_PERCENT_OPTIONS, FEE_BUMP_TIMEOUT_SECS, FOCUS_DURATION_OPTIONS_MIN,
+    INPUT_LEVEL_DECAY, MAX_CHANNEL_NAME_CHARS, MAX_CHANNEL_NOTES_CHARS, MAX_CHANNEL_NUMBER, MAX_CHANNEL_TOPIC_CHARS, MAX_PANE_RATIO,
+    MAX_TEXT_CHARS, MAX_USERNAME_CHARS, MAX_UTXO_DUST_THRESHOLD, MESSAGE_TTL_OPTIONS_SECS, MIN_PANE_RATIO, OPUS_BITRATE,
+    OPUS_BITRATE_OPTIONS, OS_THEME_POLL_SECS, PANE_RATIOS_SAVE_DELAY_MS, PINNED_PREVIEW_CHARS, PLAYBACK_STREAM_OPTIONS,
+    PROTOCOL_VERSION, RPC_RETRY_OPTIONS, RPC_TIMEOUT_OPTIONS_SECS, SOMPI_PER_KAS, UTXO_DUST_SWEEP_MIN_COUNT, UTXO_MAX_COUNT_OPTIONS,
+    VOICE_BATCH_MAX_FRAGMENTS, WAVEFORM_HEIGHT,
+};
+use crate::core::hotkeys::ensure_push_to_talk_listener;
+use crate::core::identity::parse_public_key;
+use crate::core::inspector::InspectedPayload;
+use crate::core::message_signing::{verify_signed_message, SignedMessage};
+use crate::logs::telemetry::preview_report;
+use crate::models::avatar::prepare_avatar;
+use crate::models::contact::{BlockedSender, Contact};
+use crate::models::content_filter::{ContentFilter, FilterAction};
+use crate::models::derivation::{AccountDerivation, DerivationKind};
+use crate::models::faucet::{FaucetKind, FaucetSource};
+use crate::models::message::{DeliveryState, Message as ChatMessage, PinnedMessage};
+use crate::models::network::{Network, NotificationScopes, RpcPolicy};
+use crate::models::payload::MessageType;
+use crate::models::shortcut::{Shortcut, ShortcutAction, Shortcuts, SHORTCUT_KEYS};
+use crate::models::transaction::{CostEstimate, FeeMode, ObservedTransaction, SentTransaction, SessionCost, SpendLimits};
+use crate::models::transcript::TranscriptFormat;
+use crate::models::user::{SeenUser, User};
+use crate::settings::{list_profiles, ChannelInfo, ChatDensity, CustomPalette};
+use crate::tray::{is_tray_available, poll_tray_action, TrayAction};
+use crate::utils::format::{
+    amount_to_input, format_amount, format_date, format_date_time, format_decimal, format_elapsed, format_integer, format_skew,
+    format_time, parse_amount, ClockFormat, Denomination, FormatLocale,
+};
+use crate::utils::i18n::{tr, tr_args, UiLanguage};
+use crate::utils::identicon::identicon_rgba;
+use crate::utils::link_preview::{split_links, LinkPreview};
+use crate::utils::{build_permalink, parse_permalink, shorten_address, shorten_key};
+use chrono::{DateTime, Days, Local, NaiveDate};
 use cpal::traits::DeviceTrait;
 use iced::keyboard::{self, key};
+use iced::theme::Palette;
 use iced::widget::{
-    button, column, container, pick_list, rich_text, row, scrollable, span, text, text_editor, text_input, toggler, Column, Row, Rule,
+    button, center, checkbox, column, container, image, mouse_area, opaque, pane_grid, pick_list, progress_bar, qr_code, rich_text,
+    row, scrollable, span, stack, text, text_editor, text_input, toggler, tooltip, Column, Row, Rule, Space,
 };
-use iced::{font, time, Subscription, Task};
-use iced::{Alignment, Color, Length, Theme};
-use std::time::Duration;
+use iced::{font, time, window, Subscription, Task};
+use iced::{Alignment, Color, Element, Length, Theme};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::Sender;
 use uuid::Uuid;
 
@@ -20,6 +61,24 @@ pub enum GuiEvent {
     StopRecording,
     NodeConnectButtonPressed(Option<String>),
     MessageSent(String),
+    AnnouncementSent(String),
+    /// (hex-ключ получателя, текст)
+    DirectMessageSent(String, String),
+    ClaimUsername,
+    /// Разослать свой аватар
+    AnnounceAvatar,
+    /// Попросить владельца адреса повторить рассылку аватара
+    RequestAvatar(String),
+    /// (адрес получателя, сумма в сомпи)
+    SendFunds(String, u64),
+    /// Перейти на новый адрес получения
+    NewReceiveAddress,
+    /// Запросить средства у крана, не дожидаясь низкого баланса
+    RequestAirdrop,
+    /// Подписать текст ключом адреса кошелька
+    SignMessage(String),
+    /// Команда консоли разработчика
+    ConsoleCommand(String),
 }
 
 #[derive(Debug, Clone)]
@@ -34,25 +93,626 @@ pub enum Message {
     NodeConnectComplete(Result<(), String>),
     ToggleListenSelf(bool),
     ToggleMuteAll(bool),
+    TogglePauseIncoming(bool),
+    ToggleVoiceAnnounce(bool),
+    ToggleChannelMuted(bool),
+    FocusDurationChanged(u64),
+    FocusAllowlistChanged(String),
+    StartFocusMode,
+    EndFocusMode,
+    MaxPlaybackStreamsChanged(usize),
+    DedupeWindowChanged(u64),
     ThemeChanged(Theme),
+    /// Тема для светлого (false) или тёмного (true) оформления ОС
+    AutoThemeChanged(bool, Theme),
+    CustomColorChanged(PaletteColor, String),
+    OpusBitrateChanged(OpusBitrate),
+    /// Открыть или закрыть окно настроек
+    ToggleSettings(bool),
+    ToggleWallet(bool),
+    RequestAirdropPressed,
+    AirdropRequested(Result<(), String>),
+    ToggleDiagnostics,
+    ToggleStartMinimized(bool),
+    ToggleMinimizeToTray(bool),
+    WindowCloseRequested(window::Id),
+    LocaleChanged(FormatLocale),
+    ClockFormatChanged(ClockFormat),
+    ChatDensityChanged(ChatDensity),
+    LanguageChanged(UiLanguage),
+    DenominationChanged(Denomination),
     ChatEditorAction(text_editor::Action),
     ChatSendPressed,
     SendMessageDone(Result<(), String>),
     FeeInputChanged(String),
+    FeeModeChanged(FeeMode),
+    UtxoMaxCountChanged(usize),
+    SpendLimitHourChanged(String),
+    SpendLimitDayChanged(String),
+    DustThresholdInputChanged(String),
     ChannelInputChanged(String),
+    JoinChannel,
+    SwitchChannel(u32),
+    ToggleFavoriteChannel(u32, bool),
+    /// Открыть или закрыть редактор своего описания текущего канала
+    EditChannelInfo,
+    ChannelNameInputChanged(String),
+    ChannelTopicInputChanged(String),
+    ChannelNotesInputChanged(String),
+    SaveChannelInfo,
     OpenLink(String),
+    ToggleDirectMode(bool),
+    ToggleLinkPreviews(bool),
+    ToggleAnnouncementMode(bool),
+    DismissAnnouncement(u32, Uuid),
+    ChatExportFormatChanged(TranscriptFormat),
+    /// Выгрузить переписку текущего канала в файл
+    ExportChat,
+    ToggleTelemetry(bool),
+    ToggleSalvagePayloads(bool),
+    ToggleMempoolListening(bool),
+    BackfillChanged(Backfill),
+    ToggleComfortNoise(bool),
+    ToggleInputMeter(bool),
+    ShortcutChanged(ShortcutAction, Option<Shortcut>),
+    /// Нажатие клавиши, не перехваченное виджетами
+    KeyPressed(keyboard::Key, keyboard::Modifiers),
+    ToggleVoiceBatching(bool),
+    ToggleIncomingFundsSound(bool),
+    ToggleAutoReconnect(bool),
+    RpcConnectTimeoutChanged(u64),
+    RpcRequestTimeoutChanged(u64),
+    RpcRetriesChanged(u32),
+    ToggleBlockAddedScope(bool),
+    ToggleVirtualChainScope(bool),
+    DismissToast(u64),
+    ToggleConsole,
+    TogglePayloadInspector(bool),
+    ConsoleInputChanged(String),
+    ConsoleSubmit,
+    ConsoleCommandDone(Result<(), String>),
+    TelemetryEndpointChanged(String),
+    DirectPeerInputChanged(String),
+    CopyIdentityKey,
+    UsernameInputChanged(String),
+    SaveUsername,
+    ClaimUsernamePressed,
+    ClaimUsernameDone(Result<(), String>),
+    RevealMnemonicPressed,
+    ConfirmRevealMnemonic,
+    HideMnemonic,
+    CopyMnemonic,
+    ExportMnemonic,
+    ExportTransactionsCsv,
+    ToggleMnemonicImport,
+    ProfileInputChanged(String),
+    SwitchProfile(String),
+    NetworkSelected(Network),
+    DerivationKindSelected(DerivationKind),
+    DerivationIndexChanged(String),
+    ApplyDerivation,
+    FaucetKindSelected(FaucetKind),
+    FaucetInputChanged(String),
+    SaveFaucet,
+    NodeUrlInputChanged(String),
+    AddNodeUrl,
+    RemoveNodeUrl(usize),
+    /// Поднять ноду на одну позицию в приоритете
+    RaiseNodeUrl(usize),
+    ConfirmNetworkSwitch,
+    CancelNetworkSwitch,
+    /// Подтверждение или отмена действия, оценка расходов на которое выше порога
+    ConfirmCost,
+    CancelCost,
+    CostThresholdChanged(String),
+    MnemonicImportInputChanged(String),
+    ImportMnemonicPressed,
+    AvatarPathChanged(String),
+    SetAvatarPressed,
+    AnnounceAvatarPressed,
+    RequestAvatarPressed(String),
+    AvatarEventDone(Result<(), String>),
+    CopyPermalink(String),
+    ChatScrolled(scrollable::Viewport),
+    JumpToLatest,
+    PaneResized(pane_grid::ResizeEvent),
+    /// Контекстное меню сообщения (правый клик или кнопка «⋯»)
+    OpenMessageMenu(Uuid),
+    CloseMessageMenu,
+    MessageSelectionAction(text_editor::Action),
+    CopyToClipboard(String),
+    ReplyToMessage(Uuid),
+    PinMessage(Uuid),
+    UnpinMessage(PinnedMessage),
+    PermalinkInputChanged(String),
+    OpenPermalink(String),
+    MessageTtlChanged(MessageTtl),
+    FeeBumpChanged(FeeBump),
+    NewAddressPressed,
+    ToggleMessageSigning,
+    SignInputChanged(String),
+    SignMessagePressed,
+    SignMessageSent(Result<(), String>),
+    CopySignature,
+    VerifyAddressChanged(String),
+    VerifyMessageChanged(String),
+    VerifySignatureChanged(String),
+    VerifyMessagePressed,
+    NewAddressDone(Result<(), String>),
+    AddressRotationChanged(AddressRotation),
+    ContactNameChanged(String),
+    ContactTargetChanged(String),
+    ContactNoteChanged(String),
+    SaveContactPressed,
+    RemoveContact(Contact),
+    /// (имя, адрес) отправителя сообщения — заполнить форму контакта
+    AddSenderToContacts(String, String),
+    BlockSender(String, Option<String>),
+    UnblockSender(BlockedSender),
+    ToggleUserMuted(String, bool),
+    /// Скрыть сообщения собеседника в ленте (true) или снова показывать
+    ToggleUserIgnored(String, bool),
+    ContentFilterActionChanged(FilterAction),
+    ChannelFilterChanged(ChannelFilterChoice),
+    FilterPatternInputChanged(String),
+    AddFilterPattern,
+    RemoveFilterPattern(usize),
+    SendAddressChanged(String),
+    SendContactSelected(Contact),
+    SendAmountChanged(String),
+    SendFundsPressed,
+    SendFundsDone(Result<(), String>),
     Tick,
 }
 
+/// Панели основного окна; границы между ними перетаскиваются
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PaneKind {
+    LeftSideBar,
+    Chat,
+    RightSideBar,
+}
+
+/// Раскладка панелей по сохранённым долям: левая панель | (чат | правая панель)
+fn build_panes(ratios: [f32; 2]) -> pane_grid::State<PaneKind> {
+    let [left, chat] = ratios.map(|ratio| ratio.clamp(MIN_PANE_RATIO, MAX_PANE_RATIO));
+    pane_grid::State::with_configuration(pane_grid::Configuration::Split {
+        axis: pane_grid::Axis::Vertical,
+        ratio: left,
+        a: Box::new(pane_grid::Configuration::Pane(PaneKind::LeftSideBar)),
+        b: Box::new(pane_grid::Configuration::Split {
+            axis: pane_grid::Axis::Vertical,
+            ratio: chat,
+            a: Box::new(pane_grid::Configuration::Pane(PaneKind::Chat)),
+            b: Box::new(pane_grid::Configuration::Pane(PaneKind::RightSideBar)),
+        }),
+    })
+}
+
+/// Доли панелей из текущей раскладки (см. `build_panes`)
+fn pane_ratios(panes: &pane_grid::State<PaneKind>) -> [f32; 2] {
+    match panes.layout() {
+        pane_grid::Node::Split { ratio: left, b, .. } => match b.as_ref() {
+            pane_grid::Node::Split { ratio: chat, .. } => [*left, *chat],
+            _ => [*left, DEFAULT_PANE_RATIOS[1]],
+        },
+        _ => DEFAULT_PANE_RATIOS,
+    }
+}
+
+/// Действие из многих транзакций, которое может потребовать подтверждения расходов
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CostAction {
+    Recording,
+    AvatarBroadcast,
+}
+
+/// Показ мнемоники: сначала подтверждение, потом сами слова
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MnemonicReveal {
+    Hidden,
+    Confirming,
+    Shown,
+}
+
+/// Действие фильтра для канала: своё или общее (None), для выпадающего списка
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelFilterChoice(Option<FilterAction>);
+
+impl ChannelFilterChoice {
+    fn options() -> Vec<ChannelFilterChoice> {
+        std::iter::once(ChannelFilterChoice(None))
+            .chain(FilterAction::ALL.iter().map(|action| ChannelFilterChoice(Some(*action))))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for ChannelFilterChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => write!(f, "{}", tr("filter-inherit")),
+            Some(action) => write!(f, "{}", action),
+        }
+    }
+}
+
+/// Срок жизни отправляемых сообщений для выпадающего списка
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageTtl(Option<u64>);
+
+impl MessageTtl {
+    fn options() -> Vec<MessageTtl> {
+        std::iter::once(MessageTtl(None)).chain(MESSAGE_TTL_OPTIONS_SECS.iter().map(|secs| MessageTtl(Some(*secs)))).collect()
+    }
+}
+
+impl std::fmt::Display for MessageTtl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => write!(f, "{}", tr("ttl-forever")),
+            Some(secs) if secs % 86_400 == 0 => write!(f, "{}", tr_args("ttl-days", &[("n", &(secs / 86_400))])),
+            Some(secs) if secs % 3_600 == 0 => write!(f, "{}", tr_args("ttl-hours", &[("n", &(secs / 3_600))])),
+            Some(secs) => write!(f, "{}", tr_args("ttl-minutes", &[("n", &(secs / 60))])),
+        }
+    }
+}
+
+/// Во сколько раз поднимать комиссию зависшей транзакции, для выпадающего списка
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeBump(Option<u32>);
+
+impl FeeBump {
+    fn options() -> Vec<FeeBump> {
+        std::iter::once(FeeBump(None)).chain(FEE_BUMP_PERCENT_OPTIONS.iter().map(|percent| FeeBump(Some(*percent)))).collect()
+    }
+}
+
+impl std::fmt::Display for FeeBump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => write!(f, "{}", tr("fee-bump-never")),
+            Some(percent) => write!(f, "×{}", format_decimal(percent as f64 / 100.0, 1)),
+        }
+    }
+}
+
+/// Битрейт Opus-энкодера для выпадающего списка
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpusBitrate(i32);
+
+impl OpusBitrate {
+    fn options() -> Vec<OpusBitrate> {
+        OPUS_BITRATE_OPTIONS.iter().map(|bitrate| OpusBitrate(*bitrate)).collect()
+    }
+}
+
+impl std::fmt::Display for OpusBitrate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", tr_args("bitrate-kbps", &[("n", &(self.0 / 1000))]))
+    }
+}
+
+/// Период смены адреса получения для выпадающего списка
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressRotation(Option<u64>);
+
+impl AddressRotation {
+    fn options() -> Vec<AddressRotation> {
+        std::iter::once(AddressRotation(None))
+            .chain(ADDRESS_ROTATION_OPTIONS_SECS.iter().map(|secs| AddressRotation(Some(*secs))))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for AddressRotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => write!(f, "{}", tr("rotation-keep")),
+            Some(secs) if secs % 604_800 == 0 => write!(f, "{}", tr_args("rotation-weeks", &[("n", &(secs / 604_800))])),
+            Some(secs) if secs % 86_400 == 0 => write!(f, "{}", tr_args("rotation-days", &[("n", &(secs / 86_400))])),
+            Some(secs) => write!(f, "{}", tr_args("rotation-hours", &[("n", &(secs / 3_600))])),
+        }
+    }
+}
+
+/// Глубина подгрузки истории канала при подключении для выпадающего списка
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backfill(Option<u64>);
+
+impl Backfill {
+    fn options() -> Vec<Backfill> {
+        std::iter::once(Backfill(None)).chain(BACKFILL_OPTIONS_MIN.iter().map(|min| Backfill(Some(*min)))).collect()
+    }
+}
+
+impl std::fmt::Display for Backfill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            None => write!(f, "{}", tr("backfill-none")),
+            Some(min) if min % 60 == 0 => write!(f, "{}", tr_args("backfill-hours", &[("n", &(min / 60))])),
+            Some(min) => write!(f, "{}", tr_args("backfill-minutes", &[("n", &min)])),
+        }
+    }
+}
+
+/// Ссылка на страницу обозревателя текущей сети (`kind` — "txs" или "addresses"); у devnet обозревателя нет
+/// Клавиша сочетания в списке выбора; None — действие без клавиши
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyChoice(Option<&'static str>);
+
+impl std::fmt::Display for KeyChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.unwrap_or(tr("shortcut-none")))
+    }
+}
+
+/// Имя клавиши iced из SHORTCUT_KEYS (см. `models::shortcut`)
+fn key_name(key: &keyboard::Key) -> Option<&'static str> {
+    match key.as_ref() {
+        keyboard::Key::Character("`") => Some("Backquote"),
+        keyboard::Key::Character(c) => SHORTCUT_KEYS.into_iter().find(|name| name.len() == 1 && name.eq_ignore_ascii_case(c)),
+        keyboard::Key::Named(named) => Some(match named {
+            key::Named::F1 => "F1",
+            key::Named::F2 => "F2",
+            key::Named::F3 => "F3",
+            key::Named::F4 => "F4",
+            key::Named::F5 => "F5",
+            key::Named::F6 => "F6",
+            key::Named::F7 => "F7",
+            key::Named::F8 => "F8",
+            key::Named::F9 => "F9",
+            key::Named::F10 => "F10",
+            key::Named::F11 => "F11",
+            key::Named::F12 => "F12",
+            key::Named::Tab => "Tab",
+            key::Named::Space => "Space",
+            key::Named::Enter => "Enter",
+            key::Named::Escape => "Escape",
+            key::Named::Backspace => "Backspace",
+            key::Named::Insert => "Insert",
+            key::Named::Delete => "Delete",
+            key::Named::Home => "Home",
+            key::Named::End => "End",
+            key::Named::PageUp => "PageUp",
+            key::Named::PageDown => "PageDown",
+            key::Named::ArrowUp => "Up",
+            key::Named::ArrowDown => "Down",
+            key::Named::ArrowLeft => "Left",
+            key::Named::ArrowRight => "Right",
+            _ => return None,
+        }),
+        keyboard::Key::Unidentified => None,
+    }
+}
+
+/// Identicon строится по ключу идентичности, пока он неизвестен — по имени
+fn identicon_seed(username: &str, key: Option<String>) -> String {
+    key.unwrap_or_else(|| username.to_lowercase())
+}
+
+/// Подпись типа транзакции в ленте
+fn message_type_label(message_type: MessageType) -> &'static str {
+    tr(match message_type {
+        MessageType::Text => "tx-type-text",
+        MessageType::Voice | MessageType::VoiceBatch => "tx-type-voice",
+        MessageType::File => "tx-type-file",
+        MessageType::Direct => "tx-type-direct",
+        MessageType::Claim => "tx-type-claim",
+        MessageType::Announcement => "tx-type-announcement",
+        MessageType::Avatar => "tx-type-avatar",
+        MessageType::AvatarRequest => "tx-type-avatar-request",
+        MessageType::Unknown(_) => "tx-type-unknown",
+    })
+}
+
+/// Подпись разделителя дней в ленте: «Сегодня», «Вчера» или дата
+fn day_label(date: NaiveDate) -> String {
+    let today = Local::now().date_naive();
+    if date == today {
+        tr("date-today").to_string()
+    } else if today.checked_sub_days(Days::new(1)) == Some(date) {
+        tr("date-yesterday").to_string()
+    } else {
+        format_date(&date)
+    }
+}
+
+/// QR-код адреса; None, если адрес ещё не получен или не помещается в QR
+fn address_qr(address: &str) -> Option<qr_code::Data> {
+    address.contains(':').then(|| qr_code::Data::new(address).ok()).flatten()
+}
+
+fn explorer_link(kind: &str, id: &str) -> Option<Message> {
+    APP_STATE.get_network().explorer_url().map(|url| Message::OpenLink(format!("{url}/{kind}/{id}")))
+}
+
+/// Фон сообщения, в котором упомянут локальный пользователь
+fn mention_style(theme: &Theme) -> container::Style {
+    let accent = theme.palette().primary;
+    container::Style { background: Some(Color { a: 0.15, ..accent }.into()), ..container::Style::default() }
+}
+
+/// Тема по сохранённому имени; неизвестное или пустое имя — тема по умолчанию
+fn saved_theme(name: &str, palette: &CustomPalette) -> Theme {
+    if name == CUSTOM_THEME_NAME {
+        return custom_theme(palette);
+    }
+    if name == AUTO_THEME_NAME {
+        return auto_theme_option();
+    }
+    Theme::ALL.iter().find(|theme| theme.to_string() == name).cloned().unwrap_or(Theme::Oxocarbon)
+}
+
+/// «last seen 5m ago» по реестру активности собеседников
+fn last_seen_text(username: &str) -> Option<String> {
+    let last = APP_STATE.chat_state.get_last_activity(username)?;
+    Some(tr_args("last-seen", &[("ago", &format_elapsed(chrono::Utc::now().timestamp() - last))]))
+}
+
+/// Текст уведомления об ошибке
+fn error_toast_text(event: &ErrorEvent) -> String {
+    match event {
+        ErrorEvent::InsufficientFunds => tr("toast-insufficient-funds").to_string(),
+        ErrorEvent::TransactionRejected(reason) => tr_args("toast-tx-rejected", &[("reason", reason)]),
+        ErrorEvent::SendFailed(reason) => tr_args("toast-send-failed", &[("reason", reason)]),
+        ErrorEvent::ConnectFailed(reason) => tr_args("toast-connect-failed", &[("reason", reason)]),
+        ErrorEvent::DecodeFailed(tx_id) => tr_args("toast-decode-failed", &[("tx_id", &shorten_key(tx_id))]),
+    }
+}
+
+/// Пункт «Auto» в списке тем: сама тема берётся из `light_theme`/`dark_theme` по оформлению ОС
+fn auto_theme_option() -> Theme {
+    Theme::custom(AUTO_THEME_NAME.to_string(), Palette::DARK)
+}
+
+/// Тёмное ли оформление в ОС; если определить не удалось — тёмное, как тема по умолчанию
+fn os_prefers_dark() -> bool {
+    !matches!(dark_light::detect(), dark_light::Mode::Light)
+}
+
+/// Своя тема из сохранённых цветов; цвета успеха и ошибки как у тёмной темы
+fn custom_theme(palette: &CustomPalette) -> Theme {
+    let color = |[r, g, b]: [u8; 3]| Color::from_rgb8(r, g, b);
+    Theme::custom(
+        CUSTOM_THEME_NAME.to_string(),
+        Palette {
+            background: color(palette.background),
+            text: color(palette.text),
+            primary: color(palette.primary),
+            success: Palette::DARK.success,
+            danger: Palette::DARK.danger,
+        },
+    )
+}
+
+/// Встроенные темы iced и своя последней строкой
+fn theme_options(palette: &CustomPalette) -> Vec<Theme> {
+    Theme::ALL.iter().cloned().chain(std::iter::once(custom_theme(palette))).collect()
+}
+
+/// Список тем окна: «Auto» первой строкой, дальше как в `theme_options`
+fn window_theme_options(palette: &CustomPalette) -> Vec<Theme> {
+    std::iter::once(auto_theme_option()).chain(theme_options(palette)).collect()
+}
+
+/// Цвет своей темы, редактируемый в настройках
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteColor {
+    Background,
+    Text,
+    Primary,
+}
+
+impl PaletteColor {
+    const ALL: [PaletteColor; 3] = [PaletteColor::Background, PaletteColor::Text, PaletteColor::Primary];
+
+    fn get(self, palette: &CustomPalette) -> [u8; 3] {
+        match self {
+            PaletteColor::Background => palette.background,
+            PaletteColor::Text => palette.text,
+            PaletteColor::Primary => palette.primary,
+        }
+    }
+
+    fn set(self, palette: &mut CustomPalette, rgb: [u8; 3]) {
+        match self {
+            PaletteColor::Background => palette.background = rgb,
+            PaletteColor::Text => palette.text = rgb,
+            PaletteColor::Primary => palette.primary = rgb,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            PaletteColor::Background => tr("palette-background"),
+            PaletteColor::Text => tr("palette-text"),
+            PaletteColor::Primary => tr("palette-primary"),
+        }
+    }
+}
+
+/// Цвет из "#rrggbb" (решётка необязательна)
+fn parse_hex_color(input: &str) -> Option<[u8; 3]> {
+    let digits = input.trim().trim_start_matches('#');
+    if digits.len() != 6 {
+        return None;
+    }
+    let bytes = hex::decode(digits).ok()?;
+    Some([bytes[0], bytes[1], bytes[2]])
+}
+
+fn format_hex_color([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
 pub struct Gui {
     event_tx: Sender<GuiEvent>,
     chat_scroll_id: scrollable::Id,
     is_recording: bool,
     listen_self: bool,
     mute_all: bool,
+    pause_incoming: bool,
+    voice_announce: bool,
+    channel_muted: bool,
+
+    // Режим фокусировки
+    focus_duration_min: u64,
+    focus_allowlist_input: String,
+    focus_until: Option<i64>,
+    max_playback_streams: usize,
+    comfort_noise: bool,
+    input_meter: bool,
+    shortcuts: Shortcuts,
+    /// Уровень микрофона для индикатора, с плавным спадом
+    input_level: f32,
+    voice_batching: bool,
+    /// Отправки в очереди повторов
+    pending_retries: usize,
+    incoming_funds_sound: bool,
+    auto_reconnect: bool,
+    rpc_policy: RpcPolicy,
+    notification_scopes: NotificationScopes,
+    toasts: Vec<Toast>,
+    dedupe_window_daa: u64,
+    overflow_speakers: Vec<String>,
+    // Недавние собеседники текущего канала
+    seen_users: Vec<SeenUser>,
+    sent_transactions: Vec<SentTransaction>,
+    /// Результат последней выгрузки истории в CSV
+    tx_export_status: Option<Result<String, String>>,
+    session_cost: SessionCost,
+    /// Открыто ли окно настроек
+    settings_open: bool,
+    /// Открыто ли окно кошелька
+    wallet_open: bool,
+    diagnostics_open: bool,
+    /// Панели основного окна и время последнего перетаскивания границы, ещё не сохранённого
+    panes: pane_grid::State<PaneKind>,
+    panes_resized_at: Option<Instant>,
+    /// Окно спрятано в трей
+    window_hidden: bool,
+    start_minimized: bool,
+    minimize_to_tray: bool,
+    // Консоль разработчика
+    console_visible: bool,
+    console_input: String,
+    console_output: Vec<String>,
+    console_scroll_id: scrollable::Id,
+    payload_inspector: bool,
+    inspected_payloads: Vec<InspectedPayload>,
+    inspector_revision: u64,
+    tx_feed: Vec<ObservedTransaction>,
+    tx_feed_revision: u64,
+    recording_stats: RecordingStats,
+    inspector_scroll_id: scrollable::Id,
+    stream_stats: Vec<(String, StreamStats)>,
     full_address: String,
-    display_address: String,
+    /// QR-код адреса получения; пересобирается при смене адреса
+    address_qr: Option<qr_code::Data>,
     username: String,
+    /// Новое имя в окне настроек и ошибка его проверки
+    username_input: String,
+    username_error: Option<String>,
     node_address: String,
 
     // Размер комиссии (цельные сомпи)
@@ -62,15 +722,158 @@ pub struct Gui {
     input_device: String,
     output_device: String,
     selected_theme: Theme,
+    /// Темы для светлого и тёмного оформления ОС, оформление ОС и время последней проверки
+    light_theme: Theme,
+    dark_theme: Theme,
+    os_dark: bool,
+    os_theme_checked_at: Instant,
+    /// Цвета своей темы и поля их ввода (в порядке `PaletteColor::ALL`)
+    custom_palette: CustomPalette,
+    custom_color_inputs: [String; 3],
+    opus_bitrate: i32,
+    selected_locale: FormatLocale,
+    selected_clock_format: ClockFormat,
+    chat_density: ChatDensity,
+    selected_language: UiLanguage,
+    selected_denomination: Denomination,
     available_input_devices: Vec<String>,
 
     fee_size_input: String,
+    utxo_max_count: usize,
+    /// Лимиты трат (сомпи) и их ввод в выбранной единице; пустой ввод — без лимита
+    spend_limits: SpendLimits,
+    spend_limit_hour_input: String,
+    spend_limit_day_input: String,
+    /// Порог подтверждения расходов (сомпи) и его ввод; пустой ввод — не спрашивать
+    cost_threshold: Option<u64>,
+    cost_threshold_input: String,
+    /// Действие, ожидающее подтверждения расходов, и его оценка
+    pending_cost: Option<(CostAction, CostEstimate)>,
+    /// Расходы на запись уже подтверждены в этом запуске: push-to-talk больше не прерывается диалогом
+    recording_cost_confirmed: bool,
+    /// Описание лимита, из-за которого отправка на паузе
+    spend_pause: Option<String>,
+    /// Траты за час и за сутки, сомпи
+    recent_spend: (u64, u64),
+    /// Порог пыли, сомпи, и его ввод в выбранной единице
+    dust_threshold: u64,
+    dust_threshold_input: String,
+    fee_mode: FeeMode,
     channel_number_input: String,
+    favorite_channels: Vec<u32>,
+    recent_channels: Vec<u32>,
+    /// Непрочитанные упоминания по каналам
+    unread_mentions: HashMap<u32, u32>,
 
     users: Vec<User>,
     chat_messages: Vec<ChatMessage>,
     last_seen_message_id: Option<Uuid>,
+    /// Изменение сообщений и лента ("#канал" или "@собеседник"), с которых прочитана chat_messages
+    chat_revision: Option<(u64, String)>,
+    /// Прокрутка и высота видимой части ленты; None — лента прижата к концу
+    chat_viewport: Option<(f32, f32)>,
+    /// Лента прокручена до конца: только тогда новые сообщения прокручивают её сами
+    chat_at_bottom: bool,
+    /// Сколько сообщений пришло, пока лента была прокручена вверх
+    unseen_messages: usize,
     chat_editor_content: text_editor::Content,
+
+    // Личные сообщения
+    identity_key: String,
+    // Кому закреплено наше имя: None — никому, Some(true) — нам
+    username_claimed_by_me: Option<bool>,
+    direct_mode: bool,
+    link_previews_enabled: bool,
+    /// Формат выгрузки переписки и результат последней выгрузки
+    chat_export_format: TranscriptFormat,
+    chat_export_status: Option<Result<String, String>>,
+    // Следующее сообщение уйдёт как объявление канала
+    announcement_mode: bool,
+    message_ttl: MessageTtl,
+    fee_bump: FeeBump,
+    address_rotation: AddressRotation,
+    message_signing_visible: bool,
+    sign_input: String,
+    /// Последняя подпись или ошибка; None — подписи ещё не было
+    signed_message: Option<Result<SignedMessage, String>>,
+    verify_address_input: String,
+    verify_message_input: String,
+    verify_signature_input: String,
+    verify_result: Option<Result<(), String>>,
+    announcement: Option<ChatMessage>,
+    telemetry_enabled: bool,
+    telemetry_endpoint: String,
+    salvage_payloads: bool,
+    mempool_listening: bool,
+    backfill: Backfill,
+    direct_peer_input: String,
+    direct_peers: Vec<String>,
+
+    // Ссылки на сообщения
+    permalink_input: String,
+    highlighted_message_id: Option<Uuid>,
+    /// Сообщение с открытым контекстным меню и его текст, доступный для выделения
+    message_menu: Option<Uuid>,
+    message_selection: text_editor::Content,
+
+    // Резервная копия мнемоники
+    mnemonic_reveal: MnemonicReveal,
+    mnemonic_status: Option<String>,
+    mnemonic_import_visible: bool,
+    // Профили: переключение перезапускает приложение
+    profiles: Vec<String>,
+    profile_input: String,
+    profile_error: Option<String>,
+    // Сеть: переход в mainnet требует подтверждения, смена сети перезапускает приложение
+    pending_network: Option<Network>,
+    network_error: Option<String>,
+    /// Путь вывода ключей до применения (применение перезапускает приложение)
+    derivation_kind: DerivationKind,
+    derivation_index_input: String,
+    derivation_error: Option<String>,
+    /// Источник пополнения: выбранный вид, URL или мнемоника до сохранения, ошибка сохранения
+    faucet_kind: FaucetKind,
+    faucet_input: String,
+    faucet_status: Option<Result<String, String>>,
+    /// Список нод по приоритету и поле добавления новой
+    node_urls: Vec<String>,
+    node_url_input: String,
+    node_list_error: Option<String>,
+    mnemonic_import_input: String,
+    mnemonic_import_error: Option<String>,
+
+    // Аватары: картинки пересоздаются, только когда меняется кэш в APP_STATE
+    avatar_path_input: String,
+    avatar_revision: Option<u64>,
+    avatar_images: HashMap<String, image::Handle>,
+    /// Identicon отправителей без аватара по ключу идентичности или имени
+    identicons: HashMap<String, image::Handle>,
+
+    // Адресная книга: в поле цели — адрес или hex-ключ идентичности
+    contacts: Vec<Contact>,
+    blocklist: Vec<BlockedSender>,
+    /// Собеседники с заглушённым голосом и те, кто сейчас говорит
+    muted_users: Vec<String>,
+    ignored_users: Vec<String>,
+    /// Фильтр входящих сообщений, поле нового шаблона и ошибка последнего сохранения
+    content_filter: ContentFilter,
+    filter_pattern_input: String,
+    content_filter_error: Option<String>,
+    /// Свои описания каналов и редактор описания текущего канала (открыт, если Some)
+    channel_info: BTreeMap<u32, ChannelInfo>,
+    channel_info_editor: Option<ChannelInfo>,
+    /// Закреплённые сообщения текущего канала
+    pinned_messages: Vec<PinnedMessage>,
+    speaking_users: Vec<String>,
+    contact_name_input: String,
+    contact_target_input: String,
+    contact_note_input: String,
+    contact_error: Option<String>,
+
+    // Перевод средств
+    send_address_input: String,
+    send_amount_input: String,
+    send_status: Option<String>,
 }
 
 impl Gui {
@@ -89,7 +892,17 @@ impl Gui {
         let users = vec![];
 
         let default_fee = app_state.get_fee_size().unwrap_or(0);
+        let default_denomination = app_state.get_denomination().unwrap_or_default();
         let default_channel: u32 = app_state.get_channel_number().unwrap_or(0);
+        let faucet = app_state.get_faucet_source().unwrap_or_default();
+        let derivation = app_state.get_account_derivation().unwrap_or_default();
+        let spend_limits = app_state.get_spend_limits().unwrap_or_default();
+        let cost_threshold = app_state.get_cost_confirm_threshold().unwrap_or_default();
+        let default_dust_threshold = app_state.get_utxo_dust_threshold().unwrap_or(DEFAULT_UTXO_DUST_THRESHOLD);
+
+        let custom_palette = app_state.get_custom_palette().unwrap_or_default();
+        let (light_theme, dark_theme) =
+            app_state.get_auto_theme_names().unwrap_or_else(|_| (DEFAULT_LIGHT_THEME.to_string(), DEFAULT_DARK_THEME.to_string()));
 
         let full_address = match APP_STATE.get_account_address() {
             Ok(val) => val.unwrap_or("Empty".to_string()),
@@ -98,37 +911,205 @@ impl Gui {
                 "Error".to_string()
             }
         };
-        let display_address = shorten_address(&full_address);
 
         Self {
             event_tx,
             chat_scroll_id: scrollable::Id::unique(),
             is_recording: false,
-            listen_self: false,
-            mute_all: false,
+            listen_self: app_state.is_listen_self().unwrap_or(false),
+            mute_all: app_state.is_mute_all().unwrap_or(false),
+            pause_incoming: false,
+            voice_announce: app_state.is_voice_announce_enabled(default_channel).unwrap_or(false),
+            channel_muted: app_state.is_channel_muted(default_channel).unwrap_or(false),
+            focus_duration_min: DEFAULT_FOCUS_DURATION_MIN,
+            focus_allowlist_input: default_channel.to_string(),
+            focus_until: None,
+            max_playback_streams: app_state.get_max_playback_streams().unwrap_or(DEFAULT_MAX_PLAYBACK_STREAMS),
+            comfort_noise: app_state.is_comfort_noise_enabled().unwrap_or(false),
+            input_meter: app_state.is_input_meter_enabled().unwrap_or(true),
+            shortcuts: app_state.get_shortcuts().unwrap_or_default(),
+            input_level: 0.0,
+            voice_batching: app_state.is_voice_batching_enabled().unwrap_or(false),
+            pending_retries: 0,
+            incoming_funds_sound: app_state.is_incoming_funds_sound_enabled().unwrap_or(true),
+            auto_reconnect: app_state.is_auto_reconnect_enabled().unwrap_or(true),
+            rpc_policy: app_state.get_rpc_policy().unwrap_or_default(),
+            notification_scopes: app_state.get_notification_scopes().unwrap_or_default(),
+            toasts: Vec::new(),
+            dedupe_window_daa: app_state.get_dedupe_window_daa().unwrap_or(DEFAULT_DEDUPE_WINDOW_DAA),
+            overflow_speakers: Vec::new(),
+            seen_users: Vec::new(),
+            sent_transactions: Vec::new(),
+            tx_export_status: None,
+            session_cost: SessionCost::default(),
+            settings_open: false,
+            wallet_open: false,
+            diagnostics_open: false,
+            panes: build_panes(app_state.get_pane_ratios().unwrap_or(DEFAULT_PANE_RATIOS)),
+            panes_resized_at: None,
+            window_hidden: is_tray_available() && app_state.is_start_minimized().unwrap_or(false),
+            start_minimized: app_state.is_start_minimized().unwrap_or(false),
+            minimize_to_tray: app_state.is_minimize_to_tray().unwrap_or(false),
+            console_visible: false,
+            console_input: String::new(),
+            console_output: Vec::new(),
+            console_scroll_id: scrollable::Id::unique(),
+            payload_inspector: false,
+            inspected_payloads: Vec::new(),
+            inspector_revision: 0,
+            tx_feed: Vec::new(),
+            tx_feed_revision: 0,
+            recording_stats: RecordingStats::default(),
+            inspector_scroll_id: scrollable::Id::unique(),
+            stream_stats: Vec::new(),
+            address_qr: address_qr(&full_address),
             full_address,
-            display_address,
             username: app_state.get_username(),
+            username_input: app_state.get_username(),
+            username_error: None,
             // node_address: "ws://127.0.0.1:17310".to_string(),
             node_address: "".to_string(),
             fee_size: default_fee,
             channel_number: default_channel,
             input_device,
             output_device: "Speaker 1".to_string(),
-            selected_theme: Theme::Oxocarbon,
+            selected_theme: saved_theme(&app_state.get_theme_name().unwrap_or_default(), &custom_palette),
+            light_theme: saved_theme(&light_theme, &custom_palette),
+            dark_theme: saved_theme(&dark_theme, &custom_palette),
+            os_dark: os_prefers_dark(),
+            os_theme_checked_at: Instant::now(),
+            custom_palette,
+            custom_color_inputs: PaletteColor::ALL.map(|color| format_hex_color(color.get(&custom_palette))),
+            opus_bitrate: app_state.get_opus_bitrate().unwrap_or(OPUS_BITRATE),
+            selected_locale: app_state.get_locale().unwrap_or_default(),
+            selected_clock_format: app_state.get_clock_format().unwrap_or_default(),
+            chat_density: app_state.get_chat_density().unwrap_or_default(),
+            selected_language: app_state.get_language().unwrap_or_default(),
+            selected_denomination: default_denomination,
             available_input_devices: available_devices,
-            fee_size_input: default_fee.to_string(),
-            channel_number_input: default_channel.to_string(),
+            fee_size_input: amount_to_input(default_fee, default_denomination),
+            utxo_max_count: app_state.get_utxo_max_count().unwrap_or(DEFAULT_UTXO_MAX_COUNT),
+            spend_limits,
+            spend_limit_hour_input: spend_limits
+                .per_hour
+                .map(|limit| amount_to_input(limit, default_denomination))
+                .unwrap_or_default(),
+            spend_limit_day_input: spend_limits.per_day.map(|limit| amount_to_input(limit, default_denomination)).unwrap_or_default(),
+            cost_threshold,
+            cost_threshold_input: cost_threshold.map(|limit| amount_to_input(limit, default_denomination)).unwrap_or_default(),
+            pending_cost: None,
+            recording_cost_confirmed: false,
+            spend_pause: None,
+            recent_spend: (0, 0),
+            dust_threshold: default_dust_threshold,
+            dust_threshold_input: amount_to_input(default_dust_threshold, default_denomination),
+            fee_mode: app_state.get_fee_mode().unwrap_or_default(),
+            channel_number_input: String::new(),
+            favorite_channels: app_state.get_favorite_channels().unwrap_or_default(),
+            recent_channels: app_state.get_recent_channels().unwrap_or_default(),
+            unread_mentions: HashMap::new(),
             users,
             chat_messages: Vec::new(),
             last_seen_message_id: None,
+            chat_revision: None,
+            chat_viewport: None,
+            chat_at_bottom: true,
+            unseen_messages: 0,
             chat_editor_content: text_editor::Content::new(),
+            identity_key: app_state.get_identity_public_key(),
+            username_claimed_by_me: None,
+            direct_mode: false,
+            link_previews_enabled: app_state.is_link_previews_enabled().unwrap_or(false),
+            chat_export_format: TranscriptFormat::default(),
+            chat_export_status: None,
+            announcement_mode: false,
+            message_ttl: MessageTtl(app_state.get_message_ttl().unwrap_or(None)),
+            fee_bump: FeeBump(app_state.get_fee_bump_percent().unwrap_or(None)),
+            address_rotation: AddressRotation(app_state.get_address_rotation().unwrap_or(None)),
+            message_signing_visible: false,
+            sign_input: String::new(),
+            signed_message: None,
+            verify_address_input: String::new(),
+            verify_message_input: String::new(),
+            verify_signature_input: String::new(),
+            verify_result: None,
+            announcement: None,
+            telemetry_enabled: app_state.is_telemetry_enabled().unwrap_or(false),
+            telemetry_endpoint: app_state.get_telemetry_endpoint_input().unwrap_or_default(),
+            salvage_payloads: app_state.is_salvage_payloads_enabled().unwrap_or(false),
+            mempool_listening: app_state.is_mempool_listening_enabled().unwrap_or(false),
+            backfill: Backfill(app_state.get_backfill_minutes().unwrap_or(None)),
+            direct_peer_input: String::new(),
+            direct_peers: Vec::new(),
+            permalink_input: String::new(),
+            highlighted_message_id: None,
+            message_menu: None,
+            message_selection: text_editor::Content::new(),
+            mnemonic_reveal: MnemonicReveal::Hidden,
+            mnemonic_status: None,
+            // При первом запуске сразу предлагаем восстановить существующий кошелёк
+            mnemonic_import_visible: app_state.is_first_run(),
+            mnemonic_import_input: String::new(),
+            profiles: list_profiles(),
+            profile_input: String::new(),
+            profile_error: None,
+            pending_network: None,
+            network_error: None,
+            derivation_kind: derivation.kind(),
+            derivation_index_input: match derivation {
+                AccountDerivation::Bip44 { account_index } => account_index.to_string(),
+                AccountDerivation::Legacy => "0".to_string(),
+            },
+            derivation_error: None,
+            faucet_kind: faucet.kind(),
+            faucet_input: match faucet {
+                FaucetSource::Url(url) => url,
+                _ => String::new(),
+            },
+            faucet_status: None,
+            node_urls: app_state.get_node_urls().unwrap_or_default(),
+            node_url_input: String::new(),
+            node_list_error: None,
+            mnemonic_import_error: None,
+            avatar_path_input: String::new(),
+            avatar_revision: None,
+            avatar_images: HashMap::new(),
+            identicons: HashMap::new(),
+            contacts: app_state.get_contacts().unwrap_or_default(),
+            blocklist: app_state.get_blocklist().unwrap_or_default(),
+            muted_users: app_state.get_muted_users().unwrap_or_default(),
+            ignored_users: app_state.get_ignored_users().unwrap_or_default(),
+            content_filter: app_state.get_content_filter().unwrap_or_default(),
+            filter_pattern_input: String::new(),
+            content_filter_error: None,
+            channel_info: app_state.get_channel_info().unwrap_or_default(),
+            channel_info_editor: None,
+            pinned_messages: app_state.chat_state.get_pinned_messages(default_channel),
+            speaking_users: Vec::new(),
+            contact_name_input: String::new(),
+            contact_target_input: String::new(),
+            contact_note_input: String::new(),
+            contact_error: None,
+            send_address_input: String::new(),
+            send_amount_input: String::new(),
+            send_status: None,
         }
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::ToggleRecording(value) => {
+                if value && !self.recording_cost_confirmed {
+                    match APP_STATE.estimate_recording_cost() {
+                        Ok(estimate) if self.exceeds_cost_threshold(&estimate) => {
+                            self.pending_cost = Some((CostAction::Recording, estimate));
+                            return Task::none();
+                        }
+                        Ok(_) => {}
+                        Err(err) => log::error!("Error while estimating recording cost: {}", err),
+                    }
+                }
+                self.clear_away();
                 let tx = self.event_tx.clone();
                 if value {
                     Task::perform(
@@ -199,6 +1180,16 @@ impl Gui {
                 }
                 Task::none()
             }
+            Message::TogglePauseIncoming(value) => {
+                match APP_STATE.set_listening_paused(value) {
+                    Ok(_) => {
+                        self.pause_incoming = value;
+                        log::info!("Incoming messages are {}.", if value { "paused" } else { "resumed" });
+                    }
+                    Err(err) => log::error!("Error while pausing incoming messages: {}", err),
+                }
+                Task::none()
+            }
             Message::ToggleMuteAll(value) => {
                 match APP_STATE.set_mute_all(value) {
                     Ok(_) => {
@@ -211,329 +1202,4033 @@ impl Gui {
                 }
                 Task::none()
             }
-            Message::ThemeChanged(theme) => {
-                self.selected_theme = theme;
+            Message::ToggleVoiceAnnounce(value) => {
+                match APP_STATE.set_voice_announce_enabled(self.channel_number, value) {
+                    Ok(_) => {
+                        self.voice_announce = value;
+                        log::info!(
+                            "Voice announcement for channel {} is {}active.",
+                            self.channel_number,
+                            if value { "" } else { "not " }
+                        );
+                    }
+                    Err(err) => {
+                        log::error!("Error enabling / disabling voice announcement: {}", err)
+                    }
+                }
                 Task::none()
             }
-
-            // --- Многострочный ввод text_editor, постобработка ---
-            Message::ChatEditorAction(action) => {
-                let old_text = self.chat_editor_content.text().to_string();
-                self.chat_editor_content.perform(action);
-
-                let new_text = self.chat_editor_content.text();
-                let new_char_count = new_text.chars().count();
-                if new_char_count > MAX_TEXT_CHARS {
-                    self.chat_editor_content = text_editor::Content::with_text(&old_text);
+            Message::MaxPlaybackStreamsChanged(value) => {
+                match APP_STATE.set_max_playback_streams(value) {
+                    Ok(_) => {
+                        self.max_playback_streams = value;
+                        log::info!("Max concurrent playback streams set to {}", value);
+                    }
+                    Err(err) => {
+                        log::error!("Error while changing max playback streams: {}", err)
+                    }
                 }
                 Task::none()
             }
-            Message::ChatSendPressed => {
-                let text = self.chat_editor_content.text().trim().to_string();
-                if !text.is_empty() {
-                    let tx = self.event_tx.clone();
-                    Task::perform(
-                        async move {
-                            let send_res = tx.send(GuiEvent::MessageSent(text)).await.map_err(|e| e.to_string());
-                            if let Err(err) = send_res {
-                                return Err(err);
-                            }
-                            Ok(())
-                        },
-                        Message::SendMessageDone,
-                    )
-                } else {
-                    Task::none()
+            Message::ShortcutChanged(action, shortcut) => {
+                match APP_STATE.set_shortcut(action, shortcut.clone()) {
+                    Ok(_) => {
+                        self.shortcuts.set(action, shortcut);
+                        if action == ShortcutAction::PushToTalk {
+                            ensure_push_to_talk_listener();
+                        }
+                    }
+                    Err(err) => log::error!("Error while changing shortcut: {}", err),
                 }
+                Task::none()
             }
-            Message::SendMessageDone(result) => match result {
-                Ok(_) => {
-                    self.chat_editor_content = text_editor::Content::new();
-                    Task::none()
+            Message::KeyPressed(key, modifiers) => {
+                let action = key_name(&key)
+                    .and_then(|name| self.shortcuts.window_action_for(name, modifiers.control(), modifiers.shift(), modifiers.alt()));
+                match action {
+                    Some(ShortcutAction::ToggleMute) => self.update(Message::ToggleMuteAll(!self.mute_all)),
+                    Some(ShortcutAction::NextChannel) => {
+                        self.switch_channel((self.channel_number + 1).min(MAX_CHANNEL_NUMBER));
+                        Task::none()
+                    }
+                    Some(ShortcutAction::PreviousChannel) => {
+                        self.switch_channel(self.channel_number.saturating_sub(1));
+                        Task::none()
+                    }
+                    _ => Task::none(),
                 }
-                Err(err) => {
-                    log::error!("Error while sending message: {}", err);
-                    Task::none()
+            }
+            Message::ToggleInputMeter(value) => {
+                match APP_STATE.set_input_meter_enabled(value) {
+                    Ok(_) => self.input_meter = value,
+                    Err(err) => log::error!("Error while changing input meter: {}", err),
                 }
-            },
-            Message::Tick => {
-                let channel_number = APP_STATE.get_channel_number().unwrap_or(0);
-                let new_messages =
-                    APP_STATE.chat_state.messages_by_channel.get(&channel_number).map(|messages| messages.clone()).unwrap_or_default();
-                let last_new_id = new_messages.last().map(|msg| msg.get_id());
-                let need_scroll = match (last_new_id, self.last_seen_message_id) {
-                    (Some(new_id), Some(old_id)) => new_id != old_id,
-                    (Some(_), None) => true,
-                    _ => false,
-                };
-                self.chat_messages = new_messages;
-                if need_scroll {
-                    self.last_seen_message_id = last_new_id;
-                    return scrollable::snap_to(self.chat_scroll_id.clone(), scrollable::RelativeOffset::END);
+                Task::none()
+            }
+            Message::ToggleComfortNoise(value) => {
+                match APP_STATE.set_comfort_noise_enabled(value) {
+                    Ok(_) => self.comfort_noise = value,
+                    Err(err) => log::error!("Error while changing comfort noise: {}", err),
                 }
                 Task::none()
             }
-            Message::FeeInputChanged(value) => {
-                let filtered: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
-                if filtered.is_empty() {
-                    self.fee_size = 0;
-                    APP_STATE.set_fee_size(self.fee_size).unwrap();
-                    self.fee_size_input.clear();
-                } else if let Ok(mut parsed) = filtered.parse::<u64>() {
-                    let max_fee = UNIT as u64 * 10; // max 10 KAS
-                    if parsed > max_fee {
-                        parsed = max_fee;
+            Message::ToggleVoiceBatching(value) => {
+                match APP_STATE.set_voice_batching_enabled(value) {
+                    Ok(_) => self.voice_batching = value,
+                    Err(err) => log::error!("Error while changing voice batching: {}", err),
+                }
+                Task::none()
+            }
+            Message::ToggleIncomingFundsSound(value) => {
+                match APP_STATE.set_incoming_funds_sound_enabled(value) {
+                    Ok(_) => self.incoming_funds_sound = value,
+                    Err(err) => log::error!("Error while changing incoming funds sound: {}", err),
+                }
+                Task::none()
+            }
+            Message::ToggleAutoReconnect(value) => {
+                match APP_STATE.set_auto_reconnect_enabled(value) {
+                    Ok(_) => self.auto_reconnect = value,
+                    Err(err) => log::error!("Error while changing auto reconnect: {}", err),
+                }
+                Task::none()
+            }
+            Message::RpcConnectTimeoutChanged(secs) => {
+                self.save_rpc_policy(RpcPolicy { connect_timeout_secs: secs, ..self.rpc_policy });
+                Task::none()
+            }
+            Message::RpcRequestTimeoutChanged(secs) => {
+                self.save_rpc_policy(RpcPolicy { request_timeout_secs: secs, ..self.rpc_policy });
+                Task::none()
+            }
+            Message::RpcRetriesChanged(retries) => {
+                self.save_rpc_policy(RpcPolicy { request_retries: retries, ..self.rpc_policy });
+                Task::none()
+            }
+            Message::ToggleBlockAddedScope(value) => {
+                self.save_notification_scopes(NotificationScopes { block_added: value, ..self.notification_scopes });
+                Task::none()
+            }
+            Message::ToggleVirtualChainScope(value) => {
+                self.save_notification_scopes(NotificationScopes { virtual_chain_changed: value, ..self.notification_scopes });
+                Task::none()
+            }
+            Message::DismissToast(id) => {
+                APP_STATE.dismiss_toast(id);
+                self.toasts = APP_STATE.get_toasts();
+                Task::none()
+            }
+            Message::ToggleConsole => {
+                self.console_visible = !self.console_visible;
+                Task::none()
+            }
+            Message::TogglePayloadInspector(value) => {
+                APP_STATE.set_payload_inspector_enabled(value);
+                self.payload_inspector = value;
+                Task::none()
+            }
+            Message::ConsoleInputChanged(value) => {
+                self.console_input = value;
+                Task::none()
+            }
+            Message::ConsoleSubmit => {
+                let command = std::mem::take(&mut self.console_input).trim().to_string();
+                if command.is_empty() {
+                    return Task::none();
+                }
+                let tx = self.event_tx.clone();
+                Task::perform(
+                    async move { tx.send(GuiEvent::ConsoleCommand(command)).await.map_err(|e| e.to_string()) },
+                    Message::ConsoleCommandDone,
+                )
+            }
+            Message::ConsoleCommandDone(result) => {
+                if let Err(err) = result {
+                    log::error!("Error while sending console command: {}", err);
+                }
+                Task::none()
+            }
+            Message::DedupeWindowChanged(value) => {
+                match APP_STATE.set_dedupe_window_daa(value) {
+                    Ok(_) => {
+                        self.dedupe_window_daa = value;
+                        log::info!("Transaction dedupe window set to {} DAA score units", value);
                     }
-                    self.fee_size = parsed;
-                    APP_STATE.set_fee_size(self.fee_size).unwrap();
-                    self.fee_size_input = parsed.to_string();
-                } else {
-                    // parse error => игнор
+                    Err(err) => log::error!("Error while changing dedupe window: {}", err),
                 }
                 Task::none()
             }
-
-            Message::ChannelInputChanged(value) => {
-                // только цифры
-                let filtered: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
-                // не более 7 символов
-                let limited = if filtered.len() > 7 { &filtered[0..7] } else { &filtered };
-                if limited.is_empty() {
-                    self.channel_number = 0;
-                    self.channel_number_input.clear();
-                } else if let Ok(parsed) = limited.parse::<u32>() {
-                    self.channel_number = parsed;
-                    self.channel_number_input = parsed.to_string();
+            Message::ThemeChanged(theme) => {
+                if let Err(err) = APP_STATE.set_theme_name(&theme.to_string()) {
+                    log::error!("Error while saving theme: {}", err);
+                }
+                self.selected_theme = theme;
+                self.os_dark = os_prefers_dark();
+                self.os_theme_checked_at = Instant::now();
+                Task::none()
+            }
+            Message::AutoThemeChanged(dark, theme) => {
+                if let Err(err) = APP_STATE.set_auto_theme_name(dark, &theme.to_string()) {
+                    log::error!("Error while saving theme: {}", err);
                 }
-                if let Err(err) = APP_STATE.set_channel_number(self.channel_number) {
-                    log::error!("Error while changing channel: {}", &err)
+                if dark {
+                    self.dark_theme = theme;
+                } else {
+                    self.light_theme = theme;
                 }
-
                 Task::none()
             }
-            Message::OpenLink(url) => {
-                if let Err(e) = webbrowser::open(&url) {
-                    log::error!("Could not open link {}: {}", url, e);
+            Message::CustomColorChanged(color, input) => {
+                let parsed = parse_hex_color(&input);
+                self.custom_color_inputs[color as usize] = input;
+                if let Some(rgb) = parsed {
+                    let mut palette = self.custom_palette;
+                    color.set(&mut palette, rgb);
+                    match APP_STATE.set_custom_palette(palette) {
+                        Ok(_) => {
+                            self.custom_palette = palette;
+                            for theme in [&mut self.selected_theme, &mut self.light_theme, &mut self.dark_theme] {
+                                if theme.to_string() == CUSTOM_THEME_NAME {
+                                    *theme = custom_theme(&palette);
+                                }
+                            }
+                        }
+                        Err(err) => log::error!("Error while saving custom theme: {}", err),
+                    }
                 }
                 Task::none()
             }
-        }
-    }
-
-    pub fn view(&self) -> Column<Message> {
-        column![self.build_top_bar(), Rule::horizontal(1), self.build_main_layout(), Rule::horizontal(1), self.build_footer(),]
-            .spacing(0)
+            Message::OpusBitrateChanged(OpusBitrate(bitrate)) => {
+                match APP_STATE.set_opus_bitrate(bitrate) {
+                    Ok(_) => {
+                        self.opus_bitrate = bitrate;
+                        log::info!("Voice bitrate set to {} bits/s", bitrate);
+                    }
+                    Err(err) => log::error!("Error while changing voice bitrate: {}", err),
+                }
+                Task::none()
+            }
+            Message::ToggleSettings(open) => {
+                self.settings_open = open;
+                Task::none()
+            }
+            Message::ToggleWallet(open) => {
+                self.wallet_open = open;
+                Task::none()
+            }
+            Message::RequestAirdropPressed => {
+                let tx = self.event_tx.clone();
+                Task::perform(
+                    async move { tx.send(GuiEvent::RequestAirdrop).await.map_err(|e| e.to_string()) },
+                    Message::AirdropRequested,
+                )
+            }
+            Message::AirdropRequested(result) => {
+                self.faucet_status = Some(match result {
+                    Ok(_) => Ok(tr("airdrop-requested").to_string()),
+                    Err(err) => {
+                        log::error!("Error while requesting airdrop: {}", err);
+                        Err(err)
+                    }
+                });
+                Task::none()
+            }
+            Message::ToggleDiagnostics => {
+                self.diagnostics_open = !self.diagnostics_open;
+                Task::none()
+            }
+            Message::ToggleStartMinimized(value) => {
+                match APP_STATE.set_start_minimized(value) {
+                    Ok(_) => self.start_minimized = value,
+                    Err(err) => log::error!("Error while changing start minimized: {}", err),
+                }
+                Task::none()
+            }
+            Message::ToggleMinimizeToTray(value) => {
+                match APP_STATE.set_minimize_to_tray(value) {
+                    Ok(_) => self.minimize_to_tray = value,
+                    Err(err) => log::error!("Error while changing minimize to tray: {}", err),
+                }
+                Task::none()
+            }
+            Message::WindowCloseRequested(id) => {
+                if self.minimize_to_tray {
+                    self.window_hidden = true;
+                    window::change_mode(id, window::Mode::Hidden)
+                } else {
+                    iced::exit()
+                }
+            }
+            Message::LocaleChanged(locale) => {
+                match APP_STATE.set_locale(locale) {
+                    Ok(_) => self.selected_locale = locale,
+                    Err(err) => log::error!("Error while changing locale: {}", err),
+                }
+                Task::none()
+            }
+            Message::ChatDensityChanged(density) => {
+                match APP_STATE.set_chat_density(density) {
+                    Ok(_) => self.chat_density = density,
+                    Err(err) => log::error!("Error while changing chat density: {}", err),
+                }
+                Task::none()
+            }
+            Message::ClockFormatChanged(clock_format) => {
+                match APP_STATE.set_clock_format(clock_format) {
+                    Ok(_) => self.selected_clock_format = clock_format,
+                    Err(err) => log::error!("Error while changing clock format: {}", err),
+                }
+                Task::none()
+            }
+            Message::LanguageChanged(language) => {
+                match APP_STATE.set_language(language) {
+                    Ok(_) => self.selected_language = language,
+                    Err(err) => log::error!("Error while changing language: {}", err),
+                }
+                Task::none()
+            }
+            Message::DenominationChanged(denomination) => {
+                match APP_STATE.set_denomination(denomination) {
+                    Ok(_) => {
+                        self.selected_denomination = denomination;
+                        self.fee_size_input = amount_to_input(self.fee_size, denomination);
+                        self.dust_threshold_input = amount_to_input(self.dust_threshold, denomination);
+                        self.spend_limit_hour_input =
+                            self.spend_limits.per_hour.map(|limit| amount_to_input(limit, denomination)).unwrap_or_default();
+                        self.spend_limit_day_input =
+                            self.spend_limits.per_day.map(|limit| amount_to_input(limit, denomination)).unwrap_or_default();
+                        self.cost_threshold_input =
+                            self.cost_threshold.map(|limit| amount_to_input(limit, denomination)).unwrap_or_default();
+                    }
+                    Err(err) => log::error!("Error while changing denomination: {}", err),
+                }
+                Task::none()
+            }
+
+            // --- Многострочный ввод text_editor, постобработка ---
+            Message::ChatEditorAction(action) => {
+                let old_text = self.chat_editor_content.text().to_string();
+                self.chat_editor_content.perform(action);
+
+                let new_text = self.chat_editor_content.text();
+                let new_char_count = new_text.chars().count();
+                if new_char_count > MAX_TEXT_CHARS {
+                    self.chat_editor_content = text_editor::Content::with_text(&old_text);
+                }
+                Task::none()
+            }
+            Message::ChatSendPressed => {
+                self.clear_away();
+                let text = self.chat_editor_content.text().trim().to_string();
+                if self.direct_mode && parse_public_key(&self.direct_peer_input).is_err() {
+                    log::error!("Cannot send direct message: invalid recipient key '{}'", self.direct_peer_input);
+                    return Task::none();
+                }
+                if !text.is_empty() {
+                    // Своё сообщение показываем сразу, даже если лента была прокручена вверх
+                    self.chat_at_bottom = true;
+                    self.unseen_messages = 0;
+                    let tx = self.event_tx.clone();
+                    let event = if self.direct_mode {
+                        GuiEvent::DirectMessageSent(self.direct_peer_input.trim().to_string(), text)
+                    } else if self.announcement_mode {
+                        GuiEvent::AnnouncementSent(text)
+                    } else {
+                        GuiEvent::MessageSent(text)
+                    };
+                    Task::perform(
+                        async move {
+                            let send_res = tx.send(event).await.map_err(|e| e.to_string());
+                            if let Err(err) = send_res {
+                                return Err(err);
+                            }
+                            Ok(())
+                        },
+                        Message::SendMessageDone,
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            Message::SendMessageDone(result) => match result {
+                Ok(_) => {
+                    self.chat_editor_content = text_editor::Content::new();
+                    self.announcement_mode = false;
+                    Task::none()
+                }
+                Err(err) => {
+                    log::error!("Error while sending message: {}", err);
+                    Task::none()
+                }
+            },
+            Message::Tick => {
+                if let Some(action) = poll_tray_action() {
+                    return self.handle_tray_action(action);
+                }
+                if self.selected_theme.to_string() == AUTO_THEME_NAME
+                    && self.os_theme_checked_at.elapsed() >= Duration::from_secs(OS_THEME_POLL_SECS)
+                {
+                    self.os_theme_checked_at = Instant::now();
+                    self.os_dark = os_prefers_dark();
+                }
+                // Глобальная клавиша push-to-talk: запись идёт тем же путём, что и по кнопке
+                if let Some(pressed) = APP_STATE.take_push_to_talk() {
+                    if pressed != self.is_recording {
+                        return self.update(Message::ToggleRecording(pressed));
+                    }
+                }
+                // Доли сохраняются, когда перетаскивание закончилось, а не на каждое движение мыши
+                if self.panes_resized_at.is_some_and(|at| at.elapsed() >= Duration::from_millis(PANE_RATIOS_SAVE_DELAY_MS)) {
+                    self.panes_resized_at = None;
+                    if let Err(err) = APP_STATE.set_pane_ratios(pane_ratios(&self.panes)) {
+                        log::error!("Error while saving pane sizes: {}", err);
+                    }
+                }
+                if let Some(link) = APP_STATE.chat_state.take_pending_permalink() {
+                    return self.open_permalink(&link);
+                }
+                let channel_number = APP_STATE.get_channel_number().unwrap_or(0);
+                self.toasts = APP_STATE.get_toasts();
+                if let Some(signed) = APP_STATE.take_signed_message() {
+                    self.signed_message = Some(signed);
+                }
+                if let Some(address) = APP_STATE.get_account_address().ok().flatten().filter(|address| *address != self.full_address) {
+                    self.address_qr = address_qr(&address);
+                    self.full_address = address;
+                }
+                self.direct_peers = APP_STATE.chat_state.get_direct_peers();
+                // Ленту перечитываем, только если сообщения изменились, открыта другая лента или пора скрыть истёкшие
+                let chat_source = self.chat_source();
+                let revision = APP_STATE.chat_state.revision();
+                let mut need_scroll = false;
+                let mut last_new_id = self.last_seen_message_id;
+                let source_changed = self.chat_revision.as_ref().map(|(_, source)| source) != Some(&chat_source);
+                let stale = self.chat_revision.as_ref().map(|(seen, _)| *seen) != Some(revision);
+                if source_changed || stale || self.chat_messages.iter().any(|message| message.is_expired()) {
+                    if source_changed {
+                        self.chat_viewport = None;
+                        self.chat_at_bottom = true;
+                        self.unseen_messages = 0;
+                    }
+                    self.chat_revision = Some((revision, chat_source));
+                    let mut new_messages = if self.direct_mode {
+                        let peer = self.direct_peer_input.trim().to_lowercase();
+                        APP_STATE.chat_state.direct_messages_by_peer.get(&peer).map(|messages| messages.clone()).unwrap_or_default()
+                    } else {
+                        APP_STATE
+                            .chat_state
+                            .messages_by_channel
+                            .get(&channel_number)
+                            .map(|messages| messages.clone())
+                            .unwrap_or_default()
+                    };
+                    // Истёкшие сообщения и сообщения игнорируемых скрываем
+                    new_messages.retain(|message| !message.is_expired() && !self.is_ignored(message));
+                    last_new_id = new_messages.last().map(|msg| msg.get_id());
+                    need_scroll = match (last_new_id, self.last_seen_message_id) {
+                        (Some(new_id), Some(old_id)) => new_id != old_id,
+                        (Some(_), None) => true,
+                        _ => false,
+                    };
+                    // Пока читают историю, ленту не дёргаем: считаем новые сообщения для кнопки «к последним»
+                    if need_scroll && !self.chat_at_bottom {
+                        let old_position =
+                            self.last_seen_message_id.and_then(|old_id| new_messages.iter().position(|msg| msg.get_id() == old_id));
+                        self.unseen_messages += old_position.map_or(1, |position| new_messages.len() - position - 1);
+                        self.last_seen_message_id = last_new_id;
+                        need_scroll = false;
+                    }
+                    self.chat_messages = new_messages;
+                    let senders: Vec<(String, Option<String>)> =
+                        self.chat_messages.iter().map(|msg| (msg.get_username().to_string(), msg.sender_key.clone())).collect();
+                    for (username, key) in senders {
+                        self.cache_identicon(&username, key.as_deref());
+                    }
+                }
+                self.announcement = APP_STATE.chat_state.get_announcement(channel_number);
+                self.overflow_speakers = APP_STATE.get_overflow_speakers().unwrap_or_default();
+                self.seen_users = APP_STATE.chat_state.get_seen_users(channel_number);
+                for user in self.seen_users.clone() {
+                    self.cache_identicon(&user.username, user.key.as_deref());
+                }
+                self.speaking_users = APP_STATE.get_speaking_users().unwrap_or_default();
+                self.unread_mentions = APP_STATE.chat_state.get_unread_mentions();
+                self.sent_transactions = APP_STATE.get_sent_transactions().unwrap_or_default();
+                self.pending_retries = APP_STATE.get_pending_retries().unwrap_or(0);
+                self.session_cost = APP_STATE.get_session_cost().unwrap_or_default();
+                self.spend_pause = APP_STATE.get_spend_pause().unwrap_or_default();
+                self.recent_spend = APP_STATE.get_recent_spend().unwrap_or_default();
+                let avatar_revision = APP_STATE.get_avatar_revision();
+                if self.avatar_revision != Some(avatar_revision) {
+                    self.avatar_revision = Some(avatar_revision);
+                    self.avatar_images =
+                        APP_STATE.get_avatars().into_iter().map(|(key, bytes)| (key, image::Handle::from_bytes(bytes))).collect();
+                }
+                if self.is_recording {
+                    self.recording_stats = APP_STATE.get_recording_stats();
+                }
+                if self.input_meter {
+                    self.input_level = APP_STATE.take_input_level().max(self.input_level * INPUT_LEVEL_DECAY);
+                }
+                let tx_feed_revision = APP_STATE.get_tx_feed_revision();
+                if tx_feed_revision != self.tx_feed_revision {
+                    self.tx_feed_revision = tx_feed_revision;
+                    self.tx_feed = APP_STATE.get_tx_feed();
+                }
+                if self.console_visible {
+                    let inspector_revision = APP_STATE.get_inspector_revision();
+                    if inspector_revision != self.inspector_revision {
+                        self.inspector_revision = inspector_revision;
+                        self.inspected_payloads = APP_STATE.get_inspected_payloads();
+                    }
+                    let console_output = APP_STATE.get_console_output();
+                    if console_output != self.console_output {
+                        self.console_output = console_output;
+                        return scrollable::snap_to(self.console_scroll_id.clone(), scrollable::RelativeOffset::END);
+                    }
+                }
+                self.stream_stats = APP_STATE.get_stream_stats().unwrap_or_default();
+                if let Err(err) = APP_STATE.expire_focus_mode() {
+                    log::error!("Error while ending focus mode: {}", err);
+                }
+                self.focus_until = APP_STATE.get_focus_mode().unwrap_or_default().map(|focus| focus.until);
+                self.channel_muted = APP_STATE.is_channel_muted(channel_number).unwrap_or(false);
+                // После сна запись остановлена в обход GUI
+                if self.is_recording && APP_STATE.is_away().unwrap_or(false) {
+                    self.is_recording = false;
+                }
+                self.username_claimed_by_me =
+                    APP_STATE.get_claim_owner(&self.username).unwrap_or_default().map(|owner| owner == self.identity_key);
+                if need_scroll {
+                    self.last_seen_message_id = last_new_id;
+                    self.chat_viewport = None;
+                    return scrollable::snap_to(self.chat_scroll_id.clone(), scrollable::RelativeOffset::END);
+                }
+                Task::none()
+            }
+            Message::FeeInputChanged(value) => {
+                // Ввод в выбранной единице; некорректный ввод игнорируем
+                if let Some(parsed) = parse_amount(&value, self.selected_denomination) {
+                    let max_fee = SOMPI_PER_KAS * 10; // max 10 KAS
+                    self.fee_size = parsed.min(max_fee);
+                    APP_STATE.set_fee_size(self.fee_size).unwrap();
+                    self.fee_size_input =
+                        if parsed > max_fee { amount_to_input(max_fee, self.selected_denomination) } else { value.trim().to_string() };
+                }
+                Task::none()
+            }
+            Message::SpendLimitHourChanged(value) => {
+                if let Some(parsed) = parse_amount(&value, self.selected_denomination) {
+                    let limits = SpendLimits { per_hour: (parsed > 0).then_some(parsed), ..self.spend_limits };
+                    if self.save_spend_limits(limits) {
+                        self.spend_limit_hour_input = value.trim().to_string();
+                    }
+                }
+                Task::none()
+            }
+            Message::SpendLimitDayChanged(value) => {
+                if let Some(parsed) = parse_amount(&value, self.selected_denomination) {
+                    let limits = SpendLimits { per_day: (parsed > 0).then_some(parsed), ..self.spend_limits };
+                    if self.save_spend_limits(limits) {
+                        self.spend_limit_day_input = value.trim().to_string();
+                    }
+                }
+                Task::none()
+            }
+            Message::UtxoMaxCountChanged(count) => {
+                match APP_STATE.set_utxo_max_count(count) {
+                    Ok(_) => self.utxo_max_count = count,
+                    Err(err) => log::error!("Error while changing UTXO limit: {}", err),
+                }
+                Task::none()
+            }
+            Message::DustThresholdInputChanged(value) => {
+                // Как и комиссия: ввод в выбранной единице, некорректный игнорируем
+                if let Some(parsed) = parse_amount(&value, self.selected_denomination) {
+                    let threshold = parsed.min(MAX_UTXO_DUST_THRESHOLD);
+                    match APP_STATE.set_utxo_dust_threshold(threshold) {
+                        Ok(_) => {
+                            self.dust_threshold = threshold;
+                            self.dust_threshold_input = if parsed > MAX_UTXO_DUST_THRESHOLD {
+                                amount_to_input(MAX_UTXO_DUST_THRESHOLD, self.selected_denomination)
+                            } else {
+                                value.trim().to_string()
+                            };
+                        }
+                        Err(err) => log::error!("Error while changing dust threshold: {}", err),
+                    }
+                }
+                Task::none()
+            }
+            Message::FeeModeChanged(fee_mode) => {
+                match APP_STATE.set_fee_mode(fee_mode) {
+                    Ok(_) => self.fee_mode = fee_mode,
+                    Err(err) => log::error!("Error while changing fee mode: {}", err),
+                }
+                Task::none()
+            }
+
+            Message::ChannelInputChanged(value) => {
+                // только цифры
+                let filtered: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+                // не более 7 символов
+                let limited = if filtered.len() > 7 { &filtered[0..7] } else { &filtered };
+                self.channel_number_input = limited.to_string();
+                Task::none()
+            }
+            Message::JoinChannel => {
+                if let Ok(channel) = self.channel_number_input.parse::<u32>() {
+                    self.switch_channel(channel);
+                    self.channel_number_input.clear();
+                }
+                Task::none()
+            }
+            Message::SwitchChannel(channel) => {
+                self.switch_channel(channel);
+                Task::none()
+            }
+            Message::ToggleFavoriteChannel(channel, favorite) => {
+                if let Err(err) = APP_STATE.set_channel_favorite(channel, favorite) {
+                    log::error!("Error while changing favorite channels: {}", err);
+                }
+                self.favorite_channels = APP_STATE.get_favorite_channels().unwrap_or_default();
+                Task::none()
+            }
+            Message::EditChannelInfo => {
+                self.channel_info_editor = match self.channel_info_editor {
+                    Some(_) => None,
+                    None => Some(self.channel_info.get(&self.channel_number).cloned().unwrap_or_default()),
+                };
+                Task::none()
+            }
+            Message::ChannelNameInputChanged(value) => {
+                if let Some(editor) = self.channel_info_editor.as_mut() {
+                    editor.name = value.chars().take(MAX_CHANNEL_NAME_CHARS).collect();
+                }
+                Task::none()
+            }
+            Message::ChannelTopicInputChanged(value) => {
+                if let Some(editor) = self.channel_info_editor.as_mut() {
+                    editor.topic = value.chars().take(MAX_CHANNEL_TOPIC_CHARS).collect();
+                }
+                Task::none()
+            }
+            Message::ChannelNotesInputChanged(value) => {
+                if let Some(editor) = self.channel_info_editor.as_mut() {
+                    editor.notes = value.chars().take(MAX_CHANNEL_NOTES_CHARS).collect();
+                }
+                Task::none()
+            }
+            Message::SaveChannelInfo => {
+                if let Some(editor) = self.channel_info_editor.take() {
+                    let info = ChannelInfo {
+                        name: editor.name.trim().to_string(),
+                        topic: editor.topic.trim().to_string(),
+                        notes: editor.notes.trim().to_string(),
+                    };
+                    if let Err(err) = APP_STATE.set_channel_info(self.channel_number, info) {
+                        log::error!("Error while saving channel info: {}", err);
+                    }
+                    self.channel_info = APP_STATE.get_channel_info().unwrap_or_default();
+                }
+                Task::none()
+            }
+            Message::ToggleChannelMuted(value) => {
+                match APP_STATE.set_channel_muted(self.channel_number, value) {
+                    Ok(_) => self.channel_muted = APP_STATE.is_channel_muted(self.channel_number).unwrap_or(value),
+                    Err(err) => log::error!("Error while changing channel notifications: {}", err),
+                }
+                Task::none()
+            }
+            Message::FocusDurationChanged(minutes) => {
+                self.focus_duration_min = minutes;
+                Task::none()
+            }
+            Message::FocusAllowlistChanged(value) => {
+                self.focus_allowlist_input = value.chars().filter(|c| c.is_ascii_digit() || *c == ',' || *c == ' ').collect();
+                Task::none()
+            }
+            Message::StartFocusMode => {
+                let allowlist: Vec<u32> =
+                    self.focus_allowlist_input.split(',').filter_map(|channel| channel.trim().parse().ok()).collect();
+                if let Err(err) = APP_STATE.start_focus_mode(allowlist, Duration::from_secs(self.focus_duration_min * 60)) {
+                    log::error!("Error while starting focus mode: {}", err);
+                }
+                self.focus_until = APP_STATE.get_focus_mode().unwrap_or_default().map(|focus| focus.until);
+                Task::none()
+            }
+            Message::EndFocusMode => {
+                if let Err(err) = APP_STATE.end_focus_mode() {
+                    log::error!("Error while ending focus mode: {}", err);
+                }
+                self.focus_until = None;
+                Task::none()
+            }
+            Message::ToggleDirectMode(value) => {
+                self.direct_mode = value;
+                self.last_seen_message_id = None;
+                Task::none()
+            }
+            Message::MessageTtlChanged(ttl) => {
+                match APP_STATE.set_message_ttl(ttl.0) {
+                    Ok(_) => self.message_ttl = ttl,
+                    Err(err) => log::error!("Error while changing message TTL: {}", err),
+                }
+                Task::none()
+            }
+            Message::ToggleMessageSigning => {
+                self.message_signing_visible = !self.message_signing_visible;
+                Task::none()
+            }
+            Message::SignInputChanged(value) => {
+                self.sign_input = value;
+                Task::none()
+            }
+            Message::SignMessagePressed => {
+                let message = self.sign_input.clone();
+                self.signed_message = None;
+                let tx = self.event_tx.clone();
+                Task::perform(
+                    async move { tx.send(GuiEvent::SignMessage(message)).await.map_err(|e| e.to_string()) },
+                    Message::SignMessageSent,
+                )
+            }
+            Message::SignMessageSent(result) => {
+                if let Err(err) = result {
+                    self.signed_message = Some(Err(err));
+                }
+                Task::none()
+            }
+            Message::CopySignature => match &self.signed_message {
+                Some(Ok(signed)) => iced::clipboard::write(signed.signature.clone()),
+                _ => Task::none(),
+            },
+            Message::VerifyAddressChanged(value) => {
+                self.verify_address_input = value;
+                self.verify_result = None;
+                Task::none()
+            }
+            Message::VerifyMessageChanged(value) => {
+                self.verify_message_input = value;
+                self.verify_result = None;
+                Task::none()
+            }
+            Message::VerifySignatureChanged(value) => {
+                self.verify_signature_input = value;
+                self.verify_result = None;
+                Task::none()
+            }
+            Message::VerifyMessagePressed => {
+                self.verify_result =
+                    Some(verify_signed_message(&self.verify_address_input, &self.verify_message_input, &self.verify_signature_input));
+                Task::none()
+            }
+            Message::NewAddressPressed => {
+                let tx = self.event_tx.clone();
+                Task::perform(
+                    async move { tx.send(GuiEvent::NewReceiveAddress).await.map_err(|e| e.to_string()) },
+                    Message::NewAddressDone,
+                )
+            }
+            Message::NewAddressDone(result) => {
+                if let Err(err) = result {
+                    log::error!("Error while requesting a new address: {}", err);
+                }
+                Task::none()
+            }
+            Message::AddressRotationChanged(rotation) => {
+                match APP_STATE.set_address_rotation(rotation.0) {
+                    Ok(_) => self.address_rotation = rotation,
+                    Err(err) => log::error!("Error while changing address rotation: {}", err),
+                }
+                Task::none()
+            }
+            Message::FeeBumpChanged(bump) => {
+                match APP_STATE.set_fee_bump_percent(bump.0) {
+                    Ok(_) => self.fee_bump = bump,
+                    Err(err) => log::error!("Error while changing fee bump: {}", err),
+                }
+                Task::none()
+            }
+            Message::ContactNameChanged(value) => {
+                self.contact_name_input = value;
+                self.contact_error = None;
+                Task::none()
+            }
+            Message::ContactTargetChanged(value) => {
+                self.contact_target_input = value;
+                self.contact_error = None;
+                Task::none()
+            }
+            Message::ContactNoteChanged(value) => {
+                self.contact_note_input = value;
+                Task::none()
+            }
+            Message::SaveContactPressed => {
+                let target = self.contact_target_input.trim().to_string();
+                // Адрес узнаём по префиксу сети, всё остальное считаем ключом идентичности
+                let (address, identity_key) = if target.contains(':') { (Some(target), None) } else { (None, Some(target)) };
+                let contact = Contact {
+                    name: self.contact_name_input.trim().to_string(),
+                    address,
+                    identity_key,
+                    note: self.contact_note_input.trim().to_string(),
+                };
+                match APP_STATE.save_contact(contact) {
+                    Ok(_) => {
+                        self.contacts = APP_STATE.get_contacts().unwrap_or_default();
+                        self.contact_name_input.clear();
+                        self.contact_target_input.clear();
+                        self.contact_note_input.clear();
+                    }
+                    Err(err) => self.contact_error = Some(err),
+                }
+                Task::none()
+            }
+            Message::RemoveContact(contact) => {
+                if let Err(err) = APP_STATE.remove_contact(&contact) {
+                    log::error!("Error while removing contact: {}", err);
+                }
+                self.contacts = APP_STATE.get_contacts().unwrap_or_default();
+                Task::none()
+            }
+            Message::AddSenderToContacts(username, target) => {
+                self.contact_name_input = username;
+                self.contact_target_input = target;
+                self.contact_error = None;
+                Task::none()
+            }
+            Message::BlockSender(username, key) => {
+                self.message_menu = None;
+                // По ключу подписи, если сообщение подписано: имя можно сменить
+                let sender = match key {
+                    Some(key) => BlockedSender::IdentityKey(key),
+                    None => BlockedSender::Username(username),
+                };
+                if let Err(err) = APP_STATE.block_sender(sender) {
+                    log::error!("Error while blocking sender: {}", err);
+                }
+                self.blocklist = APP_STATE.get_blocklist().unwrap_or_default();
+                Task::none()
+            }
+            Message::ToggleUserMuted(username, muted) => {
+                match APP_STATE.set_user_muted(&username, muted) {
+                    Ok(_) => log::info!("Voice of '{}' is {}", username, if muted { "muted" } else { "unmuted" }),
+                    Err(err) => log::error!("Error while muting user: {}", err),
+                }
+                self.muted_users = APP_STATE.get_muted_users().unwrap_or_default();
+                Task::none()
+            }
+            Message::ToggleUserIgnored(username, ignored) => {
+                self.message_menu = None;
+                match APP_STATE.set_user_ignored(&username, ignored) {
+                    Ok(_) => log::info!("Messages of '{}' are {}", username, if ignored { "hidden" } else { "shown" }),
+                    Err(err) => log::error!("Error while ignoring user: {}", err),
+                }
+                self.ignored_users = APP_STATE.get_ignored_users().unwrap_or_default();
+                // Ленту перечитываем на следующем Tick, не сбрасывая прокрутку
+                if let Some((seen, _)) = self.chat_revision.as_mut() {
+                    *seen = seen.wrapping_sub(1);
+                }
+                Task::none()
+            }
+            Message::ContentFilterActionChanged(action) => {
+                let mut filter = self.content_filter.clone();
+                filter.action = action;
+                self.save_content_filter(filter);
+                Task::none()
+            }
+            Message::ChannelFilterChanged(ChannelFilterChoice(action)) => {
+                let mut filter = self.content_filter.clone();
+                match action {
+                    Some(action) => filter.channel_overrides.insert(self.channel_number, action),
+                    None => filter.channel_overrides.remove(&self.channel_number),
+                };
+                self.save_content_filter(filter);
+                Task::none()
+            }
+            Message::FilterPatternInputChanged(value) => {
+                self.filter_pattern_input = value;
+                self.content_filter_error = None;
+                Task::none()
+            }
+            Message::AddFilterPattern => {
+                let pattern = self.filter_pattern_input.trim().to_string();
+                if !pattern.is_empty() && !self.content_filter.patterns.contains(&pattern) {
+                    let mut filter = self.content_filter.clone();
+                    filter.patterns.push(pattern);
+                    if self.save_content_filter(filter) {
+                        self.filter_pattern_input.clear();
+                    }
+                }
+                Task::none()
+            }
+            Message::RemoveFilterPattern(index) => {
+                let mut filter = self.content_filter.clone();
+                if index < filter.patterns.len() {
+                    filter.patterns.remove(index);
+                    self.save_content_filter(filter);
+                }
+                Task::none()
+            }
+            Message::UnblockSender(sender) => {
+                if let Err(err) = APP_STATE.unblock_sender(&sender) {
+                    log::error!("Error while unblocking sender: {}", err);
+                }
+                self.blocklist = APP_STATE.get_blocklist().unwrap_or_default();
+                Task::none()
+            }
+            Message::SendAddressChanged(value) => {
+                self.send_address_input = value;
+                self.send_status = None;
+                Task::none()
+            }
+            Message::SendContactSelected(contact) => {
+                self.send_address_input = contact.address.unwrap_or_default();
+                self.send_status = None;
+                Task::none()
+            }
+            Message::SendAmountChanged(value) => {
+                self.send_amount_input = value;
+                self.send_status = None;
+                Task::none()
+            }
+            Message::SendFundsPressed => {
+                let address = self.send_address_input.trim().to_string();
+                let Some(amount) = parse_amount(&self.send_amount_input, self.selected_denomination).filter(|amount| *amount > 0)
+                else {
+                    self.send_status = Some(tr("send-enter-amount").to_string());
+                    return Task::none();
+                };
+                if amount > APP_STATE.get_balance().unwrap_or(0) {
+                    self.send_status = Some(tr("send-not-enough-funds").to_string());
+                    return Task::none();
+                }
+                self.send_status = Some(tr_args("send-in-progress", &[("amount", &format_amount(amount, 8))]));
+                self.send_amount_input.clear();
+                let tx = self.event_tx.clone();
+                Task::perform(
+                    async move { tx.send(GuiEvent::SendFunds(address, amount)).await.map_err(|e| e.to_string()) },
+                    Message::SendFundsDone,
+                )
+            }
+            Message::SendFundsDone(result) => {
+                if let Err(err) = result {
+                    log::error!("Error while sending funds: {}", err);
+                    self.send_status = Some(tr("send-failed").to_string());
+                }
+                Task::none()
+            }
+            Message::ToggleAnnouncementMode(value) => {
+                self.announcement_mode = value;
+                Task::none()
+            }
+            Message::DismissAnnouncement(channel, id) => {
+                APP_STATE.chat_state.dismiss_announcement(channel, id);
+                self.announcement = None;
+                Task::none()
+            }
+            Message::ChatExportFormatChanged(format) => {
+                self.chat_export_format = format;
+                Task::none()
+            }
+            Message::ExportChat => {
+                self.chat_export_status = Some(match APP_STATE.export_chat_transcript(self.channel_number, self.chat_export_format) {
+                    Ok(path) => Ok(tr_args("saved-to", &[("path", &path)])),
+                    Err(err) => {
+                        log::error!("Error while exporting chat: {}", err);
+                        Err(tr("export-failed").to_string())
+                    }
+                });
+                Task::none()
+            }
+            Message::ToggleLinkPreviews(value) => {
+                match APP_STATE.set_link_previews_enabled(value) {
+                    Ok(_) => self.link_previews_enabled = value,
+                    Err(err) => log::error!("Error while changing link previews: {}", err),
+                }
+                Task::none()
+            }
+            Message::ToggleMempoolListening(value) => {
+                match APP_STATE.set_mempool_listening_enabled(value) {
+                    Ok(_) => self.mempool_listening = value,
+                    Err(err) => log::error!("Error while changing mempool listening: {}", err),
+                }
+                Task::none()
+            }
+            Message::BackfillChanged(backfill) => {
+                match APP_STATE.set_backfill_minutes(backfill.0) {
+                    Ok(_) => self.backfill = backfill,
+                    Err(err) => log::error!("Error while changing history backfill: {}", err),
+                }
+                Task::none()
+            }
+            Message::ToggleSalvagePayloads(value) => {
+                match APP_STATE.set_salvage_payloads_enabled(value) {
+                    Ok(_) => self.salvage_payloads = value,
+                    Err(err) => log::error!("Error while changing payload salvage mode: {}", err),
+                }
+                Task::none()
+            }
+            Message::ToggleTelemetry(value) => {
+                match APP_STATE.set_telemetry(value, &self.telemetry_endpoint) {
+                    Ok(_) => self.telemetry_enabled = value,
+                    Err(err) => log::error!("Error while changing telemetry: {}", err),
+                }
+                Task::none()
+            }
+            Message::TelemetryEndpointChanged(value) => {
+                self.telemetry_endpoint = value;
+                if let Err(err) = APP_STATE.set_telemetry(self.telemetry_enabled, &self.telemetry_endpoint) {
+                    log::error!("Error while changing telemetry endpoint: {}", err);
+                }
+                Task::none()
+            }
+            Message::DirectPeerInputChanged(value) => {
+                self.direct_peer_input = value.trim().to_string();
+                self.last_seen_message_id = None;
+                Task::none()
+            }
+            Message::CopyIdentityKey => iced::clipboard::write(self.identity_key.clone()),
+            Message::UsernameInputChanged(value) => {
+                self.username_input = value;
+                self.username_error = None;
+                Task::none()
+            }
+            Message::SaveUsername => {
+                match APP_STATE.set_username(&self.username_input) {
+                    Ok(_) => {
+                        self.username = APP_STATE.get_username();
+                        self.username_input = self.username.clone();
+                        self.username_error = None;
+                        log::info!("Username changed to '{}'", self.username);
+                    }
+                    Err(err) => self.username_error = Some(err),
+                }
+                Task::none()
+            }
+            Message::ClaimUsernamePressed => {
+                let tx = self.event_tx.clone();
+                Task::perform(
+                    async move { tx.send(GuiEvent::ClaimUsername).await.map_err(|e| e.to_string()) },
+                    Message::ClaimUsernameDone,
+                )
+            }
+            Message::ClaimUsernameDone(result) => {
+                if let Err(err) = result {
+                    log::error!("Error while claiming username: {}", err);
+                }
+                Task::none()
+            }
+            Message::RevealMnemonicPressed => {
+                self.mnemonic_reveal = MnemonicReveal::Confirming;
+                Task::none()
+            }
+            Message::ConfirmRevealMnemonic => {
+                self.mnemonic_reveal = MnemonicReveal::Shown;
+                Task::none()
+            }
+            Message::HideMnemonic => {
+                self.mnemonic_reveal = MnemonicReveal::Hidden;
+                self.mnemonic_status = None;
+                Task::none()
+            }
+            Message::CopyMnemonic => {
+                self.mnemonic_status = Some(tr("copied-to-clipboard").to_string());
+                iced::clipboard::write(APP_STATE.get_mnemonic())
+            }
+            Message::ExportMnemonic => {
+                self.mnemonic_status = Some(match APP_STATE.export_mnemonic() {
+                    Ok(path) => tr_args("saved-to", &[("path", &path)]),
+                    Err(err) => {
+                        log::error!("Error while exporting mnemonic: {}", err);
+                        tr("export-failed").to_string()
+                    }
+                });
+                Task::none()
+            }
+            Message::ExportTransactionsCsv => {
+                self.tx_export_status = Some(match APP_STATE.export_sent_transactions_csv() {
+                    Ok(path) => Ok(tr_args("saved-to", &[("path", &path)])),
+                    Err(err) => {
+                        log::error!("Error while exporting transactions: {}", err);
+                        Err(tr("export-failed").to_string())
+                    }
+                });
+                Task::none()
+            }
+            Message::ToggleMnemonicImport => {
+                self.mnemonic_import_visible = !self.mnemonic_import_visible;
+                self.mnemonic_import_input.clear();
+                self.mnemonic_import_error = None;
+                Task::none()
+            }
+            Message::ProfileInputChanged(value) => {
+                self.profile_input = value;
+                self.profile_error = None;
+                Task::none()
+            }
+            Message::SwitchProfile(profile) => {
+                let profile = profile.trim().to_string();
+                if profile == APP_STATE.get_profile() {
+                    return Task::none();
+                }
+                match APP_STATE.request_restart(&profile) {
+                    // Окно закрывается, main запускает приложение с выбранным профилем
+                    Ok(_) => iced::exit(),
+                    Err(err) => {
+                        self.profile_error = Some(err);
+                        Task::none()
+                    }
+                }
+            }
+            Message::NetworkSelected(network) => {
+                self.network_error = None;
+                if network == APP_STATE.get_network() {
+                    self.pending_network = None;
+                    return Task::none();
+                }
+                if network.is_mainnet() {
+                    // Сначала предупреждение, переключаемся только после подтверждения
+                    self.pending_network = Some(network);
+                    return Task::none();
+                }
+                self.switch_network(network)
+            }
+            Message::DerivationKindSelected(kind) => {
+                self.derivation_kind = kind;
+                self.derivation_error = None;
+                Task::none()
+            }
+            Message::DerivationIndexChanged(value) => {
+                self.derivation_index_input = value;
+                self.derivation_error = None;
+                Task::none()
+            }
+            Message::ApplyDerivation => {
+                let derivation = match self.selected_derivation() {
+                    Ok(derivation) => derivation,
+                    Err(err) => {
+                        self.derivation_error = Some(err);
+                        return Task::none();
+                    }
+                };
+                match APP_STATE.set_account_derivation(derivation) {
+                    // Окно закрывается, main перезапускает приложение с другим аккаунтом
+                    Ok(_) => iced::exit(),
+                    Err(err) => {
+                        log::error!("Error while changing account derivation: {}", err);
+                        self.derivation_error = Some(err);
+                        Task::none()
+                    }
+                }
+            }
+            Message::ConfirmNetworkSwitch => match self.pending_network.take() {
+                Some(network) => self.switch_network(network),
+                None => Task::none(),
+            },
+            Message::CancelNetworkSwitch => {
+                self.pending_network = None;
+                Task::none()
+            }
+            Message::FaucetKindSelected(kind) => {
+                self.faucet_kind = kind;
+                self.faucet_input.clear();
+                self.faucet_status = None;
+                // Источникам без параметров нечего вводить — сохраняем сразу
+                match kind {
+                    FaucetKind::BuiltIn => self.save_faucet(FaucetSource::BuiltIn),
+                    FaucetKind::Disabled => self.save_faucet(FaucetSource::Disabled),
+                    FaucetKind::Url | FaucetKind::Mnemonic => {}
+                }
+                Task::none()
+            }
+            Message::FaucetInputChanged(value) => {
+                self.faucet_input = value;
+                self.faucet_status = None;
+                Task::none()
+            }
+            Message::SaveFaucet => {
+                let value = self.faucet_input.trim().to_string();
+                match self.faucet_kind {
+                    FaucetKind::Url => self.save_faucet(FaucetSource::Url(value)),
+                    FaucetKind::Mnemonic => {
+                        self.save_faucet(FaucetSource::Mnemonic(value));
+                        // Мнемонику на экране не держим
+                        self.faucet_input.clear();
+                    }
+                    FaucetKind::BuiltIn | FaucetKind::Disabled => {}
+                }
+                Task::none()
+            }
+            Message::NodeUrlInputChanged(value) => {
+                self.node_url_input = value;
+                self.node_list_error = None;
+                Task::none()
+            }
+            Message::AddNodeUrl => {
+                let mut urls = self.node_urls.clone();
+                urls.push(self.node_url_input.trim().to_string());
+                if self.save_node_urls(urls) {
+                    self.node_url_input.clear();
+                }
+                Task::none()
+            }
+            Message::RemoveNodeUrl(index) => {
+                let mut urls = self.node_urls.clone();
+                if index < urls.len() {
+                    urls.remove(index);
+                    self.save_node_urls(urls);
+                }
+                Task::none()
+            }
+            Message::RaiseNodeUrl(index) => {
+                let mut urls = self.node_urls.clone();
+                if index > 0 && index < urls.len() {
+                    urls.swap(index - 1, index);
+                    self.save_node_urls(urls);
+                }
+                Task::none()
+            }
+            Message::MnemonicImportInputChanged(value) => {
+                self.mnemonic_import_input = value;
+                self.mnemonic_import_error = None;
+                Task::none()
+            }
+            Message::ImportMnemonicPressed => match APP_STATE.import_mnemonic(&self.mnemonic_import_input) {
+                // Окно закрывается, main перезапускает приложение с новым аккаунтом
+                Ok(_) => iced::exit(),
+                Err(err) => {
+                    log::error!("Error while importing mnemonic: {}", err);
+                    self.mnemonic_import_error = Some(err);
+                    Task::none()
+                }
+            },
+            Message::AvatarPathChanged(value) => {
+                self.avatar_path_input = value;
+                Task::none()
+            }
+            Message::SetAvatarPressed => {
+                match prepare_avatar(self.avatar_path_input.trim()).and_then(|avatar| APP_STATE.set_own_avatar(avatar)) {
+                    Ok(_) => self.avatar_path_input.clear(),
+                    Err(err) => log::error!("Error while setting avatar: {}", err),
+                }
+                Task::none()
+            }
+            Message::AnnounceAvatarPressed => match APP_STATE.estimate_avatar_cost() {
+                Ok(Some(estimate)) if self.exceeds_cost_threshold(&estimate) => {
+                    self.pending_cost = Some((CostAction::AvatarBroadcast, estimate));
+                    Task::none()
+                }
+                Ok(_) => self.announce_avatar(),
+                Err(err) => {
+                    log::error!("Error while estimating avatar cost: {}", err);
+                    self.announce_avatar()
+                }
+            },
+            Message::ConfirmCost => match self.pending_cost.take() {
+                Some((CostAction::Recording, _)) => {
+                    self.recording_cost_confirmed = true;
+                    self.update(Message::ToggleRecording(true))
+                }
+                Some((CostAction::AvatarBroadcast, _)) => self.announce_avatar(),
+                None => Task::none(),
+            },
+            Message::CancelCost => {
+                self.pending_cost = None;
+                Task::none()
+            }
+            Message::CostThresholdChanged(value) => {
+                if let Some(parsed) = parse_amount(&value, self.selected_denomination) {
+                    let threshold = (parsed > 0).then_some(parsed);
+                    match APP_STATE.set_cost_confirm_threshold(threshold) {
+                        Ok(_) => {
+                            self.cost_threshold = threshold;
+                            self.cost_threshold_input = value.trim().to_string();
+                        }
+                        Err(err) => log::error!("Error while changing cost confirmation threshold: {}", err),
+                    }
+                }
+                Task::none()
+            }
+            Message::RequestAvatarPressed(address) => {
+                let tx = self.event_tx.clone();
+                Task::perform(
+                    async move { tx.send(GuiEvent::RequestAvatar(address)).await.map_err(|e| e.to_string()) },
+                    Message::AvatarEventDone,
+                )
+            }
+            Message::AvatarEventDone(result) => {
+                if let Err(err) = result {
+                    log::error!("Error while sending avatar event: {}", err);
+                }
+                Task::none()
+            }
+            Message::CopyPermalink(tx_id) => iced::clipboard::write(build_permalink(&tx_id)),
+            Message::PaneResized(pane_grid::ResizeEvent { split, ratio }) => {
+                self.panes.resize(split, ratio.clamp(MIN_PANE_RATIO, MAX_PANE_RATIO));
+                self.panes_resized_at = Some(Instant::now());
+                Task::none()
+            }
+            Message::ChatScrolled(viewport) => {
+                self.chat_viewport = Some((viewport.absolute_offset().y, viewport.bounds().height));
+                self.chat_at_bottom =
+                    viewport.content_bounds().height <= viewport.bounds().height || viewport.relative_offset().y >= 0.999;
+                if self.chat_at_bottom {
+                    self.unseen_messages = 0;
+                }
+                Task::none()
+            }
+            Message::JumpToLatest => {
+                self.chat_at_bottom = true;
+                self.unseen_messages = 0;
+                self.chat_viewport = None;
+                scrollable::snap_to(self.chat_scroll_id.clone(), scrollable::RelativeOffset::END)
+            }
+            Message::OpenMessageMenu(message_id) => {
+                let content =
+                    self.chat_messages.iter().find(|msg| msg.get_id() == message_id).map(|msg| msg.get_content().to_string());
+                if let Some(content) = content {
+                    self.message_menu = Some(message_id);
+                    self.message_selection = text_editor::Content::with_text(&content);
+                }
+                Task::none()
+            }
+            Message::CloseMessageMenu => {
+                self.message_menu = None;
+                Task::none()
+            }
+            Message::MessageSelectionAction(action) => {
+                // Текст только выделяется и копируется, правки не принимаются
+                if !action.is_edit() {
+                    self.message_selection.perform(action);
+                }
+                Task::none()
+            }
+            Message::CopyToClipboard(value) => {
+                self.message_menu = None;
+                iced::clipboard::write(value)
+            }
+            Message::ReplyToMessage(message_id) => {
+                self.message_menu = None;
+                let Some(msg) = self.chat_messages.iter().find(|msg| msg.get_id() == message_id) else {
+                    return Task::none();
+                };
+                // Цитата первой строки; протокол ответов не знает, поэтому это обычный текст
+                let first_line = msg.get_content().lines().next().unwrap_or_default();
+                let quote = format!("> {}: {}\n{}", msg.get_username(), first_line, self.chat_editor_content.text().trim_end());
+                self.chat_editor_content = text_editor::Content::with_text(&quote);
+                self.chat_editor_content.perform(text_editor::Action::Move(text_editor::Motion::DocumentEnd));
+                Task::none()
+            }
+            Message::PinMessage(message_id) => {
+                self.message_menu = None;
+                if let Some(msg) = self.chat_messages.iter().find(|msg| msg.get_id() == message_id) {
+                    if let Err(err) = APP_STATE.chat_state.set_message_pinned(msg, true) {
+                        log::error!("Error while pinning message: {}", err);
+                    }
+                }
+                self.pinned_messages = APP_STATE.chat_state.get_pinned_messages(self.channel_number);
+                Task::none()
+            }
+            Message::UnpinMessage(pin) => {
+                self.message_menu = None;
+                if let Err(err) = APP_STATE.chat_state.unpin_message(self.channel_number, &pin) {
+                    log::error!("Error while unpinning message: {}", err);
+                }
+                self.pinned_messages = APP_STATE.chat_state.get_pinned_messages(self.channel_number);
+                Task::none()
+            }
+            Message::PermalinkInputChanged(value) => {
+                self.permalink_input = value;
+                Task::none()
+            }
+            Message::OpenPermalink(link) => {
+                self.permalink_input.clear();
+                self.open_permalink(&link)
+            }
+            Message::OpenLink(url) => {
+                if let Err(e) = webbrowser::open(&url) {
+                    log::error!("Could not open link {}: {}", url, e);
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Первое действие пользователя после сна снимает статус «Away»
+    fn clear_away(&self) {
+        if let Err(err) = APP_STATE.set_away(false) {
+            log::error!("Error while clearing away flag: {}", err);
+        }
+    }
+
+    /// Действие из трея: показать/спрятать окно, остановить запись или выйти
+    fn handle_tray_action(&mut self, action: TrayAction) -> Task<Message> {
+        match action {
+            TrayAction::ToggleWindow => {
+                self.window_hidden = !self.window_hidden;
+                if self.window_hidden {
+                    window::get_latest().and_then(|id| window::change_mode(id, window::Mode::Hidden))
+                } else {
+                    window::get_latest().and_then(|id| window::change_mode(id, window::Mode::Windowed).chain(window::gain_focus(id)))
+                }
+            }
+            TrayAction::MuteMicrophone if self.is_recording => self.update(Message::ToggleRecording(false)),
+            TrayAction::MuteMicrophone => Task::none(),
+            TrayAction::Quit => iced::exit(),
+        }
+    }
+
+    /// Открытая лента: "#канал" или "@ключ собеседника"
+    fn chat_source(&self) -> String {
+        if self.direct_mode {
+            format!("@{}", self.direct_peer_input.trim().to_lowercase())
+        } else {
+            format!("#{}", self.channel_number)
+        }
+    }
+
+    /// Переключает канал: Listener начинает пропускать его сообщения, чат обновится на следующем Tick
+    fn switch_channel(&mut self, channel: u32) {
+        self.channel_number = channel;
+        if let Err(err) = APP_STATE.set_channel_number(channel) {
+            log::error!("Error while changing channel: {}", &err)
+        }
+        self.direct_mode = false;
+        self.voice_announce = APP_STATE.is_voice_announce_enabled(channel).unwrap_or(false);
+        self.channel_muted = APP_STATE.is_channel_muted(channel).unwrap_or(false);
+        self.recent_channels = APP_STATE.get_recent_channels().unwrap_or_default();
+        APP_STATE.chat_state.clear_unread_mentions(channel);
+        self.unread_mentions.remove(&channel);
+        self.chat_export_status = None;
+        self.channel_info_editor = None;
+        self.pinned_messages = APP_STATE.chat_state.get_pinned_messages(channel);
+    }
+
+    /// «# 5» или «# 5 · своё название»
+    fn channel_label(&self, channel: u32) -> String {
+        match self.channel_info.get(&channel).filter(|info| !info.name.is_empty()) {
+            Some(info) => format!("# {} · {}", channel, info.name),
+            None => format!("# {}", channel),
+        }
+    }
+
+    /// Переходит к сообщению по ссылке kaspeak://msg/<txid>: переключает канал и подсвечивает сообщение
+    fn open_permalink(&mut self, link: &str) -> Task<Message> {
+        let Some(tx_id) = parse_permalink(link) else {
+            log::error!("Invalid message link: {}", link);
+            return Task::none();
+        };
+        // Ищем только среди полученных в этой сессии, догрузка истории из сети пока не поддерживается
+        let Some((channel, message_id)) = APP_STATE.chat_state.find_by_tx_id(&tx_id) else {
+            log::warn!("Message {} is not in the local history yet", tx_id);
+            return Task::none();
+        };
+
+        if channel != self.channel_number {
+            self.switch_channel(channel);
+        }
+        self.direct_mode = false;
+        self.highlighted_message_id = Some(message_id);
+        let mut messages = APP_STATE.chat_state.messages_by_channel.get(&channel).map(|messages| messages.clone()).unwrap_or_default();
+        messages.retain(|message| !self.is_ignored(message));
+        self.chat_messages = messages;
+        // Чтобы Tick не прокрутил чат обратно в конец
+        self.last_seen_message_id = self.chat_messages.last().map(|msg| msg.get_id());
+        self.chat_revision = Some((APP_STATE.chat_state.revision(), self.chat_source()));
+
+        let index = self.chat_messages.iter().position(|msg| msg.get_id() == message_id).unwrap_or(0);
+        self.chat_at_bottom = index + 1 >= self.chat_messages.len();
+        self.unseen_messages = 0;
+        self.chat_viewport = Some((self.chat_row_heights()[..index].iter().sum(), CHAT_DEFAULT_VIEWPORT_PX));
+        let y = if self.chat_messages.len() > 1 { index as f32 / (self.chat_messages.len() - 1) as f32 } else { 0.0 };
+        scrollable::snap_to(self.chat_scroll_id.clone(), scrollable::RelativeOffset { x: 0.0, y })
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        let mut layout = column![self.build_top_bar(), Rule::horizontal(1), self.build_main_layout(), Rule::horizontal(1)];
+        if self.console_visible {
+            layout = layout.push(self.build_console()).push(Rule::horizontal(1));
+        }
+        let layout = layout.push(self.build_footer()).spacing(0).padding(0).width(Length::Fill).height(Length::Fill);
+        if self.toasts.is_empty() && !self.settings_open && !self.wallet_open && self.pending_cost.is_none() {
+            return layout.into();
+        }
+        let mut overlay = stack![layout];
+        if self.wallet_open {
+            overlay = overlay.push(self.build_wallet_dialog());
+        }
+        if self.settings_open {
+            overlay = overlay.push(self.build_settings_dialog());
+        }
+        if let Some((action, estimate)) = self.pending_cost {
+            overlay = overlay.push(self.build_cost_confirmation(action, estimate));
+        }
+        if !self.toasts.is_empty() {
+            overlay = overlay.push(self.build_toasts());
+        }
+        overlay.into()
+    }
+
+    /// Диалог подтверждения: сколько транзакций и комиссий потребует действие
+    fn build_cost_confirmation(&self, action: CostAction, estimate: CostEstimate) -> Element<Message> {
+        let title = match action {
+            CostAction::Recording => tr_args("cost-confirm-recording", &[("minutes", &(COST_ESTIMATE_RECORDING_SECS / 60))]),
+            CostAction::AvatarBroadcast => tr("cost-confirm-avatar").to_string(),
+        };
+        let threshold = self.cost_threshold.map(|threshold| format_amount(threshold, 8)).unwrap_or_default();
+        let dialog = container(
+            column![
+                text(title).size(16),
+                text(tr_args(
+                    "cost-confirm-estimate",
+                    &[("count", &format_integer(estimate.transactions)), ("amount", &format_amount(estimate.fee, 8))],
+                ))
+                .size(14),
+                text(tr_args("cost-confirm-over", &[("amount", &threshold)])).size(12).style(text::secondary),
+                row![
+                    button(text(tr("cost-confirm-continue")).size(14)).on_press(Message::ConfirmCost).padding(6),
+                    button(text(tr("cancel")).size(14)).on_press(Message::CancelCost).style(button::secondary).padding(6),
+                ]
+                .spacing(8),
+            ]
+            .spacing(10),
+        )
+        .padding(16)
+        .width(Length::Fixed(420.0))
+        .style(container::rounded_box);
+
+        opaque(
+            mouse_area(center(opaque(dialog)).style(|_theme| container::Style {
+                background: Some(Color { a: 0.6, ..Color::BLACK }.into()),
+                ..container::Style::default()
+            }))
+            .on_press(Message::CancelCost),
+        )
+    }
+
+    /// Всплывающие уведомления в правом нижнем углу, над футером
+    fn build_toasts(&self) -> Element<Message> {
+        let toasts = self.toasts.iter().fold(column![].spacing(6), |toasts, toast| {
+            let content = match &toast.kind {
+                ToastKind::IncomingFunds(amount) => tr_args("toast-incoming-funds", &[("amount", &format_amount(*amount, 8))]),
+                ToastKind::SpendLimit(reason) => format!("⛔ {}", reason),
+                ToastKind::NodeFailover(url) => tr_args("toast-node-failover", &[("url", url)]),
+                ToastKind::RpcTimeout(what, secs) => tr_args("toast-rpc-timeout", &[("request", what), ("secs", secs)]),
+                ToastKind::NewerProtocol(version) => tr_args("toast-newer-protocol", &[("version", version)]),
+                ToastKind::Error(event) => error_toast_text(event),
+            };
+            let style = if matches!(toast.kind, ToastKind::Error(_)) { text::danger } else { text::default };
+            toasts.push(
+                container(
+                    row![
+                        text(content).size(14).style(style),
+                        button(text("✕").size(12)).on_press(Message::DismissToast(toast.id)).style(button::text).padding(0),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+                )
+                .padding(10)
+                .style(container::rounded_box),
+            )
+        });
+        container(toasts)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(Alignment::End)
+            .align_y(Alignment::End)
+            .padding(iced::Padding { top: 0.0, right: 16.0, bottom: 48.0, left: 0.0 })
+            .into()
+    }
+
+    /// Консоль разработчика (Ctrl+Shift+D)
+    fn build_console(&self) -> Column<Message> {
+        let output = self
+            .console_output
+            .iter()
+            .fold(column![].spacing(1), |output, line| output.push(text(line.clone()).size(13).font(font::Font::MONOSPACE)));
+        let input = text_input(tr("console-placeholder"), &self.console_input)
+            .on_input(Message::ConsoleInputChanged)
+            .on_submit(Message::ConsoleSubmit)
+            .font(font::Font::MONOSPACE)
+            .size(13);
+        let inspector_toggle =
+            toggler(self.payload_inspector).label(tr("console-payload-inspector")).on_toggle(Message::TogglePayloadInspector).size(16);
+        let console = column![
+            scrollable(output.padding(6)).id(self.console_scroll_id.clone()).width(Length::Fill).height(Length::Fixed(160.0)),
+            row![input, inspector_toggle].spacing(12).align_y(Alignment::Center).padding(6)
+        ];
+        if !self.payload_inspector {
+            return console;
+        }
+        console.push(self.build_payload_inspector())
+    }
+
+    /// Инспектор payload: последние увиденные payload, новые внизу
+    fn build_payload_inspector(&self) -> Element<Message> {
+        let entries = self.inspected_payloads.iter().fold(column![].spacing(6), |entries, inspected| {
+            let sizes = match inspected.raw_size {
+                Some(raw) => format!("{} B → {} B", inspected.compressed_size, raw),
+                None => format!("{} B", inspected.compressed_size),
+            };
+            entries.push(
+                column![
+                    text(format!("{} {} {} — {}", inspected.seen_at, inspected.tx_id, sizes, inspected.outcome))
+                        .size(12)
+                        .font(font::Font::MONOSPACE),
+                    text(&inspected.header).size(12).font(font::Font::MONOSPACE).style(text::secondary),
+                    text(&inspected.fields).size(12).font(font::Font::MONOSPACE).style(text::secondary),
+                ]
+                .spacing(1),
+            )
+        });
+        let entries = if self.inspected_payloads.is_empty() {
+            entries.push(text(tr("console-waiting-payloads")).size(12).style(text::secondary))
+        } else {
+            entries
+        };
+        scrollable(entries.padding(6))
+            .id(self.inspector_scroll_id.clone())
+            .anchor_bottom()
+            .width(Length::Fill)
+            .height(Length::Fixed(200.0))
+            .into()
+    }
+
+    fn build_top_bar(&self) -> Row<Message> {
+        let input_device_options = self.available_input_devices.clone();
+        // let output_device_options = vec!["Speaker 1".to_string(), "Speaker 2".to_string()];
+
+        let mut pick_list_input_device =
+            column![pick_list(input_device_options, Some(&self.input_device), Message::UpdateInputDevice,)
+                .placeholder(tr("select-input-device"))
+                .width(Length::Fill)]
+            .spacing(2)
+            .width(Length::FillPortion(2))
+            .height(Length::Shrink);
+        // Индикатор уровня: сразу видно, что выбранное устройство слышит
+        if self.input_meter {
+            pick_list_input_device =
+                pick_list_input_device.push(progress_bar(0.0..=1.0, self.input_level).height(Length::Fixed(4.0)).width(Length::Fill));
+        }
+
+        // let pick_list_output_device = pick_list(
+        //     output_device_options,
+        //     Some(&self.output_device),
+        //     Message::UpdateOutputDevice,
+        // )
+        // .placeholder("Select Output Device")
+        // .padding(9)
+        // .width(Length::FillPortion(2));
+
+        let input_node_address = column![text_input(tr("node-address-placeholder"), &self.node_address)
+            .on_input(Message::InputNodeAddress)
+            .width(Length::Fill)]
+        .width(Length::FillPortion(3))
+        .height(Length::Shrink);
+
+        let button_connect =
+            column![button(tr("connect")).on_press(Message::ConnectNodeAddress).style(button::success).width(Length::Fill)]
+                .width(Length::FillPortion(1))
+                .height(Length::Shrink);
+
+        let title = column![row![
+            text("KASPEAK")
+                .style(text::success)
+                .font(font::Font { weight: iced::font::Weight::Semibold, ..font::Font::DEFAULT })
+                .size(26),
+            text("Alpha").style(text::secondary).size(14)
+        ]
+        .height(Length::Shrink)]
+        .width(Length::FillPortion(6))
+        .height(Length::Shrink);
+
+        row![
+            title,
+            pick_list_input_device,
+            // pick_list_output_device,
+            input_node_address,
+            button_connect,
+        ]
+        .padding(6)
+        .spacing(6)
+    }
+
+    fn build_left_side_bar(&self) -> Column<Message> {
+        column![self.build_channel_list(), Rule::horizontal(1), self.build_recorder(), Rule::horizontal(1), self.build_user_info(),]
+            .spacing(10)
             .padding(0)
-            .width(Length::Fill)
             .height(Length::Fill)
+            .align_x(Alignment::Start)
     }
 
-    fn build_top_bar(&self) -> Row<Message> {
-        let input_device_options = self.available_input_devices.clone();
-        // let output_device_options = vec!["Speaker 1".to_string(), "Speaker 2".to_string()];
+    fn build_right_side_bar(&self) -> Column<Message> {
+        let clients_title = text(tr_args("clients-title", &[("count", &self.seen_users.len())]));
+        let clients = self.seen_users.iter().fold(column![clients_title].spacing(4), |clients, user| {
+            let speaking = self.speaking_users.contains(&user.username);
+            let name = if speaking {
+                text(user.username.clone()).size(14).style(text::success)
+            } else {
+                text(user.username.clone()).size(14)
+            };
+            let mut entry = row![].spacing(4).align_y(Alignment::Center);
+            if speaking {
+                entry = entry.push(text("🎙").size(12));
+            }
+            if let Some(avatar) = self.build_avatar(&user.username, user.key.as_deref(), 20.0) {
+                entry = entry.push(avatar);
+            }
+            match &user.address {
+                Some(address) => {
+                    entry = entry.push(tooltip(name, text(shorten_address(address)).size(12), tooltip::Position::Left));
+                    // Запрос стоит комиссию, поэтому только по кнопке
+                    if *address != self.full_address && !APP_STATE.has_avatar_for_address(address) {
+                        let request = button(text("🖼").size(12))
+                            .style(button::text)
+                            .padding(0)
+                            .on_press(Message::RequestAvatarPressed(address.clone()));
+                        entry = entry.push(tooltip(request, text(tr("request-avatar")).size(12), tooltip::Position::Left));
+                    }
+                }
+                None => entry = entry.push(name),
+            }
+            if let Some(last_seen) = last_seen_text(&user.username).filter(|_| !speaking) {
+                entry = entry.push(text(last_seen).size(11).style(text::secondary));
+            }
+            // Себя не глушим и не блокируем
+            let is_self = user.username == self.username || user.key.as_deref() == Some(self.identity_key.as_str());
+            if !is_self {
+                let muted = self.muted_users.contains(&user.username);
+                let mute = button(text(if muted { "🔇" } else { "🔈" }).size(12))
+                    .style(button::text)
+                    .padding(0)
+                    .on_press(Message::ToggleUserMuted(user.username.clone(), !muted));
+                let ignored = self.ignored_users.contains(&user.username);
+                let ignore = button(text(if ignored { "🙈" } else { "👁" }).size(12))
+                    .style(button::text)
+                    .padding(0)
+                    .on_press(Message::ToggleUserIgnored(user.username.clone(), !ignored));
+                let block = button(text("🚫").size(12))
+                    .style(button::text)
+                    .padding(0)
+                    .on_press(Message::BlockSender(user.username.clone(), user.key.clone()));
+                entry = entry
+                    .push(tooltip(
+                        mute,
+                        text(if muted { tr("unmute-voice") } else { tr("mute-voice") }).size(12),
+                        tooltip::Position::Left,
+                    ))
+                    .push(tooltip(
+                        ignore,
+                        text(if ignored { tr("unignore-user") } else { tr("ignore-user") }).size(12),
+                        tooltip::Position::Left,
+                    ))
+                    .push(tooltip(block, text(tr("block-sender")).size(12), tooltip::Position::Left));
+            }
+            clients.push(entry)
+        });
+        let clients_container = scrollable(clients.padding(10)).width(Length::Fill).height(Length::FillPortion(1));
 
-        let pick_list_input_device = column![pick_list(input_device_options, Some(&self.input_device), Message::UpdateInputDevice,)
-            .placeholder("Select Input Device")
-            .width(Length::Fill)]
-        .width(Length::FillPortion(2))
-        .height(Length::Shrink);
+        // Лента транзакций канала; комиссию своих транзакций из блоков берём из истории отправок
+        let feed_title = text(tr_args("tx-feed-title", &[("count", &self.tx_feed.len())]));
+        let feed = self.tx_feed.iter().fold(column![feed_title].spacing(6), |feed, tx| {
+            let time = DateTime::from_timestamp(tx.timestamp, 0).map(|at| format_time(&at.with_timezone(&Local))).unwrap_or_default();
+            let link = button(text(shorten_key(&tx.tx_id)).size(12))
+                .style(button::text)
+                .padding(0)
+                .on_press_maybe(explorer_link("txs", &tx.tx_id));
+            let fee = tx
+                .fee
+                .or_else(|| self.sent_transactions.iter().find(|sent| sent.tx_id == tx.tx_id).map(|sent| sent.fee))
+                .map(|fee| format_amount(fee, 8))
+                .unwrap_or_else(|| tr("tx-feed-fee-unknown").to_string());
+            let sender = match &tx.sender_address {
+                Some(address) => {
+                    tooltip(text(tx.username.clone()).size(12), text(shorten_address(address)).size(12), tooltip::Position::Left)
+                }
+                None => tooltip(text(tx.username.clone()).size(12), text(tr("tx-feed-no-address")).size(12), tooltip::Position::Left),
+            };
+            let status = if tx.confirmed { "" } else { " ⏳" };
+            feed.push(
+                column![
+                    row![
+                        text(format!("{}{}", message_type_label(tx.message_type), status)).size(12).style(text::primary),
+                        sender,
+                        Space::with_width(Length::Fill),
+                        text(time).size(12).style(text::secondary),
+                    ]
+                    .spacing(6)
+                    .align_y(Alignment::Center),
+                    row![
+                        link,
+                        text(tr_args("tx-feed-summary", &[("size", &format_integer(tx.payload_size as u64)), ("fee", &fee)])).size(12),
+                    ]
+                    .spacing(6)
+                    .align_y(Alignment::Center),
+                ]
+                .spacing(2),
+            )
+        });
+        let feed_scroll = scrollable(feed.padding(10)).width(Length::Fill).height(Length::FillPortion(1));
 
-        // let pick_list_output_device = pick_list(
-        //     output_device_options,
-        //     Some(&self.output_device),
-        //     Message::UpdateOutputDevice,
-        // )
-        // .placeholder("Select Output Device")
-        // .padding(9)
-        // .width(Length::FillPortion(2));
+        // Окно дедупликации: сколько единиц DAA score помнить уже обработанные транзакции
+        let dedupe_window = tooltip(
+            row![
+                text(tr("dedupe-window")).size(14).width(Length::Fill),
+                pick_list(DEDUPE_WINDOW_OPTIONS, Some(self.dedupe_window_daa), Message::DedupeWindowChanged).width(Length::Shrink),
+            ]
+            .align_y(Alignment::Center),
+            container(text(tr("dedupe-window-hint")).size(14)).padding(6).style(container::rounded_box),
+            tooltip::Position::Top,
+        );
 
-        let input_node_address = column![text_input("Enter node address (Optional)", &self.node_address)
-            .on_input(Message::InputNodeAddress)
-            .width(Length::Fill)]
-        .width(Length::FillPortion(3))
-        .height(Length::Shrink);
+        let mut transactions_header = column![
+            row![
+                text(tr("transactions")).width(Length::Fill),
+                button(text(tr("export-csv")).size(12))
+                    .on_press_maybe((!self.sent_transactions.is_empty()).then_some(Message::ExportTransactionsCsv))
+                    .style(button::secondary)
+                    .padding(4),
+            ]
+            .align_y(Alignment::Center),
+            dedupe_window
+        ]
+        .spacing(6);
+        match &self.tx_export_status {
+            Some(Ok(status)) => transactions_header = transactions_header.push(text(status).size(12).style(text::success)),
+            Some(Err(err)) => transactions_header = transactions_header.push(text(err).size(12).style(text::danger)),
+            None => {}
+        }
+        let transactions = self.sent_transactions.iter().fold(transactions_header, |list, tx| {
+            let time =
+                DateTime::from_timestamp(tx.timestamp, 0).map(|at| format_date_time(&at.with_timezone(&Local))).unwrap_or_default();
+            let link = button(text(shorten_key(&tx.tx_id)).size(12))
+                .style(button::text)
+                .padding(0)
+                .on_press_maybe(explorer_link("txs", &tx.tx_id));
+            list.push(
+                column![
+                    row![link, text(time).size(12).style(text::secondary)].spacing(6).align_y(Alignment::Center),
+                    text(tr_args(
+                        "transaction-summary",
+                        &[
+                            ("amount", &format_amount(tx.amount, 8)),
+                            ("fee", &format_amount(tx.fee, 8)),
+                            ("size", &format_integer(tx.payload_size as u64)),
+                        ]
+                    ))
+                    .size(12),
+                ]
+                .spacing(2),
+            )
+        });
+        let transactions_scroll = scrollable(transactions.padding(10)).width(Length::Fill).height(Length::FillPortion(1));
 
-        let button_connect =
-            column![button("Connect").on_press(Message::ConnectNodeAddress).style(button::success).width(Length::Fill)]
-                .width(Length::FillPortion(1))
-                .height(Length::Shrink);
+        column![clients_container, Rule::horizontal(1), feed_scroll, Rule::horizontal(1), transactions_scroll]
+            .spacing(10)
+            .padding(0)
+            .height(Length::Fill)
+            .align_x(Alignment::Start)
+    }
+
+    /// Время записи, осциллограмма микрофона и сколько уже отправлено: видно, что передача идёт
+    fn build_recording_status(&self) -> Element<Message> {
+        let stats = &self.recording_stats;
+        let elapsed = stats.started_at.map(|started| started.elapsed().as_secs()).unwrap_or(0);
+        let header = row![
+            text(format!("⏺ {:02}:{:02}", elapsed / 60, elapsed % 60)).size(14).style(text::danger),
+            text(tr_args("recording-sent", &[("fragments", &stats.fragments_sent), ("transactions", &stats.transactions_sent)]))
+                .size(12)
+                .style(text::secondary),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let waveform = stats.levels.iter().fold(row![].spacing(1).align_y(Alignment::Center), |waveform, level| {
+            waveform.push(
+                container(Space::new(Length::Fixed(2.0), Length::Fixed((level * WAVEFORM_HEIGHT).max(1.0))))
+                    .style(|theme: &Theme| container::Style::default().background(theme.palette().primary)),
+            )
+        });
+
+        column![header, container(waveform).height(Length::Fixed(WAVEFORM_HEIGHT)).align_y(Alignment::Center).clip(true)]
+            .spacing(4)
+            .padding(5)
+            .into()
+    }
+
+    fn build_recorder(&self) -> Column<Message> {
+        let button_recording = button(if self.is_recording { tr("stop-recording") } else { tr("start-recording") })
+            .on_press(Message::ToggleRecording(!self.is_recording));
+
+        let toggle_listen_self = toggler(self.listen_self).label(tr("listen-self")).on_toggle(Message::ToggleListenSelf);
+
+        let toggle_mute_all = toggler(self.mute_all).label(tr("mute-all")).on_toggle(Message::ToggleMuteAll);
+
+        let toggle_pause_incoming = tooltip(
+            toggler(self.pause_incoming).label(tr("pause-incoming")).on_toggle(Message::TogglePauseIncoming),
+            text(tr("pause-incoming-hint")).size(12),
+            tooltip::Position::Bottom,
+        );
+
+        let toggle_voice_announce =
+            toggler(self.voice_announce).label(tr("announce-speakers")).on_toggle(Message::ToggleVoiceAnnounce);
+
+        let toggle_comfort_noise = tooltip(
+            toggler(self.comfort_noise).label(tr("comfort-noise")).on_toggle(Message::ToggleComfortNoise),
+            text(tr("comfort-noise-hint")).size(12),
+            tooltip::Position::Bottom,
+        );
+
+        let toggle_voice_batching = tooltip(
+            toggler(self.voice_batching).label(tr("voice-batching")).on_toggle(Message::ToggleVoiceBatching),
+            text(tr_args("voice-batching-hint", &[("n", &VOICE_BATCH_MAX_FRAGMENTS)])).size(12),
+            tooltip::Position::Bottom,
+        );
+
+        let pick_list_max_streams =
+            pick_list(PLAYBACK_STREAM_OPTIONS, Some(self.max_playback_streams), Message::MaxPlaybackStreamsChanged)
+                .width(Length::Shrink);
+
+        let mut recorder_column = column![row![button_recording.width(Length::FillPortion(1)).padding(9)].padding(5)];
+        if self.is_recording {
+            recorder_column = recorder_column.push(self.build_recording_status());
+        }
+        recorder_column = recorder_column.extend(
+            [
+                row![toggle_listen_self.width(Length::FillPortion(1))].padding(5),
+                row![toggle_mute_all.width(Length::FillPortion(1))].padding(5),
+                row![toggle_pause_incoming].padding(5),
+                row![toggle_voice_announce.width(Length::FillPortion(1))].padding(5),
+                row![toggle_comfort_noise].padding(5),
+                row![toggle_voice_batching].padding(5),
+                row![text(tr("max-speakers")).width(Length::Fill), pick_list_max_streams].align_y(Alignment::Center).padding(5),
+            ]
+            .map(Element::from),
+        );
+
+        // Собеседники сверх лимита: слышны не будут, но показываем, что они говорят
+        if !self.overflow_speakers.is_empty() {
+            recorder_column = recorder_column.push(
+                row![text(tr_args("over-limit-speakers", &[("names", &self.overflow_speakers.join(", "))]))
+                    .size(14)
+                    .style(text::secondary)]
+                .padding(5),
+            );
+        }
+
+        // Потери по отправителям: помогают отличить проблемы сети от проблем микрофона
+        if !self.stream_stats.is_empty() {
+            let mut stats_column = column![text(tr("stream-stats")).size(14)].spacing(2);
+            for (name, stats) in &self.stream_stats {
+                stats_column = stats_column.push(tooltip(
+                    text(tr_args(
+                        "stream-loss",
+                        &[("name", name), ("lost", &stats.lost), ("percent", &format_decimal(stats.loss_percent(), 1))],
+                    ))
+                    .size(13)
+                    .style(if stats.lost > 0 { text::danger } else { text::secondary }),
+                    container(
+                        text(tr_args(
+                            "stream-details",
+                            &[
+                                ("streams", &stats.streams),
+                                ("received", &stats.received),
+                                ("lost", &stats.lost),
+                                ("duplicates", &stats.duplicates),
+                                ("reordered", &stats.reordered),
+                            ],
+                        ))
+                        .size(13),
+                    )
+                    .padding(6)
+                    .style(container::rounded_box),
+                    tooltip::Position::Right,
+                ));
+            }
+            recorder_column = recorder_column.push(row![stats_column].padding(5));
+        }
+
+        recorder_column
+    }
+
+    fn build_user_info(&self) -> Column<Message> {
+        let balance = APP_STATE.get_balance().unwrap_or_else(|e| {
+            log::error!("Error while reading balance: {}", e);
+            0
+        });
+
+        let (pending, outgoing) = APP_STATE.get_pending_balance().unwrap_or_else(|e| {
+            log::error!("Error while reading pending balance: {}", e);
+            (0, 0)
+        });
+
+        column![
+            self.build_balance(balance, pending, outgoing).push(Space::with_width(Length::Fill)).push(
+                button(text(tr("wallet-open")).size(12)).on_press(Message::ToggleWallet(true)).style(button::secondary).padding(4)
+            ),
+            row![
+                rich_text([span(format!("{} ", tr("label-name"))).size(16)]),
+                rich_text([span(&self.username).size(16)]),
+                self.build_claim_status()
+            ]
+            .spacing(6)
+            .padding(6),
+            row![
+                rich_text([span(format!("{} ", tr("label-key"))).size(16)]),
+                button(text(shorten_key(&self.identity_key)).size(16))
+                    .on_press(Message::CopyIdentityKey)
+                    .style(button::text)
+                    .padding(0)
+            ]
+            .padding(6),
+            self.build_avatar_settings(),
+            self.build_message_signing(),
+            row![toggler(self.channel_muted).label(tr("mute-notifications")).on_toggle(Message::ToggleChannelMuted)].padding(6),
+            self.build_focus_mode(),
+            self.build_cost_meter(),
+            self.build_spend_limits(),
+            row![text_input(tr("open-permalink-placeholder"), &self.permalink_input)
+                .on_input(Message::PermalinkInputChanged)
+                .on_submit(Message::OpenPermalink(self.permalink_input.clone()))
+                .size(14)]
+            .padding(6),
+            self.build_contacts(),
+            self.build_blocklist(),
+            self.build_ignore_list(),
+            self.build_content_filter(),
+            self.build_telemetry(),
+            self.build_mnemonic_backup(),
+            self.build_profiles(),
+        ]
+    }
+
+    /// Окно кошелька: полный адрес с QR-кодом, разбивка баланса, перевод средств и кран
+    fn build_wallet_dialog(&self) -> Element<Message> {
+        let balance = APP_STATE.get_balance().unwrap_or_else(|e| {
+            log::error!("Error while reading balance: {}", e);
+            0
+        });
+        let (pending, outgoing) = APP_STATE.get_pending_balance().unwrap_or_else(|e| {
+            log::error!("Error while reading pending balance: {}", e);
+            (0, 0)
+        });
+        let utxos = APP_STATE.get_utxos().unwrap_or_else(|e| {
+            log::error!("Error while reading UTXO count: {}", e);
+            0
+        });
+
+        let header = row![
+            text(tr("wallet-title"))
+                .font(font::Font { weight: iced::font::Weight::Semibold, ..font::Font::DEFAULT })
+                .size(22)
+                .width(Length::Fill),
+            button(text("✕").size(16)).on_press(Message::ToggleWallet(false)).style(button::text).padding(0),
+        ]
+        .align_y(Alignment::Center);
+
+        let qr: Element<Message> = match &self.address_qr {
+            Some(data) => qr_code(data).cell_size(4).into(),
+            None => text(tr("wallet-no-address")).size(12).style(text::secondary).into(),
+        };
+
+        let breakdown_row = |label: &'static str, value: String| {
+            row![text(label).size(14).width(Length::Fill), text(value).size(14)].spacing(6).align_y(Alignment::Center)
+        };
+        let breakdown = column![
+            rich_text([span(format!("{} ", tr("label-balance"))).size(16)]),
+            breakdown_row(tr("wallet-available"), format_amount(balance, 8)),
+            breakdown_row(tr("wallet-pending"), format_amount(pending, 8)),
+            breakdown_row(tr("wallet-outgoing"), format_amount(outgoing, 8)),
+            breakdown_row(tr("wallet-total"), format_amount(balance + pending, 8)),
+            breakdown_row(tr("label-utxos"), format_integer(utxos as u64)),
+        ]
+        .spacing(4)
+        .width(Length::Fill);
+
+        let address = column![
+            rich_text([span(format!("{} ", tr("label-address"))).size(16)]),
+            text(&self.full_address).size(13).font(font::Font::MONOSPACE),
+            row![
+                button(text(tr("copy")).size(12))
+                    .on_press(Message::CopyToClipboard(self.full_address.clone()))
+                    .style(button::secondary)
+                    .padding(4),
+                button(text(tr("wallet-explorer")).size(12))
+                    .on_press_maybe(explorer_link("addresses", &self.full_address))
+                    .style(button::secondary)
+                    .padding(4),
+                tooltip(
+                    button(text(tr("new-address")).size(12)).on_press(Message::NewAddressPressed).style(button::secondary).padding(4),
+                    text(tr("new-address-hint")).size(12),
+                    tooltip::Position::Top,
+                ),
+                pick_list(AddressRotation::options(), Some(self.address_rotation), Message::AddressRotationChanged).text_size(12),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+            row![
+                tooltip(
+                    button(text(tr("wallet-request-airdrop")).size(12))
+                        .on_press_maybe(APP_STATE.is_faucet_available().then_some(Message::RequestAirdropPressed))
+                        .style(button::secondary)
+                        .padding(4),
+                    text(tr("wallet-request-airdrop-hint")).size(12),
+                    tooltip::Position::Top,
+                ),
+                toggler(self.incoming_funds_sound).label(tr("incoming-funds-sound")).on_toggle(Message::ToggleIncomingFundsSound),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(6);
+
+        let dialog = container(
+            column![
+                header,
+                scrollable(
+                    column![
+                        row![qr, breakdown].spacing(16).align_y(Alignment::Center),
+                        address,
+                        Rule::horizontal(1),
+                        self.build_send_funds(),
+                        self.build_utxo_limits(),
+                        self.build_faucet(),
+                    ]
+                    .spacing(12)
+                    .padding(6)
+                )
+                .height(Length::Fill)
+            ]
+            .spacing(10),
+        )
+        .padding(16)
+        .width(Length::Fixed(560.0))
+        .height(Length::Fill)
+        .max_height(640.0)
+        .style(container::rounded_box);
+
+        opaque(
+            mouse_area(center(opaque(dialog)).style(|_theme| container::Style {
+                background: Some(Color { a: 0.6, ..Color::BLACK }.into()),
+                ..container::Style::default()
+            }))
+            .on_press(Message::ToggleWallet(false)),
+        )
+    }
+
+    /// Баланс «доступно / ожидает»: сразу после эйрдропа средства ещё не созрели и тратить их нельзя
+    fn build_balance(&self, available: u64, pending: u64, outgoing: u64) -> Row<Message> {
+        let mut balance =
+            row![rich_text([span(format!("{} ", tr("label-balance"))).size(16)]), text(format_amount(available, 3)).size(16)];
+        if pending > 0 || outgoing > 0 {
+            let mut details =
+                tr_args("balance-details", &[("available", &format_amount(available, 8)), ("pending", &format_amount(pending, 8))]);
+            if outgoing > 0 {
+                details.push('\n');
+                details.push_str(&tr_args("balance-outgoing", &[("amount", &format_amount(outgoing, 8))]));
+            }
+            balance = balance.push(tooltip(
+                text(tr_args("balance-pending", &[("amount", &format_amount(pending, 3))])).size(14).style(text::secondary),
+                container(text(details).size(14)).padding(6).style(container::rounded_box),
+                tooltip::Position::Bottom,
+            ));
+        }
+        balance.align_y(Alignment::Center).padding(6)
+    }
+
+    /// Перевод средств; получателя можно выбрать из адресной книги
+    fn build_send_funds(&self) -> Column<Message> {
+        let recipients: Vec<Contact> = self.contacts.iter().filter(|contact| contact.address.is_some()).cloned().collect();
+        let selected = recipients.iter().find(|contact| contact.address.as_deref() == Some(self.send_address_input.trim())).cloned();
+        let mut send = column![rich_text([span(format!("{} ", tr("label-send-funds"))).size(16)])].spacing(6).padding(6);
+        if !recipients.is_empty() {
+            send = send.push(
+                pick_list(recipients, selected, Message::SendContactSelected).placeholder(tr("contact-placeholder")).text_size(14),
+            );
+        }
+        send = send
+            .push(
+                text_input(tr("address-placeholder"), &self.send_address_input)
+                    .on_input(Message::SendAddressChanged)
+                    .padding(5)
+                    .size(14),
+            )
+            .push(
+                row![
+                    text_input(
+                        &tr_args("amount-placeholder", &[("unit", &self.selected_denomination.input_unit())]),
+                        &self.send_amount_input
+                    )
+                    .on_input(Message::SendAmountChanged)
+                    .on_submit(Message::SendFundsPressed)
+                    .padding(5)
+                    .size(14),
+                    button(text(tr("send")).size(12)).on_press(Message::SendFundsPressed).style(button::secondary).padding(4),
+                ]
+                .spacing(4)
+                .align_y(Alignment::Center),
+            );
+        if let Some(status) = &self.send_status {
+            send = send.push(text(status).size(12).style(text::secondary));
+        }
+        send
+    }
+
+    /// Подпись текста ключом своего адреса и проверка чужих подписей по адресу подписавшего
+    fn build_message_signing(&self) -> Column<Message> {
+        let header = button(text(format!("{} {}", tr("sign-verify"), if self.message_signing_visible { "▾" } else { "▸" })).size(16))
+            .on_press(Message::ToggleMessageSigning)
+            .style(button::text)
+            .padding(0);
+        let mut signing = column![header].spacing(6).padding(6);
+        if !self.message_signing_visible {
+            return signing;
+        }
+        signing = signing.push(
+            row![
+                text_input(tr("message-to-sign"), &self.sign_input)
+                    .on_input(Message::SignInputChanged)
+                    .on_submit(Message::SignMessagePressed)
+                    .padding(5)
+                    .size(14),
+                button(text(tr("sign")).size(12))
+                    .on_press_maybe((!self.sign_input.is_empty()).then_some(Message::SignMessagePressed))
+                    .style(button::secondary)
+                    .padding(4),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        );
+        match &self.signed_message {
+            Some(Ok(signed)) => {
+                signing = signing.push(
+                    row![
+                        text(&signed.signature).size(12).font(font::Font::MONOSPACE).width(Length::Fill),
+                        button(text(tr("copy")).size(12)).on_press(Message::CopySignature).style(button::secondary).padding(4),
+                    ]
+                    .spacing(4)
+                    .align_y(Alignment::Center),
+                );
+                signing = signing.push(
+                    text(tr_args("signed-by", &[("address", &shorten_address(&signed.address))])).size(12).style(text::secondary),
+                );
+            }
+            Some(Err(err)) => signing = signing.push(text(err).size(12).style(text::danger)),
+            None => {}
+        }
+        signing = signing
+            .push(
+                text_input(tr("signer-address"), &self.verify_address_input)
+                    .on_input(Message::VerifyAddressChanged)
+                    .padding(5)
+                    .size(14),
+            )
+            .push(
+                text_input(tr("signed-message"), &self.verify_message_input)
+                    .on_input(Message::VerifyMessageChanged)
+                    .padding(5)
+                    .size(14),
+            )
+            .push(
+                row![
+                    text_input(tr("signature-hex"), &self.verify_signature_input)
+                        .on_input(Message::VerifySignatureChanged)
+                        .on_submit(Message::VerifyMessagePressed)
+                        .padding(5)
+                        .size(14),
+                    button(text(tr("verify")).size(12)).on_press(Message::VerifyMessagePressed).style(button::secondary).padding(4),
+                ]
+                .spacing(4)
+                .align_y(Alignment::Center),
+            );
+        match &self.verify_result {
+            Some(Ok(())) => signing.push(text(tr("valid-signature")).size(12).style(text::success)),
+            Some(Err(err)) => signing.push(text(format!("✗ {}", err)).size(12).style(text::danger)),
+            None => signing,
+        }
+    }
+
+    /// Адресная книга: имена для адресов и ключей собеседников
+    fn build_contacts(&self) -> Column<Message> {
+        let mut contacts = column![rich_text([span(format!("{} ", tr("label-contacts"))).size(16)])].spacing(6).padding(6);
+        for contact in &self.contacts {
+            let target = contact.address.as_deref().map(shorten_address).or_else(|| contact.identity_key.as_deref().map(shorten_key));
+            let mut entry = column![row![
+                text(&contact.name).size(14).width(Length::Fill),
+                button(text("✕").size(12)).on_press(Message::RemoveContact(contact.clone())).style(button::text).padding(0),
+            ]
+            .align_y(Alignment::Center)];
+            if let Some(target) = target {
+                entry = entry.push(text(target).size(12).style(text::secondary));
+            }
+            if !contact.note.is_empty() {
+                entry = entry.push(text(&contact.note).size(12));
+            }
+            contacts = contacts.push(entry.spacing(2));
+        }
+        contacts = contacts
+            .push(
+                text_input(tr("name-placeholder"), &self.contact_name_input).on_input(Message::ContactNameChanged).padding(5).size(14),
+            )
+            .push(
+                text_input(tr("contact-target-placeholder"), &self.contact_target_input)
+                    .on_input(Message::ContactTargetChanged)
+                    .padding(5)
+                    .size(14),
+            )
+            .push(
+                row![
+                    text_input(tr("contact-note-placeholder"), &self.contact_note_input)
+                        .on_input(Message::ContactNoteChanged)
+                        .on_submit(Message::SaveContactPressed)
+                        .padding(5)
+                        .size(14),
+                    button(text(tr("save")).size(12)).on_press(Message::SaveContactPressed).style(button::secondary).padding(4),
+                ]
+                .spacing(4)
+                .align_y(Alignment::Center),
+            );
+        if let Some(err) = &self.contact_error {
+            contacts = contacts.push(text(err).size(12).style(text::danger));
+        }
+        contacts
+    }
+
+    /// Заблокированные отправители: их сообщения и голос не доходят до чата и плеера
+    fn build_blocklist(&self) -> Column<Message> {
+        let mut blocklist = column![rich_text([span(format!("{} ", tr("label-blocked"))).size(16)])].spacing(6).padding(6);
+        if self.blocklist.is_empty() {
+            return blocklist.push(text(tr("nobody-blocked")).size(12).style(text::secondary));
+        }
+        for sender in &self.blocklist {
+            let label = match sender {
+                BlockedSender::Username(name) => name.clone(),
+                BlockedSender::IdentityKey(key) => format!("🔑 {}", shorten_key(key)),
+            };
+            blocklist = blocklist.push(
+                row![
+                    text(label).size(14).width(Length::Fill),
+                    button(text(tr("unblock")).size(12))
+                        .on_press(Message::UnblockSender(sender.clone()))
+                        .style(button::secondary)
+                        .padding(4),
+                ]
+                .align_y(Alignment::Center),
+            );
+        }
+        blocklist
+    }
+
+    /// Игнорируемые собеседники: их сообщения скрыты только в ленте, в истории и выгрузке они есть
+    fn build_ignore_list(&self) -> Column<Message> {
+        let mut ignore_list = column![rich_text([span(format!("{} ", tr("label-ignored"))).size(16)])].spacing(6).padding(6);
+        if self.ignored_users.is_empty() {
+            return ignore_list.push(text(tr("nobody-ignored")).size(12).style(text::secondary));
+        }
+        for username in &self.ignored_users {
+            ignore_list = ignore_list.push(
+                row![
+                    text(username.clone()).size(14).width(Length::Fill),
+                    button(text(tr("unignore")).size(12))
+                        .on_press(Message::ToggleUserIgnored(username.clone(), false))
+                        .style(button::secondary)
+                        .padding(4),
+                ]
+                .align_y(Alignment::Center),
+            );
+        }
+        ignore_list
+    }
+
+    /// Фильтр входящих сообщений: общее действие, шаблоны и своё действие для текущего канала
+    fn build_content_filter(&self) -> Column<Message> {
+        let channel_choice = ChannelFilterChoice(self.content_filter.channel_overrides.get(&self.channel_number).copied());
+        let mut filter = column![
+            row![
+                rich_text([span(format!("{} ", tr("label-content-filter"))).size(16)]),
+                pick_list(FilterAction::ALL, Some(self.content_filter.action), Message::ContentFilterActionChanged).text_size(14),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+            row![
+                text(tr_args("filter-channel", &[("channel", &self.channel_number)])).size(14).width(Length::Fill),
+                pick_list(ChannelFilterChoice::options(), Some(channel_choice), Message::ChannelFilterChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center),
+        ]
+        .spacing(6)
+        .padding(6);
+        for (index, pattern) in self.content_filter.patterns.iter().enumerate() {
+            filter = filter.push(
+                row![
+                    text(pattern.clone()).size(12).width(Length::Fill),
+                    button(text("✕").size(12)).on_press(Message::RemoveFilterPattern(index)).style(button::text).padding(0),
+                ]
+                .spacing(4)
+                .align_y(Alignment::Center),
+            );
+        }
+        filter = filter.push(
+            row![
+                text_input(tr("filter-pattern-placeholder"), &self.filter_pattern_input)
+                    .on_input(Message::FilterPatternInputChanged)
+                    .on_submit(Message::AddFilterPattern)
+                    .padding(5)
+                    .size(14),
+                button(text(tr("add")).size(12))
+                    .on_press_maybe((!self.filter_pattern_input.trim().is_empty()).then_some(Message::AddFilterPattern))
+                    .style(button::secondary)
+                    .padding(4),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        );
+        if let Some(err) = &self.content_filter_error {
+            filter = filter.push(text(err).size(12).style(text::danger));
+        }
+        filter.push(text(tr("filter-hint")).size(12).style(text::secondary))
+    }
+
+    /// Доплата к комиссии: ручное значение или пресет по оценке ноды
+    fn build_fee(&self) -> Column<Message> {
+        let mut fee = column![row![
+            rich_text([span(format!("{} ", tr("label-fee"))).size(16)]),
+            pick_list(FeeMode::ALL, Some(self.fee_mode), Message::FeeModeChanged).text_size(14),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center)]
+        .spacing(6)
+        .padding(6);
+        fee = fee.push(
+            row![
+                tooltip(
+                    text(tr("fee-bump")).size(14).width(Length::Fill),
+                    text(tr_args("fee-bump-hint", &[("secs", &FEE_BUMP_TIMEOUT_SECS)])).size(12),
+                    tooltip::Position::Top,
+                ),
+                pick_list(FeeBump::options(), Some(self.fee_bump), Message::FeeBumpChanged).text_size(14),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+        );
+        if self.fee_mode == FeeMode::Manual {
+            return fee.push(text(format_amount(self.fee_size, 8)).size(16)).push(
+                text_input(&tr_args("fee-placeholder", &[("unit", &self.selected_denomination.input_unit())]), &self.fee_size_input)
+                    .on_input(Message::FeeInputChanged)
+                    .padding(5)
+                    .size(16),
+            );
+        }
+        match APP_STATE.get_fee_rates().unwrap_or_default() {
+            Some(rates) => {
+                // Для примера — текстовое сообщение с payload около 200 байт
+                let estimate = rates.priority_fee(self.fee_mode, 200).unwrap_or_default();
+                fee = fee.push(text(tr_args("fee-per-message", &[("amount", &format_amount(estimate, 8))])).size(16));
+                fee = fee.push(
+                    text(tr_args(
+                        "fee-node-rates",
+                        &[
+                            ("low", &format_decimal(rates.low, 2)),
+                            ("normal", &format_decimal(rates.normal, 2)),
+                            ("priority", &format_decimal(rates.priority, 2)),
+                        ],
+                    ))
+                    .size(12)
+                    .style(text::secondary),
+                );
+            }
+            None => {
+                fee = fee.push(
+                    text(tr_args("fee-no-estimate", &[("amount", &format_amount(self.fee_size, 8))])).size(12).style(text::secondary),
+                );
+            }
+        }
+        fee
+    }
+
+    /// Когда объединять UTXO: предел их числа и порог пыли
+    fn build_utxo_limits(&self) -> Column<Message> {
+        column![
+            row![
+                text(tr("compound-above")).size(14).width(Length::Fill),
+                pick_list(UTXO_MAX_COUNT_OPTIONS, Some(self.utxo_max_count), Message::UtxoMaxCountChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center),
+            row![
+                tooltip(
+                    text(tr("dust-below")).size(14),
+                    text(tr_args(
+                        "dust-hint",
+                        &[("amount", &format_amount(self.dust_threshold, 8)), ("count", &UTXO_DUST_SWEEP_MIN_COUNT)],
+                    ))
+                    .size(12),
+                    tooltip::Position::Top,
+                ),
+                text_input(
+                    &tr_args("dust-placeholder", &[("unit", &self.selected_denomination.input_unit())]),
+                    &self.dust_threshold_input
+                )
+                .on_input(Message::DustThresholdInputChanged)
+                .size(14),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(4)
+        .padding(6)
+    }
+
+    /// Лимиты трат в час и в сутки: комиссии и переводы на чужие адреса
+    fn build_spend_limits(&self) -> Column<Message> {
+        column![
+            rich_text([span(tr("label-spending-limits")).size(16)]),
+            self.build_spend_limit_row(
+                tr("per-hour"),
+                self.recent_spend.0,
+                &self.spend_limit_hour_input,
+                Message::SpendLimitHourChanged
+            ),
+            self.build_spend_limit_row(tr("per-day"), self.recent_spend.1, &self.spend_limit_day_input, Message::SpendLimitDayChanged),
+            row![
+                tooltip(
+                    text(tr("cost-confirm-above")).size(14).width(Length::Fill),
+                    text(tr("cost-confirm-hint")).size(12),
+                    tooltip::Position::Top
+                ),
+                text_input(
+                    &tr_args("cost-confirm-placeholder", &[("unit", &self.selected_denomination.input_unit())]),
+                    &self.cost_threshold_input
+                )
+                .on_input(Message::CostThresholdChanged)
+                .size(14)
+                .width(Length::Fixed(140.0)),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(4)
+        .padding(6)
+    }
+
+    fn build_spend_limit_row<'a>(&self, label: &str, spent: u64, input: &'a str, on_input: fn(String) -> Message) -> Row<'a, Message> {
+        row![
+            text(tr_args("spend-limit-row", &[("label", &label), ("spent", &format_amount(spent, 8))])).size(14).width(Length::Fill),
+            text_input(&tr_args("no-limit-placeholder", &[("unit", &self.selected_denomination.input_unit())]), input)
+                .on_input(on_input)
+                .size(14)
+                .width(Length::Fixed(140.0)),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center)
+    }
+
+    fn exceeds_cost_threshold(&self, estimate: &CostEstimate) -> bool {
+        self.cost_threshold.is_some_and(|threshold| estimate.fee > threshold)
+    }
+
+    fn announce_avatar(&self) -> Task<Message> {
+        let tx = self.event_tx.clone();
+        Task::perform(async move { tx.send(GuiEvent::AnnounceAvatar).await.map_err(|e| e.to_string()) }, Message::AvatarEventDone)
+    }
+
+    /// Сохраняет лимиты трат; false, если сохранить не удалось
+    fn save_spend_limits(&mut self, limits: SpendLimits) -> bool {
+        match APP_STATE.set_spend_limits(limits) {
+            Ok(_) => {
+                self.spend_limits = limits;
+                true
+            }
+            Err(err) => {
+                log::error!("Error while changing spending limits: {}", err);
+                false
+            }
+        }
+    }
+
+    /// Счётчик расходов за сессию: все комиссии и отдельно транзакции текущего канала
+    fn build_cost_meter(&self) -> Column<Message> {
+        let cost = &self.session_cost;
+        let channel_cost = cost.channel(self.channel_number);
+        column![
+            row![
+                rich_text([span(format!("{} ", tr("label-session-cost"))).size(16)]),
+                tooltip(
+                    text(format_amount(cost.fees, 8)).size(16),
+                    container(
+                        text(tr_args(
+                            "session-cost-details",
+                            &[("transactions", &format_integer(cost.transactions)), ("bytes", &format_integer(cost.payload_bytes))],
+                        ))
+                        .size(14)
+                    )
+                    .padding(6)
+                    .style(container::rounded_box),
+                    tooltip::Position::Top,
+                ),
+            ]
+            .align_y(Alignment::Center),
+            text(tr_args(
+                "channel-cost",
+                &[
+                    ("channel", &self.channel_number),
+                    ("fees", &format_amount(channel_cost.fees, 8)),
+                    ("transactions", &format_integer(channel_cost.transactions)),
+                ],
+            ))
+            .size(12)
+            .style(text::secondary),
+        ]
+        .spacing(4)
+        .padding(6)
+    }
+
+    /// Выбор сети Kaspa; в mainnet сообщения тратят настоящие KAS, поэтому переход туда подтверждается
+    fn build_network(&self) -> Column<Message> {
+        let current = APP_STATE.get_network();
+        let mut network = column![row![
+            rich_text([span(format!("{} ", tr("label-network"))).size(16)]),
+            pick_list(Network::ALL, Some(current), Message::NetworkSelected).text_size(14),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center)]
+        .spacing(6)
+        .padding(6);
+        if current.is_mainnet() {
+            network = network.push(text(tr("mainnet-warning")).size(12).style(text::danger));
+        }
+        if let Some(pending) = self.pending_network {
+            network = network.push(text(tr_args("mainnet-confirm", &[("network", &pending)])).size(12).style(text::danger));
+            network = network.push(
+                row![
+                    button(text(tr("switch-to-mainnet")).size(12))
+                        .on_press(Message::ConfirmNetworkSwitch)
+                        .style(button::danger)
+                        .padding(4),
+                    button(text(tr("cancel")).size(12)).on_press(Message::CancelNetworkSwitch).style(button::secondary).padding(4),
+                ]
+                .spacing(6),
+            );
+        } else {
+            network = network.push(text(tr("network-switch-hint")).size(12).style(text::secondary));
+        }
+        if let Some(err) = &self.network_error {
+            network = network.push(text(err).size(12).style(text::danger));
+        }
+        network
+    }
+
+    /// Путь вывода личного аккаунта: для мнемоник, которые уже использовались в других кошельках
+    fn build_derivation(&self) -> Column<Message> {
+        let current = APP_STATE.get_account_derivation().unwrap_or_default();
+        let mut derivation = column![row![
+            rich_text([span(format!("{} ", tr("label-derivation"))).size(16)]),
+            pick_list(DerivationKind::ALL, Some(self.derivation_kind), Message::DerivationKindSelected).text_size(14),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center)]
+        .spacing(6)
+        .padding(6);
+        if self.derivation_kind == DerivationKind::Bip44 {
+            derivation = derivation.push(
+                row![
+                    text(tr("account-index")).size(14),
+                    text_input("0", &self.derivation_index_input)
+                        .on_input(Message::DerivationIndexChanged)
+                        .on_submit(Message::ApplyDerivation)
+                        .size(14),
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
+            );
+        }
+        derivation = derivation.push(text(tr_args("derivation-current", &[("path", &current)])).size(12).style(text::secondary));
+        if self.selected_derivation().map_or(true, |selected| selected != current) {
+            derivation = derivation.push(
+                row![
+                    text(tr("derivation-apply-hint")).size(12).style(text::danger).width(Length::Fill),
+                    button(text(tr("apply")).size(12)).on_press(Message::ApplyDerivation).style(button::danger).padding(4),
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
+            );
+        }
+        if let Some(err) = &self.derivation_error {
+            derivation = derivation.push(text(err).size(12).style(text::danger));
+        }
+        derivation
+    }
+
+    fn selected_derivation(&self) -> Result<AccountDerivation, String> {
+        match self.derivation_kind {
+            DerivationKind::Legacy => Ok(AccountDerivation::Legacy),
+            DerivationKind::Bip44 => self
+                .derivation_index_input
+                .trim()
+                .parse::<u64>()
+                .map(|account_index| AccountDerivation::Bip44 { account_index })
+                .map_err(|_| tr("account-index-invalid").to_string()),
+        }
+    }
+
+    /// Источник пополнения кошелька при низком балансе
+    fn build_faucet(&self) -> Column<Message> {
+        let mut faucet = column![row![
+            rich_text([span(format!("{} ", tr("label-faucet"))).size(16)]),
+            pick_list(FaucetKind::ALL, Some(self.faucet_kind), Message::FaucetKindSelected).text_size(14),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center)]
+        .spacing(6)
+        .padding(6);
+        match self.faucet_kind {
+            FaucetKind::Url => {
+                faucet = faucet.push(
+                    text_input("https://faucet.example/{address}", &self.faucet_input)
+                        .on_input(Message::FaucetInputChanged)
+                        .on_submit(Message::SaveFaucet)
+                        .size(14),
+                );
+            }
+            FaucetKind::Mnemonic => {
+                faucet = faucet.push(
+                    text_input(tr("faucet-mnemonic-placeholder"), &self.faucet_input)
+                        .on_input(Message::FaucetInputChanged)
+                        .on_submit(Message::SaveFaucet)
+                        .secure(true)
+                        .size(14),
+                );
+            }
+            FaucetKind::BuiltIn if !FaucetSource::BuiltIn.is_available(APP_STATE.get_network()) => {
+                faucet = faucet.push(
+                    text(tr_args("faucet-no-builtin", &[("network", &APP_STATE.get_network())])).size(12).style(text::secondary),
+                );
+            }
+            FaucetKind::BuiltIn | FaucetKind::Disabled => {}
+        }
+        if matches!(self.faucet_kind, FaucetKind::Url | FaucetKind::Mnemonic) {
+            faucet = faucet.push(
+                button(text(tr("save")).size(12))
+                    .on_press_maybe((!self.faucet_input.trim().is_empty()).then_some(Message::SaveFaucet))
+                    .padding(4),
+            );
+        }
+        match &self.faucet_status {
+            Some(Ok(status)) => faucet = faucet.push(text(status).size(12).style(text::success)),
+            Some(Err(err)) => faucet = faucet.push(text(err).size(12).style(text::danger)),
+            None => {}
+        }
+        faucet
+    }
+
+    fn save_faucet(&mut self, source: FaucetSource) {
+        let kind = source.kind();
+        self.faucet_status = Some(match APP_STATE.set_faucet_source(source) {
+            Ok(_) => Ok(tr_args("faucet-saved", &[("kind", &kind)])),
+            Err(err) => {
+                log::error!("Error while changing faucet: {}", err);
+                Err(err)
+            }
+        });
+    }
+
+    /// Ноды по приоритету: при отказе активной клиент переключается на следующую
+    fn build_node_list(&self) -> Column<Message> {
+        let mut nodes = column![rich_text([span(format!("{} ", tr("label-nodes"))).size(16)])].spacing(6).padding(6);
+        if self.node_urls.is_empty() {
+            nodes = nodes.push(text(tr("nodes-empty")).size(12).style(text::secondary));
+        }
+        for (index, url) in self.node_urls.iter().enumerate() {
+            nodes = nodes.push(
+                row![
+                    text(format!("{}. {}", index + 1, url)).size(12).width(Length::Fill),
+                    button(text("↑").size(12))
+                        .on_press_maybe((index > 0).then_some(Message::RaiseNodeUrl(index)))
+                        .style(button::text)
+                        .padding(0),
+                    button(text("✕").size(12)).on_press(Message::RemoveNodeUrl(index)).style(button::text).padding(0),
+                ]
+                .spacing(4)
+                .align_y(Alignment::Center),
+            );
+        }
+        nodes = nodes.push(
+            row![
+                text_input("ws://host:port", &self.node_url_input)
+                    .on_input(Message::NodeUrlInputChanged)
+                    .on_submit(Message::AddNodeUrl)
+                    .padding(5)
+                    .size(14),
+                button(text(tr("add")).size(12))
+                    .on_press_maybe((!self.node_url_input.trim().is_empty()).then_some(Message::AddNodeUrl))
+                    .style(button::secondary)
+                    .padding(4),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        );
+        if let Some(err) = &self.node_list_error {
+            nodes = nodes.push(text(err).size(12).style(text::danger));
+        }
+        nodes
+            .push(toggler(self.auto_reconnect).label(tr("auto-reconnect")).on_toggle(Message::ToggleAutoReconnect))
+            .push(self.build_notification_scopes())
+            .push(
+                row![
+                    text(tr("backfill")).size(14).width(Length::Fill),
+                    pick_list(Backfill::options(), Some(self.backfill), Message::BackfillChanged).text_size(14),
+                ]
+                .align_y(Alignment::Center),
+            )
+            .push(self.build_rpc_policy())
+    }
+
+    /// Таймауты подключения и запросов к ноде и число повторов запроса
+    fn build_rpc_policy(&self) -> Column<Message> {
+        column![
+            row![
+                text(tr("connect-timeout")).size(14).width(Length::Fill),
+                pick_list(RPC_TIMEOUT_OPTIONS_SECS, Some(self.rpc_policy.connect_timeout_secs), Message::RpcConnectTimeoutChanged)
+                    .text_size(14),
+            ]
+            .align_y(Alignment::Center),
+            row![
+                text(tr("request-timeout")).size(14).width(Length::Fill),
+                pick_list(RPC_TIMEOUT_OPTIONS_SECS, Some(self.rpc_policy.request_timeout_secs), Message::RpcRequestTimeoutChanged)
+                    .text_size(14),
+            ]
+            .align_y(Alignment::Center),
+            row![
+                tooltip(
+                    text(tr("request-retries")).size(14).width(Length::Fill),
+                    text(tr("request-retries-hint")).size(12),
+                    tooltip::Position::Top,
+                ),
+                pick_list(RPC_RETRY_OPTIONS, Some(self.rpc_policy.request_retries), Message::RpcRetriesChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center),
+        ]
+        .spacing(4)
+    }
+
+    /// Источники входящих сообщений: уведомления ноды (со следующего подключения) и мемпул
+    fn build_notification_scopes(&self) -> Column<Message> {
+        let scopes = self.notification_scopes;
+        column![
+            tooltip(
+                toggler(scopes.block_added).label(tr("scope-blocks")).on_toggle(Message::ToggleBlockAddedScope),
+                text(tr("scope-blocks-hint")).size(12),
+                tooltip::Position::Top,
+            ),
+            tooltip(
+                toggler(scopes.virtual_chain_changed)
+                    .label(tr("scope-chain"))
+                    .on_toggle_maybe(scopes.block_added.then_some(Message::ToggleVirtualChainScope)),
+                text(tr("scope-chain-hint")).size(12),
+                tooltip::Position::Top,
+            ),
+            tooltip(
+                toggler(self.mempool_listening).label(tr("scope-mempool")).on_toggle(Message::ToggleMempoolListening),
+                text(tr("scope-mempool-hint")).size(12),
+                tooltip::Position::Top,
+            ),
+        ]
+        .spacing(4)
+    }
+
+    fn save_notification_scopes(&mut self, scopes: NotificationScopes) {
+        match APP_STATE.set_notification_scopes(scopes) {
+            Ok(_) => self.notification_scopes = scopes,
+            Err(err) => log::error!("Error while changing notification scopes: {}", err),
+        }
+    }
+
+    fn save_rpc_policy(&mut self, policy: RpcPolicy) {
+        match APP_STATE.set_rpc_policy(policy) {
+            Ok(_) => self.rpc_policy = policy,
+            Err(err) => log::error!("Error while changing RPC timeouts: {}", err),
+        }
+    }
+
+    /// Сохраняет фильтр содержимого; неверный шаблон не сохраняется, причина видна под списком
+    fn save_content_filter(&mut self, filter: ContentFilter) -> bool {
+        match APP_STATE.set_content_filter(filter.clone()) {
+            Ok(_) => {
+                self.content_filter = filter;
+                self.content_filter_error = None;
+                true
+            }
+            Err(err) => {
+                log::error!("Error while saving content filter: {}", err);
+                self.content_filter_error = Some(err);
+                false
+            }
+        }
+    }
+
+    /// Сохраняет список нод; при ошибке оставляет прежний и показывает причину
+    fn save_node_urls(&mut self, urls: Vec<String>) -> bool {
+        match APP_STATE.set_node_urls(urls) {
+            Ok(_) => {
+                self.node_urls = APP_STATE.get_node_urls().unwrap_or_default();
+                self.node_list_error = None;
+                true
+            }
+            Err(err) => {
+                log::error!("Error while saving node list: {}", err);
+                self.node_list_error = Some(err);
+                false
+            }
+        }
+    }
 
-        let title = column![row![
-            text("KASPEAK")
-                .style(text::success)
-                .font(font::Font { weight: iced::font::Weight::Semibold, ..font::Font::DEFAULT })
-                .size(26),
-            text("Alpha").style(text::secondary).size(14)
-        ]
-        .height(Length::Shrink)]
-        .width(Length::FillPortion(6))
-        .height(Length::Shrink);
+    fn switch_network(&mut self, network: Network) -> Task<Message> {
+        match APP_STATE.set_network(network) {
+            // Окно закрывается, main перезапускает приложение в выбранной сети
+            Ok(_) => iced::exit(),
+            Err(err) => {
+                log::error!("Error while switching network: {}", err);
+                self.network_error = Some(err);
+                Task::none()
+            }
+        }
+    }
 
-        row![
-            title,
-            pick_list_input_device,
-            // pick_list_output_device,
-            input_node_address,
-            button_connect,
+    /// Профили со своей мнемоникой, именем и файлом настроек
+    fn build_profiles(&self) -> Column<Message> {
+        let current = APP_STATE.get_profile();
+        let new_profile = text_input(tr("new-profile-placeholder"), &self.profile_input)
+            .on_input(Message::ProfileInputChanged)
+            .on_submit(Message::SwitchProfile(self.profile_input.clone()))
+            .padding(5)
+            .size(14);
+        let mut profiles = column![
+            row![
+                rich_text([span(format!("{} ", tr("label-profile"))).size(16)]),
+                pick_list(self.profiles.clone(), Some(current), Message::SwitchProfile).text_size(14),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+            row![
+                new_profile,
+                button(text(tr("create")).size(12))
+                    .on_press(Message::SwitchProfile(self.profile_input.clone()))
+                    .style(button::secondary)
+                    .padding(4),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+            text(tr("profile-switch-hint")).size(12).style(text::secondary),
         ]
-        .padding(6)
         .spacing(6)
+        .padding(6);
+        if let Some(err) = &self.profile_error {
+            profiles = profiles.push(text(err).size(12).style(text::danger));
+        }
+        profiles
     }
 
-    fn build_left_side_bar(&self) -> Column<Message> {
-        column![self.build_recorder(), Rule::horizontal(1), self.build_user_info(),]
-            .spacing(10)
-            .padding(0)
-            .height(Length::Fill)
-            .align_x(Alignment::Start)
+    /// Резервная копия мнемоники: слова показываются только после подтверждения
+    fn build_mnemonic_backup(&self) -> Column<Message> {
+        let content = match self.mnemonic_reveal {
+            MnemonicReveal::Hidden => column![button(text(tr("show-recovery-phrase")).size(12))
+                .on_press(Message::RevealMnemonicPressed)
+                .style(button::secondary)
+                .padding(4)],
+            MnemonicReveal::Confirming => column![
+                text(tr("show-recovery-phrase-confirm")).size(12).style(text::danger),
+                row![
+                    button(text(tr("show")).size(12)).on_press(Message::ConfirmRevealMnemonic).style(button::danger).padding(4),
+                    button(text(tr("cancel")).size(12)).on_press(Message::HideMnemonic).style(button::secondary).padding(4),
+                ]
+                .spacing(6),
+            ]
+            .spacing(6),
+            MnemonicReveal::Shown => {
+                let mut shown = column![
+                    container(text(APP_STATE.get_mnemonic()).size(14).font(font::Font::MONOSPACE))
+                        .padding(6)
+                        .style(container::rounded_box),
+                    row![
+                        button(text(tr("copy")).size(12)).on_press(Message::CopyMnemonic).style(button::secondary).padding(4),
+                        button(text(tr("export")).size(12)).on_press(Message::ExportMnemonic).style(button::secondary).padding(4),
+                        button(text(tr("hide")).size(12)).on_press(Message::HideMnemonic).style(button::secondary).padding(4),
+                    ]
+                    .spacing(6),
+                ]
+                .spacing(6);
+                if let Some(status) = &self.mnemonic_status {
+                    shown = shown.push(text(status).size(12).style(text::secondary));
+                }
+                shown
+            }
+        };
+        column![rich_text([span(format!("{} ", tr("label-backup"))).size(16)]), content, self.build_mnemonic_import()]
+            .spacing(6)
+            .padding(6)
     }
 
-    fn build_right_side_bar(&self) -> Column<Message> {
-        let clients_container = container(text("Clients")).padding(10).width(Length::Fill).height(Length::FillPortion(1));
+    /// Восстановление аккаунта из своей мнемоники; после импорта приложение перезапускается
+    fn build_mnemonic_import(&self) -> Column<Message> {
+        if !self.mnemonic_import_visible {
+            return column![button(text(tr("restore-recovery-phrase")).size(12))
+                .on_press(Message::ToggleMnemonicImport)
+                .style(button::secondary)
+                .padding(4)];
+        }
+        let mut import = column![];
+        if APP_STATE.is_first_run() {
+            import = import.push(text(tr("restore-first-run-hint")).size(12));
+        }
+        import = import
+            .push(
+                text_input(tr("mnemonic-words-placeholder"), &self.mnemonic_import_input)
+                    .on_input(Message::MnemonicImportInputChanged)
+                    .on_submit(Message::ImportMnemonicPressed)
+                    .padding(5)
+                    .size(14),
+            )
+            .push(text(tr("restore-replace-warning")).size(12).style(text::danger))
+            .push(
+                row![
+                    button(text(tr("restore-and-restart")).size(12))
+                        .on_press(Message::ImportMnemonicPressed)
+                        .style(button::danger)
+                        .padding(4),
+                    button(text(tr("cancel")).size(12)).on_press(Message::ToggleMnemonicImport).style(button::secondary).padding(4),
+                ]
+                .spacing(6),
+            );
+        if let Some(err) = &self.mnemonic_import_error {
+            import = import.push(text(err).size(12).style(text::danger));
+        }
+        import.spacing(6)
+    }
 
-        let transactions_scroll =
-            scrollable(column![text("Transactions")].padding(10)).width(Length::Fill).height(Length::FillPortion(1));
+    /// Переключатель телеметрии с предпросмотром отправляемого отчёта
+    fn build_telemetry(&self) -> Column<Message> {
+        let preview = tooltip(
+            text("ℹ").size(16),
+            container(text(preview_report()).size(12).font(font::Font::MONOSPACE)).padding(6).style(container::rounded_box),
+            tooltip::Position::Top,
+        );
+        let mut telemetry_column =
+            column![row![toggler(self.telemetry_enabled).label(tr("share-stats")).on_toggle(Message::ToggleTelemetry), preview]
+                .spacing(6)
+                .align_y(Alignment::Center)
+                .padding(6)];
+        if self.telemetry_enabled {
+            telemetry_column = telemetry_column.push(
+                row![text_input(tr("telemetry-endpoint-placeholder"), &self.telemetry_endpoint)
+                    .on_input(Message::TelemetryEndpointChanged)
+                    .size(14)]
+                .padding(6),
+            );
+        }
+        telemetry_column.push(self.build_protocol_stats()).push(
+            row![tooltip(
+                toggler(self.salvage_payloads).label(tr("salvage-payloads")).on_toggle(Message::ToggleSalvagePayloads),
+                text(tr("salvage-payloads-hint")).size(12),
+                tooltip::Position::Top,
+            )]
+            .padding(6),
+        )
+    }
 
-        column![clients_container, Rule::horizontal(1), transactions_scroll]
-            .spacing(10)
-            .padding(0)
-            .height(Length::Fill)
-            .align_x(Alignment::Start)
+    /// Какие версии протокола и типы сообщений встречаются в сети с последнего отчёта телеметрии
+    fn build_protocol_stats(&self) -> Column<Message> {
+        let counters = APP_STATE.get_telemetry_counters().unwrap_or_default();
+        let versions =
+            counters.protocol_versions.iter().map(|(version, count)| format!("v{version} ×{count}")).collect::<Vec<_>>().join(", ");
+        let types = counters
+            .message_types
+            .iter()
+            .map(|(message_type, count)| format!("{message_type} ×{count}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut stats = column![text(tr("protocol-stats-title")).size(14)].spacing(2).padding(6);
+        if versions.is_empty() {
+            return stats.push(text(tr("protocol-stats-empty")).size(12).style(text::secondary));
+        }
+        stats = stats
+            .push(text(tr_args("protocol-stats-versions", &[("versions", &versions)])).size(12).style(text::secondary))
+            .push(text(tr_args("protocol-stats-types", &[("types", &types)])).size(12).style(text::secondary));
+        match counters.newest_protocol_version {
+            Some(newest) if newest > PROTOCOL_VERSION => stats.push(
+                text(tr_args("protocol-newer", &[("newest", &newest), ("current", &PROTOCOL_VERSION)])).size(12).style(text::danger),
+            ),
+            _ => stats,
+        }
     }
 
-    fn build_recorder(&self) -> Column<Message> {
-        let button_recording = button(if self.is_recording { "Stop Recording" } else { "Start Recording" })
-            .on_press(Message::ToggleRecording(!self.is_recording));
+    /// Список каналов: вход по номеру, избранные и недавние; переключение одним кликом
+    fn build_channel_list(&self) -> Column<Message> {
+        let channel_entry = |channel: u32| {
+            let favorite = self.favorite_channels.contains(&channel);
+            let current = channel == self.channel_number && !self.direct_mode;
+            let label = match self.unread_mentions.get(&channel) {
+                Some(count) => {
+                    row![text(self.channel_label(channel)).size(14), text(format!("@{}", count)).size(12).style(text::danger)]
+                        .spacing(6)
+                }
+                None => row![text(self.channel_label(channel)).size(14)],
+            };
+            row![
+                button(label)
+                    .on_press_maybe((!current).then_some(Message::SwitchChannel(channel)))
+                    .style(if current { button::primary } else { button::text })
+                    .padding(2)
+                    .width(Length::Fill),
+                button(text(if favorite { "★" } else { "☆" }).size(14))
+                    .on_press(Message::ToggleFavoriteChannel(channel, !favorite))
+                    .style(button::text)
+                    .padding(0),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center)
+        };
 
-        let toggle_listen_self = toggler(self.listen_self).label("Listen to yourself").on_toggle(Message::ToggleListenSelf);
+        let join = row![
+            text_input(tr("channel-number-placeholder"), &self.channel_number_input)
+                .on_input(Message::ChannelInputChanged)
+                .on_submit(Message::JoinChannel)
+                .padding(5)
+                .size(14),
+            button(text(tr("join")).size(14))
+                .on_press_maybe((!self.channel_number_input.is_empty()).then_some(Message::JoinChannel))
+                .style(button::secondary)
+                .padding(5),
+        ]
+        .spacing(6);
 
-        let toggle_mute_all = toggler(self.mute_all).label("Mute All").on_toggle(Message::ToggleMuteAll);
+        let mut channels = column![rich_text([span(tr_args("channel-title", &[("channel", &self.channel_number)])).size(16)]), join]
+            .spacing(4)
+            .padding(6);
+        if !self.favorite_channels.is_empty() {
+            channels = channels.push(text(tr("channels-favorites")).size(12).style(text::secondary));
+            channels = self.favorite_channels.iter().fold(channels, |channels, channel| channels.push(channel_entry(*channel)));
+        }
+        // Каналы с упоминаниями, которых нет ни в избранном, ни в недавних
+        let mut mentioned: Vec<u32> = self
+            .unread_mentions
+            .keys()
+            .copied()
+            .filter(|channel| !self.favorite_channels.contains(channel) && !self.recent_channels.contains(channel))
+            .collect();
+        if !mentioned.is_empty() {
+            mentioned.sort();
+            channels = channels.push(text(tr("channels-mentions")).size(12).style(text::danger));
+            channels = mentioned.into_iter().fold(channels, |channels, channel| channels.push(channel_entry(channel)));
+        }
+        // Избранные уже показаны выше
+        let recent: Vec<u32> =
+            self.recent_channels.iter().copied().filter(|channel| !self.favorite_channels.contains(channel)).collect();
+        if !recent.is_empty() {
+            channels = channels.push(text(tr("channels-recent")).size(12).style(text::secondary));
+            channels = recent.into_iter().fold(channels, |channels, channel| channels.push(channel_entry(channel)));
+        }
+        channels
+    }
 
-        column![
-            row![button_recording.width(Length::FillPortion(1)).padding(9)].padding(5),
-            row![toggle_listen_self.width(Length::FillPortion(1))].padding(5),
-            row![toggle_mute_all.width(Length::FillPortion(1))].padding(5),
-        ]
+    /// Режим фокусировки: уведомления только из выбранных каналов на заданное время
+    fn build_focus_mode(&self) -> Row<Message> {
+        match self.focus_until {
+            Some(until) => {
+                let left = (until - chrono::Utc::now().timestamp()).max(0);
+                row![
+                    text(tr_args("focus-left", &[("time", &format!("{}:{:02}", left / 60, left % 60))])).size(14).width(Length::Fill),
+                    button(text(tr("focus-end")).size(12)).on_press(Message::EndFocusMode).style(button::secondary).padding(2),
+                ]
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .padding(6)
+            }
+            None => row![
+                text_input(tr("focus-allowlist-placeholder"), &self.focus_allowlist_input)
+                    .on_input(Message::FocusAllowlistChanged)
+                    .size(14),
+                pick_list(FOCUS_DURATION_OPTIONS_MIN, Some(self.focus_duration_min), Message::FocusDurationChanged).text_size(14),
+                button(text(tr("focus-start")).size(12)).on_press(Message::StartFocusMode).style(button::secondary).padding(4),
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4)
+            .padding(6),
+        }
     }
 
-    fn build_user_info(&self) -> Column<Message> {
-        let balance = APP_STATE.get_balance().unwrap_or_else(|e| {
-            log::error!("Error while reading balance: {}", e);
-            0
-        });
-        let balance_in_kas = (balance as f64) / UNIT;
+    /// Состояние заявки на наше имя: кнопка, если имя свободно, иначе отметка владельца
+    fn build_claim_status(&self) -> Row<Message> {
+        match self.username_claimed_by_me {
+            Some(true) => {
+                row![tooltip(text("✔").size(16).style(text::success), text(tr("claim-mine")).size(12), tooltip::Position::Bottom)]
+            }
+            Some(false) => {
+                row![tooltip(text("⚠").size(16).style(text::danger), text(tr("claim-other")).size(12), tooltip::Position::Bottom)]
+            }
+            None => {
+                row![button(text(tr("claim")).size(12)).on_press(Message::ClaimUsernamePressed).style(button::secondary).padding(2)]
+            }
+        }
+    }
 
-        let utxos = APP_STATE.get_utxos().unwrap_or_else(|e| {
-            log::error!("Error while reading UTXO count: {}", e);
-            0
-        });
+    /// Раздел настроек с сочетаниями клавиш: модификаторы и клавиша для каждого действия
+    fn build_shortcut_settings(&self) -> Column<Message> {
+        let key_options: Vec<KeyChoice> =
+            std::iter::once(KeyChoice(None)).chain(SHORTCUT_KEYS.map(|key| KeyChoice(Some(key)))).collect();
+        let mut shortcuts = column![section(tr("settings-shortcuts"))];
+        for action in ShortcutAction::ALL {
+            let current = self.shortcuts.get(action).cloned();
+            let modifier = |label: &'static str, checked: bool, apply: fn(&mut Shortcut, bool)| {
+                let current = current.clone();
+                let toggle = current.as_ref().map(|shortcut| {
+                    let shortcut = shortcut.clone();
+                    move |value: bool| {
+                        let mut shortcut = shortcut.clone();
+                        apply(&mut shortcut, value);
+                        Message::ShortcutChanged(action, Some(shortcut))
+                    }
+                });
+                checkbox(label, checked).on_toggle_maybe(toggle).size(14).text_size(12)
+            };
+            let selected = KeyChoice(current.as_ref().and_then(|shortcut| SHORTCUT_KEYS.into_iter().find(|key| *key == shortcut.key)));
+            let base = current.clone();
+            let key_pick = pick_list(key_options.clone(), Some(selected), move |choice: KeyChoice| {
+                let shortcut =
+                    choice.0.map(|key| Shortcut { key: key.to_string(), ..base.clone().unwrap_or_else(|| Shortcut::new(key)) });
+                Message::ShortcutChanged(action, shortcut)
+            })
+            .text_size(14);
+            let label = text(action.to_string()).size(14).width(Length::Fill);
+            let label: Element<Message> = if action == ShortcutAction::PushToTalk {
+                tooltip(label, text(tr("shortcut-push-to-talk-hint")).size(12), tooltip::Position::Top).into()
+            } else {
+                label.into()
+            };
+            shortcuts = shortcuts.push(
+                row![
+                    label,
+                    modifier("Ctrl", current.as_ref().is_some_and(|shortcut| shortcut.ctrl), |shortcut, value| shortcut.ctrl = value),
+                    modifier("Shift", current.as_ref().is_some_and(|shortcut| shortcut.shift), |shortcut, value| shortcut.shift =
+                        value),
+                    modifier("Alt", current.as_ref().is_some_and(|shortcut| shortcut.alt), |shortcut, value| shortcut.alt = value),
+                    key_pick,
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .padding(6),
+            );
+        }
+        shortcuts
+    }
 
-        let fee_in_kas = (self.fee_size as f64) / UNIT;
-        let channel = self.channel_number.to_string();
-        let fee_input = text_input("Fee (sompi)", &self.fee_size_input)
-            .on_input(Message::FeeInputChanged)
-            .padding(5)
-            .size(16)
-            .width(Length::FillPortion(1));
+    /// Аватар по ключу подписи отправителя, а без него — identicon (None, только если он ещё не построен).
+    /// Неподписанным сообщениям аватар не положен: имя может взять любой
+    fn build_avatar(&self, username: &str, sender_key: Option<&str>, size: f32) -> Option<Element<Message>> {
+        let handle = match sender_key.and_then(|key| self.avatar_images.get(key)) {
+            Some(handle) => handle,
+            None => self.identicons.get(&identicon_seed(username, sender_key.map(str::to_string)))?,
+        };
+        Some(image(handle.clone()).width(size).height(size).into())
+    }
+
+    /// Строит identicon отправителя, если его ещё нет; seed меняется, когда становится известен ключ
+    fn cache_identicon(&mut self, username: &str, sender_key: Option<&str>) {
+        let seed = identicon_seed(username, sender_key.map(str::to_string));
+        if !self.identicons.contains_key(&seed) {
+            let (side, rgba) = identicon_rgba(&seed);
+            self.identicons.insert(seed, image::Handle::from_rgba(side, side, rgba));
+        }
+    }
 
-        let channel_input = text_input("Channel", &self.channel_number_input)
-            .on_input(Message::ChannelInputChanged)
+    fn build_avatar_settings(&self) -> Column<Message> {
+        let mut title = row![rich_text([span(format!("{} ", tr("label-avatar"))).size(16)])].spacing(6).align_y(Alignment::Center);
+        if let Some(handle) = self.avatar_images.get(&self.identity_key) {
+            title = title.push(image(handle.clone()).width(32.0).height(32.0));
+        }
+        let path_input = text_input(tr("avatar-file-placeholder"), &self.avatar_path_input)
+            .on_input(Message::AvatarPathChanged)
+            .on_submit(Message::SetAvatarPressed)
             .padding(5)
-            .size(16)
-            .width(Length::FillPortion(1));
+            .size(14);
+        let set_button =
+            button(text(tr("avatar-set")).size(12)).on_press(Message::SetAvatarPressed).style(button::secondary).padding(4);
+        let announce_button = tooltip(
+            button(text(tr("avatar-broadcast")).size(12)).on_press(Message::AnnounceAvatarPressed).style(button::secondary).padding(4),
+            text(tr("avatar-broadcast-hint")).size(12),
+            tooltip::Position::Top,
+        );
 
-        let address_button = button(text(&self.display_address).align_x(Alignment::End))
-            .on_press(Message::OpenLink(format!("https://explorer-tn11.kaspa.org/addresses/{}", &self.full_address)))
-            .style(button::text)
-            .width(Length::Shrink)
-            .height(Length::Shrink);
+        column![title, row![path_input, set_button, announce_button].spacing(4).align_y(Alignment::Center)].spacing(4).padding(6)
+    }
 
-        column![
-            row![rich_text([span("📍 Address: ").size(16)])].padding(6),
-            row![address_button],
-            row![rich_text([span("🪪 Name: ").size(16)]), rich_text([span(&self.username).size(16)])].padding(6),
-            row![rich_text([span("💵 Balance: ").size(16)]), text(format!("{:.3} TKAS", balance_in_kas)).size(16)].padding(6),
-            row![rich_text([span("↕️ UTXO's: ").size(16)]), text(utxos.to_string()).size(16)].padding(6),
-            row![rich_text([span("💬 Channel: ").size(16)]), text(format!("{channel}")).size(16)].padding(6),
-            row![channel_input].padding(6),
-            row![rich_text([span("🧾 Fee: ").size(16)]), text(format!("{:.8} TKAS", fee_in_kas)).size(16),].padding(6),
-            row![fee_input].padding(6),
-        ]
+    /// Расчётная высота каждого сообщения ленты: строка на каждые CHAT_CHARS_PER_LINE_ESTIMATE символов абзаца,
+    /// карточка превью ссылки и разделитель дня перед сообщением. Объявления в ленте выглядят как обычные сообщения
+    fn chat_row_heights(&self) -> Vec<f32> {
+        let mut previous_date = None;
+        self.chat_messages
+            .iter()
+            .map(|msg| {
+                let lines: usize =
+                    msg.get_content().lines().map(|line| line.chars().count().div_ceil(CHAT_CHARS_PER_LINE_ESTIMATE).max(1)).sum();
+                let mut height = self.chat_density.row_estimate_px() + lines.saturating_sub(1) as f32 * CHAT_LINE_ESTIMATE_PX;
+                if msg.link_url.as_deref().is_some_and(|url| APP_STATE.chat_state.get_link_preview(url).is_some()) {
+                    height += CHAT_LINK_PREVIEW_ESTIMATE_PX;
+                }
+                let date = msg.time.date_naive();
+                if previous_date.replace(date) != Some(date) {
+                    height += CHAT_DAY_SEPARATOR_ESTIMATE_PX;
+                }
+                height
+            })
+            .collect()
     }
 
-    fn build_chat_view(&self) -> Column<Message> {
-        let mut messages_col = column![];
+    /// Диапазон сообщений для построения: видимая часть ленты и CHAT_RENDER_OVERSCAN сверху и снизу.
+    /// До первой прокрутки лента стоит в конце
+    fn visible_message_range(&self, heights: &[f32]) -> (usize, usize) {
+        let total = heights.len();
+        let (offset, height) = self.chat_viewport.unwrap_or((f32::MAX, CHAT_DEFAULT_VIEWPORT_PX));
+        let mut first_visible = 0;
+        let mut top = 0.0;
+        while first_visible < total && top + heights[first_visible] <= offset {
+            top += heights[first_visible];
+            first_visible += 1;
+        }
+        let mut last_visible = first_visible;
+        let mut covered = 0.0;
+        while last_visible < total && covered < height {
+            covered += heights[last_visible];
+            last_visible += 1;
+        }
+        // У конца ленты видимую область заполняют сообщения выше
+        while first_visible > 0 && covered < height {
+            first_visible -= 1;
+            covered += heights[first_visible];
+        }
+        let first = first_visible.saturating_sub(CHAT_RENDER_OVERSCAN);
+        let last = (last_visible + CHAT_RENDER_OVERSCAN).min(total);
+        (first, last)
+    }
+
+    /// Одно сообщение ленты: имя, текст, превью ссылки, отметки доставки и меню действий
+    fn build_chat_message<'a>(&'a self, msg: &'a ChatMessage) -> Element<'a, Message> {
+        let compact = self.chat_density == ChatDensity::Compact;
+        let name_size = if compact { 14 } else { 16 };
+        let name = rich_text([span(msg.user.get_username()).size(name_size)]).style(if msg.claim_mismatch {
+            text::danger
+        } else {
+            text::primary
+        });
+        let name: Element<Message> = match last_seen_text(msg.get_username()) {
+            Some(last_seen) => tooltip(name, text(last_seen).size(12), tooltip::Position::Top).into(),
+            None => name.into(),
+        };
+        let name_text = if msg.claim_mismatch {
+            row![tooltip(text("⚠").size(16).style(text::danger), text(tr("claim-other")).size(12), tooltip::Position::Right), name]
+                .spacing(4)
+        } else {
+            row![name]
+        };
+        let name_text = match self.contact_for(msg) {
+            Some(contact) => name_text.push(text(format!("📇 {}", contact.name)).size(14).style(text::secondary)),
+            // Ключ подписи надёжнее адреса: адрес из выходов транзакции выбирает сам отправитель
+            None => match msg.sender_key.as_deref().or(msg.sender_address.as_deref()) {
+                Some(target) => name_text.push(tooltip(
+                    button(text("📇").size(12))
+                        .style(button::text)
+                        .padding(0)
+                        .on_press(Message::AddSenderToContacts(msg.get_username().to_string(), target.to_string())),
+                    text(tr("add-to-contacts")).size(12),
+                    tooltip::Position::Right,
+                )),
+                None => name_text,
+            },
+        };
+        let name_text = if msg.get_username() == self.username {
+            name_text
+        } else {
+            name_text
+                .push(tooltip(
+                    button(text("🙈").size(12))
+                        .style(button::text)
+                        .padding(0)
+                        .on_press(Message::ToggleUserIgnored(msg.get_username().to_string(), true)),
+                    text(tr("ignore-user")).size(12),
+                    tooltip::Position::Right,
+                ))
+                .push(tooltip(
+                    button(text("🚫").size(12))
+                        .style(button::text)
+                        .padding(0)
+                        .on_press(Message::BlockSender(msg.get_username().to_string(), msg.sender_key.clone())),
+                    text(tr("block-sender")).size(12),
+                    tooltip::Position::Right,
+                ))
+        };
+        let name_text = name_text.spacing(6).align_y(Alignment::Center).width(if compact { Length::Shrink } else { Length::Fill });
+
+        let menu_open = self.message_menu == Some(msg.get_id());
+        let content_text: Element<Message> = if menu_open {
+            text_editor(&self.message_selection).on_action(Message::MessageSelectionAction).size(15).into()
+        } else {
+            Self::build_message_content(msg.get_content(), if compact { 14 } else { 15 })
+        };
 
-        for msg in &self.chat_messages {
-            let name_text = rich_text([span(msg.user.get_username()).size(16)]).style(text::primary).width(Length::Fill);
+        // Полная дата и время — в подсказке, а в раскрытом сообщении вместо короткого времени
+        let full_time = format_date_time(&msg.time);
+        let time_text: Element<Message> = if menu_open {
+            text(full_time).size(12).style(text::secondary).into()
+        } else {
+            tooltip(text(msg.get_time()).size(12).style(text::secondary), text(full_time).size(12), tooltip::Position::Left).into()
+        };
 
-            let content_text = rich_text([span(msg.get_content()).size(15)]).style(text::base).width(Length::Fill);
+        // Отметки доставки и действия: в уютном режиме под текстом, в компактном — в конце строки
+        let mut marks = row![].spacing(6).align_y(Alignment::Center);
+        if let Some(skew) = msg.clock_skew_secs {
+            let hint = tr_args("clock-skew-hint", &[("skew", &format_skew(skew))]);
+            marks = marks.push(tooltip(text("⏱").size(12).style(text::secondary), text(hint).size(12), tooltip::Position::Left));
+        }
+        if let Some(delivery) = msg.delivery {
+            let (mark, hint) = match delivery {
+                DeliveryState::Sent => ("🕓", tr("delivery-sent")),
+                DeliveryState::Confirmed => ("✓", tr("delivery-confirmed")),
+                DeliveryState::Failed => ("⚠", tr("delivery-failed")),
+            };
+            let mark = match delivery {
+                DeliveryState::Failed => text(mark).size(12).style(text::danger),
+                DeliveryState::Sent | DeliveryState::Confirmed => text(mark).size(12).style(text::secondary),
+            };
+            marks = marks.push(tooltip(mark, text(hint).size(12), tooltip::Position::Left));
+        }
+        if msg.possibly_reverted {
+            marks = marks.push(tooltip(
+                text("↺").size(12).style(text::danger),
+                text(tr("delivery-reverted")).size(12),
+                tooltip::Position::Left,
+            ));
+        }
+        if let Some(tx_id) = msg.get_tx_id() {
+            let copy_link =
+                button(text("🔗").size(12)).style(button::text).padding(0).on_press(Message::CopyPermalink(tx_id.to_string()));
+            marks = marks.push(tooltip(copy_link, text(tr("copy-message-link")).size(12), tooltip::Position::Left));
+        }
+        let menu_button = button(text("⋯").size(12)).style(button::text).padding(0).on_press(if menu_open {
+            Message::CloseMessageMenu
+        } else {
+            Message::OpenMessageMenu(msg.get_id())
+        });
+        marks = marks.push(menu_button);
 
-            let time_text =
-                rich_text([span(msg.get_time()).size(12)]).width(Length::Fill).style(text::secondary).align_x(Alignment::End);
+        let preview = msg.link_url.as_deref().and_then(|url| APP_STATE.chat_state.get_link_preview(url));
+        let mut message_block = if compact {
+            // IRC-стиль: время, имя и текст в одну строку
+            let line = row![time_text, name_text, content_text, marks].spacing(6).align_y(Alignment::Center);
+            column![line].spacing(2).padding([1, 5])
+        } else {
+            column![name_text, content_text].spacing(2).padding(5)
+        };
+        if let Some(preview) = preview {
+            message_block = message_block.push(Self::build_link_preview(preview));
+        }
+        if !compact {
+            message_block =
+                message_block.push(row![Space::with_width(Length::Fill), time_text, marks].spacing(6).align_y(Alignment::Center));
+        }
+        if menu_open {
+            message_block = message_block.push(self.build_message_menu(msg));
+        }
+        let avatar = if compact { None } else { self.build_avatar(msg.get_username(), msg.sender_key.as_deref(), 32.0) };
+        let message_block: Element<Message> = match avatar {
+            Some(avatar) => row![avatar, message_block].spacing(6).into(),
+            None => message_block.into(),
+        };
+        let message_block = mouse_area(message_block).on_right_press(Message::OpenMessageMenu(msg.get_id()));
 
-            let message_block = column![name_text, content_text, time_text].spacing(2).padding(5);
+        if self.highlighted_message_id == Some(msg.get_id()) {
+            container(message_block).style(container::rounded_box).width(Length::Fill).into()
+        } else if msg.mentions_me {
+            container(message_block).style(mention_style).width(Length::Fill).into()
+        } else {
+            message_block.into()
+        }
+    }
 
-            messages_col = messages_col.push(message_block);
+    fn build_chat_view(&self) -> Column<Message> {
+        // Строятся только сообщения в видимой области (с запасом); остальные заменены отступами расчётной высоты
+        let heights = self.chat_row_heights();
+        let (first, last) = self.visible_message_range(&heights);
+        let mut messages_col = column![Space::with_height(heights[..first].iter().sum::<f32>())];
+        for index in first..last {
+            let msg = &self.chat_messages[index];
+            let date = msg.time.date_naive();
+            if index == 0 || self.chat_messages[index - 1].time.date_naive() != date {
+                messages_col = messages_col.push(Self::build_day_separator(date));
+            }
+            messages_col = messages_col.push(self.build_chat_message(msg));
         }
+        messages_col = messages_col.push(Space::with_height(heights[last..].iter().sum::<f32>()));
 
-        let scroll_of_messages =
-            scrollable(messages_col).id(self.chat_scroll_id.clone()).width(Length::Fill).height(Length::FillPortion(7));
+        let scroll_of_messages = scrollable(messages_col)
+            .id(self.chat_scroll_id.clone())
+            .on_scroll(Message::ChatScrolled)
+            .width(Length::Fill)
+            .height(Length::FillPortion(7));
+        let scroll_of_messages: Element<Message> = if self.chat_at_bottom {
+            scroll_of_messages.into()
+        } else {
+            let label = if self.unseen_messages > 0 {
+                tr_args("jump-to-latest-new", &[("count", &self.unseen_messages)])
+            } else {
+                tr("jump-to-latest").to_string()
+            };
+            let jump = button(text(label).size(13)).on_press(Message::JumpToLatest).style(button::primary).padding([4, 10]);
+            stack![
+                scroll_of_messages,
+                container(jump).width(Length::Fill).height(Length::Fill).align_x(Alignment::End).align_y(Alignment::End).padding(12)
+            ]
+            .width(Length::Fill)
+            .height(Length::FillPortion(7))
+            .into()
+        };
 
+        let placeholder = if self.direct_mode {
+            tr_args("chat-placeholder-direct", &[("peer", &shorten_key(&self.direct_peer_input))])
+        } else if self.announcement_mode {
+            tr_args("chat-placeholder-announcement", &[("channel", &self.channel_number)])
+        } else {
+            tr_args("chat-placeholder", &[("channel", &self.channel_number)])
+        };
+        let send_shortcut = self.shortcuts.send_message.clone();
         let chat_editor = text_editor(&self.chat_editor_content)
-            .placeholder(format!("Type a message for channel #{}", self.channel_number))
+            .placeholder(placeholder)
             .on_action(Message::ChatEditorAction)
             .size(16)
             .height(Length::FillPortion(2))
-            .key_binding(|key_press| match key_press.key.as_ref() {
-                keyboard::Key::Named(key::Named::Enter) if !key_press.modifiers.shift() => {
+            .key_binding(move |key_press| {
+                let modifiers = key_press.modifiers;
+                let is_send = send_shortcut.as_ref().is_some_and(|shortcut| {
+                    key_name(&key_press.key)
+                        .is_some_and(|name| shortcut.matches(name, modifiers.control(), modifiers.shift(), modifiers.alt()))
+                });
+                if is_send {
                     Some(text_editor::Binding::Custom(Message::ChatSendPressed))
+                } else {
+                    text_editor::Binding::from_key_press(key_press)
                 }
-                _ => text_editor::Binding::from_key_press(key_press),
             });
 
         let msg_len = self.chat_editor_content.text().chars().count();
         let max_symbols_field =
             rich_text([span(format!("{}/{}", msg_len, MAX_TEXT_CHARS)).size(14)]).style(text::secondary).width(Length::FillPortion(5));
 
+        // Срок жизни действует только на сообщения в канал
+        let ttl_pick_list =
+            pick_list(MessageTtl::options(), Some(self.message_ttl), Message::MessageTtlChanged).text_size(14).width(Length::Shrink);
+
         let send_button =
-            button(text("Send").align_x(Alignment::Center)).on_press(Message::ChatSendPressed).width(Length::FillPortion(1));
+            button(text(tr("send")).align_x(Alignment::Center)).on_press(Message::ChatSendPressed).width(Length::FillPortion(1));
 
-        column![row![scroll_of_messages], row![chat_editor], row![max_symbols_field, send_button],]
+        let mut chat_column = column![self.build_direct_header()];
+        if !self.direct_mode {
+            chat_column = chat_column.push(self.build_channel_header());
+        }
+        if let Some(announcement) = self.announcement.as_ref().filter(|_| !self.direct_mode) {
+            chat_column = chat_column.push(Self::build_announcement_banner(announcement));
+        }
+        if !self.direct_mode && !self.pinned_messages.is_empty() {
+            chat_column = chat_column.push(self.build_pinned_bar());
+        }
+        chat_column
+            .push(row![scroll_of_messages])
+            .push(row![chat_editor])
+            .push(row![max_symbols_field, ttl_pick_list, send_button])
             .spacing(0)
             .padding(0)
             .height(Length::Fill)
     }
 
+    /// Разделитель перед первым сообщением нового дня
+    fn build_day_separator<'a>(date: NaiveDate) -> Element<'a, Message> {
+        row![Rule::horizontal(1), text(day_label(date)).size(12).style(text::secondary), Rule::horizontal(1)]
+            .spacing(8)
+            .padding([4, 5])
+            .align_y(Alignment::Center)
+            .into()
+    }
+
+    /// Текст сообщения; ссылки подчёркнуты и открываются кликом
+    fn build_message_content<'a>(content: &'a str, size: u16) -> Element<'a, Message> {
+        let spans: Vec<_> = split_links(content)
+            .into_iter()
+            .map(|(segment, is_link)| {
+                if is_link {
+                    span(segment).size(size).underline(true).color(Color::from_rgb(0.35, 0.6, 1.0)).link(segment.to_string())
+                } else {
+                    span(segment).size(size)
+                }
+            })
+            .collect();
+        rich_text(spans).on_link(Message::OpenLink).style(text::base).width(Length::Fill).into()
+    }
+
+    /// Действия над сообщением: копирование, ответ и блокировка отправителя
+    fn build_message_menu<'a>(&self, msg: &ChatMessage) -> Element<'a, Message> {
+        let action =
+            |label: &'static str, message: Message| button(text(label).size(12)).on_press(message).style(button::secondary).padding(4);
+        let mut actions = row![
+            action(tr("menu-copy-text"), Message::CopyToClipboard(msg.get_content().to_string())),
+            action(tr("menu-copy-name"), Message::CopyToClipboard(msg.get_username().to_string())),
+        ]
+        .spacing(4);
+        if let Some(tx_id) = msg.get_tx_id() {
+            actions = actions.push(action(tr("menu-copy-tx-id"), Message::CopyToClipboard(tx_id.to_string())));
+        }
+        actions = actions.push(action(tr("menu-reply"), Message::ReplyToMessage(msg.get_id())));
+        if !self.direct_mode {
+            actions = match self.pinned_messages.iter().find(|pin| pin.matches(msg)) {
+                Some(pin) => actions.push(action(tr("menu-unpin"), Message::UnpinMessage(pin.clone()))),
+                None => actions.push(action(tr("menu-pin"), Message::PinMessage(msg.get_id()))),
+            };
+        }
+        if msg.get_username() != self.username {
+            actions = actions.push(action(tr("ignore-user"), Message::ToggleUserIgnored(msg.get_username().to_string(), true)));
+            actions = actions.push(
+                button(text(tr("block-sender")).size(12))
+                    .on_press(Message::BlockSender(msg.get_username().to_string(), msg.sender_key.clone()))
+                    .style(button::danger)
+                    .padding(4),
+            );
+        }
+        actions = actions.push(button(text("✕").size(12)).on_press(Message::CloseMessageMenu).style(button::text).padding(4));
+        container(actions.align_y(Alignment::Center)).padding(4).style(container::rounded_box).into()
+    }
+
+    /// Закреплённое объявление канала над лентой сообщений
+    fn build_announcement_banner<'a>(announcement: &ChatMessage) -> Element<'a, Message> {
+        let title = text(format!("📢 {}", announcement.get_username()))
+            .size(14)
+            .style(if announcement.claim_mismatch { text::danger } else { text::primary })
+            .width(Length::Fill);
+        let dismiss = button(text("✕").size(12))
+            .style(button::text)
+            .padding(0)
+            .on_press(Message::DismissAnnouncement(announcement.channel, announcement.get_id()));
+        let content = text(announcement.get_content().to_string()).size(15);
+        let time = text(announcement.get_time()).size(12).style(text::secondary);
+
+        container(column![row![title, time, dismiss].spacing(6).align_y(Alignment::Center), content].spacing(4))
+            .padding(8)
+            .style(container::rounded_box)
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// Закреплённые сообщения канала над лентой; клик по сообщению из сети переходит к нему
+    fn build_pinned_bar(&self) -> Column<Message> {
+        self.pinned_messages.iter().fold(column![].spacing(2).padding(6), |bar, pin| {
+            let first_line = pin.content.lines().next().unwrap_or_default();
+            let mut preview: String = first_line.chars().take(PINNED_PREVIEW_CHARS).collect();
+            if preview.len() < pin.content.len() {
+                preview.push('…');
+            }
+            let open = pin.tx_id.as_ref().map(|tx_id| Message::OpenPermalink(build_permalink(tx_id)));
+            bar.push(
+                row![
+                    button(text(format!("📌 {}: {}", pin.username, preview)).size(13))
+                        .on_press_maybe(open)
+                        .style(button::text)
+                        .padding(0)
+                        .width(Length::Fill),
+                    button(text("✕").size(12)).on_press(Message::UnpinMessage(pin.clone())).style(button::text).padding(0),
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
+            )
+        })
+    }
+
+    /// Карточка превью ссылки под сообщением
+    fn build_link_preview<'a>(preview: LinkPreview) -> Element<'a, Message> {
+        let mut card =
+            column![text(preview.title).size(14).font(font::Font { weight: font::Weight::Semibold, ..font::Font::DEFAULT })];
+        if let Some(description) = preview.description {
+            card = card.push(text(description).size(13).style(text::secondary));
+        }
+        button(container(card.spacing(2)).padding(6).style(container::rounded_box).width(Length::Fill))
+            .on_press(Message::OpenLink(preview.url))
+            .style(button::text)
+            .padding(0)
+            .into()
+    }
+
+    /// Своё название, тема и заметки текущего канала; ✎ открывает их редактор
+    fn build_channel_header(&self) -> Column<Message> {
+        if let Some(editor) = &self.channel_info_editor {
+            return column![
+                text_input(tr("channel-name-placeholder"), &editor.name)
+                    .on_input(Message::ChannelNameInputChanged)
+                    .on_submit(Message::SaveChannelInfo)
+                    .padding(5)
+                    .size(14),
+                text_input(tr("channel-topic-placeholder"), &editor.topic)
+                    .on_input(Message::ChannelTopicInputChanged)
+                    .on_submit(Message::SaveChannelInfo)
+                    .padding(5)
+                    .size(14),
+                text_input(tr("channel-notes-placeholder"), &editor.notes)
+                    .on_input(Message::ChannelNotesInputChanged)
+                    .on_submit(Message::SaveChannelInfo)
+                    .padding(5)
+                    .size(14),
+                row![
+                    button(text(tr("save")).size(12)).on_press(Message::SaveChannelInfo).style(button::primary).padding(4),
+                    button(text(tr("cancel")).size(12)).on_press(Message::EditChannelInfo).style(button::secondary).padding(4),
+                    text(tr("channel-info-hint")).size(12).style(text::secondary),
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
+            ]
+            .spacing(4)
+            .padding(5);
+        }
+        let info = self.channel_info.get(&self.channel_number).cloned().unwrap_or_default();
+        let mut title =
+            row![text(self.channel_label(self.channel_number)).size(15).style(text::primary)].spacing(8).align_y(Alignment::Center);
+        if !info.topic.is_empty() {
+            title = title.push(text(info.topic).size(13).style(text::secondary));
+        }
+        let edit = button(text("✎").size(12)).style(button::text).padding(0).on_press(Message::EditChannelInfo);
+        title = title.push(Space::with_width(Length::Fill)).push(tooltip(
+            edit,
+            text(tr("channel-info-edit")).size(12),
+            tooltip::Position::Left,
+        ));
+        let mut header = column![title].spacing(2).padding([2, 5]);
+        if !info.notes.is_empty() {
+            header = header.push(text(info.notes).size(12).style(text::secondary));
+        }
+        header
+    }
+
+    /// Переключатель личных сообщений: ключ получателя и список известных собеседников
+    fn build_direct_header(&self) -> Column<Message> {
+        let toggle_direct = toggler(self.direct_mode).label(tr("direct-messages")).on_toggle(Message::ToggleDirectMode);
+        let toggle_previews = tooltip(
+            toggler(self.link_previews_enabled).label(tr("link-previews")).on_toggle(Message::ToggleLinkPreviews),
+            text(tr("link-previews-hint")).size(12),
+            tooltip::Position::Bottom,
+        );
+        let mut toggles = row![toggle_direct, toggle_previews].spacing(20).padding(5);
+        if !self.direct_mode {
+            toggles = toggles.push(tooltip(
+                toggler(self.announcement_mode).label(tr("announcement")).on_toggle(Message::ToggleAnnouncementMode),
+                text(tr("announcement-hint")).size(12),
+                tooltip::Position::Bottom,
+            ));
+        }
+        if !self.direct_mode {
+            toggles = toggles.push(Space::with_width(Length::Fill)).push(
+                row![
+                    pick_list(TranscriptFormat::ALL, Some(self.chat_export_format), Message::ChatExportFormatChanged).text_size(12),
+                    button(text(tr("export-chat")).size(12))
+                        .on_press_maybe((!self.chat_messages.is_empty()).then_some(Message::ExportChat))
+                        .style(button::secondary)
+                        .padding(4),
+                ]
+                .spacing(4)
+                .align_y(Alignment::Center),
+            );
+        }
+        let mut header = column![toggles.align_y(Alignment::Center)];
+        match &self.chat_export_status {
+            Some(Ok(status)) if !self.direct_mode => header = header.push(text(status).size(12).style(text::success)),
+            Some(Err(err)) if !self.direct_mode => header = header.push(text(err).size(12).style(text::danger)),
+            _ => {}
+        }
+
+        if self.direct_mode {
+            let peer_input = text_input(tr("direct-peer-placeholder"), &self.direct_peer_input)
+                .on_input(Message::DirectPeerInputChanged)
+                .padding(5)
+                .size(14);
+            let peers = self.direct_peers.iter().fold(row![].spacing(4), |peers_row, peer| {
+                peers_row.push(
+                    button(text(self.contact_for_key(peer).map_or_else(|| shorten_key(peer), |contact| contact.name)).size(12))
+                        .on_press(Message::DirectPeerInputChanged(peer.clone()))
+                        .style(if *peer == self.direct_peer_input { button::primary } else { button::secondary }),
+                )
+            });
+            header = header.push(row![peer_input].padding(5)).push(row![peers].padding(5));
+        }
+
+        header
+    }
+
+    /// Контакт отправителя — только по ключу, которым подписано сообщение.
+    /// Адрес из выходов транзакции выбирает сам отправитель, поэтому по нему контакт не ищем
+    fn contact_for(&self, msg: &ChatMessage) -> Option<Contact> {
+        let sender_key = msg.sender_key.as_deref()?;
+        self.contacts.iter().find(|contact| contact.matches(None, Some(sender_key))).cloned()
+    }
+
+    /// Скрыто ли сообщение канала: отправитель в списке игнорируемых. Личные сообщения не скрываются
+    fn is_ignored(&self, msg: &ChatMessage) -> bool {
+        !self.direct_mode && self.ignored_users.iter().any(|username| username == msg.get_username())
+    }
+
+    fn contact_for_key(&self, identity_key: &str) -> Option<Contact> {
+        self.contacts.iter().find(|contact| contact.matches(None, Some(identity_key))).cloned()
+    }
+
     fn build_main_layout(&self) -> Row<Message> {
-        let left_side_bar = self.build_left_side_bar();
-        // let right_side_bar = self.build_right_side_bar();
-        let chat = self.build_chat_view();
+        let panes = pane_grid(&self.panes, |_, kind, _| {
+            let content: Element<Message> = match kind {
+                PaneKind::LeftSideBar => self.build_left_side_bar().into(),
+                PaneKind::Chat => self.build_chat_view().into(),
+                PaneKind::RightSideBar => self.build_right_side_bar().into(),
+            };
+            pane_grid::Content::new(content)
+        })
+        .on_resize(10, Message::PaneResized)
+        .style(|theme| pane_grid::Style {
+            hovered_split: pane_grid::Line { color: theme.palette().primary, width: 2.0 },
+            picked_split: pane_grid::Line { color: theme.palette().primary, width: 2.0 },
+            ..pane_grid::default(theme)
+        })
+        .spacing(1)
+        .height(Length::Fill)
+        .width(Length::Fill);
 
-        row![
-            left_side_bar.width(Length::FillPortion(1)),
-            Rule::vertical(1),
-            chat.width(Length::FillPortion(4)),
-            Rule::vertical(1),
-            // right_side_bar.width(Length::FillPortion(1))
+        row![panes].height(Length::Fill).width(Length::Fill)
+    }
+
+    /// Окно настроек поверх основного экрана; клик мимо окна закрывает его
+    fn build_settings_dialog(&self) -> Element<Message> {
+        let section = |title: &'static str| column![text(title).size(18).style(text::success), Rule::horizontal(1)].spacing(4);
+
+        let header = row![
+            text(tr("settings-title"))
+                .font(font::Font { weight: iced::font::Weight::Semibold, ..font::Font::DEFAULT })
+                .size(22)
+                .width(Length::Fill),
+            button(text("✕").size(16)).on_press(Message::ToggleSettings(false)).style(button::text).padding(0),
         ]
-        .spacing(0)
+        .align_y(Alignment::Center);
+
+        let username_changed = self.username_input.trim() != self.username;
+        let mut profile = column![
+            section(tr("settings-profile")),
+            row![
+                rich_text([span(format!("{} ", tr("label-name"))).size(16)]),
+                text_input(tr("name-placeholder"), &self.username_input)
+                    .on_input(Message::UsernameInputChanged)
+                    .on_submit(Message::SaveUsername)
+                    .padding(5)
+                    .size(14),
+                button(text(tr("save")).size(14))
+                    .on_press_maybe(username_changed.then_some(Message::SaveUsername))
+                    .style(button::secondary)
+                    .padding(5),
+                self.build_claim_status(),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center)
+            .padding(6),
+        ];
+        if let Some(err) = &self.username_error {
+            profile = profile.push(text(err).size(12).style(text::danger));
+        } else if username_changed {
+            profile = profile.push(text(tr_args("username-hint", &[("max", &MAX_USERNAME_CHARS)])).size(12).style(text::secondary));
+        }
+
+        let audio = column![
+            section(tr("settings-audio")),
+            row![
+                text(tr("input-device")).size(14).width(Length::Fill),
+                pick_list(self.available_input_devices.clone(), Some(&self.input_device), Message::UpdateInputDevice)
+                    .placeholder(tr("select-input-device"))
+                    .text_size(14),
+            ]
+            .align_y(Alignment::Center)
+            .padding(6),
+            row![
+                tooltip(
+                    text(tr("voice-bitrate")).size(14).width(Length::Fill),
+                    text(tr("voice-bitrate-hint")).size(12),
+                    tooltip::Position::Top,
+                ),
+                pick_list(OpusBitrate::options(), Some(OpusBitrate(self.opus_bitrate)), Message::OpusBitrateChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center)
+            .padding(6),
+            row![tooltip(
+                toggler(self.input_meter).label(tr("input-meter")).on_toggle(Message::ToggleInputMeter),
+                text(tr("input-meter-hint")).size(12),
+                tooltip::Position::Top,
+            )]
+            .padding(6),
+            row![toggler(self.listen_self).label(tr("listen-self")).on_toggle(Message::ToggleListenSelf)].padding(6),
+            row![toggler(self.mute_all).label(tr("mute-all")).on_toggle(Message::ToggleMuteAll)].padding(6),
+        ];
+
+        let appearance = column![
+            section(tr("settings-appearance")),
+            row![
+                text(tr("language")).size(14).width(Length::Fill),
+                pick_list(UiLanguage::ALL, Some(self.selected_language), Message::LanguageChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center)
+            .padding(6),
+            row![
+                text(tr("theme")).size(14).width(Length::Fill),
+                pick_list(window_theme_options(&self.custom_palette), Some(&self.selected_theme), Message::ThemeChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center)
+            .padding(6),
+            self.build_auto_theme_settings(),
+            self.build_custom_theme_editor(),
+            row![
+                text(tr("number-format")).size(14).width(Length::Fill),
+                pick_list(FormatLocale::ALL, Some(self.selected_locale), Message::LocaleChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center)
+            .padding(6),
+            row![
+                text(tr("chat-density")).size(14).width(Length::Fill),
+                pick_list(ChatDensity::ALL, Some(self.chat_density), Message::ChatDensityChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center)
+            .padding(6),
+            row![
+                text(tr("clock-format")).size(14).width(Length::Fill),
+                pick_list(ClockFormat::ALL, Some(self.selected_clock_format), Message::ClockFormatChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center)
+            .padding(6),
+            row![
+                text(tr("amounts-in")).size(14).width(Length::Fill),
+                pick_list(Denomination::ALL, Some(self.selected_denomination), Message::DenominationChanged).text_size(14),
+            ]
+            .align_y(Alignment::Center)
+            .padding(6),
+        ];
+
+        let mut window_section = column![section(tr("settings-window"))];
+        if is_tray_available() {
+            window_section = window_section
+                .push(
+                    row![toggler(self.minimize_to_tray).label(tr("close-to-tray")).on_toggle(Message::ToggleMinimizeToTray)]
+                        .padding(6),
+                )
+                .push(
+                    row![toggler(self.start_minimized).label(tr("start-minimized")).on_toggle(Message::ToggleStartMinimized)]
+                        .padding(6),
+                );
+        } else {
+            window_section = window_section.push(text(tr("tray-unavailable")).size(12).style(text::secondary));
+        }
+
+        let shortcuts = self.build_shortcut_settings();
+
+        let node = column![section(tr("settings-node")), self.build_network(), self.build_derivation(), self.build_node_list()];
+        let fees = column![section(tr("settings-fees")), self.build_fee()];
+
+        let dialog = container(
+            column![
+                header,
+                scrollable(column![profile, audio, appearance, window_section, shortcuts, node, fees].spacing(16).padding(6))
+                    .height(Length::Fill)
+            ]
+            .spacing(10),
+        )
+        .padding(16)
+        .width(Length::Fixed(520.0))
         .height(Length::Fill)
-        .width(Length::Fill)
+        .max_height(640.0)
+        .style(container::rounded_box);
+
+        opaque(
+            mouse_area(center(opaque(dialog)).style(|_theme| container::Style {
+                background: Some(Color { a: 0.6, ..Color::BLACK }.into()),
+                ..container::Style::default()
+            }))
+            .on_press(Message::ToggleSettings(false)),
+        )
+    }
+
+    /// Цвета своей темы в hex; показываются, пока она выбрана
+    /// Темы для светлого и тёмного оформления ОС; показываются, пока выбрана тема «Auto»
+    fn build_auto_theme_settings(&self) -> Column<Message> {
+        if self.selected_theme.to_string() != AUTO_THEME_NAME {
+            return column![];
+        }
+        let options = theme_options(&self.custom_palette);
+        column![
+            row![
+                text(tr("theme-light")).size(14).width(Length::Fill),
+                pick_list(options.clone(), Some(&self.light_theme), |theme| Message::AutoThemeChanged(false, theme)).text_size(14),
+            ]
+            .align_y(Alignment::Center),
+            row![
+                text(tr("theme-dark")).size(14).width(Length::Fill),
+                pick_list(options, Some(&self.dark_theme), |theme| Message::AutoThemeChanged(true, theme)).text_size(14),
+            ]
+            .align_y(Alignment::Center),
+            text(tr("theme-auto-hint")).size(12).style(text::secondary),
+        ]
+        .spacing(4)
+        .padding([0, 6])
+    }
+
+    fn build_custom_theme_editor(&self) -> Column<Message> {
+        if self.theme().to_string() != CUSTOM_THEME_NAME {
+            return column![];
+        }
+        PaletteColor::ALL.into_iter().fold(column![].spacing(4).padding([0, 6]), |editor, color| {
+            let input = &self.custom_color_inputs[color as usize];
+            let valid = parse_hex_color(input).is_some();
+            let [r, g, b] = color.get(&self.custom_palette);
+            let swatch = container(Space::new(Length::Fixed(20.0), Length::Fixed(20.0))).style(move |_theme| container::Style {
+                background: Some(Color::from_rgb8(r, g, b).into()),
+                border: iced::Border { color: Color::from_rgb(0.5, 0.5, 0.5), width: 1.0, radius: 4.0.into() },
+                ..container::Style::default()
+            });
+            editor.push(
+                row![
+                    text(color.label()).size(14).width(Length::Fill),
+                    text_input("#rrggbb", input)
+                        .on_input(move |value| Message::CustomColorChanged(color, value))
+                        .size(14)
+                        .width(Length::Fixed(100.0))
+                        .style(move |theme, status| {
+                            let style = text_input::default(theme, status);
+                            if valid {
+                                style
+                            } else {
+                                text_input::Style { border: style.border.color(theme.palette().danger), ..style }
+                            }
+                        }),
+                    swatch,
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
+            )
+        })
     }
 
     fn build_footer(&self) -> Row<Message> {
         // Recording: Active/Inactive
-        let recording_prefix = text("Recording: ").size(16);
+        let recording_prefix = text(format!("{} ", tr("footer-recording"))).size(16);
         let (recording_status_text, recording_color) = if self.is_recording {
-            ("Active", Color::from_rgb(0.0, 1.0, 0.0)) // зелёный
+            (tr("footer-recording-active"), Color::from_rgb(0.0, 1.0, 0.0)) // зелёный
         } else {
-            ("Inactive", Color::from_rgb(1.0, 0.0, 0.0)) // красный
+            (tr("footer-recording-inactive"), Color::from_rgb(1.0, 0.0, 0.0)) // красный
         };
         let recording_status_label = text(recording_status_text).size(16).color(recording_color);
 
         // "Status: Connected/Disconnected"
-        let connected_prefix = text("Status: ").size(16);
+        let connected_prefix = text(format!("{} ", tr("footer-status"))).size(16);
         let listener_connected = APP_STATE.is_listener_connected().unwrap_or_else(|e| {
             log::error!("Error while reading Listener status: {}", e);
             false
@@ -542,28 +5237,86 @@ impl Gui {
             log::error!("Error while reading Broadcaster status: {}", e);
             false
         });
-        let overall_connected = if listener_connected && broadcaster_connected { "Connected" } else { "Disconnected" };
-        let (overall_text, overall_color) = if overall_connected == "Connected" {
-            (overall_connected, Color::from_rgb(0.0, 1.0, 0.0)) // зелёный
+        let overall_connected = listener_connected && broadcaster_connected;
+        let (overall_text, overall_color) = if overall_connected {
+            (tr("footer-connected"), Color::from_rgb(0.0, 1.0, 0.0)) // зелёный
+        } else {
+            (tr("footer-disconnected"), Color::from_rgb(1.0, 0.0, 0.0)) // красный
+        };
+        let overall_status_label = if APP_STATE.is_away().unwrap_or(false) {
+            text(tr_args("footer-away", &[("status", &overall_text)])).size(16).color(Color::from_rgb(1.0, 0.8, 0.0))
+        // жёлтый
         } else {
-            (overall_connected, Color::from_rgb(1.0, 0.0, 0.0)) // красный
+            text(overall_text).size(16).color(overall_color)
         };
-        let overall_status_label = text(overall_text).size(16).color(overall_color);
+        // Статус раскрывает панель диагностики
+        let overall_status_button = button(row![overall_status_label, text(if self.diagnostics_open { " ▾" } else { " ▸" }).size(14)])
+            .on_press(Message::ToggleDiagnostics)
+            .style(button::text)
+            .padding(0);
+
+        // "Quality: [|||||   ]" с подсказкой о факторах
+        let quality = APP_STATE.get_connection_quality().unwrap_or_else(|e| {
+            log::error!("Error while reading connection quality: {}", e);
+            ConnectionQuality { score: 0, factors: vec![e] }
+        });
+        let quality_prefix = text(format!("{} ", tr("footer-quality"))).size(16);
+        let quality_bar = progress_bar(0.0..=100.0, quality.score as f32).height(Length::Fixed(10.0)).width(Length::Fixed(120.0));
+        let quality_tooltip = tooltip(
+            row![quality_bar, text(format!(" {}", quality.score)).size(14)].align_y(Alignment::Center),
+            container(text(quality.factors.join("\n")).size(14)).padding(6).style(container::rounded_box),
+            tooltip::Position::Top,
+        );
 
-        let status_column = column![
+        let mut status_column = column![
             row![recording_prefix, recording_status_label].height(Length::Shrink), // "Recording: Active/Inactive"
-            row![connected_prefix, overall_status_label].height(Length::Shrink),   // "Status: Connected/Disconnected"
-        ]
-        .height(Length::Shrink)
-        .width(Length::FillPortion(1))
-        .align_x(Alignment::Start);
+            row![connected_prefix, overall_status_button].align_y(Alignment::Center).height(Length::Shrink), // "Status: Connected/Disconnected"
+            row![quality_prefix, quality_tooltip].align_y(Alignment::Center).height(Length::Shrink),
+        ];
+        // Синхронизация ноды: отставание чата часто объясняется несинхронизированной нодой
+        if overall_connected {
+            let diagnostics = APP_STATE.get_connection_diagnostics().unwrap_or_default();
+            let daa = diagnostics.daa_score.map(format_integer).unwrap_or_else(|| "—".to_string());
+            match diagnostics.is_synced {
+                Some(true) => {
+                    status_column =
+                        status_column.push(text(tr_args("footer-synced", &[("daa", &daa)])).size(14).style(text::secondary))
+                }
+                Some(false) => {
+                    status_column = status_column
+                        .push(text(tr_args("footer-syncing", &[("daa", &daa)])).size(14).color(Color::from_rgb(1.0, 0.8, 0.0)))
+                }
+                None => {}
+            }
+        }
+        if self.diagnostics_open {
+            status_column = status_column.push(self.build_diagnostics(listener_connected, broadcaster_connected));
+        } else if overall_connected {
+            if let Some(node) = APP_STATE.get_active_node() {
+                status_column = status_column.push(text(tr_args("footer-node", &[("node", &node)])).size(14).style(text::secondary));
+            }
+        }
+        if let Some(attempt) = APP_STATE.get_reconnect_attempt() {
+            status_column = status_column
+                .push(text(tr_args("footer-reconnecting", &[("attempt", &attempt)])).size(14).color(Color::from_rgb(1.0, 0.8, 0.0)));
+        }
+        if let Some(reason) = &self.spend_pause {
+            status_column = status_column
+                .push(text(tr_args("footer-sending-paused", &[("reason", reason)])).size(14).color(Color::from_rgb(1.0, 0.3, 0.3)));
+        }
+        if self.pending_retries > 0 {
+            status_column = status_column.push(
+                text(tr_args("footer-retrying", &[("count", &self.pending_retries)])).size(14).color(Color::from_rgb(1.0, 0.8, 0.0)),
+            );
+        }
+        let status_column = status_column.height(Length::Shrink).width(Length::FillPortion(1)).align_x(Alignment::Start);
 
-        let en_chat_button = button(text("EN TG GROUP").size(12).align_x(iced::alignment::Horizontal::Right))
+        let en_chat_button = button(text(tr("footer-tg-en")).size(12).align_x(iced::alignment::Horizontal::Right))
             .on_press(Message::OpenLink("https://t.me/kaspeak_en".to_string()))
             .style(button::text)
             .width(Length::Fill);
 
-        let ru_chat_button = button(text("RU TG GROUP").size(12).align_x(iced::alignment::Horizontal::Left))
+        let ru_chat_button = button(text(tr("footer-tg-ru")).size(12).align_x(iced::alignment::Horizontal::Left))
             .on_press(Message::OpenLink("https://t.me/kaspeak_ru".to_string()))
             .style(button::text)
             .width(Length::Fill);
@@ -573,19 +5326,108 @@ impl Gui {
             .height(Length::Shrink)
             .align_x(Alignment::Center);
 
-        let theme_column = column![pick_list(Theme::ALL, Some(&self.selected_theme), Message::ThemeChanged,).width(Length::Fill)]
-            .width(Length::FillPortion(1))
-            .height(Length::Shrink)
-            .align_x(Alignment::End);
+        let theme_column = column![button(text(tr("settings-title")).size(14))
+            .on_press(Message::ToggleSettings(true))
+            .style(button::secondary)
+            .padding(6)]
+        .spacing(4)
+        .width(Length::FillPortion(1))
+        .height(Length::Shrink)
+        .align_x(Alignment::End);
 
         row![status_column, links_column, theme_column,].padding(6).spacing(6)
     }
 
+    /// Строки панели диагностики: подпись и значение
+    fn diagnostics_lines(&self, listener_connected: bool, broadcaster_connected: bool) -> Vec<(&'static str, String)> {
+        let diagnostics = APP_STATE.get_connection_diagnostics().unwrap_or_else(|e| {
+            log::error!("Error while reading connection diagnostics: {}", e);
+            ConnectionDiagnostics::default()
+        });
+        let unknown = || tr("diag-unknown").to_string();
+        let yes_no = |value: bool| tr(if value { "diag-yes" } else { "diag-no" }).to_string();
+        let connection = |connected: bool| tr(if connected { "footer-connected" } else { "footer-disconnected" }).to_string();
+
+        let listener_subscriptions = match &diagnostics.listener_scopes {
+            Some(scopes) if listener_connected => {
+                let mut subscriptions = Vec::new();
+                if scopes.block_added {
+                    subscriptions.push(tr("diag-sub-blocks"));
+                }
+                if scopes.uses_virtual_chain() {
+                    subscriptions.push(tr("diag-sub-chain"));
+                }
+                if self.mempool_listening {
+                    subscriptions.push(tr("diag-sub-mempool"));
+                }
+                if subscriptions.is_empty() {
+                    tr("diag-sub-none").to_string()
+                } else {
+                    subscriptions.join(", ")
+                }
+            }
+            _ => tr("diag-sub-none").to_string(),
+        };
+        let broadcaster_subscriptions =
+            if broadcaster_connected { tr("diag-sub-account").to_string() } else { tr("diag-sub-none").to_string() };
+        let last_error = diagnostics
+            .last_error
+            .map(|(at, error)| {
+                let at = DateTime::from_timestamp(at, 0).map(|at| format_date_time(&at.with_timezone(&Local))).unwrap_or_default();
+                format!("{} — {}", at, error)
+            })
+            .unwrap_or_else(|| tr("diag-none").to_string());
+
+        vec![
+            ("diag-node", APP_STATE.get_active_node().unwrap_or_else(unknown)),
+            ("diag-network", APP_STATE.get_network().to_string()),
+            ("diag-server-version", diagnostics.server_version.unwrap_or_else(unknown)),
+            ("diag-server-network", diagnostics.server_network.unwrap_or_else(unknown)),
+            ("diag-synced", diagnostics.is_synced.map(yes_no).unwrap_or_else(unknown)),
+            ("diag-daa-score", diagnostics.daa_score.map(format_integer).unwrap_or_else(unknown)),
+            ("diag-latency", diagnostics.latency_ms.map(|ms| tr_args("diag-latency-ms", &[("ms", &ms)])).unwrap_or_else(unknown)),
+            ("diag-listener", format!("{} ({})", connection(listener_connected), listener_subscriptions)),
+            ("diag-broadcaster", format!("{} ({})", connection(broadcaster_connected), broadcaster_subscriptions)),
+            ("diag-last-error", last_error),
+        ]
+    }
+
+    fn build_diagnostics(&self, listener_connected: bool, broadcaster_connected: bool) -> Element<Message> {
+        let lines = self.diagnostics_lines(listener_connected, broadcaster_connected);
+        let report = lines.iter().map(|(label, value)| format!("{} {}", tr(label), value)).collect::<Vec<_>>().join("\n");
+
+        let mut rows = column![].spacing(2);
+        for (label, value) in lines {
+            rows = rows.push(row![text(format!("{} ", tr(label))).size(13).style(text::secondary), text(value).size(13)]);
+        }
+        let copy_button =
+            button(text(tr("copy")).size(12)).on_press(Message::CopyToClipboard(report)).style(button::secondary).padding(4);
+
+        container(column![rows, copy_button].spacing(6)).padding(6).style(container::rounded_box).into()
+    }
+
     pub fn theme(&self) -> Theme {
-        self.selected_theme.clone()
+        if self.selected_theme.to_string() != AUTO_THEME_NAME {
+            return self.selected_theme.clone();
+        }
+        if self.os_dark {
+            self.dark_theme.clone()
+        } else {
+            self.light_theme.clone()
+        }
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        time::every(Duration::from_millis(100)).map(|_| Message::Tick)
+        Subscription::batch([
+            time::every(Duration::from_millis(100)).map(|_| Message::Tick),
+            // Приходит, только когда есть трей: без него закрытие окна завершает приложение само
+            window::close_requests().map(Message::WindowCloseRequested),
+            keyboard::on_key_press(|key, modifiers| match key.as_ref() {
+                keyboard::Key::Character(c) if modifiers.control() && modifiers.shift() && c.eq_ignore_ascii_case("d") => {
+                    Some(Message::ToggleConsole)
+                }
+                _ => Some(Message::KeyPressed(key, modifiers)),
+            }),
+        ])
     }
 }
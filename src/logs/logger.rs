@@ -28,6 +28,8 @@ pub fn init() {
             writeln!(buf, "{} [{}][{:5}] {}", now, file, level, record.args())
         })
         .filter_level(LevelFilter::Off);
-    builder.filter_module("kaspeak", LevelFilter::Info);
+    // Фильтр пропускает всё, а уровень задаётся глобально: его можно поменять на лету из консоли разработчика
+    builder.filter_module("kaspeak", LevelFilter::Trace);
     builder.init();
+    log::set_max_level(LevelFilter::Info);
 }
@@ -0,0 +1,114 @@
+//! Добровольная анонимная телеметрия.
+//!
+//! Выключена по умолчанию и ничего не отправляет, пока пользователь сам не включит её
+//! и не укажет адрес сервера. Отправляются только агрегированные счётчики за период:
+//! число падений, переподключений к ноде, потерянных голосовых фрагментов, ошибок разбора
+//! входящих payload по видам, входящих payload по версиям протокола и типам, а также версия приложения и ОС. Адреса, имена, ключи, тексты и логи не отправляются никогда.
+//! Отправка не чаще раза в `TELEMETRY_UPLOAD_INTERVAL_SECS`; содержимое можно посмотреть в GUI.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::app_state::APP_STATE;
+use crate::constants::{TELEMETRY_CHECK_INTERVAL_SECS, TELEMETRY_TIMEOUT_SECS, TELEMETRY_UPLOAD_INTERVAL_SECS};
+
+/// Отправляемый отчёт — всё, что уходит на сервер
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TelemetryReport {
+    pub app_version: &'static str,
+    pub os: &'static str,
+    pub period_secs: u64,
+    pub crashes: u32,
+    pub reconnects: u32,
+    pub audio_dropouts: u32,
+    pub parse_errors: BTreeMap<String, u32>,
+    pub protocol_versions: BTreeMap<u8, u32>,
+    pub message_types: BTreeMap<String, u32>,
+}
+
+/// Отчёт по текущим счётчикам
+pub fn build_report() -> TelemetryReport {
+    let counters = APP_STATE.get_telemetry_counters().unwrap_or_default();
+    TelemetryReport {
+        app_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        period_secs: counters.period_start.elapsed().as_secs(),
+        crashes: APP_STATE.get_pending_crashes().unwrap_or(0),
+        reconnects: counters.reconnects,
+        audio_dropouts: counters.audio_dropouts,
+        parse_errors: counters.parse_errors,
+        protocol_versions: counters.protocol_versions,
+        message_types: counters.message_types,
+    }
+}
+
+/// Отчёт в том виде, в каком он будет отправлен
+pub fn preview_report() -> String {
+    serde_json::to_string_pretty(&build_report()).unwrap_or_else(|e| format!("Failed to build report: {e}"))
+}
+
+/// Считает падения, если телеметрия включена: счётчик сохраняется в настройки, чтобы пережить перезапуск.
+/// Сохраняет стандартный обработчик паники, чтобы сообщение по-прежнему попадало в stderr
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!("Application panicked: {}", info);
+        if let Err(err) = APP_STATE.record_crash() {
+            log::error!("Error while recording crash: {}", err);
+        }
+        default_hook(info);
+    }));
+}
+
+/// Фоновая отправка отчётов, если телеметрия включена и задан адрес сервера
+pub fn spawn_telemetry_uploader() {
+    thread::spawn(|| {
+        let mut last_upload: Option<Instant> = None;
+        loop {
+            thread::sleep(Duration::from_secs(TELEMETRY_CHECK_INTERVAL_SECS));
+            if let Err(err) = APP_STATE.save_unsaved_crashes() {
+                log::error!("Error while saving crash counter: {}", err);
+            }
+
+            let Some(endpoint) = APP_STATE.get_telemetry_endpoint().ok().flatten() else {
+                continue;
+            };
+            if last_upload.is_some_and(|at| at.elapsed() < Duration::from_secs(TELEMETRY_UPLOAD_INTERVAL_SECS)) {
+                continue;
+            }
+
+            let report = build_report();
+            match upload(&endpoint, &report) {
+                Ok(_) => {
+                    log::info!("Telemetry report uploaded to {}", endpoint);
+                    if let Err(err) = APP_STATE.reset_telemetry(&report) {
+                        log::error!("Error while resetting telemetry counters: {}", err);
+                    }
+                }
+                Err(err) => log::warn!("Telemetry upload failed: {}", err),
+            }
+            // Неудачная попытка тоже считается: не долбим недоступный сервер
+            last_upload = Some(Instant::now());
+        }
+    });
+}
+
+fn upload(endpoint: &str, report: &TelemetryReport) -> Result<(), String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(TELEMETRY_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+    let body = serde_json::to_vec(report).map_err(|e| format!("Failed to serialize report: {e}"))?;
+    let response = client
+        .post(endpoint)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .map_err(|e| format!("Request failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Unexpected status {}", response.status()));
+    }
+    Ok(())
+}
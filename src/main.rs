@@ -1,9 +1,10 @@
 use std::future::Future;
 use std::io::Error;
 use std::sync::mpsc::Receiver;
+use std::sync::Arc;
 use std::sync::{mpsc, RwLock};
-use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 
 mod app_state;
 mod audio;
@@ -14,29 +15,38 @@ mod gui;
 mod logs;
 mod models;
 mod settings;
+mod tray;
 mod utils;
 
+use crate::audio::level_meter::spawn_level_meter;
 use crate::audio::player::Player;
 use crate::audio::recorder::Recorder;
 use crate::gui::{Gui, GuiEvent};
 use core::broadcaster::Broadcaster;
+use core::console;
+use core::diagnostics::spawn_latency_probe;
+use core::hotkeys::ensure_push_to_talk_listener;
 use core::listener::Listener;
+use core::node_failover::{connect_preferred_node, spawn_node_failover};
+use core::sleep_watch::spawn_sleep_watch;
 use core::wallet::WalletService;
 
 use kaspa_wrpc_client::result::Result;
 
 use crate::app_state::APP_STATE;
 use crate::chat::Chat;
-use crate::constants::{APP_ICON_FILE_INLINED, MARKER, NETWORK_ID};
-use crate::logs::logger;
+use crate::constants::{
+    APP_ICON_FILE_INLINED, MARKER, MAX_PAYLOAD_BYTES, PERMALINK_PREFIX, VOICE_BATCH_ENTRY_HEADER, VOICE_BATCH_MAX_FRAGMENTS,
+    VOICE_BATCH_MAX_WAIT_MS,
+};
+use crate::logs::{logger, telemetry};
 use crate::models::instruction::Instruction;
-use crate::models::payload::{MessageType, Payload};
+use crate::models::payload::{MessageType, Payload, StatusFlag};
 use crate::models::recording::Recording;
 use crate::utils::try_connect_to_node;
 use iced::{window, Executor, Task};
 use image::ImageFormat;
 use kaspa_wrpc_client::KaspaRpcClient;
-use rdev::{listen, EventType};
 use tokio::main;
 use tokio::runtime::Handle;
 use tokio::sync::mpsc as async_mpsc;
@@ -60,6 +70,12 @@ impl Executor for TokioExecutor {
 #[main]
 async fn main() -> Result<()> {
     logger::init();
+    telemetry::install_panic_hook();
+    // Ссылка kaspeak://msg/<txid>, с которой ОС запустила приложение (Windows/Linux передают её аргументом)
+    if let Some(link) = std::env::args().skip(1).find(|arg| arg.starts_with(PERMALINK_PREFIX)) {
+        log::info!("Opened with message link: {}", link);
+        APP_STATE.chat_state.set_pending_permalink(link);
+    }
     // Инициализация основных компонентов
     let (kaspa_rpc_client, broadcaster, listener, recorder, payload_tx) = init_core_components().await?;
 
@@ -81,29 +97,58 @@ async fn main() -> Result<()> {
     // Мосты payload(async) -> (player, payload_logger, chat)
     let payload_rx_logger = payload_tx.subscribe();
     let payload_rx_dispatcher = payload_tx.subscribe();
-    spawn_payload_dispatcher_bridge(payload_rx_dispatcher, player_tx, chat_tx);
+    spawn_payload_dispatcher_bridge(payload_rx_dispatcher, broadcaster.clone(), player_tx, chat_tx);
     spawn_payload_logger(payload_rx_logger);
 
     // Мост recorder -> broadcaster(async)
     let (recording_tx, recording_rx) = mpsc::channel::<Arc<Recording>>();
     spawn_recording_bridge(broadcaster.clone(), recording_rx);
 
-    // (опционально) глобальный обработчик клавиши TAB
-    // let is_recording = Arc::new(Mutex::new(false));
-    // spawn_keyboard_listener(is_recording, event_tx.clone());
+    // Глобальная клавиша push-to-talk, если назначена
+    ensure_push_to_talk_listener();
 
     // Обработчик сигналов завершения
     setup_signal_handler();
 
+    // Добровольная телеметрия (ничего не отправляет, пока не включена в GUI)
+    telemetry::spawn_telemetry_uploader();
+
     // mpsc канал для событий от GUI
     let (event_tx, event_rx) = async_mpsc::channel::<GuiEvent>(100);
     // GUI event handler (запуск/остановка записи)
-    spawn_gui_event_handler(recorder_rw_lock.clone(), kaspa_rpc_client.clone(), broadcaster.clone(), recording_tx.clone(), event_rx);
+    spawn_gui_event_handler(
+        recorder_rw_lock.clone(),
+        kaspa_rpc_client.clone(),
+        broadcaster.clone(),
+        recording_tx.clone(),
+        payload_tx.clone(),
+        event_rx,
+    );
+
+    // Остановка записи и переподключение после сна системы
+    spawn_sleep_watch(kaspa_rpc_client.clone(), recorder_rw_lock.clone());
+
+    // Индикатор уровня микрофона
+    spawn_level_meter();
+
+    // Переключение на следующую ноду списка при отказе активной
+    spawn_node_failover(kaspa_rpc_client.clone());
+
+    // Замер задержки ответа ноды для панели диагностики
+    spawn_latency_probe(kaspa_rpc_client.clone());
 
     // Запуск Iced GUI
     let cloned_event_tx = event_tx.clone();
     let icon = icon::from_file_data(APP_ICON_FILE_INLINED, Some(ImageFormat::Png)).expect("Failed to load application icon");
-    let window_settings = window::Settings { icon: Some(icon), position: window::Position::Centered, ..Default::default() };
+    // Иконка в трее; без неё окно нельзя прятать, и закрытие окна завершает приложение как раньше
+    let _tray = tray::spawn_tray();
+    let window_settings = window::Settings {
+        icon: Some(icon),
+        position: window::Position::Centered,
+        visible: !(tray::is_tray_available() && APP_STATE.is_start_minimized().unwrap_or(false)),
+        exit_on_close_request: !tray::is_tray_available(),
+        ..Default::default()
+    };
     let _ = iced::application("KASPEAK", Gui::update, Gui::view)
         .subscription(Gui::subscription)
         .theme(Gui::theme)
@@ -111,8 +156,18 @@ async fn main() -> Result<()> {
         .executor::<TokioExecutor>()
         .run_with(move || (Gui::new(cloned_event_tx), Task::none()));
 
+    if let Err(err) = APP_STATE.chat_state.save_seen_users() {
+        log::error!("Error while saving seen users: {}", err);
+    }
     // Graceful Shutdown listener и broadcaster
     shutdown(listener.clone(), broadcaster.clone()).await;
+    // Сменились мнемоника или профиль: кошелёк и ключ идентичности соберутся заново в новом процессе
+    if let Some(profile) = APP_STATE.take_restart_profile() {
+        log::info!("Restarting with profile '{}'", profile);
+        if let Err(err) = utils::spawn_new_instance(&profile) {
+            log::error!("Error while restarting: {}", err);
+        }
+    }
     Ok(())
 }
 
@@ -121,7 +176,11 @@ async fn init_core_components(
 ) -> Result<(Arc<KaspaRpcClient>, Arc<Broadcaster>, Arc<Listener>, Option<Recorder>, tokio::sync::broadcast::Sender<Payload>)> {
     let (payload_tx, _payload_rx) = tokio::sync::broadcast::channel(1024);
 
-    let kaspa_rpc_client = utils::bootstrap_rpc_client(NETWORK_ID, None);
+    let network = APP_STATE.get_network();
+    if network.is_mainnet() {
+        log::warn!("Running on MAINNET: every message spends real KAS");
+    }
+    let kaspa_rpc_client = utils::bootstrap_rpc_client(network.network_id(), None);
 
     let wallet_service = match WalletService::new(kaspa_rpc_client.clone(), APP_STATE.get_mnemonic()).await {
         Ok(wallet_service) => Arc::new(wallet_service),
@@ -160,24 +219,32 @@ fn spawn_chat_thread(chat: Chat, chat_rx: Receiver<Payload>) {
 /// Читаем Payload пакеты из broadcast (async) и пересылаем в синхронный канал потребителя.
 fn spawn_payload_dispatcher_bridge(
     mut rx_player: tokio::sync::broadcast::Receiver<Payload>,
+    broadcaster: Arc<Broadcaster>,
     player_tx: mpsc::Sender<Payload>,
     chat_tx: mpsc::Sender<Payload>,
 ) {
     tokio::spawn(async move {
         while let Ok(payload) = rx_player.recv().await {
             match payload.get_message_type() {
-                MessageType::Voice => {
+                MessageType::Voice | MessageType::VoiceBatch => {
                     let player_tx = player_tx.clone();
                     tokio::task::spawn_blocking(move || {
                         let _ = player_tx.send(payload);
                     });
                 }
-                MessageType::Text => {
+                MessageType::Text | MessageType::Direct | MessageType::Announcement => {
                     let chat_tx = chat_tx.clone();
                     tokio::task::spawn_blocking(move || {
                         let _ = chat_tx.send(payload);
                     });
                 }
+                // Заявки на имена и аватары обрабатывает Listener
+                MessageType::Claim | MessageType::Avatar => {}
+                // Listener пропускает только запросы нашего аватара
+                MessageType::AvatarRequest => {
+                    log::info!("Avatar requested by '{}', announcing it again", payload.get_username());
+                    check_and_send_instructions(broadcaster.clone(), Instruction::try_from_avatar()).await;
+                }
                 MessageType::File | MessageType::Unknown(_) => {
                     log::error!("Messages of this type are not yet implemented");
                 }
@@ -215,45 +282,58 @@ fn spawn_recording_bridge(broadcaster: Arc<Broadcaster>, recording_rx: Receiver<
     });
 
     // Асинхронная задача: читает из async_rx и отправляет броадкастеру инструкции.
+    // В режиме пакетной отправки последовательные фрагменты копятся и уходят одной транзакцией
     tokio::spawn(async move {
-        while let Some(fragment) = async_rx.recv().await {
+        let mut batch: Vec<Arc<Recording>> = Vec::new();
+        loop {
+            // Начатый пакет не держим дольше VOICE_BATCH_MAX_WAIT_MS, иначе вырастет задержка речи
+            let next = if batch.is_empty() {
+                async_rx.recv().await
+            } else {
+                match tokio::time::timeout(Duration::from_millis(VOICE_BATCH_MAX_WAIT_MS), async_rx.recv()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        flush_voice_batch(&broadcaster, &mut batch).await;
+                        continue;
+                    }
+                }
+            };
+            let Some(fragment) = next else {
+                break;
+            };
             log::debug!(
-                "Sending instruction to broadcaster: state={:?}, num={}, size={}",
+                "Queued fragment for broadcaster: state={:?}, num={}, size={}",
                 fragment.state,
                 fragment.fragment_num,
                 fragment.audio.len()
             );
-            let instruction = Instruction::try_from_recording(fragment.as_ref());
-            check_and_send_instruction(broadcaster.clone(), instruction).await;
-        }
-    });
-}
-
-/// Обработчик клавиатуры (TAB): начало/остановка аудиозаписи
-fn spawn_keyboard_listener(is_recording: Arc<Mutex<bool>>, event_tx: async_mpsc::Sender<GuiEvent>) {
-    thread::spawn(move || {
-        if let Err(error) = listen(move |event| match event.event_type {
-            EventType::KeyPress(key) if key == rdev::Key::Tab => {
-                let mut recording = is_recording.lock().unwrap();
-                if !*recording {
-                    *recording = true;
-                    let _ = event_tx.blocking_send(GuiEvent::StartRecording);
-                }
+            let batch_limit = if APP_STATE.is_voice_batching_enabled().unwrap_or(false) { VOICE_BATCH_MAX_FRAGMENTS } else { 1 };
+            if !batch.is_empty()
+                && Payload::voice_batch_len(&batch) + VOICE_BATCH_ENTRY_HEADER + fragment.audio.len() > MAX_PAYLOAD_BYTES
+            {
+                flush_voice_batch(&broadcaster, &mut batch).await;
             }
-            EventType::KeyRelease(key) if key == rdev::Key::Tab => {
-                let mut recording = is_recording.lock().unwrap();
-                if *recording {
-                    *recording = false;
-                    let _ = event_tx.blocking_send(GuiEvent::StopRecording);
-                }
+            let is_last = fragment.state == StatusFlag::End;
+            batch.push(fragment);
+            if is_last || batch.len() >= batch_limit {
+                flush_voice_batch(&broadcaster, &mut batch).await;
             }
-            _ => {}
-        }) {
-            log::error!("Input error: {:?}", error);
         }
+        flush_voice_batch(&broadcaster, &mut batch).await;
     });
 }
 
+/// Отправляет накопленные фрагменты одной инструкцией
+async fn flush_voice_batch(broadcaster: &Arc<Broadcaster>, batch: &mut Vec<Arc<Recording>>) {
+    if batch.is_empty() {
+        return;
+    }
+    log::debug!("Sending {} voice fragment(s) from #{} to broadcaster", batch.len(), batch[0].fragment_num);
+    let instruction = Instruction::try_from_recordings(batch);
+    batch.clear();
+    check_and_send_instruction(broadcaster.clone(), instruction).await;
+}
+
 /// Установка обработчика сигналов завершения (Ctrl+C, SIGTERM).
 fn setup_signal_handler() {
     let (shutdown_sender, _shutdown_receiver) = oneshot::<()>();
@@ -272,6 +352,7 @@ fn spawn_gui_event_handler(
     kaspa_rpc_client: Arc<KaspaRpcClient>,
     broadcaster: Arc<Broadcaster>,
     recording_tx: mpsc::Sender<Arc<Recording>>,
+    payload_tx: tokio::sync::broadcast::Sender<Payload>,
     mut event_rx: async_mpsc::Receiver<GuiEvent>,
 ) {
     tokio::spawn(async move {
@@ -292,13 +373,60 @@ fn spawn_gui_event_handler(
                         recorder.read().unwrap().stop_recording();
                     }
                 }
-                GuiEvent::NodeConnectButtonPressed(node_url) => {
-                    try_connect_to_node(kaspa_rpc_client, node_url.clone()).await;
+                GuiEvent::NodeConnectButtonPressed(Some(node_url)) => {
+                    try_connect_to_node(kaspa_rpc_client, Some(node_url)).await;
+                }
+                GuiEvent::NodeConnectButtonPressed(None) => {
+                    connect_preferred_node(kaspa_rpc_client).await;
                 }
                 GuiEvent::MessageSent(message) => {
                     let instruction = Instruction::try_from_message(message);
                     check_and_send_instruction(broadcaster.clone(), instruction).await;
                 }
+                GuiEvent::AnnouncementSent(message) => {
+                    let instruction = Instruction::try_from_announcement(message);
+                    check_and_send_instruction(broadcaster.clone(), instruction).await;
+                }
+                GuiEvent::DirectMessageSent(recipient, message) => {
+                    let instruction = Instruction::try_from_direct_message(&recipient, message);
+                    check_and_send_instruction(broadcaster.clone(), instruction).await;
+                }
+                GuiEvent::ClaimUsername => {
+                    let instruction = Instruction::try_from_claim();
+                    check_and_send_instruction(broadcaster.clone(), instruction).await;
+                }
+                GuiEvent::AnnounceAvatar => {
+                    check_and_send_instructions(broadcaster.clone(), Instruction::try_from_avatar()).await;
+                }
+                GuiEvent::RequestAvatar(address) => {
+                    let instruction = Instruction::try_from_avatar_request(&address);
+                    check_and_send_instruction(broadcaster.clone(), instruction).await;
+                }
+                GuiEvent::SignMessage(message) => {
+                    let result = broadcaster.wallet_service().sign_message(&message).await.map_err(|e| e.to_string());
+                    if let Err(err) = &result {
+                        log::error!("Error while signing message: {}", err);
+                    }
+                    APP_STATE.set_signed_message(result);
+                }
+                GuiEvent::NewReceiveAddress => {
+                    check_and_send_instruction(broadcaster.clone(), Ok(Instruction::RotateAddress)).await;
+                }
+                GuiEvent::RequestAirdrop => {
+                    check_and_send_instruction(broadcaster.clone(), Ok(Instruction::Airdrop)).await;
+                }
+                GuiEvent::SendFunds(address, amount) => {
+                    let instruction = Instruction::try_from_transfer(&address, amount);
+                    check_and_send_instruction(broadcaster.clone(), instruction).await;
+                }
+                GuiEvent::ConsoleCommand(command) => {
+                    log::info!("Developer console command: {}", command);
+                    APP_STATE.push_console_output(&format!("> {command}"));
+                    match console::execute(&command, &kaspa_rpc_client, &payload_tx).await {
+                        Ok(output) => APP_STATE.push_console_output(&output),
+                        Err(err) => APP_STATE.push_console_output(&format!("error: {err}")),
+                    }
+                }
             }
         }
     });
@@ -322,3 +450,16 @@ async fn check_and_send_instruction(broadcaster: Arc<Broadcaster>, instruction:
         }
     }
 }
+
+async fn check_and_send_instructions(broadcaster: Arc<Broadcaster>, instructions: Result<Vec<Instruction>>) {
+    match instructions {
+        Ok(instructions) => {
+            for instruction in instructions {
+                check_and_send_instruction(broadcaster.clone(), Ok(instruction)).await;
+            }
+        }
+        Err(e) => {
+            log::error!("Error while generating instructions for broadcaster: {}", e);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::constants::{AVATAR_CHUNK_SIZE, AVATAR_DOMAIN, AVATAR_SIZE_PX, MAX_AVATAR_CHUNKS};
+use crate::core::identity::{verify_digest, Identity};
+use image::io::Reader as ImageReader;
+use image::ImageOutputFormat;
+use secp256k1::ecdsa::Signature;
+use secp256k1::PublicKey;
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+
+/// Размер сжатого публичного ключа secp256k1
+const PUBLIC_KEY_SIZE: usize = 33;
+/// Размер компактной подписи ECDSA
+const SIGNATURE_SIZE: usize = 64;
+/// 33 (PUBLIC_KEY) + 64 (SIGNATURE) + 1 (TOTAL)
+pub const CHUNK_HEADER_SIZE: usize = PUBLIC_KEY_SIZE + SIGNATURE_SIZE + 1;
+
+/// Фрагмент аватара — поле data у payload с типом `MessageType::Avatar`:
+///   33 (PUBLIC_KEY) + 64 (SIGNATURE) + 1 (TOTAL) + часть изображения
+/// Номер фрагмента передаётся в fragment_number. Подпись покрывает всё изображение и адрес отправителя,
+/// поэтому аватар нельзя ни подменить, ни переотправить со своего адреса.
+#[derive(Debug, Clone)]
+pub struct AvatarChunk {
+    pub public_key: PublicKey,
+    signature: Signature,
+    /// Сколько всего фрагментов в изображении
+    pub total: u8,
+    pub bytes: Vec<u8>,
+}
+
+impl AvatarChunk {
+    /// Делит изображение на фрагменты, подписанные ключом идентичности
+    pub fn split(identity: &Identity, image: &[u8], address: &str) -> Result<Vec<Self>, String> {
+        let total = image.len().div_ceil(AVATAR_CHUNK_SIZE);
+        if total == 0 || total > MAX_AVATAR_CHUNKS {
+            return Err(format!("Avatar has {} bytes, max allowed is {}", image.len(), AVATAR_CHUNK_SIZE * MAX_AVATAR_CHUNKS));
+        }
+        let signature = identity.sign_digest(Self::digest(image, address));
+        Ok(image
+            .chunks(AVATAR_CHUNK_SIZE)
+            .map(|bytes| Self { public_key: identity.public_key(), signature, total: total as u8, bytes: bytes.to_vec() })
+            .collect())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() <= CHUNK_HEADER_SIZE {
+            return Err(format!("Avatar chunk has {} bytes, expected more than {}", bytes.len(), CHUNK_HEADER_SIZE));
+        }
+        let public_key = PublicKey::from_slice(&bytes[..PUBLIC_KEY_SIZE]).map_err(|e| format!("Invalid avatar key: {e}"))?;
+        let signature = Signature::from_compact(&bytes[PUBLIC_KEY_SIZE..PUBLIC_KEY_SIZE + SIGNATURE_SIZE])
+            .map_err(|e| format!("Invalid avatar signature: {e}"))?;
+        let total = bytes[CHUNK_HEADER_SIZE - 1];
+        if total == 0 || total as usize > MAX_AVATAR_CHUNKS {
+            return Err(format!("Avatar has {} chunks, max allowed is {}", total, MAX_AVATAR_CHUNKS));
+        }
+        Ok(Self { public_key, signature, total, bytes: bytes[CHUNK_HEADER_SIZE..].to_vec() })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(CHUNK_HEADER_SIZE + self.bytes.len());
+        bytes.extend_from_slice(&self.public_key.serialize());
+        bytes.extend_from_slice(&self.signature.serialize_compact());
+        bytes.push(self.total);
+        bytes.extend_from_slice(&self.bytes);
+        bytes
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.public_key.serialize())
+    }
+
+    /// Подпись общая для всех фрагментов одного изображения: по ней отличаем версии аватара
+    pub fn signature_bytes(&self) -> [u8; SIGNATURE_SIZE] {
+        self.signature.serialize_compact()
+    }
+
+    /// Проверяет подпись над собранным изображением
+    pub fn verify(&self, image: &[u8], address: &str) -> Result<(), String> {
+        if verify_digest(&self.public_key, Self::digest(image, address), &self.signature) {
+            Ok(())
+        } else {
+            Err(format!("Avatar signature for {} does not match", self.public_key_hex()))
+        }
+    }
+
+    fn digest(image: &[u8], address: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(AVATAR_DOMAIN);
+        hasher.update(image);
+        hasher.update([0u8]);
+        hasher.update(address.as_bytes());
+        hasher.finalize().into()
+    }
+}
+
+/// Уменьшает картинку из файла до AVATAR_SIZE_PX и кодирует в PNG
+pub fn prepare_avatar(path: &str) -> Result<Vec<u8>, String> {
+    let image = image::open(path).map_err(|e| format!("Failed to open avatar image: {e}"))?;
+    let mut bytes = Vec::new();
+    image
+        .thumbnail(AVATAR_SIZE_PX, AVATAR_SIZE_PX)
+        .write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode avatar: {e}"))?;
+    if bytes.len() > AVATAR_CHUNK_SIZE * MAX_AVATAR_CHUNKS {
+        return Err(format!("Avatar has {} bytes, max allowed is {}", bytes.len(), AVATAR_CHUNK_SIZE * MAX_AVATAR_CHUNKS));
+    }
+    Ok(bytes)
+}
+
+/// Проверяет чужой аватар без полного декодирования: формат известен, размеры не больше AVATAR_SIZE_PX
+pub fn check_avatar(bytes: &[u8]) -> Result<(), String> {
+    let (width, height) = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| format!("Unknown avatar format: {e}"))?
+        .into_dimensions()
+        .map_err(|e| format!("Invalid avatar image: {e}"))?;
+    if width > AVATAR_SIZE_PX || height > AVATAR_SIZE_PX {
+        return Err(format!("Avatar is {width}x{height}, max allowed is {AVATAR_SIZE_PX}x{AVATAR_SIZE_PX}"));
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::constants::CLAIM_DOMAIN;
+use crate::core::identity::{verify_digest, Identity};
+use secp256k1::ecdsa::Signature;
+use secp256k1::PublicKey;
+use sha2::{Digest, Sha256};
+
+/// Размер сжатого публичного ключа secp256k1
+const PUBLIC_KEY_SIZE: usize = 33;
+/// Размер компактной подписи ECDSA
+const SIGNATURE_SIZE: usize = 64;
+
+/// Заявка на имя — поле data у payload с типом `MessageType::Claim`:
+///   33 (PUBLIC_KEY) + 64 (SIGNATURE)
+/// Подпись покрывает имя и адрес кошелька, с которого отправлена заявка,
+/// поэтому чужую заявку нельзя переотправить со своего адреса.
+#[derive(Debug, Clone)]
+pub struct Claim {
+    pub public_key: PublicKey,
+    signature: Signature,
+}
+
+impl Claim {
+    pub fn sign(identity: &Identity, username: &str, address: &str) -> Self {
+        let signature = identity.sign_digest(Self::digest(username, address));
+        Self { public_key: identity.public_key(), signature }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != PUBLIC_KEY_SIZE + SIGNATURE_SIZE {
+            return Err(format!("Claim has {} bytes, expected {}", bytes.len(), PUBLIC_KEY_SIZE + SIGNATURE_SIZE));
+        }
+        let public_key = PublicKey::from_slice(&bytes[..PUBLIC_KEY_SIZE]).map_err(|e| format!("Invalid claim key: {e}"))?;
+        let signature = Signature::from_compact(&bytes[PUBLIC_KEY_SIZE..]).map_err(|e| format!("Invalid claim signature: {e}"))?;
+        Ok(Self { public_key, signature })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(PUBLIC_KEY_SIZE + SIGNATURE_SIZE);
+        bytes.extend_from_slice(&self.public_key.serialize());
+        bytes.extend_from_slice(&self.signature.serialize_compact());
+        bytes
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.public_key.serialize())
+    }
+
+    /// Проверяет, что заявка подписана владельцем ключа для этого имени и адреса
+    pub fn verify(&self, username: &str, address: &str) -> Result<(), String> {
+        if verify_digest(&self.public_key, Self::digest(username, address), &self.signature) {
+            Ok(())
+        } else {
+            Err(format!("Claim signature for '{}' does not match", username))
+        }
+    }
+
+    fn digest(username: &str, address: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(CLAIM_DOMAIN);
+        hasher.update(username.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(address.as_bytes());
+        hasher.finalize().into()
+    }
+}
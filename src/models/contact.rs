@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Запись адресной книги: своё имя для адреса кошелька и/или ключа идентичности собеседника
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    pub name: String,
+    pub address: Option<String>,
+    /// Hex-ключ идентичности (личные сообщения, заявки на имя)
+    pub identity_key: Option<String>,
+    pub note: String,
+}
+
+impl Contact {
+    pub fn matches(&self, address: Option<&str>, identity_key: Option<&str>) -> bool {
+        (address.is_some() && self.address.as_deref() == address)
+            || (identity_key.is_some() && self.identity_key.as_deref() == identity_key)
+    }
+}
+
+impl fmt::Display for Contact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Заблокированный отправитель. Ключ идентичности переживает смену имени, имя — единственное,
+/// что известно об отправителе без заявки на имя и аватара
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockedSender {
+    Username(String),
+    IdentityKey(String),
+}
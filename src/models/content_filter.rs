@@ -0,0 +1,104 @@
+use crate::utils::i18n::tr;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Что делать с входящим сообщением, в котором нашлось совпадение
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FilterAction {
+    /// Фильтр выключен
+    #[default]
+    Off,
+    /// Совпадения заменяются звёздочками
+    Mask,
+    /// Сообщение не попадает в чат
+    Hide,
+}
+
+impl FilterAction {
+    pub const ALL: [FilterAction; 3] = [FilterAction::Off, FilterAction::Mask, FilterAction::Hide];
+}
+
+impl fmt::Display for FilterAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterAction::Off => write!(f, "{}", tr("filter-off")),
+            FilterAction::Mask => write!(f, "{}", tr("filter-mask")),
+            FilterAction::Hide => write!(f, "{}", tr("filter-hide")),
+        }
+    }
+}
+
+/// Фильтр входящих сообщений: слова и регулярные выражения, общее действие и свои действия для каналов
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentFilter {
+    /// Слова ищутся целиком без учёта регистра; строка вида `/…/` — регулярное выражение
+    pub patterns: Vec<String>,
+    pub action: FilterAction,
+    /// Действие для отдельных каналов вместо общего
+    pub channel_overrides: BTreeMap<u32, FilterAction>,
+}
+
+impl ContentFilter {
+    /// Действие для канала с учётом его собственной настройки
+    pub fn action_for(&self, channel: u32) -> FilterAction {
+        self.channel_overrides.get(&channel).copied().unwrap_or(self.action)
+    }
+
+    /// Собирает шаблоны; первый неверный шаблон — ошибка
+    pub fn compile(&self) -> Result<CompiledFilter, String> {
+        let regexes = self.patterns.iter().map(|pattern| compile_pattern(pattern)).collect::<Result<Vec<_>, _>>()?;
+        Ok(CompiledFilter { filter: self.clone(), regexes })
+    }
+}
+
+/// Итог проверки сообщения фильтром
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterOutcome {
+    Pass,
+    /// Текст с замаскированными совпадениями
+    Masked(String),
+    Hidden,
+}
+
+/// Фильтр с собранными регулярными выражениями, чтобы не собирать их на каждое сообщение
+#[derive(Debug, Clone, Default)]
+pub struct CompiledFilter {
+    filter: ContentFilter,
+    regexes: Vec<Regex>,
+}
+
+impl CompiledFilter {
+    pub fn apply(&self, channel: u32, content: &str) -> FilterOutcome {
+        let action = self.filter.action_for(channel);
+        if action == FilterAction::Off || !self.regexes.iter().any(|regex| regex.is_match(content)) {
+            return FilterOutcome::Pass;
+        }
+        if action == FilterAction::Hide {
+            return FilterOutcome::Hidden;
+        }
+        let masked = self.regexes.iter().fold(content.to_string(), |masked, regex| {
+            regex.replace_all(&masked, |captures: &regex::Captures| "*".repeat(captures[0].chars().count())).into_owned()
+        });
+        FilterOutcome::Masked(masked)
+    }
+}
+
+/// Граница слова ставится только со стороны буквы, цифры или `_`: у `\b` рядом со знаком вроде `c++` совпадений нет
+fn word_pattern(word: &str) -> String {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let start = if word.starts_with(is_word_char) { r"\b" } else { "" };
+    let end = if word.ends_with(is_word_char) { r"\b" } else { "" };
+    format!("{}{}{}", start, regex::escape(word), end)
+}
+
+/// `/…/` — регулярное выражение как есть, иначе слово целиком; регистр не учитывается
+fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    let source = match pattern.strip_prefix('/').and_then(|rest| rest.strip_suffix('/')) {
+        Some(expression) if !expression.is_empty() => expression.to_string(),
+        _ => word_pattern(pattern),
+    };
+    RegexBuilder::new(&source).case_insensitive(true).build().map_err(|e| format!("Invalid filter pattern {}: {}", pattern, e))
+}
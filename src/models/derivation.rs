@@ -0,0 +1,61 @@
+use crate::constants::MAX_ACCOUNT_INDEX;
+use crate::utils::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Путь вывода ключей личного аккаунта из мнемоники. Меняет адреса, поэтому применяется после перезапуска
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AccountDerivation {
+    /// m/44'/972/0' — прежний формат Kaspeak и кошелька KDX
+    #[default]
+    Legacy,
+    /// m/44'/111111'/<index>' — стандартный BIP44, как в Kaspa NG и kaspa-wallet
+    Bip44 { account_index: u64 },
+}
+
+impl AccountDerivation {
+    pub fn kind(&self) -> DerivationKind {
+        match self {
+            AccountDerivation::Legacy => DerivationKind::Legacy,
+            AccountDerivation::Bip44 { .. } => DerivationKind::Bip44,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            AccountDerivation::Bip44 { account_index } if *account_index > MAX_ACCOUNT_INDEX => {
+                Err(format!("Account index must be between 0 and {}", MAX_ACCOUNT_INDEX))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for AccountDerivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountDerivation::Legacy => write!(f, "m/44'/972/0'"),
+            AccountDerivation::Bip44 { account_index } => write!(f, "m/44'/111111'/{}'", account_index),
+        }
+    }
+}
+
+/// Вид пути без номера аккаунта, для выбора в GUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DerivationKind {
+    Legacy,
+    Bip44,
+}
+
+impl DerivationKind {
+    pub const ALL: [DerivationKind; 2] = [DerivationKind::Legacy, DerivationKind::Bip44];
+}
+
+impl fmt::Display for DerivationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerivationKind::Legacy => write!(f, "{}", tr("derivation-legacy")),
+            DerivationKind::Bip44 => write!(f, "{}", tr("derivation-bip44")),
+        }
+    }
+}
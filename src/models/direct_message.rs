@@ -0,0 +1,61 @@
+use crate::core::identity::Identity;
+use secp256k1::PublicKey;
+
+/// Размер сжатого публичного ключа secp256k1
+const PUBLIC_KEY_SIZE: usize = 33;
+
+/// Конверт личного сообщения — поле data у payload с типом `MessageType::Direct`:
+///   33 (SENDER_KEY) + 33 (RECIPIENT_KEY) + 12 (NONCE) + шифртекст
+#[derive(Debug, Clone)]
+pub struct DirectEnvelope {
+    pub sender: PublicKey,
+    pub recipient: PublicKey,
+    sealed: Vec<u8>,
+}
+
+impl DirectEnvelope {
+    /// Шифрует текст для получателя ключом идентичности отправителя
+    pub fn seal(identity: &Identity, recipient: &PublicKey, text: &str) -> Result<Self, String> {
+        let sealed = identity.encrypt_for(recipient, text.as_bytes())?;
+        Ok(Self { sender: identity.public_key(), recipient: *recipient, sealed })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < PUBLIC_KEY_SIZE * 2 {
+            return Err("Direct message is too short for keys".to_string());
+        }
+        let sender = PublicKey::from_slice(&bytes[..PUBLIC_KEY_SIZE]).map_err(|e| format!("Invalid sender key: {e}"))?;
+        let recipient =
+            PublicKey::from_slice(&bytes[PUBLIC_KEY_SIZE..PUBLIC_KEY_SIZE * 2]).map_err(|e| format!("Invalid recipient key: {e}"))?;
+        Ok(Self { sender, recipient, sealed: bytes[PUBLIC_KEY_SIZE * 2..].to_vec() })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(PUBLIC_KEY_SIZE * 2 + self.sealed.len());
+        bytes.extend_from_slice(&self.sender.serialize());
+        bytes.extend_from_slice(&self.recipient.serialize());
+        bytes.extend_from_slice(&self.sealed);
+        bytes
+    }
+
+    /// Адресовано ли сообщение нам (или отправлено нами)
+    pub fn involves(&self, identity: &Identity) -> bool {
+        let own = identity.public_key();
+        self.recipient == own || self.sender == own
+    }
+
+    /// Ключ собеседника с нашей точки зрения
+    pub fn peer(&self, identity: &Identity) -> PublicKey {
+        if self.sender == identity.public_key() {
+            self.recipient
+        } else {
+            self.sender
+        }
+    }
+
+    /// Расшифровывает текст сообщения
+    pub fn open(&self, identity: &Identity) -> Result<String, String> {
+        let plaintext = identity.decrypt_from(&self.peer(identity), &self.sealed)?;
+        String::from_utf8(plaintext).map_err(|_| "Invalid direct message encoding (UTF-8)".to_string())
+    }
+}
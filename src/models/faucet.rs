@@ -0,0 +1,67 @@
+use crate::models::network::Network;
+use crate::utils::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Откуда пополнять кошелёк, когда баланс падает ниже MINIMUM_AIRDROP_BALANCE
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FaucetSource {
+    /// Встроенный аккаунт эйрдропа: только testnet-10 и сборки с фичей `builtin-faucet`
+    #[default]
+    BuiltIn,
+    /// Внешний кран: POST на URL, `{address}` в нём заменяется адресом кошелька
+    Url(String),
+    /// Свой аккаунт пополнения, заданный мнемоникой
+    Mnemonic(String),
+    Disabled,
+}
+
+/// Вид источника без параметров, для выбора в GUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaucetKind {
+    BuiltIn,
+    Url,
+    Mnemonic,
+    Disabled,
+}
+
+impl FaucetKind {
+    pub const ALL: [FaucetKind; 4] = [FaucetKind::BuiltIn, FaucetKind::Url, FaucetKind::Mnemonic, FaucetKind::Disabled];
+}
+
+impl fmt::Display for FaucetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FaucetKind::BuiltIn => write!(f, "{}", tr("faucet-builtin")),
+            FaucetKind::Url => write!(f, "{}", tr("faucet-url")),
+            FaucetKind::Mnemonic => write!(f, "{}", tr("faucet-mnemonic")),
+            FaucetKind::Disabled => write!(f, "{}", tr("faucet-off")),
+        }
+    }
+}
+
+impl FaucetSource {
+    pub fn kind(&self) -> FaucetKind {
+        match self {
+            FaucetSource::BuiltIn => FaucetKind::BuiltIn,
+            FaucetSource::Url(_) => FaucetKind::Url,
+            FaucetSource::Mnemonic(_) => FaucetKind::Mnemonic,
+            FaucetSource::Disabled => FaucetKind::Disabled,
+        }
+    }
+
+    /// Можно ли пополняться из этого источника в сети `network`
+    pub fn is_available(&self, network: Network) -> bool {
+        match self {
+            FaucetSource::BuiltIn => cfg!(feature = "builtin-faucet") && network.has_airdrop(),
+            FaucetSource::Url(url) => !url.trim().is_empty(),
+            FaucetSource::Mnemonic(phrase) => !phrase.trim().is_empty(),
+            FaucetSource::Disabled => false,
+        }
+    }
+
+    /// URL запроса к внешнему крану для адреса
+    pub fn request_url(url: &str, address: &str) -> String {
+        url.trim().replace("{address}", address)
+    }
+}
@@ -1,10 +1,23 @@
+use crate::app_state::APP_STATE;
+use crate::core::identity::parse_public_key;
 use crate::models::payload::Payload;
 use crate::models::recording::Recording;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub enum Instruction {
     SendTx(SendTxInstruction),
     Airdrop,
+    /// Объединение мелких UTXO кошелька в один
+    Compound,
+    /// Перевод средств на чужой адрес
+    Transfer(TransferInstruction),
+    /// Повтор отправки из очереди повторов
+    Retry(RetryInstruction),
+    /// Замена зависшей транзакции с payload транзакцией с большей комиссией
+    BumpFee(BumpFeeInstruction),
+    /// Переход на новый адрес получения
+    RotateAddress,
 }
 
 #[derive(Debug)]
@@ -12,14 +25,33 @@ pub struct SendTxInstruction {
     pub tx_payload: Option<Vec<u8>>,
 }
 
+#[derive(Debug)]
+pub struct RetryInstruction {
+    /// Номер записи в очереди повторов
+    pub id: u64,
+    pub tx_payload: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct BumpFeeInstruction {
+    pub tx_id: String,
+}
+
+#[derive(Debug)]
+pub struct TransferInstruction {
+    pub address: String,
+    /// Сумма, сомпи
+    pub amount: u64,
+}
+
 //TODO изменить тип Result
 impl Instruction {
-    /// Формирование инструкции для Broadcaster из Recording.
-    pub(crate) fn try_from_recording(recording: &Recording) -> kaspa_wrpc_client::result::Result<Instruction> {
-        let mut payload = Payload::from_recording(&recording)?;
+    /// Формирование инструкции для Broadcaster из последовательных фрагментов записи (одного или пакета).
+    pub(crate) fn try_from_recordings(recordings: &[Arc<Recording>]) -> kaspa_wrpc_client::result::Result<Instruction> {
+        let mut payload = Payload::from_recordings(recordings)?;
         payload.compress_zstd()?;
 
-        Ok(Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_bytes()) }))
+        Ok(Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_signed_bytes(&APP_STATE.identity)) }))
     }
 
     /// Формирование инструкции для Broadcaster из Message.
@@ -27,6 +59,64 @@ impl Instruction {
         let mut payload = Payload::from_chat_message(&message)?;
         payload.compress_zstd()?;
 
-        Ok(Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_bytes()) }))
+        Ok(Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_signed_bytes(&APP_STATE.identity)) }))
+    }
+
+    /// Формирование инструкции для Broadcaster из объявления канала.
+    pub(crate) fn try_from_announcement(message: String) -> kaspa_wrpc_client::result::Result<Instruction> {
+        let mut payload = Payload::from_announcement(&message)?;
+        payload.compress_zstd()?;
+
+        Ok(Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_signed_bytes(&APP_STATE.identity)) }))
+    }
+
+    /// Формирование инструкции для Broadcaster из заявки на текущее имя.
+    pub(crate) fn try_from_claim() -> kaspa_wrpc_client::result::Result<Instruction> {
+        let address = APP_STATE.get_account_address()?.ok_or_else(|| "Wallet address is not known yet".to_string())?;
+        let payload = Payload::from_claim(&address)?;
+
+        Ok(Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_signed_bytes(&APP_STATE.identity)) }))
+    }
+
+    /// Формирование инструкций для Broadcaster из своего аватара: по одной на фрагмент.
+    pub(crate) fn try_from_avatar() -> kaspa_wrpc_client::result::Result<Vec<Instruction>> {
+        let avatar = APP_STATE.get_own_avatar()?.ok_or_else(|| "Avatar is not set".to_string())?;
+        let address = APP_STATE.get_account_address()?.ok_or_else(|| "Wallet address is not known yet".to_string())?;
+        let payloads = Payload::from_avatar(&avatar, &address)?;
+        APP_STATE.mark_avatar_announced()?;
+
+        Ok(payloads
+            .into_iter()
+            .map(|payload| Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_signed_bytes(&APP_STATE.identity)) }))
+            .collect())
+    }
+
+    /// Формирование инструкции для Broadcaster из запроса аватара владельца адреса.
+    pub(crate) fn try_from_avatar_request(address: &str) -> kaspa_wrpc_client::result::Result<Instruction> {
+        let payload = Payload::from_avatar_request(address)?;
+
+        Ok(Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_signed_bytes(&APP_STATE.identity)) }))
+    }
+
+    /// Формирование инструкции для Broadcaster из перевода средств.
+    pub(crate) fn try_from_transfer(address: &str, amount: u64) -> kaspa_wrpc_client::result::Result<Instruction> {
+        let address = address.trim();
+        if !address.starts_with(APP_STATE.get_network().address_prefix()) {
+            return Err(format!("Address {} does not belong to {}", address, APP_STATE.get_network()).into());
+        }
+        if amount == 0 {
+            return Err("Transfer amount is zero".to_string().into());
+        }
+
+        Ok(Instruction::Transfer(TransferInstruction { address: address.to_string(), amount }))
+    }
+
+    /// Формирование инструкции для Broadcaster из личного сообщения.
+    /// Шифртекст не сжимается: zstd для него бесполезен.
+    pub(crate) fn try_from_direct_message(recipient: &str, message: String) -> kaspa_wrpc_client::result::Result<Instruction> {
+        let recipient = parse_public_key(recipient)?;
+        let payload = Payload::from_direct_message(&recipient, &message)?;
+
+        Ok(Instruction::SendTx(SendTxInstruction { tx_payload: Some(payload.to_signed_bytes(&APP_STATE.identity)) }))
     }
 }
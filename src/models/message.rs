@@ -1,27 +1,100 @@
+use crate::constants::{MAX_CLOCK_SKEW_SECS, MAX_MENTIONS_PER_MESSAGE, MAX_USERNAME_CHARS};
 use crate::models::payload::Payload;
 use crate::models::user::User;
-use chrono::Local;
+use crate::utils::format::format_time;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Состояние доставки своего сообщения: отправлено → подтверждено (транзакция в блоке) или не подтверждено за отведённое время
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeliveryState {
+    Sent,
+    Confirmed,
+    Failed,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Message {
     pub id: Uuid,
     pub user: User,
     pub channel: u32,
     pub content: String,
-    pub time: String,
+    pub time: DateTime<Local>,
+    /// Транзакция, в которой пришло сообщение (нет у локальных сообщений)
+    pub tx_id: Option<String>,
+    /// Номер фрагмента payload; вместе с `tx_id` отличает повторно полученное сообщение от нового
+    pub fragment: u32,
+    /// Адрес отправителя (нет у локальных сообщений)
+    pub sender_address: Option<String>,
+    /// Ключ идентичности, которым подписан payload (нет у локальных и неподписанных сообщений)
+    pub sender_key: Option<String>,
+    /// Имя закреплено за другим ключом — возможная подмена
+    pub claim_mismatch: bool,
+    /// Первая ссылка в тексте, для превью
+    pub link_url: Option<String>,
+    /// Время, после которого сообщение скрывается
+    pub expires_at: Option<DateTime<Local>>,
+    /// Насколько часы отправителя расходятся со временем блока, секунды; есть только у сильно расходящихся
+    pub clock_skew_secs: Option<i64>,
+    /// Доставка своего сообщения; у чужих сообщений нет
+    pub delivery: Option<DeliveryState>,
+    /// Блок, принявший транзакцию, ушёл из выбранной цепочки, а новый её ещё не принял
+    pub possibly_reverted: bool,
+    /// В тексте есть `@имя` локального пользователя
+    pub mentions_me: bool,
 }
 
 impl Message {
     pub fn new(user: &mut User, content: &str, channel: u32) -> Self {
         user.update_last_message_time();
-        let time_str = Local::now().format("%H:%M  ").to_string();
         let message_id = Uuid::new_v4();
-        Self { id: message_id, user: user.clone(), channel, content: content.to_string(), time: time_str }
+        Self {
+            id: message_id,
+            user: user.clone(),
+            channel,
+            content: content.to_string(),
+            time: Local::now(),
+            tx_id: None,
+            fragment: 0,
+            sender_address: None,
+            sender_key: None,
+            claim_mismatch: false,
+            link_url: None,
+            expires_at: None,
+            clock_skew_secs: None,
+            delivery: None,
+            possibly_reverted: false,
+            mentions_me: false,
+        }
     }
 
     pub fn from_payload(payload: Payload) -> Self {
-        Self::new(&mut User::new(payload.get_username()), &*String::from_utf8_lossy(payload.get_data()).trim(), payload.get_channel())
+        let mut message = Self::new(
+            &mut User::new(payload.get_username()),
+            &*String::from_utf8_lossy(payload.get_data()).trim(),
+            payload.get_channel(),
+        );
+        message.tx_id = payload.get_tx_id().map(str::to_string);
+        message.fragment = payload.get_fragment_number();
+        message.sender_address = payload.get_sender_address().map(str::to_string);
+        message.sender_key = payload.get_sender_key().map(str::to_string);
+        message.expires_at =
+            payload.get_expires_at().and_then(|at| DateTime::from_timestamp(at, 0)).map(|at| at.with_timezone(&Local));
+        let (time, clock_skew_secs) = resolve_sent_time(payload.get_sent_at(), payload.get_block_time());
+        if let Some(time) = time {
+            message.time = time;
+        }
+        message.clock_skew_secs = clock_skew_secs;
+        message
+    }
+
+    /// Своё сообщение сразу после отправки транзакции `tx_id`, до того как Listener увидит его в блоке
+    pub fn outgoing(payload: Payload, tx_id: String) -> Self {
+        let mut message = Self::from_payload(payload);
+        message.tx_id = Some(tx_id);
+        message.delivery = Some(DeliveryState::Sent);
+        message
     }
 
     pub fn get_username(&self) -> &str {
@@ -32,11 +105,102 @@ impl Message {
         &self.content
     }
 
-    pub fn get_time(&self) -> &str {
-        &self.time
+    /// Время сообщения в формате выбранной локали
+    pub fn get_time(&self) -> String {
+        format_time(&self.time)
     }
 
     pub fn get_id(&self) -> Uuid {
         self.id
     }
+
+    pub fn get_tx_id(&self) -> Option<&str> {
+        self.tx_id.as_deref()
+    }
+
+    /// Ключ для отсева повторов: транзакция и фрагмент. У локальных сообщений его нет
+    pub fn dedupe_key(&self) -> Option<(&str, u32)> {
+        self.get_tx_id().map(|tx_id| (tx_id, self.fragment))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| at <= Local::now())
+    }
+}
+
+/// Сообщение из локального списка закреплённых в канале. Текст хранится копией: лента чата
+/// не переживает перезапуск, а закреплённое должно (см. `ChatSharedState::save_pinned_messages`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PinnedMessage {
+    /// Id сообщения в сессии, где его закрепили; нужен только локальным сообщениям без транзакции
+    pub message_id: String,
+    pub tx_id: Option<String>,
+    pub fragment: u32,
+    pub username: String,
+    pub content: String,
+    /// Unix-время сообщения, секунды
+    pub time: i64,
+}
+
+impl PinnedMessage {
+    pub fn from_message(msg: &Message) -> Self {
+        Self {
+            message_id: msg.get_id().to_string(),
+            tx_id: msg.tx_id.clone(),
+            fragment: msg.fragment,
+            username: msg.get_username().to_string(),
+            content: msg.get_content().to_string(),
+            time: msg.time.timestamp(),
+        }
+    }
+
+    /// То же ли это сообщение: по транзакции и фрагменту, а у локальных — по id
+    pub fn matches(&self, msg: &Message) -> bool {
+        match &self.tx_id {
+            Some(tx_id) => msg.dedupe_key() == Some((tx_id.as_str(), self.fragment)),
+            None => self.message_id == msg.get_id().to_string(),
+        }
+    }
+}
+
+/// Упоминается ли в тексте `@username` (без учёта регистра).
+/// Сразу за именем не должно идти продолжение слова: `@Bob` не упоминает `Bo`
+pub fn mentions(content: &str, username: &str) -> bool {
+    if username.is_empty() {
+        return false;
+    }
+    let content = content.to_lowercase();
+    let needle = format!("@{}", username.to_lowercase());
+    content
+        .match_indices(&needle)
+        .any(|(start, _)| content[start + needle.len()..].chars().next().map_or(true, |next| !next.is_alphanumeric() && next != '_'))
+}
+
+/// Что идёт после каждого `@` в тексте: отправитель кладёт это в расширение payload, и получатель
+/// находит упоминание в другом канале без распаковки данных. Берётся на символ больше самого длинного имени,
+/// чтобы `mentions` мог проверить, что имя не продолжается
+pub fn mention_candidates(content: &str) -> Vec<String> {
+    content
+        .match_indices('@')
+        .map(|(start, _)| content[start + 1..].chars().take(MAX_USERNAME_CHARS + 1).collect::<String>())
+        .filter(|candidate| !candidate.is_empty())
+        .take(MAX_MENTIONS_PER_MESSAGE)
+        .collect()
+}
+
+/// Время сообщения по часам отправителя, сверенное со временем блока (оба — unix-время в миллисекундах).
+/// Если расхождение больше MAX_CLOCK_SKEW_SECS, берём время блока и возвращаем величину расхождения.
+/// Без времени отправителя время не меняется (None): остаётся время получения
+fn resolve_sent_time(sent_at: Option<i64>, block_time: Option<i64>) -> (Option<DateTime<Local>>, Option<i64>) {
+    let to_local = |millis: i64| DateTime::from_timestamp_millis(millis).map(|at| at.with_timezone(&Local));
+    let Some(sent_at) = sent_at else {
+        return (None, None);
+    };
+    let reference = block_time.unwrap_or_else(|| Local::now().timestamp_millis());
+    let skew_secs = (sent_at - reference) / 1000;
+    if skew_secs.abs() > MAX_CLOCK_SKEW_SECS {
+        (to_local(reference), Some(skew_secs))
+    } else {
+        (to_local(sent_at), None)
+    }
 }
@@ -1,5 +1,20 @@
+pub(crate) mod avatar;
+pub(crate) mod claim;
+pub(crate) mod contact;
+pub(crate) mod content_filter;
+pub(crate) mod derivation;
+pub(crate) mod direct_message;
+pub(crate) mod faucet;
 pub(crate) mod instruction;
 pub(crate) mod message;
+pub(crate) mod network;
 pub(crate) mod payload;
 pub(crate) mod recording;
+pub(crate) mod retry;
+pub(crate) mod sender_signature;
+pub(crate) mod shortcut;
+#[cfg(test)]
+mod test_vectors;
+pub(crate) mod transaction;
+pub(crate) mod transcript;
 pub(crate) mod user;
@@ -0,0 +1,132 @@
+use crate::constants::{DEFAULT_RPC_CONNECT_TIMEOUT_SECS, DEFAULT_RPC_REQUEST_RETRIES, DEFAULT_RPC_REQUEST_TIMEOUT_SECS};
+use kaspa_consensus_core::network::{NetworkId, NetworkType};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Сеть Kaspa, с которой работает приложение. Выбирается в настройках и применяется после перезапуска:
+/// от неё зависят RPC-клиент, кошелёк, префикс адресов, тикер и ссылки на обозреватель
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Network {
+    #[default]
+    Testnet10,
+    Testnet11,
+    Mainnet,
+    /// Публичных нод нет, подключаться нужно к своей
+    Devnet,
+}
+
+impl Network {
+    pub const ALL: [Network; 4] = [Network::Testnet10, Network::Testnet11, Network::Mainnet, Network::Devnet];
+
+    pub fn network_id(self) -> NetworkId {
+        match self {
+            Network::Testnet10 => NetworkId::with_suffix(NetworkType::Testnet, 10),
+            Network::Testnet11 => NetworkId::with_suffix(NetworkType::Testnet, 11),
+            Network::Mainnet => NetworkId::new(NetworkType::Mainnet),
+            Network::Devnet => NetworkId::new(NetworkType::Devnet),
+        }
+    }
+
+    /// Префикс адресов вместе с двоеточием
+    pub fn address_prefix(self) -> &'static str {
+        match self {
+            Network::Testnet10 | Network::Testnet11 => "kaspatest:",
+            Network::Mainnet => "kaspa:",
+            Network::Devnet => "kaspadev:",
+        }
+    }
+
+    pub fn ticker(self) -> &'static str {
+        match self {
+            Network::Testnet10 | Network::Testnet11 => "TKAS",
+            Network::Mainnet => "KAS",
+            Network::Devnet => "DKAS",
+        }
+    }
+
+    /// Обозреватель блоков; у devnet его нет
+    pub fn explorer_url(self) -> Option<&'static str> {
+        match self {
+            Network::Testnet10 => Some("https://explorer-tn10.kaspa.org"),
+            Network::Testnet11 => Some("https://explorer-tn11.kaspa.org"),
+            Network::Mainnet => Some("https://explorer.kaspa.org"),
+            Network::Devnet => None,
+        }
+    }
+
+    /// В mainnet каждое сообщение тратит настоящие KAS
+    pub fn is_mainnet(self) -> bool {
+        self == Network::Mainnet
+    }
+
+    /// Встроенный аккаунт для эйрдропа пополнен только в testnet-10
+    pub fn has_airdrop(self) -> bool {
+        self == Network::Testnet10
+    }
+}
+
+/// Сколько ждать ноду и сколько раз повторять запросы на чтение (см. `utils::rpc::call_with_policy`).
+/// Отправка транзакций не повторяется: повтор мог бы отправить сообщение дважды
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcPolicy {
+    pub connect_timeout_secs: u64,
+    pub request_timeout_secs: u64,
+    /// Повторов после первой неудачной попытки
+    pub request_retries: u32,
+}
+
+impl RpcPolicy {
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout_secs)
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+}
+
+impl Default for RpcPolicy {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: DEFAULT_RPC_CONNECT_TIMEOUT_SECS,
+            request_timeout_secs: DEFAULT_RPC_REQUEST_TIMEOUT_SECS,
+            request_retries: DEFAULT_RPC_REQUEST_RETRIES,
+        }
+    }
+}
+
+/// Уведомления ноды, на которые подписывается Listener; применяются при следующем подключении.
+/// Мемпул уведомлений не шлёт и включается отдельно (`mempool_listening`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationScopes {
+    /// Новые блоки: единственный источник содержимого транзакций, кроме мемпула
+    pub block_added: bool,
+    /// Изменения виртуальной цепочки: payload обрабатываются, только когда цепочка примет транзакцию.
+    /// Без них payload обрабатываются сразу из блока, а реорганизации не отслеживаются
+    pub virtual_chain_changed: bool,
+}
+
+impl NotificationScopes {
+    /// Изменения цепочки содержат только идентификаторы транзакций и без блоков бесполезны
+    pub fn uses_virtual_chain(&self) -> bool {
+        self.block_added && self.virtual_chain_changed
+    }
+}
+
+impl Default for NotificationScopes {
+    fn default() -> Self {
+        Self { block_added: true, virtual_chain_changed: true }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Network::Testnet10 => write!(f, "Testnet 10"),
+            Network::Testnet11 => write!(f, "Testnet 11"),
+            Network::Mainnet => write!(f, "Mainnet"),
+            Network::Devnet => write!(f, "Devnet"),
+        }
+    }
+}
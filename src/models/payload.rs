@@ -2,8 +2,16 @@ use zstd::{decode_all, encode_all};
 
 use crate::app_state::APP_STATE;
 use crate::constants;
+use crate::core::identity::Identity;
+use crate::models::avatar::AvatarChunk;
+use crate::models::claim::Claim;
+use crate::models::direct_message::DirectEnvelope;
+use crate::models::message::{mention_candidates, mentions};
 use crate::models::recording::Recording;
+use crate::models::sender_signature::SenderSignature;
 use crate::utils::{parse_3bytes_to_u32, u32_to_3bytes};
+use secp256k1::PublicKey;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[repr(u8)]
@@ -12,15 +20,32 @@ pub enum MessageType {
     Text = 1,
     Voice = 2,
     File = 3,
+    Direct = 4,
+    Claim = 5,
+    /// Объявление канала: закрепляется над чатом, пока его не скроют
+    Announcement = 6,
+    /// Фрагмент аватара (см. `AvatarChunk`)
+    Avatar = 7,
+    /// Просьба владельца адреса из data повторить рассылку аватара
+    AvatarRequest = 8,
+    /// Несколько последовательных голосовых фрагментов в одной транзакции:
+    /// data = [STATUS_FLAG (1) | LENGTH (2, BE) | AUDIO]*, номера идут подряд от fragment_number
+    VoiceBatch = 9,
     Unknown(u8),
 }
 
 impl MessageType {
-    fn from_byte(byte: u8) -> Self {
+    pub fn from_byte(byte: u8) -> Self {
         match byte {
             1 => MessageType::Text,
             2 => MessageType::Voice,
             3 => MessageType::File,
+            4 => MessageType::Direct,
+            5 => MessageType::Claim,
+            6 => MessageType::Announcement,
+            7 => MessageType::Avatar,
+            8 => MessageType::AvatarRequest,
+            9 => MessageType::VoiceBatch,
             other => MessageType::Unknown(other),
         }
     }
@@ -30,6 +55,12 @@ impl MessageType {
             MessageType::Text => 1,
             MessageType::Voice => 2,
             MessageType::File => 3,
+            MessageType::Direct => 4,
+            MessageType::Claim => 5,
+            MessageType::Announcement => 6,
+            MessageType::Avatar => 7,
+            MessageType::AvatarRequest => 8,
+            MessageType::VoiceBatch => 9,
             MessageType::Unknown(val) => val,
         }
     }
@@ -63,16 +94,138 @@ impl StatusFlag {
         }
     }
 }
+
+/// Кодек и формат кадров голосового фрагмента
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoiceCodec {
+    /// Opus, моно, 48 кГц; кадры с префиксом длины 4 байта (LE). Подразумевается, если кодек не указан
+    OpusMono48k = 1,
+    Unknown(u8),
+}
+
+impl VoiceCodec {
+    fn from_byte(byte: u8) -> Self {
+        match byte {
+            1 => VoiceCodec::OpusMono48k,
+            other => VoiceCodec::Unknown(other),
+        }
+    }
+
+    fn to_byte(&self) -> u8 {
+        match *self {
+            VoiceCodec::OpusMono48k => 1,
+            VoiceCodec::Unknown(val) => val,
+        }
+    }
+}
+
+/// Расширение заголовка в формате TLV: 1 (TAG) + 2 (LENGTH, BE) + VALUE.
+/// Новые поля протокола (подписи, ответы, параметры кодека) добавляются новыми тегами;
+/// неизвестные теги сохраняются как есть и игнорируются, поэтому старые клиенты не ломаются
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extension {
+    pub tag: u8,
+    pub value: Vec<u8>,
+}
+
+/// Несоответствие, найденное при разборе payload.
+/// Исправимые в режиме восстановления попадают в диагностику, остальные всегда прерывают разбор
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseIssue {
+    TooShortForHeader,
+    InvalidMarker,
+    UnsupportedVersion(u8),
+    UsernameLengthMissing,
+    UsernameTruncated,
+    InvalidUsername,
+    ExtensionsLengthMissing,
+    ExtensionsTruncated,
+    DataLengthMissing,
+    /// Ограничения `Payload::new` (длина имени, размер данных)
+    Invalid(String),
+    // Исправимые
+    MalformedExtensions(String),
+    DataTruncated {
+        declared: usize,
+        available: usize,
+    },
+    ChecksumMissing,
+    ChecksumMismatch {
+        expected: u32,
+        actual: u32,
+    },
+}
+
+impl ParseIssue {
+    /// Короткий стабильный идентификатор для логов и телеметрии
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ParseIssue::TooShortForHeader => "too_short_for_header",
+            ParseIssue::InvalidMarker => "invalid_marker",
+            ParseIssue::UnsupportedVersion(_) => "unsupported_version",
+            ParseIssue::UsernameLengthMissing => "username_length_missing",
+            ParseIssue::UsernameTruncated => "username_truncated",
+            ParseIssue::InvalidUsername => "invalid_username",
+            ParseIssue::ExtensionsLengthMissing => "extensions_length_missing",
+            ParseIssue::ExtensionsTruncated => "extensions_truncated",
+            ParseIssue::DataLengthMissing => "data_length_missing",
+            ParseIssue::Invalid(_) => "invalid",
+            ParseIssue::MalformedExtensions(_) => "malformed_extensions",
+            ParseIssue::DataTruncated { .. } => "data_truncated",
+            ParseIssue::ChecksumMissing => "checksum_missing",
+            ParseIssue::ChecksumMismatch { .. } => "checksum_mismatch",
+        }
+    }
+}
+
+impl std::fmt::Display for ParseIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseIssue::TooShortForHeader => write!(f, "Incoming data is too short for header"),
+            ParseIssue::InvalidMarker => write!(f, "Invalid marker"),
+            ParseIssue::UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported protocol version: {} (expected {}..={})",
+                version,
+                constants::MIN_PROTOCOL_VERSION,
+                constants::PROTOCOL_VERSION
+            ),
+            ParseIssue::UsernameLengthMissing => write!(f, "Not enough data for username length"),
+            ParseIssue::UsernameTruncated => write!(f, "Username length exceeds available data"),
+            ParseIssue::InvalidUsername => write!(f, "Invalid username encoding (UTF-8)"),
+            ParseIssue::ExtensionsLengthMissing => write!(f, "Not enough data for extensions length"),
+            ParseIssue::ExtensionsTruncated => write!(f, "Extensions length exceeds available data"),
+            ParseIssue::DataLengthMissing => write!(f, "Not enough data for data length"),
+            ParseIssue::Invalid(err) | ParseIssue::MalformedExtensions(err) => write!(f, "{}", err),
+            ParseIssue::DataTruncated { declared, available } => {
+                write!(f, "Payload length exceeds available data: declared {}, available {}", declared, available)
+            }
+            ParseIssue::ChecksumMissing => write!(f, "Payload is truncated: checksum is missing"),
+            ParseIssue::ChecksumMismatch { expected, actual } => {
+                write!(f, "Payload checksum mismatch: expected {:08x}, got {:08x}", expected, actual)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Payload {
     protocol_version: u8,
-    channel_number: u32,       // 3 байта
-    message_type: MessageType, // 1 байт
-    status_flag: StatusFlag,   // 1 байт
-    fragment_number: u32,      // 3 байта
-    username: String,          // Переменная длина (<= 255 байт, <= 18 chars)
-    data: Vec<u8>,             // Полезная нагрузка (<= 15000 байт)
+    channel_number: u32,        // 3 байта
+    message_type: MessageType,  // 1 байт
+    status_flag: StatusFlag,    // 1 байт
+    fragment_number: u32,       // 3 байта
+    username: String,           // Переменная длина (<= 255 байт, <= 18 chars)
+    extensions: Vec<Extension>, // TLV, с версии 2 (<= 1024 байт)
+    data: Vec<u8>,              // Полезная нагрузка (<= 15000 байт)
     received_time: Option<SystemTime>,
+    tx_id: Option<String>,          // Идентификатор транзакции (только для входящих)
+    sender_address: Option<String>, // Адрес отправителя из выходов транзакции (только для входящих)
+    sender_key: Option<String>,     // Ключ идентичности с проверенной подписью отправителя (только для входящих)
+    signed_len: Option<usize>,      // Длина подписанной части, если за данными идёт подпись отправителя (только для входящих)
+    block_time: Option<i64>,        // Время блока, unix-время в миллисекундах (только для входящих)
+    backfilled: bool,               // Подгружено из истории при подключении, а не получено только что
 }
 
 impl Payload {
@@ -106,26 +259,63 @@ impl Payload {
             status_flag,
             fragment_number,
             username: username.to_string(),
+            extensions: Vec::new(),
             data,
             received_time,
+            tx_id: None,
+            sender_address: None,
+            sender_key: None,
+            signed_len: None,
+            block_time: None,
+            backfilled: false,
         })
     }
 
+    /// Тип сообщения по заголовку, без разбора всего payload
+    pub fn peek_message_type(bytes: &[u8]) -> Option<MessageType> {
+        if !bytes.starts_with(constants::MARKER) {
+            return None;
+        }
+        bytes.get(constants::MARKER.len() + 4).map(|&byte| MessageType::from_byte(byte))
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        Self::parse(bytes, false).map(|(payload, _)| payload).map_err(|issue| issue.to_string())
+    }
+
+    /// Режим восстановления: исправимые несоответствия (повреждённые расширения, обрезанные данные,
+    /// неверная или отсутствующая контрольная сумма) не прерывают разбор, а возвращаются списком.
+    /// Ошибка возвращается, только если восстановить payload невозможно
+    pub fn from_bytes_lenient(bytes: &[u8]) -> Result<(Self, Vec<ParseIssue>), ParseIssue> {
+        Self::parse(bytes, true)
+    }
+
+    fn parse(bytes: &[u8], lenient: bool) -> Result<(Self, Vec<ParseIssue>), ParseIssue> {
+        let mut issues = Vec::new();
+        // В строгом режиме первое же несоответствие — ошибка
+        let mut recover = |issue: ParseIssue| -> Result<(), ParseIssue> {
+            if lenient {
+                issues.push(issue);
+                Ok(())
+            } else {
+                Err(issue)
+            }
+        };
+
         if bytes.len() < constants::HEADER_SIZE {
-            return Err("Incoming data is too short for header".to_string());
+            return Err(ParseIssue::TooShortForHeader);
         }
 
         let mut pos = 0;
 
         if &bytes[pos..pos + 4] != constants::MARKER {
-            return Err("Invalid marker".to_string());
+            return Err(ParseIssue::InvalidMarker);
         }
         pos += 4;
 
         let protocol_version = bytes[pos];
-        if protocol_version != constants::PROTOCOL_VERSION {
-            return Err(format!("Unsupported protocol version: {} (expected {})", protocol_version, constants::PROTOCOL_VERSION));
+        if !(constants::MIN_PROTOCOL_VERSION..=constants::PROTOCOL_VERSION).contains(&protocol_version) {
+            return Err(ParseIssue::UnsupportedVersion(protocol_version));
         }
         pos += 1;
 
@@ -141,13 +331,13 @@ impl Payload {
         let fragment_number = parse_3bytes_to_u32(&bytes[pos..pos + 3]);
         pos += 3;
         if pos >= bytes.len() {
-            return Err("Not enough data for username length".to_string());
+            return Err(ParseIssue::UsernameLengthMissing);
         }
 
         let username_length = bytes[pos] as usize;
         pos += 1;
         if pos + username_length > bytes.len() {
-            return Err("Username length exceeds available data".to_string());
+            return Err(ParseIssue::UsernameTruncated);
         }
 
         let username_bytes = &bytes[pos..pos + username_length];
@@ -155,26 +345,71 @@ impl Payload {
 
         let username = match String::from_utf8(username_bytes.to_vec()) {
             Ok(s) => s,
-            Err(_) => return Err("Invalid username encoding (UTF-8)".to_string()),
+            Err(_) => return Err(ParseIssue::InvalidUsername),
         };
 
+        // С версии 2 после имени идёт область расширений
+        let mut extensions = Vec::new();
+        if protocol_version >= 2 {
+            if pos + constants::EXTENSIONS_LEN_SIZE > bytes.len() {
+                return Err(ParseIssue::ExtensionsLengthMissing);
+            }
+            let extensions_length = u16::from_be_bytes([bytes[pos], bytes[pos + 1]]) as usize;
+            pos += constants::EXTENSIONS_LEN_SIZE;
+            if pos + extensions_length > bytes.len() {
+                return Err(ParseIssue::ExtensionsTruncated);
+            }
+            // Границы области известны, поэтому повреждённые расширения можно просто отбросить
+            match Self::parse_extensions(&bytes[pos..pos + extensions_length]) {
+                Ok(parsed) => extensions = parsed,
+                Err(err) => recover(ParseIssue::MalformedExtensions(err))?,
+            }
+            pos += extensions_length;
+        }
+
         if pos + 3 > bytes.len() {
-            return Err("Not enough data for data length".to_string());
+            return Err(ParseIssue::DataLengthMissing);
         }
         let data_length = parse_3bytes_to_u32(&bytes[pos..pos + 3]) as usize;
         pos += 3;
 
-        if pos + data_length > bytes.len() {
-            return Err("Payload length exceeds available data".to_string());
+        let available = bytes.len() - pos;
+        let data_truncated = data_length > available;
+        if data_truncated {
+            recover(ParseIssue::DataTruncated { declared: data_length, available })?;
         }
-        let data = bytes[pos..pos + data_length].to_vec();
+        let data = bytes[pos..pos + data_length.min(available)].to_vec();
+        pos += data.len();
 
-        let payload = Self::new(channel_number, message_type, status_flag, fragment_number, &username, data, Some(SystemTime::now()))?;
-        Ok(payload)
+        // С версии 1 за данными идёт CRC32: обрезанный или повреждённый payload отбрасываем здесь, а не в zstd/Opus.
+        // У обрезанных данных суммы уже нет, отдельно об этом не сообщаем
+        if protocol_version >= 1 && !data_truncated {
+            if pos + constants::CHECKSUM_SIZE > bytes.len() {
+                recover(ParseIssue::ChecksumMissing)?;
+            } else {
+                let expected = u32::from_be_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]]);
+                let actual = crc32fast::hash(&data);
+                if expected != actual {
+                    recover(ParseIssue::ChecksumMismatch { expected, actual })?;
+                }
+                pos += constants::CHECKSUM_SIZE;
+            }
+        }
+
+        let mut payload =
+            Self::new(channel_number, message_type, status_flag, fragment_number, &username, data, Some(SystemTime::now()))
+                .map_err(ParseIssue::Invalid)?;
+        payload.protocol_version = protocol_version;
+        payload.extensions = extensions;
+        // Подпись отправителя проверяется позже (`verify_sender`), когда payload не отброшен по заголовку
+        if bytes.len() - pos == constants::SENDER_SIGNATURE_SIZE {
+            payload.signed_len = Some(pos);
+        }
+        Ok((payload, issues))
     }
 
     pub fn from_recording(recording: &Recording) -> Result<Self, String> {
-        Self::new(
+        let mut payload = Self::new(
             APP_STATE.get_channel_number().unwrap_or(0),
             MessageType::Voice,
             recording.state,
@@ -182,7 +417,96 @@ impl Payload {
             APP_STATE.get_username().as_str(),
             recording.audio.clone(),
             None,
-        )
+        )?;
+        payload.set_voice_extensions()?;
+        Ok(payload)
+    }
+
+    /// Пакет последовательных фрагментов одной записи (см. `MessageType::VoiceBatch`).
+    /// Статус пакета — статус последнего фрагмента; одиночный фрагмент уходит обычным Voice
+    pub fn from_recordings(recordings: &[Arc<Recording>]) -> Result<Self, String> {
+        let (first, last) = match recordings {
+            [] => return Err("Voice batch is empty".to_string()),
+            [single] => return Self::from_recording(single),
+            [first, .., last] => (first, last),
+        };
+        let mut data = Vec::with_capacity(Self::voice_batch_len(recordings));
+        for (index, recording) in recordings.iter().enumerate() {
+            if recording.fragment_num != first.fragment_num + index as u32 {
+                return Err(format!("Voice batch fragment {} is out of sequence", recording.fragment_num));
+            }
+            let len = u16::try_from(recording.audio.len())
+                .map_err(|_| format!("Voice fragment has {} bytes, too many for a batch", recording.audio.len()))?;
+            data.push(recording.state.to_byte());
+            data.extend_from_slice(&len.to_be_bytes());
+            data.extend_from_slice(&recording.audio);
+        }
+        let mut payload = Self::new(
+            APP_STATE.get_channel_number().unwrap_or(0),
+            MessageType::VoiceBatch,
+            last.state,
+            first.fragment_num,
+            APP_STATE.get_username().as_str(),
+            data,
+            None,
+        )?;
+        payload.set_voice_extensions()?;
+        Ok(payload)
+    }
+
+    /// Размер данных пакета из этих фрагментов до сжатия
+    pub fn voice_batch_len(recordings: &[Arc<Recording>]) -> usize {
+        recordings.iter().map(|recording| constants::VOICE_BATCH_ENTRY_HEADER + recording.audio.len()).sum()
+    }
+
+    /// Разворачивает пакет в обычные Voice-фрагменты с метаданными пакета; прочие payload возвращаются как есть
+    pub fn unpack_voice_batch(&self) -> Result<Vec<Self>, String> {
+        if self.message_type != MessageType::VoiceBatch {
+            return Ok(vec![self.clone()]);
+        }
+        let mut fragments = Vec::new();
+        let mut pos = 0;
+        while pos < self.data.len() {
+            let header = self
+                .data
+                .get(pos..pos + constants::VOICE_BATCH_ENTRY_HEADER)
+                .ok_or_else(|| format!("Voice batch entry {} has a truncated header", fragments.len()))?;
+            let status_flag = StatusFlag::from_byte(header[0]);
+            let len = u16::from_be_bytes([header[1], header[2]]) as usize;
+            pos += constants::VOICE_BATCH_ENTRY_HEADER;
+            let audio = self
+                .data
+                .get(pos..pos + len)
+                .ok_or_else(|| format!("Voice batch entry {} declares {} bytes, but the batch is shorter", fragments.len(), len))?;
+            pos += len;
+            fragments.push(Self {
+                protocol_version: self.protocol_version,
+                channel_number: self.channel_number,
+                message_type: MessageType::Voice,
+                status_flag,
+                fragment_number: self.fragment_number + fragments.len() as u32,
+                username: self.username.clone(),
+                extensions: self.extensions.clone(),
+                data: audio.to_vec(),
+                received_time: self.received_time,
+                tx_id: self.tx_id.clone(),
+                sender_address: self.sender_address.clone(),
+                sender_key: self.sender_key.clone(),
+                signed_len: self.signed_len,
+                block_time: self.block_time,
+                backfilled: self.backfilled,
+            });
+        }
+        if fragments.is_empty() {
+            return Err("Voice batch is empty".to_string());
+        }
+        Ok(fragments)
+    }
+
+    fn set_voice_extensions(&mut self) -> Result<(), String> {
+        // Фрагмент, пришедший позже окна воспроизведения, уже бесполезен
+        self.set_expires_at(unix_now() + constants::VOICE_FRAGMENT_TTL_SECS)?;
+        self.set_voice_codec(VoiceCodec::OpusMono48k)
     }
 
     pub fn from_chat_message(message: &str) -> Result<Self, String> {
@@ -199,11 +523,152 @@ impl Payload {
             message.as_bytes().to_vec(),
             None,
         )
+        .and_then(|mut payload| {
+            payload.set_sent_at(unix_now_millis())?;
+            payload.set_mentions(&mention_candidates(message))?;
+            if let Some(ttl) = APP_STATE.get_message_ttl().unwrap_or(None) {
+                payload.set_expires_at(unix_now() + ttl as i64)?;
+            }
+            Ok(payload)
+        })
+    }
+
+    /// Объявление в текущий канал: формат как у текстового сообщения, меняется только тип
+    pub fn from_announcement(message: &str) -> Result<Self, String> {
+        let mut payload = Self::from_chat_message(message)?;
+        payload.message_type = MessageType::Announcement;
+        Ok(payload)
+    }
+
+    /// Личное сообщение: текст шифруется ключом идентичности для получателя
+    pub fn from_direct_message(recipient: &PublicKey, message: &str) -> Result<Self, String> {
+        let msg_chars = message.chars().count();
+        if msg_chars > constants::MAX_TEXT_CHARS {
+            return Err(format!("Text data has {} chars, max allowed is {}", msg_chars, constants::MAX_TEXT_CHARS));
+        }
+        let envelope = DirectEnvelope::seal(&APP_STATE.identity, recipient, message)?;
+        Self::new(
+            constants::DEFAULT_CHANNEL,
+            MessageType::Direct,
+            StatusFlag::End,
+            0,
+            APP_STATE.get_username().as_str(),
+            envelope.to_bytes(),
+            None,
+        )
+        .and_then(|mut payload| {
+            payload.set_sent_at(unix_now_millis())?;
+            Ok(payload)
+        })
+    }
+
+    /// Разбор области расширений. Теги не проверяются: неизвестные просто сохраняются
+    fn parse_extensions(mut area: &[u8]) -> Result<Vec<Extension>, String> {
+        let mut extensions = Vec::new();
+        while !area.is_empty() {
+            if area.len() < constants::EXTENSION_HEADER_SIZE {
+                return Err("Malformed extension: truncated header".to_string());
+            }
+            let tag = area[0];
+            let length = u16::from_be_bytes([area[1], area[2]]) as usize;
+            area = &area[constants::EXTENSION_HEADER_SIZE..];
+            if length > area.len() {
+                return Err(format!("Malformed extension {tag:#04x}: value exceeds extension area"));
+            }
+            extensions.push(Extension { tag, value: area[..length].to_vec() });
+            area = &area[length..];
+        }
+        Ok(extensions)
+    }
+
+    fn extensions_len(&self) -> usize {
+        self.extensions.iter().map(|ext| constants::EXTENSION_HEADER_SIZE + ext.value.len()).sum()
+    }
+
+    /// Значение расширения с заданным тегом
+    pub fn get_extension(&self, tag: u8) -> Option<&[u8]> {
+        self.extensions.iter().find(|ext| ext.tag == tag).map(|ext| ext.value.as_slice())
+    }
+
+    /// Добавляет или заменяет расширение
+    pub fn set_extension(&mut self, tag: u8, value: Vec<u8>) -> Result<(), String> {
+        if value.len() > u16::MAX as usize {
+            return Err(format!("Extension {tag:#04x} is too large: {} bytes", value.len()));
+        }
+        let old = self.extensions.iter().position(|ext| ext.tag == tag).map(|idx| self.extensions.remove(idx));
+        self.extensions.push(Extension { tag, value });
+        if self.extensions_len() > constants::MAX_EXTENSIONS_BYTES {
+            self.extensions.pop();
+            if let Some(old) = old {
+                self.extensions.push(old);
+            }
+            return Err(format!("Extensions exceed {} bytes", constants::MAX_EXTENSIONS_BYTES));
+        }
+        Ok(())
+    }
+
+    pub fn get_extensions(&self) -> &[Extension] {
+        &self.extensions
+    }
+
+    /// Заявка на текущее имя пользователя, подписанная ключом идентичности
+    pub fn from_claim(address: &str) -> Result<Self, String> {
+        let username = APP_STATE.get_username();
+        let claim = Claim::sign(&APP_STATE.identity, &username, address);
+        Self::new(constants::DEFAULT_CHANNEL, MessageType::Claim, StatusFlag::End, 0, &username, claim.to_bytes(), None)
+    }
+
+    /// Фрагменты аватара пользователя; номер фрагмента — его позиция в изображении
+    pub fn from_avatar(image: &[u8], address: &str) -> Result<Vec<Self>, String> {
+        let username = APP_STATE.get_username();
+        let chunks = AvatarChunk::split(&APP_STATE.identity, image, address)?;
+        let last = chunks.len() - 1;
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                let status_flag = match index {
+                    i if i == last => StatusFlag::End,
+                    0 => StatusFlag::Start,
+                    _ => StatusFlag::Continue,
+                };
+                Self::new(
+                    constants::DEFAULT_CHANNEL,
+                    MessageType::Avatar,
+                    status_flag,
+                    index as u32,
+                    &username,
+                    chunk.to_bytes(),
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    /// Просьба повторить рассылку аватара владельцу адреса
+    pub fn from_avatar_request(address: &str) -> Result<Self, String> {
+        let username = APP_STATE.get_username();
+        Self::new(
+            constants::DEFAULT_CHANNEL,
+            MessageType::AvatarRequest,
+            StatusFlag::End,
+            0,
+            &username,
+            address.as_bytes().to_vec(),
+            None,
+        )
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
         let uname_bytes = self.username.as_bytes().len();
-        let mut packet = Vec::with_capacity(constants::HEADER_SIZE + uname_bytes + self.data.len());
+        let mut packet = Vec::with_capacity(
+            constants::HEADER_SIZE
+                + uname_bytes
+                + constants::EXTENSIONS_LEN_SIZE
+                + self.extensions_len()
+                + self.data.len()
+                + constants::CHECKSUM_SIZE,
+        );
         packet.extend_from_slice(constants::MARKER);
         packet.push(self.protocol_version);
         packet.extend_from_slice(&u32_to_3bytes(self.channel_number));
@@ -212,11 +677,38 @@ impl Payload {
         packet.extend_from_slice(&u32_to_3bytes(self.fragment_number));
         packet.push(uname_bytes as u8);
         packet.extend_from_slice(self.username.as_bytes());
+        if self.protocol_version >= 2 {
+            packet.extend_from_slice(&(self.extensions_len() as u16).to_be_bytes());
+            for ext in &self.extensions {
+                packet.push(ext.tag);
+                packet.extend_from_slice(&(ext.value.len() as u16).to_be_bytes());
+                packet.extend_from_slice(&ext.value);
+            }
+        }
         packet.extend_from_slice(&u32_to_3bytes(self.data.len() as u32));
         packet.extend_from_slice(&self.data);
+        if self.protocol_version >= 1 {
+            packet.extend_from_slice(&crc32fast::hash(&self.data).to_be_bytes());
+        }
+        packet
+    }
+
+    /// Байты для транзакции: payload и подпись отправителя ключом идентичности
+    pub fn to_signed_bytes(&self, identity: &Identity) -> Vec<u8> {
+        let mut packet = self.to_bytes();
+        let signature = SenderSignature::sign(identity, &packet);
+        packet.extend_from_slice(&signature.to_bytes());
         packet
     }
 
+    /// Ключ отправителя, если за данными идёт его подпись и она сходится.
+    /// `bytes` — те же байты, из которых разобран payload
+    pub fn verify_sender(&self, bytes: &[u8]) -> Option<String> {
+        let signed_len = self.signed_len?;
+        let signature = SenderSignature::from_bytes(bytes.get(signed_len..)?).ok()?;
+        signature.verify(bytes.get(..signed_len)?).then(|| signature.public_key_hex())
+    }
+
     pub fn compress_zstd(&mut self) -> Result<(), String> {
         if self.data.is_empty() {
             return Ok(());
@@ -236,6 +728,9 @@ impl Payload {
         Ok(())
     }
 
+    pub fn get_protocol_version(&self) -> u8 {
+        self.protocol_version
+    }
     pub fn get_username(&self) -> &str {
         &self.username
     }
@@ -257,6 +752,110 @@ impl Payload {
     pub fn get_received_time(&self) -> Option<SystemTime> {
         self.received_time
     }
+    /// Время истечения (unix-время, секунды), если отправитель его задал
+    pub fn get_expires_at(&self) -> Option<i64> {
+        let value: [u8; 8] = self.get_extension(constants::EXT_EXPIRES_AT)?.try_into().ok()?;
+        Some(i64::from_be_bytes(value))
+    }
+
+    pub fn set_expires_at(&mut self, expires_at: i64) -> Result<(), String> {
+        self.set_extension(constants::EXT_EXPIRES_AT, expires_at.to_be_bytes().to_vec())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.get_expires_at().is_some_and(|expires_at| expires_at <= unix_now())
+    }
+
+    pub fn get_tx_id(&self) -> Option<&str> {
+        self.tx_id.as_deref()
+    }
+    pub fn set_tx_id(&mut self, tx_id: String) {
+        self.tx_id = Some(tx_id);
+    }
+    pub fn get_sender_address(&self) -> Option<&str> {
+        self.sender_address.as_deref()
+    }
+    pub fn set_sender_address(&mut self, address: String) {
+        self.sender_address = Some(address);
+    }
+    pub fn get_sender_key(&self) -> Option<&str> {
+        self.sender_key.as_deref()
+    }
+    pub fn set_sender_key(&mut self, key: String) {
+        self.sender_key = Some(key);
+    }
+
+    /// Кодек голосового фрагмента; старые клиенты его не указывают и всегда шлют Opus
+    pub fn get_voice_codec(&self) -> VoiceCodec {
+        match self.get_extension(constants::EXT_VOICE_CODEC) {
+            Some([byte]) => VoiceCodec::from_byte(*byte),
+            Some(_) => VoiceCodec::Unknown(0),
+            None => VoiceCodec::OpusMono48k,
+        }
+    }
+
+    pub fn set_voice_codec(&mut self, codec: VoiceCodec) -> Result<(), String> {
+        self.set_extension(constants::EXT_VOICE_CODEC, vec![codec.to_byte()])
+    }
+
+    pub fn get_block_time(&self) -> Option<i64> {
+        self.block_time
+    }
+
+    pub fn set_block_time(&mut self, block_time: i64) {
+        self.block_time = Some(block_time);
+    }
+
+    pub fn is_backfilled(&self) -> bool {
+        self.backfilled
+    }
+
+    pub fn set_backfilled(&mut self) {
+        self.backfilled = true;
+    }
+
+    /// Время отправки по часам отправителя (unix-время, миллисекунды), если он его указал
+    pub fn get_sent_at(&self) -> Option<i64> {
+        let value: [u8; 8] = self.get_extension(constants::EXT_SENT_AT)?.try_into().ok()?;
+        Some(i64::from_be_bytes(value))
+    }
+
+    pub fn set_sent_at(&mut self, sent_at: i64) -> Result<(), String> {
+        self.set_extension(constants::EXT_SENT_AT, sent_at.to_be_bytes().to_vec())
+    }
+
+    /// Кандидаты в упоминания (см. `mention_candidates`); без `@` в тексте расширение не добавляется
+    pub fn set_mentions(&mut self, candidates: &[String]) -> Result<(), String> {
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        let mut value = Vec::new();
+        for candidate in candidates {
+            let length =
+                u8::try_from(candidate.len()).map_err(|_| format!("Mention has {} bytes, max allowed is 255", candidate.len()))?;
+            value.push(length);
+            value.extend_from_slice(candidate.as_bytes());
+        }
+        self.set_extension(constants::EXT_MENTIONS, value)
+    }
+
+    /// Упомянут ли `username` в тексте. Смотрит только расширение, поэтому данные можно не распаковывать.
+    /// Старые клиенты расширение не добавляют, и упоминания в их сообщениях так не найти
+    pub fn is_mentioning(&self, username: &str) -> bool {
+        let Some(mut value) = self.get_extension(constants::EXT_MENTIONS) else {
+            return false;
+        };
+        while let Some((&length, rest)) = value.split_first() {
+            let Some(candidate) = rest.get(..length as usize) else {
+                return false;
+            };
+            if std::str::from_utf8(candidate).is_ok_and(|candidate| mentions(&format!("@{candidate}"), username)) {
+                return true;
+            }
+            value = &rest[length as usize..];
+        }
+        false
+    }
 
     pub fn debug_string(&self) -> String {
         let rcv_time_str = match self.received_time {
@@ -281,6 +880,16 @@ impl Payload {
     }
 }
 
+/// Текущее unix-время в секундах
+fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0)
+}
+
+/// Текущее unix-время в миллисекундах
+fn unix_now_millis() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or(0)
+}
+
 #[cfg(test)]
 mod payload_integration_tests {
     use super::*;
@@ -322,6 +931,153 @@ mod payload_integration_tests {
         assert!(msg.contains("Username length exceeds available data"), "Expect mismatch in declared vs real data");
     }
 
+    #[test]
+    fn test_incoming_corrupted_data() {
+        let p = Payload::new(0, MessageType::Text, StatusFlag::End, 0, "RealU", b"DATA".to_vec(), None).unwrap();
+        let mut raw = p.to_bytes();
+        let data_offset = raw.len() - constants::CHECKSUM_SIZE - 1;
+        raw[data_offset] ^= 0xFF;
+        let msg = Payload::from_bytes(&raw).err().expect("Corrupted data must be rejected");
+        assert!(msg.contains("checksum mismatch"), "Expect checksum error, got: {msg}");
+
+        let truncated = &p.to_bytes()[..raw.len() - 2];
+        let msg = Payload::from_bytes(truncated).err().expect("Truncated payload must be rejected");
+        assert!(msg.contains("checksum is missing"), "Expect truncation error, got: {msg}");
+    }
+
+    #[test]
+    fn test_expiration() {
+        let mut p = Payload::new(0, MessageType::Text, StatusFlag::End, 0, "U", b"bye".to_vec(), None).unwrap();
+        assert_eq!(p.get_expires_at(), None);
+        assert!(!p.is_expired(), "Messages without expiration never expire");
+
+        p.set_expires_at(unix_now() - 1).unwrap();
+        let parsed = Payload::from_bytes(&p.to_bytes()).unwrap();
+        assert_eq!(parsed.get_expires_at(), p.get_expires_at());
+        assert!(parsed.is_expired());
+
+        p.set_expires_at(unix_now() + 60).unwrap();
+        assert!(!p.is_expired());
+        assert_eq!(p.get_extensions().len(), 1, "Expiration must be replaced, not duplicated");
+    }
+
+    #[test]
+    fn test_voice_codec() {
+        let mut p = Payload::new(0, MessageType::Voice, StatusFlag::Start, 0, "U", vec![0; 8], None).unwrap();
+        assert_eq!(p.get_voice_codec(), VoiceCodec::OpusMono48k, "Missing codec means legacy Opus");
+
+        p.set_voice_codec(VoiceCodec::Unknown(9)).unwrap();
+        let parsed = Payload::from_bytes(&p.to_bytes()).unwrap();
+        assert_eq!(parsed.get_voice_codec(), VoiceCodec::Unknown(9));
+
+        p.set_extension(constants::EXT_VOICE_CODEC, vec![1, 1]).unwrap();
+        assert_eq!(p.get_voice_codec(), VoiceCodec::Unknown(0), "Malformed codec must not be played as Opus");
+    }
+
+    #[test]
+    fn test_voice_batch_roundtrip() {
+        let recordings: Vec<Arc<Recording>> = [(StatusFlag::Continue, vec![1u8; 40]), (StatusFlag::End, vec![2u8; 7])]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (state, audio))| Arc::new(Recording { audio, state, fragment_num: 5 + i as u32 }))
+            .collect();
+        let mut batch = Payload::from_recordings(&recordings).unwrap();
+        assert_eq!(batch.get_message_type(), MessageType::VoiceBatch);
+        assert_eq!(batch.get_status_flag(), StatusFlag::End);
+        assert_eq!(batch.get_data().len(), Payload::voice_batch_len(&recordings));
+
+        batch.compress_zstd().unwrap();
+        let mut parsed = Payload::from_bytes(&batch.to_bytes()).unwrap();
+        parsed.decompress_zstd().unwrap();
+        let fragments = parsed.unpack_voice_batch().unwrap();
+        assert_eq!(fragments.len(), 2);
+        for (fragment, recording) in fragments.iter().zip(&recordings) {
+            assert_eq!(fragment.get_message_type(), MessageType::Voice);
+            assert_eq!(fragment.get_fragment_number(), recording.fragment_num);
+            assert_eq!(fragment.get_status_flag(), recording.state);
+            assert_eq!(fragment.get_data(), recording.audio.as_slice());
+            assert_eq!(fragment.get_voice_codec(), VoiceCodec::OpusMono48k);
+            assert!(fragment.get_expires_at().is_some());
+        }
+
+        let single = Payload::from_recordings(&recordings[..1]).unwrap();
+        assert_eq!(single.get_message_type(), MessageType::Voice, "A lone fragment is not batched");
+
+        let truncated = Payload::new(0, MessageType::VoiceBatch, StatusFlag::End, 0, "U", vec![3, 0, 9, 1], None).unwrap();
+        assert!(truncated.unpack_voice_batch().is_err());
+    }
+
+    #[test]
+    fn test_sent_at() {
+        let mut p = Payload::new(0, MessageType::Text, StatusFlag::End, 0, "U", b"hi".to_vec(), None).unwrap();
+        assert_eq!(p.get_sent_at(), None);
+
+        let now = unix_now_millis();
+        p.set_sent_at(now).unwrap();
+        p.set_expires_at(unix_now() + 60).unwrap();
+        let parsed = Payload::from_bytes(&p.to_bytes()).unwrap();
+        assert_eq!(parsed.get_sent_at(), Some(now));
+        assert_eq!(parsed.get_block_time(), None, "Block time is never serialized");
+        assert!(!parsed.is_expired());
+    }
+
+    #[test]
+    fn test_mentions_extension() {
+        let text = "hi @Bob and @bo_b, mail me@ or @";
+        let mut p = Payload::new(1, MessageType::Text, StatusFlag::End, 0, "U", text.as_bytes().to_vec(), None).unwrap();
+        p.set_mentions(&mention_candidates(text)).unwrap();
+        p.compress_zstd().unwrap();
+        let parsed = Payload::from_bytes(&p.to_bytes()).unwrap();
+        assert!(parsed.is_mentioning("bob"), "Mention must be found without decompression");
+        assert!(parsed.is_mentioning("Bo_b"));
+        assert!(!parsed.is_mentioning("Bo"), "Longer name is not a mention");
+        assert!(!parsed.is_mentioning("me"));
+
+        let plain = Payload::new(1, MessageType::Text, StatusFlag::End, 0, "U", b"no mentions".to_vec(), None).unwrap();
+        assert!(plain.get_extensions().is_empty());
+        assert!(!plain.is_mentioning("bob"));
+    }
+
+    #[test]
+    fn test_lenient_salvage() {
+        let p = Payload::new(0, MessageType::Text, StatusFlag::End, 0, "RealU", b"DATA".to_vec(), None).unwrap();
+        let raw = p.to_bytes();
+
+        let truncated = &raw[..raw.len() - constants::CHECKSUM_SIZE - 2];
+        assert!(Payload::from_bytes(truncated).is_err(), "Strict mode must reject truncated data");
+        let (salvaged, issues) = Payload::from_bytes_lenient(truncated).expect("Truncated data must be salvaged");
+        assert_eq!(salvaged.get_data(), b"DA");
+        assert_eq!(issues, vec![ParseIssue::DataTruncated { declared: 4, available: 2 }]);
+
+        let mut corrupted = raw.clone();
+        let data_offset = corrupted.len() - constants::CHECKSUM_SIZE - 1;
+        corrupted[data_offset] ^= 0xFF;
+        let (salvaged, issues) = Payload::from_bytes_lenient(&corrupted).expect("Corrupted data must be salvaged");
+        assert_eq!(salvaged.get_username(), "RealU");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind(), "checksum_mismatch");
+
+        let (_, issues) = Payload::from_bytes_lenient(&raw).unwrap();
+        assert!(issues.is_empty(), "Valid payload must have no issues");
+
+        let mut bad_marker = raw.clone();
+        bad_marker[0] = b'X';
+        assert_eq!(Payload::from_bytes_lenient(&bad_marker).err(), Some(ParseIssue::InvalidMarker));
+    }
+
+    #[test]
+    fn test_incoming_v0_without_checksum() {
+        let p = Payload::new(0, MessageType::Text, StatusFlag::End, 0, "OldU", b"legacy".to_vec(), None).unwrap();
+        let mut raw = p.to_bytes();
+        raw.truncate(raw.len() - constants::CHECKSUM_SIZE);
+        // Область расширений начинается сразу после имени (14 байт заголовка + имя)
+        let ext_start = 14 + "OldU".len();
+        raw.drain(ext_start..ext_start + constants::EXTENSIONS_LEN_SIZE);
+        raw[4] = 0;
+        let parsed = Payload::from_bytes(&raw).expect("Version 0 payloads are still accepted");
+        assert_eq!(parsed.get_data(), b"legacy");
+    }
+
     #[test]
     fn test_instruction_compress_decompress_ok() {
         let text = "Rust is fast!";
@@ -339,4 +1095,61 @@ mod payload_integration_tests {
             panic!("Instruction is not SendTx or no payload inside");
         }
     }
+
+    #[test]
+    fn test_direct_message_roundtrip() {
+        use crate::core::identity::Identity;
+        use crate::models::direct_message::DirectEnvelope;
+
+        let alice = Identity::from_mnemonic("alice mnemonic").unwrap();
+        let bob = Identity::from_mnemonic("bob mnemonic").unwrap();
+        let eve = Identity::from_mnemonic("eve mnemonic").unwrap();
+
+        let sealed = DirectEnvelope::seal(&alice, &bob.public_key(), "Secret hi").unwrap();
+        let parsed = DirectEnvelope::from_bytes(&sealed.to_bytes()).expect("Envelope parse must succeed");
+
+        assert!(parsed.involves(&bob) && parsed.involves(&alice));
+        assert!(!parsed.involves(&eve));
+        assert_eq!(parsed.open(&bob).unwrap(), "Secret hi");
+        assert_eq!(parsed.open(&alice).unwrap(), "Secret hi", "Sender must be able to read own message");
+        assert!(parsed.open(&eve).is_err());
+    }
+
+    #[test]
+    fn test_claim_signature() {
+        use crate::core::identity::Identity;
+        use crate::models::claim::Claim;
+
+        let alice = Identity::from_mnemonic("alice mnemonic").unwrap();
+        let address = "kaspatest:qqalice";
+
+        let claim = Claim::sign(&alice, "Alice", address);
+        let parsed = Claim::from_bytes(&claim.to_bytes()).expect("Claim parse must succeed");
+
+        assert_eq!(parsed.public_key, alice.public_key());
+        assert!(parsed.verify("Alice", address).is_ok());
+        assert!(parsed.verify("Mallory", address).is_err(), "Claim must not transfer to another name");
+        assert!(parsed.verify("Alice", "kaspatest:qqmallory").is_err(), "Claim must not be replayed from another address");
+    }
+
+    #[test]
+    fn test_sender_signature() {
+        use crate::core::identity::Identity;
+
+        let alice = Identity::from_mnemonic("alice mnemonic").unwrap();
+        let payload = Payload::new(0, MessageType::Text, StatusFlag::End, 0, "Alice", b"hi".to_vec(), None).unwrap();
+
+        let unsigned = payload.to_bytes();
+        assert_eq!(Payload::from_bytes(&unsigned).unwrap().verify_sender(&unsigned), None, "Unsigned payload has no sender");
+
+        let signed = payload.to_signed_bytes(&alice);
+        let parsed = Payload::from_bytes(&signed).expect("Signed payload must parse");
+        assert_eq!(parsed.get_data(), b"hi");
+        assert_eq!(parsed.verify_sender(&signed), Some(alice.public_key_hex()));
+
+        let mut tampered = signed.clone();
+        tampered[constants::HEADER_SIZE] ^= 1;
+        let parsed = Payload::from_bytes(&tampered).unwrap();
+        assert_eq!(parsed.verify_sender(&tampered), None, "Signature must not cover a changed payload");
+    }
 }
@@ -0,0 +1,55 @@
+use crate::constants::{RETRY_BASE_DELAY_MS, RETRY_MAX_DELAY_MS};
+use crate::models::payload::Payload;
+use serde::{Deserialize, Serialize};
+
+/// Неудавшаяся отправка payload, ожидающая повтора. Очередь хранится в файле профиля и переживает перезапуск
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingSend {
+    pub id: u64,
+    /// Payload транзакции в hex
+    pub payload_hex: String,
+    /// Сколько попыток уже сделано, включая первую отправку
+    pub attempts: u32,
+    /// Когда повторить, unix-время в миллисекундах
+    pub next_attempt_at: i64,
+    pub last_error: String,
+    /// Повтор уже отправляется; после перезапуска флаг сбрасывается и запись повторяется заново
+    #[serde(skip)]
+    pub in_flight: bool,
+}
+
+impl PendingSend {
+    pub fn new(id: u64, payload: &[u8], error: &str, now_ms: i64) -> Self {
+        Self {
+            id,
+            payload_hex: hex::encode(payload),
+            attempts: 1,
+            next_attempt_at: now_ms + Self::backoff_ms(1),
+            last_error: error.to_string(),
+            in_flight: false,
+        }
+    }
+
+    /// Экспоненциальная задержка после `attempts` неудачных попыток: 1, 2, 4... секунды, но не больше RETRY_MAX_DELAY_MS
+    pub fn backoff_ms(attempts: u32) -> i64 {
+        let factor = 1i64.checked_shl(attempts.saturating_sub(1)).unwrap_or(i64::MAX);
+        RETRY_BASE_DELAY_MS.saturating_mul(factor).min(RETRY_MAX_DELAY_MS)
+    }
+
+    /// Записывает очередную неудачу и переносит следующую попытку
+    pub fn reschedule(&mut self, error: &str, now_ms: i64) {
+        self.attempts += 1;
+        self.next_attempt_at = now_ms + Self::backoff_ms(self.attempts);
+        self.last_error = error.to_string();
+        self.in_flight = false;
+    }
+
+    pub fn payload(&self) -> Result<Vec<u8>, String> {
+        hex::decode(&self.payload_hex).map_err(|e| format!("Invalid payload in retry queue: {e}"))
+    }
+
+    /// Голосовой фрагмент или сообщение со сроком жизни после истечения повторять бессмысленно
+    pub fn is_expired(&self) -> bool {
+        self.payload().ok().and_then(|bytes| Payload::from_bytes(&bytes).ok()).is_some_and(|payload| payload.is_expired())
+    }
+}
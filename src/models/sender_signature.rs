@@ -0,0 +1,59 @@
+use crate::constants::SENDER_SIGNATURE_DOMAIN;
+use crate::core::identity::{verify_digest, Identity};
+use secp256k1::ecdsa::Signature;
+use secp256k1::PublicKey;
+use sha2::{Digest, Sha256};
+
+/// Размер сжатого публичного ключа secp256k1
+const PUBLIC_KEY_SIZE: usize = 33;
+/// Размер компактной подписи ECDSA
+const SIGNATURE_SIZE: usize = 64;
+
+/// Подпись отправителя — последние `SENDER_SIGNATURE_SIZE` байт payload транзакции:
+///   33 (PUBLIC_KEY) + 64 (SIGNATURE)
+/// Подпись покрывает все байты payload перед ней. Адрес из выходов
+/// транзакции выбирает сам отправитель, поэтому отправителя узнаём только по ключу.
+#[derive(Debug, Clone)]
+pub struct SenderSignature {
+    pub public_key: PublicKey,
+    signature: Signature,
+}
+
+impl SenderSignature {
+    pub fn sign(identity: &Identity, signed_bytes: &[u8]) -> Self {
+        let signature = identity.sign_digest(Self::digest(signed_bytes));
+        Self { public_key: identity.public_key(), signature }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != PUBLIC_KEY_SIZE + SIGNATURE_SIZE {
+            return Err(format!("Sender signature has {} bytes, expected {}", bytes.len(), PUBLIC_KEY_SIZE + SIGNATURE_SIZE));
+        }
+        let public_key = PublicKey::from_slice(&bytes[..PUBLIC_KEY_SIZE]).map_err(|e| format!("Invalid sender key: {e}"))?;
+        let signature = Signature::from_compact(&bytes[PUBLIC_KEY_SIZE..]).map_err(|e| format!("Invalid sender signature: {e}"))?;
+        Ok(Self { public_key, signature })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(PUBLIC_KEY_SIZE + SIGNATURE_SIZE);
+        bytes.extend_from_slice(&self.public_key.serialize());
+        bytes.extend_from_slice(&self.signature.serialize_compact());
+        bytes
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.public_key.serialize())
+    }
+
+    /// Проверяет, что байты перед подписью подписаны владельцем ключа
+    pub fn verify(&self, signed_bytes: &[u8]) -> bool {
+        verify_digest(&self.public_key, Self::digest(signed_bytes), &self.signature)
+    }
+
+    fn digest(signed_bytes: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(SENDER_SIGNATURE_DOMAIN);
+        hasher.update(signed_bytes);
+        hasher.finalize().into()
+    }
+}
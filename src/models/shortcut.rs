@@ -0,0 +1,196 @@
+use crate::utils::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Клавиши, которые можно назначить; имена общие для rdev и iced (см. `core::hotkeys` и GUI)
+pub const SHORTCUT_KEYS: [&str; 64] = [
+    "A",
+    "B",
+    "C",
+    "D",
+    "E",
+    "F",
+    "G",
+    "H",
+    "I",
+    "J",
+    "K",
+    "L",
+    "M",
+    "N",
+    "O",
+    "P",
+    "Q",
+    "R",
+    "S",
+    "T",
+    "U",
+    "V",
+    "W",
+    "X",
+    "Y",
+    "Z",
+    "0",
+    "1",
+    "2",
+    "3",
+    "4",
+    "5",
+    "6",
+    "7",
+    "8",
+    "9",
+    "F1",
+    "F2",
+    "F3",
+    "F4",
+    "F5",
+    "F6",
+    "F7",
+    "F8",
+    "F9",
+    "F10",
+    "F11",
+    "F12",
+    "Tab",
+    "Space",
+    "Enter",
+    "Escape",
+    "Backspace",
+    "Insert",
+    "Delete",
+    "Home",
+    "End",
+    "PageUp",
+    "PageDown",
+    "Up",
+    "Down",
+    "Left",
+    "Right",
+    "Backquote",
+];
+
+/// Действие, которому можно назначить сочетание клавиш
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    /// Запись, пока клавиша зажата; глобальная, работает и без фокуса окна
+    PushToTalk,
+    ToggleMute,
+    NextChannel,
+    PreviousChannel,
+    SendMessage,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 5] = [
+        ShortcutAction::PushToTalk,
+        ShortcutAction::ToggleMute,
+        ShortcutAction::NextChannel,
+        ShortcutAction::PreviousChannel,
+        ShortcutAction::SendMessage,
+    ];
+}
+
+impl fmt::Display for ShortcutAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortcutAction::PushToTalk => write!(f, "{}", tr("shortcut-push-to-talk")),
+            ShortcutAction::ToggleMute => write!(f, "{}", tr("shortcut-toggle-mute")),
+            ShortcutAction::NextChannel => write!(f, "{}", tr("shortcut-next-channel")),
+            ShortcutAction::PreviousChannel => write!(f, "{}", tr("shortcut-previous-channel")),
+            ShortcutAction::SendMessage => write!(f, "{}", tr("shortcut-send-message")),
+        }
+    }
+}
+
+/// Сочетание: клавиша из SHORTCUT_KEYS и модификаторы
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Shortcut {
+    pub key: String,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Shortcut {
+    pub fn new(key: &str) -> Self {
+        Self { key: key.to_string(), ctrl: false, shift: false, alt: false }
+    }
+
+    pub fn with_ctrl(key: &str) -> Self {
+        Self { ctrl: true, ..Self::new(key) }
+    }
+
+    pub fn matches(&self, key: &str, ctrl: bool, shift: bool, alt: bool) -> bool {
+        self.key == key && self.ctrl == ctrl && self.shift == shift && self.alt == alt
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// Назначенные сочетания; None — действие без клавиши
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Shortcuts {
+    pub push_to_talk: Option<Shortcut>,
+    pub toggle_mute: Option<Shortcut>,
+    pub next_channel: Option<Shortcut>,
+    pub previous_channel: Option<Shortcut>,
+    pub send_message: Option<Shortcut>,
+}
+
+impl Default for Shortcuts {
+    /// Push-to-talk по умолчанию не назначен: глобальный перехват клавиатуры на macOS требует разрешения
+    fn default() -> Self {
+        Self {
+            push_to_talk: None,
+            toggle_mute: Some(Shortcut::with_ctrl("M")),
+            next_channel: Some(Shortcut::with_ctrl("PageDown")),
+            previous_channel: Some(Shortcut::with_ctrl("PageUp")),
+            send_message: Some(Shortcut::new("Enter")),
+        }
+    }
+}
+
+impl Shortcuts {
+    pub fn get(&self, action: ShortcutAction) -> Option<&Shortcut> {
+        match action {
+            ShortcutAction::PushToTalk => self.push_to_talk.as_ref(),
+            ShortcutAction::ToggleMute => self.toggle_mute.as_ref(),
+            ShortcutAction::NextChannel => self.next_channel.as_ref(),
+            ShortcutAction::PreviousChannel => self.previous_channel.as_ref(),
+            ShortcutAction::SendMessage => self.send_message.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, action: ShortcutAction, shortcut: Option<Shortcut>) {
+        let slot = match action {
+            ShortcutAction::PushToTalk => &mut self.push_to_talk,
+            ShortcutAction::ToggleMute => &mut self.toggle_mute,
+            ShortcutAction::NextChannel => &mut self.next_channel,
+            ShortcutAction::PreviousChannel => &mut self.previous_channel,
+            ShortcutAction::SendMessage => &mut self.send_message,
+        };
+        *slot = shortcut;
+    }
+
+    /// Действие окна, назначенное на нажатое сочетание. Push-to-talk ловит глобальный перехват,
+    /// а отправку — поле ввода, поэтому они здесь не ищутся
+    pub fn window_action_for(&self, key: &str, ctrl: bool, shift: bool, alt: bool) -> Option<ShortcutAction> {
+        [ShortcutAction::ToggleMute, ShortcutAction::NextChannel, ShortcutAction::PreviousChannel]
+            .into_iter()
+            .find(|action| self.get(*action).is_some_and(|shortcut| shortcut.matches(key, ctrl, shift, alt)))
+    }
+}
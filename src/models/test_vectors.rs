@@ -0,0 +1,402 @@
+//! Эталонные байтовые примеры payload для каждого типа сообщения и версии протокола.
+//!
+//! Раскладка: MARKER "KSPK" (4) | VERSION (1) | CHANNEL (3, BE) | MESSAGE_TYPE (1) | STATUS_FLAG (1)
+//! | FRAGMENT (3, BE) | USERNAME_LEN (1) | USERNAME (UTF-8)
+//! | EXTENSIONS_LEN (2, BE, с версии 2) | EXTENSIONS: [TAG (1) | LENGTH (2, BE) | VALUE]* (с версии 2)
+//! | DATA_LEN (3, BE) | DATA | CRC32(DATA) (4, BE, с версии 1).
+//! За ними может идти подпись отправителя (`SENDER_SIGNATURE_SIZE`); векторы её не содержат.
+//!
+//! Векторы менять нельзя: сторонние реализации и будущие рефакторинги сверяются с ними.
+//! Новые версии протокола добавляются новыми векторами.
+
+use crate::models::payload::{MessageType, Payload, StatusFlag};
+
+pub(crate) struct TestVector {
+    pub name: &'static str,
+    pub protocol_version: u8,
+    pub channel: u32,
+    pub message_type: MessageType,
+    pub status_flag: StatusFlag,
+    pub fragment_number: u32,
+    pub username: &'static str,
+    pub data_hex: &'static str,
+    /// (TAG, VALUE в hex)
+    pub extensions: &'static [(u8, &'static str)],
+    pub bytes_hex: &'static str,
+}
+
+/// Payload, который обязан быть отвергнут, и фрагмент ожидаемой ошибки
+pub(crate) struct InvalidVector {
+    pub name: &'static str,
+    pub bytes_hex: &'static str,
+    pub error: &'static str,
+}
+
+pub(crate) const VECTORS: &[TestVector] = &[
+    // Текстовое сообщение (данные до сжатия zstd)
+    TestVector {
+        name: "text_v1",
+        protocol_version: 1,
+        channel: 0,
+        message_type: MessageType::Text,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "48656c6c6f2c204b6173706121",
+        extensions: &[],
+        bytes_hex: "4b53504b01000000010300000005416c69636500000d48656c6c6f2c204b6173706121f7997665",
+    },
+    // Многобайтовые имя и текст, канал 1234567
+    TestVector {
+        name: "text_utf8_v1",
+        protocol_version: 1,
+        channel: 1234567,
+        message_type: MessageType::Text,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Борис 🐐",
+        data_hex: "d09fd180d0b8d0b2d0b5d182",
+        extensions: &[],
+        bytes_hex: "4b53504b0112d68701030000000fd091d0bed180d0b8d18120f09f909000000cd09fd180d0b8d0b2d0b5d182a8eab199",
+    },
+    // Первый фрагмент голосовой передачи
+    TestVector {
+        name: "voice_start_v1",
+        protocol_version: 1,
+        channel: 42,
+        message_type: MessageType::Voice,
+        status_flag: StatusFlag::Start,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "28b52ffd",
+        extensions: &[],
+        bytes_hex: "4b53504b0100002a020100000005416c69636500000428b52ffd5647d2fa",
+    },
+    // Промежуточный фрагмент
+    TestVector {
+        name: "voice_continue_v1",
+        protocol_version: 1,
+        channel: 42,
+        message_type: MessageType::Voice,
+        status_flag: StatusFlag::Continue,
+        fragment_number: 7,
+        username: "Alice",
+        data_hex: "000102030405060708090a0b0c0d0e0f",
+        extensions: &[],
+        bytes_hex: "4b53504b0100002a020200000705416c696365000010000102030405060708090a0b0c0d0e0fcecee288",
+    },
+    // Завершающий фрагмент без данных
+    TestVector {
+        name: "voice_end_v1",
+        protocol_version: 1,
+        channel: 42,
+        message_type: MessageType::Voice,
+        status_flag: StatusFlag::End,
+        fragment_number: 8,
+        username: "Alice",
+        data_hex: "",
+        extensions: &[],
+        bytes_hex: "4b53504b0100002a020300000805416c69636500000000000000",
+    },
+    // Файл (тип зарезервирован)
+    TestVector {
+        name: "file_v1",
+        protocol_version: 1,
+        channel: 0,
+        message_type: MessageType::File,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "000102",
+        extensions: &[],
+        bytes_hex: "4b53504b01000000030300000005416c6963650000030001020854897f",
+    },
+    // Личное сообщение: ключ отправителя, ключ получателя, nonce, шифртекст
+    TestVector {
+        name: "direct_v1",
+        protocol_version: 1,
+        channel: 0,
+        message_type: MessageType::Direct,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "020000000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ff",
+        extensions: &[],
+        bytes_hex: "4b53504b01000000040300000005416c69636500004f020000000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ffa837c221",
+    },
+    // Заявка на имя: ключ идентичности и подпись
+    TestVector {
+        name: "claim_v1",
+        protocol_version: 1,
+        channel: 0,
+        message_type: MessageType::Claim,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "02000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        extensions: &[],
+        bytes_hex: "4b53504b01000000050300000005416c69636500006102000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000081fa93f8",
+    },
+    // Текст версии 2 без расширений
+    TestVector {
+        name: "text_v2",
+        protocol_version: 2,
+        channel: 0,
+        message_type: MessageType::Text,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "48656c6c6f2c204b6173706121",
+        extensions: &[],
+        bytes_hex: "4b53504b02000000010300000005416c696365000000000d48656c6c6f2c204b6173706121f7997665",
+    },
+    // Голосовой фрагмент версии 2
+    TestVector {
+        name: "voice_start_v2",
+        protocol_version: 2,
+        channel: 42,
+        message_type: MessageType::Voice,
+        status_flag: StatusFlag::Start,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "28b52ffd",
+        extensions: &[],
+        bytes_hex: "4b53504b0200002a020100000005416c696365000000000428b52ffd5647d2fa",
+    },
+    // Пакет из двух голосовых фрагментов (3 и 4) версии 2
+    TestVector {
+        name: "voice_batch_v2",
+        protocol_version: 2,
+        channel: 42,
+        message_type: MessageType::VoiceBatch,
+        status_flag: StatusFlag::End,
+        fragment_number: 3,
+        username: "Alice",
+        data_hex: "020002aabb030001cc",
+        extensions: &[],
+        bytes_hex: "4b53504b0200002a090300000305416c6963650000000009020002aabb030001cc198c36b8",
+    },
+    // Личное сообщение версии 2
+    TestVector {
+        name: "direct_v2",
+        protocol_version: 2,
+        channel: 0,
+        message_type: MessageType::Direct,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "020000000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ff",
+        extensions: &[],
+        bytes_hex: "4b53504b02000000040300000005416c696365000000004f020000000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000000000000000000000000000000ffa837c221",
+    },
+    // Заявка на имя версии 2
+    TestVector {
+        name: "claim_v2",
+        protocol_version: 2,
+        channel: 0,
+        message_type: MessageType::Claim,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "02000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        extensions: &[],
+        bytes_hex: "4b53504b02000000050300000005416c696365000000006102000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000081fa93f8",
+    },
+    // Запрос аватара: data — адрес владельца
+    TestVector {
+        name: "avatar_request_v2",
+        protocol_version: 2,
+        channel: 0,
+        message_type: MessageType::AvatarRequest,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "6b61737061746573743a717061757173766b37796639756e6578776d78736e6d673534376d687967613337637368306b6a353371367878676c32347964786a73677a746877356a",
+        extensions: &[],
+        bytes_hex: "4b53504b02000000080300000005416c69636500000000476b61737061746573743a717061757173766b37796639756e6578776d78736e6d673534376d687967613337637368306b6a353371367878676c32347964786a73677a746877356afdc9e6fd",
+    },
+    // Сообщение со сроком жизни (расширение 0x01, 2026-01-01T00:00:00Z)
+    TestVector {
+        name: "text_expiring_v2",
+        protocol_version: 2,
+        channel: 0,
+        message_type: MessageType::Text,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "48656c6c6f2c204b6173706121",
+        extensions: &[(0x01, "000000006955b900")],
+        bytes_hex: "4b53504b02000000010300000005416c696365000b010008000000006955b90000000d48656c6c6f2c204b6173706121f7997665",
+    },
+    // Голосовой фрагмент с явным кодеком (расширение 0x03, Opus моно 48 кГц)
+    TestVector {
+        name: "voice_opus_codec_v2",
+        protocol_version: 2,
+        channel: 42,
+        message_type: MessageType::Voice,
+        status_flag: StatusFlag::Start,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "28b52ffd",
+        extensions: &[(0x03, "01")],
+        bytes_hex: "4b53504b0200002a020100000005416c6963650004030001010000000428b52ffd5647d2fa",
+    },
+    // Объявление канала версии 2
+    TestVector {
+        name: "announcement_v2",
+        protocol_version: 2,
+        channel: 7,
+        message_type: MessageType::Announcement,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "52756c65733a206265206b696e64",
+        extensions: &[],
+        bytes_hex: "4b53504b02000007060300000005416c696365000000000e52756c65733a206265206b696e647c983bf6",
+    },
+    // Неизвестные расширения (в том числе пустое) сохраняются и не мешают разбору
+    TestVector {
+        name: "text_unknown_extensions_v2",
+        protocol_version: 2,
+        channel: 0,
+        message_type: MessageType::Text,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "48656c6c6f2c204b6173706121",
+        extensions: &[(0xf0, "616263"), (0xf1, "")],
+        bytes_hex: "4b53504b02000000010300000005416c6963650009f00003616263f1000000000d48656c6c6f2c204b6173706121f7997665",
+    },
+    // Текст в формате версии 0 (без контрольной суммы)
+    TestVector {
+        name: "text_v0",
+        protocol_version: 0,
+        channel: 0,
+        message_type: MessageType::Text,
+        status_flag: StatusFlag::End,
+        fragment_number: 0,
+        username: "Alice",
+        data_hex: "48656c6c6f2c204b6173706121",
+        extensions: &[],
+        bytes_hex: "4b53504b00000000010300000005416c69636500000d48656c6c6f2c204b6173706121",
+    },
+    // Голосовой фрагмент версии 0
+    TestVector {
+        name: "voice_continue_v0",
+        protocol_version: 0,
+        channel: 42,
+        message_type: MessageType::Voice,
+        status_flag: StatusFlag::Continue,
+        fragment_number: 7,
+        username: "Alice",
+        data_hex: "000102030405060708090a0b0c0d0e0f",
+        extensions: &[],
+        bytes_hex: "4b53504b0000002a020200000705416c696365000010000102030405060708090a0b0c0d0e0f",
+    },
+];
+
+pub(crate) const INVALID_VECTORS: &[InvalidVector] = &[
+    // Неверный маркер
+    InvalidVector {
+        name: "bad_marker",
+        bytes_hex: "4b53505801000000010300000005416c69636500000d48656c6c6f2c204b6173706121f7997665",
+        error: "Invalid marker",
+    },
+    // Версия новее поддерживаемой
+    InvalidVector {
+        name: "unsupported_version",
+        bytes_hex: "4b53504b03000000010300000005416c69636500000d48656c6c6f2c204b6173706121f7997665",
+        error: "Unsupported protocol version",
+    },
+    // Испорчен байт данных
+    InvalidVector {
+        name: "checksum_mismatch",
+        bytes_hex: "4b53504b01000000010300000005416c69636500000d48656c6c6f2c204b61737061def7997665",
+        error: "checksum mismatch",
+    },
+    // Обрезана контрольная сумма
+    InvalidVector {
+        name: "truncated_checksum",
+        bytes_hex: "4b53504b01000000010300000005416c69636500000d48656c6c6f2c204b6173706121f799",
+        error: "checksum is missing",
+    },
+    // Обрезаны данные
+    InvalidVector {
+        name: "truncated_data",
+        bytes_hex: "4b53504b01000000010300000005416c69636500000d48656c6c6f2c20",
+        error: "Payload length exceeds available data",
+    },
+    // Короче заголовка
+    InvalidVector { name: "short_header", bytes_hex: "4b53504b010000000103", error: "too short for header" },
+    // Длина расширения выходит за область расширений
+    InvalidVector {
+        name: "malformed_extension",
+        bytes_hex: "4b53504b02000000010300000005416c6963650006f0001461626300000248694d170e0e",
+        error: "Malformed extension",
+    },
+    // Длина области расширений больше всего payload
+    InvalidVector {
+        name: "extensions_overflow",
+        bytes_hex: "4b53504b02000000010300000005416c696365fffff0000361626300000248694d170e0e",
+        error: "Extensions length exceeds available data",
+    },
+];
+
+#[test]
+fn test_vectors_parse() {
+    for vector in VECTORS {
+        let bytes = hex::decode(vector.bytes_hex).unwrap();
+        let payload = Payload::from_bytes(&bytes).unwrap_or_else(|e| panic!("{}: parse failed: {}", vector.name, e));
+
+        assert_eq!(payload.get_protocol_version(), vector.protocol_version, "{}: version", vector.name);
+        assert_eq!(payload.get_channel(), vector.channel, "{}: channel", vector.name);
+        assert_eq!(payload.get_message_type(), vector.message_type, "{}: message type", vector.name);
+        assert_eq!(payload.get_status_flag(), vector.status_flag, "{}: status flag", vector.name);
+        assert_eq!(payload.get_fragment_number(), vector.fragment_number, "{}: fragment", vector.name);
+        assert_eq!(payload.get_username(), vector.username, "{}: username", vector.name);
+        assert_eq!(hex::encode(payload.get_data()), vector.data_hex, "{}: data", vector.name);
+        let extensions: Vec<(u8, String)> = payload.get_extensions().iter().map(|ext| (ext.tag, hex::encode(&ext.value))).collect();
+        let expected: Vec<(u8, String)> = vector.extensions.iter().map(|(tag, value)| (*tag, value.to_string())).collect();
+        assert_eq!(extensions, expected, "{}: extensions", vector.name);
+    }
+}
+
+#[test]
+fn test_vectors_roundtrip() {
+    for vector in VECTORS {
+        let bytes = hex::decode(vector.bytes_hex).unwrap();
+        let payload = Payload::from_bytes(&bytes).unwrap();
+        assert_eq!(hex::encode(payload.to_bytes()), vector.bytes_hex, "{}: re-encoding must be byte-exact", vector.name);
+    }
+}
+
+#[test]
+fn test_vectors_encode_current_version() {
+    for vector in VECTORS.iter().filter(|v| v.protocol_version == crate::constants::PROTOCOL_VERSION) {
+        let mut payload = Payload::new(
+            vector.channel,
+            vector.message_type,
+            vector.status_flag,
+            vector.fragment_number,
+            vector.username,
+            hex::decode(vector.data_hex).unwrap(),
+            None,
+        )
+        .unwrap();
+        for (tag, value) in vector.extensions {
+            payload.set_extension(*tag, hex::decode(value).unwrap()).unwrap();
+        }
+        assert_eq!(hex::encode(payload.to_bytes()), vector.bytes_hex, "{}: encoding", vector.name);
+    }
+}
+
+#[test]
+fn test_invalid_vectors_rejected() {
+    for vector in INVALID_VECTORS {
+        let bytes = hex::decode(vector.bytes_hex).unwrap();
+        match Payload::from_bytes(&bytes) {
+            Ok(_) => panic!("{}: must be rejected", vector.name),
+            Err(err) => assert!(err.contains(vector.error), "{}: unexpected error '{}'", vector.name, err),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::constants::{ESTIMATED_TX_BASE_MASS, MINIMUM_FEERATE};
+use crate::models::payload::MessageType;
+use crate::utils::format::format_amount;
+use crate::utils::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+
+/// Транзакция, отправленная со своего кошелька
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SentTransaction {
+    pub tx_id: String,
+    /// Сумма перевода, сомпи
+    pub amount: u64,
+    /// Комиссия, сомпи
+    pub fee: u64,
+    pub payload_size: usize,
+    /// Время отправки, unix-время в секундах
+    pub timestamp: i64,
+    /// Канал payload; нет у переводов и объединения UTXO (и у записей, сохранённых до появления поля)
+    #[serde(default)]
+    pub channel: Option<u32>,
+}
+
+/// Транзакция Kaspeak в текущем канале, увиденная Listener'ом: для ленты транзакций
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservedTransaction {
+    pub tx_id: String,
+    pub username: String,
+    pub sender_address: Option<String>,
+    pub message_type: MessageType,
+    pub payload_size: usize,
+    /// Комиссия, сомпи; известна только для транзакций из мемпула
+    pub fee: Option<u64>,
+    /// Время блока или получения из мемпула, unix-время в секундах
+    pub timestamp: i64,
+    /// Транзакция уже в блоке
+    pub confirmed: bool,
+}
+
+/// История отправок в CSV для учёта и отладки: суммы в сомпи, время в UTC (RFC 3339)
+pub fn sent_transactions_to_csv<'a>(transactions: impl IntoIterator<Item = &'a SentTransaction>) -> String {
+    let mut csv = String::from("timestamp,txid,amount_sompi,fee_sompi,payload_bytes,channel\n");
+    for tx in transactions {
+        let timestamp = chrono::DateTime::from_timestamp(tx.timestamp, 0).map(|at| at.to_rfc3339()).unwrap_or_default();
+        let channel = tx.channel.map(|channel| channel.to_string()).unwrap_or_default();
+        csv.push_str(&format!("{},{},{},{},{},{}\n", timestamp, tx.tx_id, tx.amount, tx.fee, tx.payload_size, channel));
+    }
+    csv
+}
+
+/// Расходы за текущий запуск. Транзакции с payload отправляются на свой адрес,
+/// поэтому их цена — только комиссия; переводы учитываются тоже по комиссии
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionCost {
+    /// Комиссии, сомпи
+    pub fees: u64,
+    pub transactions: u64,
+    pub payload_bytes: u64,
+    /// Расходы на транзакции с payload по каналам
+    pub by_channel: BTreeMap<u32, ChannelCost>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChannelCost {
+    /// Комиссии, сомпи
+    pub fees: u64,
+    pub transactions: u64,
+}
+
+impl SessionCost {
+    pub fn record(&mut self, channel: Option<u32>, fee: u64, payload_size: usize) {
+        self.fees += fee;
+        self.transactions += 1;
+        self.payload_bytes += payload_size as u64;
+        if let Some(channel) = channel {
+            let cost = self.by_channel.entry(channel).or_default();
+            cost.fees += fee;
+            cost.transactions += 1;
+        }
+    }
+
+    /// Доплата за повышение комиссии: транзакция заменяется, поэтому число транзакций и байты не растут
+    pub fn record_fee_bump(&mut self, channel: Option<u32>, extra_fee: u64) {
+        self.fees += extra_fee;
+        if let Some(channel) = channel {
+            self.by_channel.entry(channel).or_default().fees += extra_fee;
+        }
+    }
+
+    pub fn channel(&self, channel: u32) -> ChannelCost {
+        self.by_channel.get(&channel).copied().unwrap_or_default()
+    }
+}
+
+/// Оценка расходов на действие из нескольких транзакций; показывается перед подтверждением
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostEstimate {
+    pub transactions: u64,
+    /// Комиссии, сомпи
+    pub fee: u64,
+}
+
+impl CostEstimate {
+    pub fn add(&mut self, fee: u64) {
+        self.transactions += 1;
+        self.fee += fee;
+    }
+}
+
+/// Траты за последние сутки поминутно: комиссии и переводы на чужие адреса (см. `SpendLimits`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpendLog {
+    /// (начало минуты, unix-время в секундах; потрачено за минуту, сомпи), старые в начале
+    buckets: VecDeque<(i64, u64)>,
+}
+
+impl SpendLog {
+    const DAY_SECS: i64 = 24 * 60 * 60;
+
+    pub fn record(&mut self, now: i64, sompi: u64) {
+        let minute = now - now.rem_euclid(60);
+        match self.buckets.back_mut() {
+            Some((start, total)) if *start == minute => *total += sompi,
+            _ => self.buckets.push_back((minute, sompi)),
+        }
+        while self.buckets.front().is_some_and(|(start, _)| *start < now - Self::DAY_SECS - 60) {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Потрачено начиная с `since` (с точностью до минуты)
+    pub fn spent_since(&self, since: i64) -> u64 {
+        self.buckets.iter().filter(|(start, _)| *start + 60 > since).map(|(_, total)| total).sum()
+    }
+}
+
+/// Лимиты трат в час и в сутки, сомпи; None — без лимита
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpendLimits {
+    pub per_hour: Option<u64>,
+    pub per_day: Option<u64>,
+}
+
+impl SpendLimits {
+    /// Описание первого лимита, за который выйдут траты, если потратить ещё `extra` сомпи.
+    /// Отправки с payload стоят только комиссию, которая заранее неизвестна: для них `extra` = 0
+    pub fn exceeded(&self, log: &SpendLog, now: i64, extra: u64) -> Option<String> {
+        [(self.per_hour, 60 * 60, "Hourly"), (self.per_day, SpendLog::DAY_SECS, "Daily")].into_iter().find_map(
+            |(limit, window, label)| {
+                let limit = limit?;
+                let spent = log.spent_since(now - window);
+                (spent >= limit || spent.saturating_add(extra) > limit)
+                    .then(|| format!("{} spending limit reached: {} of {}", label, format_amount(spent, 8), format_amount(limit, 8)))
+            },
+        )
+    }
+}
+
+/// Как выбирается доплата к комиссии: вручную или по оценке ноды (`get_fee_estimate`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FeeMode {
+    #[default]
+    Manual,
+    Low,
+    Normal,
+    Priority,
+}
+
+impl FeeMode {
+    pub const ALL: [FeeMode; 4] = [FeeMode::Manual, FeeMode::Low, FeeMode::Normal, FeeMode::Priority];
+}
+
+impl fmt::Display for FeeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeMode::Manual => write!(f, "{}", tr("fee-mode-manual")),
+            FeeMode::Low => write!(f, "{}", tr("fee-mode-low")),
+            FeeMode::Normal => write!(f, "{}", tr("fee-mode-normal")),
+            FeeMode::Priority => write!(f, "{}", tr("fee-mode-priority")),
+        }
+    }
+}
+
+/// Оценка ноды: ставки в сомпи за грамм массы транзакции
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeRates {
+    pub low: f64,
+    pub normal: f64,
+    pub priority: f64,
+}
+
+impl FeeRates {
+    /// Доплата сверх минимальной комиссии для транзакции с payload размером `payload_size`.
+    /// Минимальную ставку (1 сомпи за грамм) кошелёк платит сам, поэтому доплачивается только разница.
+    /// Для ручного режима None
+    pub fn priority_fee(&self, mode: FeeMode, payload_size: usize) -> Option<u64> {
+        let feerate = match mode {
+            FeeMode::Manual => return None,
+            FeeMode::Low => self.low,
+            FeeMode::Normal => self.normal,
+            FeeMode::Priority => self.priority,
+        };
+        let mass = ESTIMATED_TX_BASE_MASS + payload_size as u64;
+        Some(((feerate - MINIMUM_FEERATE).max(0.0) * mass as f64).ceil() as u64)
+    }
+}
@@ -0,0 +1,103 @@
+use crate::models::message::Message;
+use crate::utils::format::format_date_time;
+use serde::Serialize;
+use std::fmt;
+
+/// Формат выгрузки переписки канала
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TranscriptFormat {
+    #[default]
+    Text,
+    Html,
+    Json,
+}
+
+impl TranscriptFormat {
+    pub const ALL: [TranscriptFormat; 3] = [TranscriptFormat::Text, TranscriptFormat::Html, TranscriptFormat::Json];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Text => "txt",
+            TranscriptFormat::Html => "html",
+            TranscriptFormat::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for TranscriptFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TranscriptFormat::Text => "Text",
+            TranscriptFormat::Html => "HTML",
+            TranscriptFormat::Json => "JSON",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Сообщение в JSON-выгрузке
+#[derive(Serialize)]
+struct TranscriptEntry<'a> {
+    sender: &'a str,
+    /// RFC 3339
+    time: String,
+    content: &'a str,
+    tx_id: Option<&'a str>,
+}
+
+/// Переписка канала `channel` в выбранном формате; сообщения идут в том порядке, в каком переданы
+pub fn render_transcript(format: TranscriptFormat, channel: u32, messages: &[Message]) -> Result<String, String> {
+    match format {
+        TranscriptFormat::Text => Ok(render_text(channel, messages)),
+        TranscriptFormat::Html => Ok(render_html(channel, messages)),
+        TranscriptFormat::Json => {
+            let entries: Vec<TranscriptEntry> = messages
+                .iter()
+                .map(|msg| TranscriptEntry {
+                    sender: msg.get_username(),
+                    time: msg.time.to_rfc3339(),
+                    content: msg.get_content(),
+                    tx_id: msg.get_tx_id(),
+                })
+                .collect();
+            serde_json::to_string_pretty(&entries).map_err(|e| format!("Error serializing transcript: {}", e))
+        }
+    }
+}
+
+/// `[время] имя: текст (tx …)`, по строке на сообщение; переносы внутри текста сдвинуты отступом
+fn render_text(channel: u32, messages: &[Message]) -> String {
+    let mut transcript = format!("Kaspeak channel #{}\n\n", channel);
+    for msg in messages {
+        let content = msg.get_content().replace('\n', "\n    ");
+        transcript.push_str(&format!("[{}] {}: {}", format_date_time(&msg.time), msg.get_username(), content));
+        if let Some(tx_id) = msg.get_tx_id() {
+            transcript.push_str(&format!(" (tx {})", tx_id));
+        }
+        transcript.push('\n');
+    }
+    transcript
+}
+
+fn render_html(channel: u32, messages: &[Message]) -> String {
+    let title = format!("Kaspeak channel #{}", channel);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n<table>\n\
+         <tr><th>Time</th><th>Sender</th><th>Message</th><th>Transaction</th></tr>\n"
+    );
+    for msg in messages {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&format_date_time(&msg.time)),
+            escape_html(msg.get_username()),
+            escape_html(msg.get_content()).replace('\n', "<br>"),
+            msg.get_tx_id().map(escape_html).unwrap_or_default(),
+        ));
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&#39;")
+}
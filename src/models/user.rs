@@ -1,4 +1,22 @@
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{MAX_USERNAME_BYTES, MAX_USERNAME_CHARS};
+
+/// Имя, которое можно выбрать себе: непустое и влезающее в заголовок payload
+pub fn validate_username(username: &str) -> Result<(), String> {
+    if username.trim().is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    let chars = username.chars().count();
+    if chars > MAX_USERNAME_CHARS {
+        return Err(format!("Name has {} chars, max allowed is {}", chars, MAX_USERNAME_CHARS));
+    }
+    if username.len() > MAX_USERNAME_BYTES {
+        return Err(format!("Name has {} bytes, max allowed is {}", username.len(), MAX_USERNAME_BYTES));
+    }
+    Ok(())
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct User {
@@ -29,3 +47,15 @@ impl User {
         self.time_last_message
     }
 }
+
+/// Недавно замеченный в канале собеседник; переживает перезапуск (см. `ChatSharedState::save_seen_users`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SeenUser {
+    pub username: String,
+    /// Адрес из транзакции, если он известен
+    pub address: Option<String>,
+    /// Ключ идентичности, которым подписаны его payload, если он известен
+    pub key: Option<String>,
+    /// Unix-время, секунды
+    pub last_seen: i64,
+}
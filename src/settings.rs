@@ -2,36 +2,374 @@ use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm, Key, Nonce,
 };
-use bincode;
+use bincode::{self, Options};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::constants::{DEFAULT_SETTINGS_PATH, KSPK_ENCRYPTION_KEY};
+use crate::constants::{
+    CHAT_COMPACT_ROW_ESTIMATE_PX, CHAT_ROW_ESTIMATE_PX, DEFAULT_COST_CONFIRM_THRESHOLD, DEFAULT_DARK_THEME, DEFAULT_DEDUPE_WINDOW_DAA,
+    DEFAULT_FEE_BUMP_PERCENT, DEFAULT_LIGHT_THEME, DEFAULT_MAX_PLAYBACK_STREAMS, DEFAULT_PANE_RATIOS, DEFAULT_PROFILE,
+    DEFAULT_PROFILES_DIR, DEFAULT_SETTINGS_PATH, DEFAULT_UTXO_DUST_THRESHOLD, DEFAULT_UTXO_MAX_COUNT, KSPK_ENCRYPTION_KEY,
+    MAX_PROFILE_NAME_CHARS, OPUS_BITRATE, PROFILE_ARG, SETTINGS_FORMAT_VERSION, SETTINGS_MAGIC,
+};
+use crate::models::contact::BlockedSender;
+use crate::models::content_filter::ContentFilter;
+use crate::models::derivation::AccountDerivation;
+use crate::models::faucet::FaucetSource;
+use crate::models::network::{Network, NotificationScopes, RpcPolicy};
+use crate::models::shortcut::Shortcuts;
+use crate::models::transaction::{FeeMode, SpendLimits};
+use crate::utils::format::{ClockFormat, Denomination, FormatLocale};
 use crate::utils::generate_username;
+use crate::utils::i18n::{tr, UiLanguage};
 use kaspa_wallet_core::prelude::{Language, Mnemonic, WordCount};
 
+/// Хранится в JSON: поля, которых нет в файле от прошлой версии, берутся из `Default`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SettingsData {
     pub mnemonic: String,
     pub username: String,
+    /// Каналы, в которых проигрывается сигнал о начале чужой голосовой передачи
+    pub voice_announce_channels: Vec<u32>,
+    /// Максимальное число одновременно воспроизводимых собеседников
+    pub max_playback_streams: usize,
+    /// Формат чисел и времени
+    pub locale: FormatLocale,
+    /// Единица отображения и ввода сумм
+    pub denomination: Denomination,
+    /// Каналы с выключенными уведомлениями
+    pub muted_channels: Vec<u32>,
+    /// Активный режим фокусировки
+    pub focus_mode: Option<FocusMode>,
+    /// Загружать превью ссылок из входящих сообщений (запросы уходят на сайты напрямую)
+    pub link_previews_enabled: bool,
+    /// Добровольная анонимная телеметрия (см. logs::telemetry)
+    pub telemetry_enabled: bool,
+    pub telemetry_endpoint: String,
+    /// Падения, ещё не попавшие в отправленный отчёт
+    pub pending_crashes: u32,
+    /// Сколько единиц DAA score помнить обработанные транзакции
+    pub dedupe_window_daa: u64,
+    /// Показывать частично повреждённые сообщения вместо того, чтобы отбрасывать их
+    pub salvage_payloads: bool,
+    /// Заполнять паузы между фрагментами активной передачи тихим шумом
+    pub comfort_noise_enabled: bool,
+    /// Срок жизни отправляемых текстовых сообщений, секунды; None — бессрочно
+    pub message_ttl_secs: Option<u64>,
+    /// Свой аватар (PNG, см. `prepare_avatar`); пустой — аватара нет
+    pub avatar: Vec<u8>,
+    /// Сеть Kaspa; применяется после перезапуска
+    pub network: Network,
+    /// Доплата к комиссии: вручную или по оценке ноды
+    pub fee_mode: FeeMode,
+    /// Звук при поступлении средств
+    pub incoming_funds_sound: bool,
+    /// Отправлять голос пакетами по несколько фрагментов в транзакции
+    pub voice_batching: bool,
+    /// Источник пополнения кошелька при низком балансе
+    pub faucet: FaucetSource,
+    /// Сколько UTXO допускается до автоматического объединения
+    pub utxo_max_count: usize,
+    /// Выходы дешевле этого порога (сомпи) считаются пылью и объединяются
+    pub utxo_dust_threshold: u64,
+    /// Лимиты трат; при превышении Broadcaster приостанавливает отправку
+    pub spend_limits: SpendLimits,
+    /// Ноды в порядке приоритета; пустой список — ноду выбирает резолвер
+    pub node_urls: Vec<String>,
+    /// Переподключаться самостоятельно после обрыва соединения
+    pub auto_reconnect: bool,
+    /// Путь вывода ключей личного аккаунта; применяется после перезапуска
+    pub account_derivation: AccountDerivation,
+    /// Во сколько раз (в процентах) поднимать комиссию зависшей транзакции; None — не поднимать
+    pub fee_bump_percent: Option<u32>,
+    /// Таймауты и повторы запросов к ноде
+    pub rpc_policy: RpcPolicy,
+    /// Как часто выводить новый адрес получения, секунды; None — не менять адрес самому
+    pub address_rotation_secs: Option<u64>,
+    /// Сколько раз адрес получения сменился: при запуске аккаунт доводится до этого адреса
+    pub receive_address_index: u32,
+    /// Когда адрес менялся последний раз (Unix time)
+    pub address_rotated_at: i64,
+    /// Читать сообщения из мемпула, не дожидаясь блока
+    pub mempool_listening: bool,
+    /// За сколько минут подгружать сообщения канала при подключении; None — не подгружать
+    pub backfill_minutes: Option<u64>,
+    /// Отправители, чьи сообщения и голос Listener отбрасывает
+    pub blocklist: Vec<BlockedSender>,
+    /// На какие уведомления ноды подписываться
+    pub notification_scopes: NotificationScopes,
+    /// Имя темы оформления (`Theme::to_string`); пустое — тема по умолчанию
+    pub theme: String,
+    /// Устройство ввода по имени; None — системное по умолчанию
+    pub input_device: Option<String>,
+    /// Битрейт Opus-энкодера, бит/с
+    pub opus_bitrate: i32,
+    /// Слышать собственную передачу
+    pub listen_self: bool,
+    /// Не воспроизводить чужой голос
+    pub mute_all: bool,
+    /// Собеседники, чей голос не воспроизводится (по имени)
+    pub muted_users: Vec<String>,
+    /// Собеседники, чьи сообщения скрыты в ленте чата; в истории они остаются
+    pub ignored_users: Vec<String>,
+    /// Фильтр слов и шаблонов во входящих сообщениях
+    pub content_filter: ContentFilter,
+    /// Избранные каналы в порядке добавления
+    pub favorite_channels: Vec<u32>,
+    /// Недавние каналы, последний — первым; с него начинается следующий запуск
+    pub recent_channels: Vec<u32>,
+    /// Свои названия, темы и заметки каналов по номеру; хранятся только на этом устройстве
+    pub channel_info: BTreeMap<u32, ChannelInfo>,
+    /// Запускаться свёрнутым в трей
+    pub start_minimized: bool,
+    /// Закрытие окна прячет его в трей вместо выхода
+    pub minimize_to_tray: bool,
+    /// Доля левой панели и доля чата в оставшемся месте
+    pub pane_ratios: [f32; 2],
+    /// Язык интерфейса
+    pub language: UiLanguage,
+    /// Цвета своей темы оформления
+    pub custom_palette: CustomPalette,
+    /// Индикатор уровня микрофона в верхней панели; держит устройство ввода открытым
+    pub input_meter_enabled: bool,
+    /// Назначенные сочетания клавиш
+    pub shortcuts: Shortcuts,
+    /// 12- или 24-часовой формат времени; по умолчанию как в локали
+    pub clock_format: ClockFormat,
+    /// Выше какой оценки комиссий (сомпи) запись и рассылка аватара требуют подтверждения; None — не спрашивать
+    pub cost_confirm_threshold: Option<u64>,
+    /// Плотность ленты чата
+    pub chat_density: ChatDensity,
+    /// Темы для светлого и тёмного оформления ОС, когда выбрана тема AUTO_THEME_NAME
+    pub light_theme: String,
+    pub dark_theme: String,
+}
+
+/// Режим фокусировки: до `until` уведомления приходят только из каналов `allowlist`.
+/// По окончании `muted_channels` восстанавливаются из снимка, сделанного при включении.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusMode {
+    /// Окончание (unix-время, секунды)
+    pub until: i64,
+    pub allowlist: Vec<u32>,
+    pub saved_muted_channels: Vec<u32>,
+}
+
+/// Своё описание канала: до появления метаданных каналов в сети их можно подписать только локально
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelInfo {
+    pub name: String,
+    pub topic: String,
+    pub notes: String,
+}
+
+impl ChannelInfo {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.topic.is_empty() && self.notes.is_empty()
+    }
+}
+
+/// Цвета своей темы (RGB); цвета успеха и ошибки берутся из тёмной палитры iced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomPalette {
+    pub background: [u8; 3],
+    pub text: [u8; 3],
+    pub primary: [u8; 3],
+}
+
+impl Default for CustomPalette {
+    fn default() -> Self {
+        Self { background: [0x16, 0x16, 0x16], text: [0xf2, 0xf4, 0xf8], primary: [0x78, 0xa9, 0xff] }
+    }
+}
+
+/// Плотность ленты чата
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ChatDensity {
+    /// Карточка: имя, текст и время на отдельных строках, с аватаром
+    #[default]
+    Cozy,
+    /// Одна строка в стиле IRC: время, имя и текст, без аватара
+    Compact,
+}
+
+impl ChatDensity {
+    pub const ALL: [ChatDensity; 2] = [ChatDensity::Cozy, ChatDensity::Compact];
+
+    /// Расчётная высота сообщения для построения только видимой части ленты
+    pub fn row_estimate_px(self) -> f32 {
+        match self {
+            ChatDensity::Cozy => CHAT_ROW_ESTIMATE_PX,
+            ChatDensity::Compact => CHAT_COMPACT_ROW_ESTIMATE_PX,
+        }
+    }
+}
+
+impl fmt::Display for ChatDensity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChatDensity::Cozy => write!(f, "{}", tr("density-cozy")),
+            ChatDensity::Compact => write!(f, "{}", tr("density-compact")),
+        }
+    }
 }
 
 impl Default for SettingsData {
     fn default() -> Self {
-        SettingsData { mnemonic: "None".to_string(), username: "DefaultUser".to_string() }
+        SettingsData {
+            mnemonic: "None".to_string(),
+            username: "DefaultUser".to_string(),
+            voice_announce_channels: Vec::new(),
+            max_playback_streams: DEFAULT_MAX_PLAYBACK_STREAMS,
+            locale: FormatLocale::default(),
+            denomination: Denomination::default(),
+            muted_channels: Vec::new(),
+            focus_mode: None,
+            link_previews_enabled: false,
+            telemetry_enabled: false,
+            telemetry_endpoint: String::new(),
+            pending_crashes: 0,
+            dedupe_window_daa: DEFAULT_DEDUPE_WINDOW_DAA,
+            salvage_payloads: false,
+            comfort_noise_enabled: false,
+            message_ttl_secs: None,
+            avatar: Vec::new(),
+            network: Network::default(),
+            fee_mode: FeeMode::default(),
+            incoming_funds_sound: true,
+            voice_batching: false,
+            faucet: FaucetSource::default(),
+            utxo_max_count: DEFAULT_UTXO_MAX_COUNT,
+            utxo_dust_threshold: DEFAULT_UTXO_DUST_THRESHOLD,
+            spend_limits: SpendLimits::default(),
+            node_urls: Vec::new(),
+            auto_reconnect: true,
+            account_derivation: AccountDerivation::default(),
+            fee_bump_percent: Some(DEFAULT_FEE_BUMP_PERCENT),
+            rpc_policy: RpcPolicy::default(),
+            address_rotation_secs: None,
+            receive_address_index: 0,
+            address_rotated_at: 0,
+            mempool_listening: false,
+            backfill_minutes: None,
+            blocklist: Vec::new(),
+            notification_scopes: NotificationScopes::default(),
+            theme: String::new(),
+            input_device: None,
+            opus_bitrate: OPUS_BITRATE,
+            listen_self: false,
+            mute_all: false,
+            muted_users: Vec::new(),
+            ignored_users: Vec::new(),
+            content_filter: ContentFilter::default(),
+            favorite_channels: Vec::new(),
+            recent_channels: Vec::new(),
+            channel_info: BTreeMap::new(),
+            start_minimized: false,
+            minimize_to_tray: false,
+            pane_ratios: DEFAULT_PANE_RATIOS,
+            language: UiLanguage::from_env(),
+            custom_palette: CustomPalette::default(),
+            input_meter_enabled: true,
+            shortcuts: Shortcuts::default(),
+            clock_format: ClockFormat::default(),
+            cost_confirm_threshold: Some(DEFAULT_COST_CONFIRM_THRESHOLD),
+            chat_density: ChatDensity::default(),
+            light_theme: DEFAULT_LIGHT_THEME.to_string(),
+            dark_theme: DEFAULT_DARK_THEME.to_string(),
+        }
     }
 }
 
+/// Формат settings.kspk версий v0.1.x: bincode без метки, только мнемоника и имя пользователя
+#[derive(Debug, Deserialize)]
+struct LegacySettingsData {
+    mnemonic: String,
+    username: String,
+}
+
 pub struct Settings {
     pub current: SettingsData,
     config_path: PathBuf,
 }
 
+/// Профиль, выбранный при запуске (`--profile <имя>`); без аргумента — основной
+pub fn active_profile() -> String {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|arg| arg == PROFILE_ARG)
+        .and_then(|index| args.get(index + 1))
+        .filter(|name| validate_profile_name(name).is_ok())
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Имя профиля становится именем файла, поэтому только латиница, цифры, '-' и '_'
+pub fn validate_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.chars().count() > MAX_PROFILE_NAME_CHARS {
+        return Err(format!("Profile name must have 1 to {} chars", MAX_PROFILE_NAME_CHARS));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("Profile name may contain only latin letters, digits, '-' and '_'".to_string());
+    }
+    Ok(())
+}
+
+/// Путь к файлу настроек профиля
+pub fn profile_settings_path(profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        PathBuf::from(DEFAULT_SETTINGS_PATH)
+    } else {
+        Path::new(DEFAULT_PROFILES_DIR).join(format!("{profile}.kspk"))
+    }
+}
+
+/// Путь к файлу данных профиля: у основного — как есть, у остальных к имени добавляется профиль
+/// (tx_history.json → tx_history-work.json)
+pub fn profile_file_path(path: &str, profile: &str) -> PathBuf {
+    let path = Path::new(path);
+    if profile == DEFAULT_PROFILE {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    let file_name = match path.extension() {
+        Some(ext) => format!("{stem}-{profile}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{profile}"),
+    };
+    path.with_file_name(file_name)
+}
+
+/// Основной профиль и все профили, для которых уже есть файл настроек
+pub fn list_profiles() -> Vec<String> {
+    let mut profiles: Vec<String> = fs::read_dir(DEFAULT_PROFILES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "kspk"))
+                .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+                .filter(|name| name != DEFAULT_PROFILE && validate_profile_name(name).is_ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+    profiles
+}
+
 impl Settings {
     pub fn new() -> Self {
-        let path = PathBuf::from(DEFAULT_SETTINGS_PATH);
-        Settings { current: SettingsData::default(), config_path: path }
+        Self::for_profile(&active_profile())
+    }
+
+    pub fn for_profile(profile: &str) -> Self {
+        Settings { current: SettingsData::default(), config_path: profile_settings_path(profile) }
     }
 
     pub fn load(&mut self) -> Result<(), String> {
@@ -40,19 +378,71 @@ impl Settings {
         }
         let encrypted_data = fs::read(&self.config_path).map_err(|e| format!("Error reading file {:?}: {}", self.config_path, e))?;
         let decrypted = self.decrypt_data(&encrypted_data).map_err(|e| format!("Error decrypting: {:?}", e))?;
-        let data: SettingsData = bincode::deserialize(&decrypted).map_err(|e| format!("Bincode deserialize error: {:?}", e))?;
-        self.current = data;
+        match decrypted.strip_prefix(SETTINGS_MAGIC.as_slice()) {
+            Some([version, body @ ..]) => self.current = Self::decode_versioned(*version, body)?,
+            _ => {
+                // Лишние байты — не v0.1.x, а неизвестный формат: такой файл не перезаписываем
+                let legacy: LegacySettingsData = bincode::DefaultOptions::new()
+                    .with_fixint_encoding()
+                    .reject_trailing_bytes()
+                    .deserialize(&decrypted)
+                    .map_err(|e| format!("Unknown settings format: {:?}", e))?;
+                log::warn!("Settings file {:?} is in the v0.1.x format, migrating mnemonic and username", self.config_path);
+                self.current = SettingsData { mnemonic: legacy.mnemonic, username: legacy.username, ..SettingsData::default() };
+                self.save()?;
+            }
+        }
         Ok(())
     }
 
+    /// Содержимое файла после метки в формате `version`
+    fn decode_versioned(version: u8, body: &[u8]) -> Result<SettingsData, String> {
+        match version {
+            SETTINGS_FORMAT_VERSION => serde_json::from_slice(body).map_err(|e| format!("Settings parse error: {}", e)),
+            version if version > SETTINGS_FORMAT_VERSION => {
+                Err(format!("Settings format {} is newer than this version of Kaspeak supports", version))
+            }
+            version => Err(format!("Unknown settings format {}", version)),
+        }
+    }
+
     /// Сохраняем конфиг в файл
     pub fn save(&self) -> Result<(), String> {
-        let serialized = bincode::serialize(&self.current).map_err(|e| format!("Bincode serialize error: {:?}", e))?;
+        let mut serialized = SETTINGS_MAGIC.to_vec();
+        serialized.push(SETTINGS_FORMAT_VERSION);
+        serde_json::to_writer(&mut serialized, &self.current).map_err(|e| format!("Settings serialize error: {}", e))?;
         let encrypted = self.encrypt_data(&serialized).map_err(|e| format!("Error encrypting: {:?}", e))?;
+        if let Some(dir) = self.config_path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| format!("Error creating directory {:?}: {}", dir, e))?;
+        }
         fs::write(&self.config_path, encrypted).map_err(|e| format!("Error writing file {:?}: {}", self.config_path, e))?;
         Ok(())
     }
 
+    /// Резервная копия мнемоники в открытом виде, по слову на строку с номером.
+    /// Файл нужно сразу убрать в надёжное место: по нему восстанавливается кошелёк
+    pub fn export_mnemonic(&self, path: &Path) -> Result<(), String> {
+        let lines: Vec<String> =
+            self.current.mnemonic.split_whitespace().enumerate().map(|(i, word)| format!("{:>2}. {}", i + 1, word)).collect();
+        let content = format!("Kaspeak recovery phrase\n\n{}\n", lines.join("\n"));
+        fs::write(path, content).map_err(|e| format!("Error writing file {:?}: {}", path, e))
+    }
+
+    /// Восстановление аккаунта из своей мнемоники (12 или 24 слова).
+    /// Имя пользователя выводится из новой мнемоники заново, аватар старого аккаунта сбрасывается
+    pub fn import_mnemonic(&mut self, phrase: &str) -> Result<(), String> {
+        let words: Vec<String> = phrase.split_whitespace().map(str::to_lowercase).collect();
+        if words.len() != 12 && words.len() != 24 {
+            return Err(format!("Recovery phrase has {} words, expected 12 or 24", words.len()));
+        }
+        let mnemonic = Mnemonic::new(words.join(" "), Language::English).map_err(|e| format!("Invalid recovery phrase: {e}"))?;
+        let mnemonic_str = mnemonic.phrase().to_string();
+        self.current.username = generate_username(&mnemonic_str);
+        self.current.mnemonic = mnemonic_str;
+        self.current.avatar = Vec::new();
+        self.save()
+    }
+
     /// Инициализация настроек при отсутствии файла
     pub fn initialize_settings(&mut self) -> Result<(), String> {
         let mnemonic =
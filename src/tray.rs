@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tray_icon::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+use crate::constants::APP_ICON_FILE_INLINED;
+use crate::utils::i18n::tr;
+
+const SHOW_ID: &str = "show";
+const MUTE_ID: &str = "mute";
+const QUIT_ID: &str = "quit";
+
+/// Иконка создана: окно можно прятать, не теряя доступ к приложению
+static TRAY_AVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// Действие, выбранное в меню иконки или кликом по ней
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    ToggleWindow,
+    MuteMicrophone,
+    Quit,
+}
+
+/// Создаёт иконку в системном трее.
+/// На Windows и macOS иконка живёт в потоке, где крутится цикл событий окна, поэтому
+/// вызывается из main до запуска GUI, а возвращённое значение держится до выхода.
+/// На Linux трей работает через GTK в отдельном потоке, и значение всегда `None`.
+pub fn spawn_tray() -> Option<TrayIcon> {
+    #[cfg(target_os = "linux")]
+    {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            if let Err(err) = gtk::init() {
+                let _ = tx.send(Err(format!("GTK init failed: {err}")));
+                return;
+            }
+            match build_tray() {
+                Ok(_tray) => {
+                    let _ = tx.send(Ok(()));
+                    gtk::main();
+                }
+                Err(err) => {
+                    let _ = tx.send(Err(err));
+                }
+            }
+        });
+        match rx.recv() {
+            Ok(Ok(())) => TRAY_AVAILABLE.store(true, Ordering::SeqCst),
+            Ok(Err(err)) => log::warn!("System tray is not available: {}", err),
+            Err(_) => log::warn!("System tray thread exited before creating the icon"),
+        }
+        None
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        match build_tray() {
+            Ok(tray) => {
+                TRAY_AVAILABLE.store(true, Ordering::SeqCst);
+                Some(tray)
+            }
+            Err(err) => {
+                log::warn!("System tray is not available: {}", err);
+                None
+            }
+        }
+    }
+}
+
+/// Подписи меню берутся на языке интерфейса при запуске; смена языка применится к трею после перезапуска
+fn build_tray() -> Result<TrayIcon, String> {
+    let image = image::load_from_memory(APP_ICON_FILE_INLINED).map_err(|e| format!("Failed to decode icon: {e}"))?.to_rgba8();
+    let (width, height) = image.dimensions();
+    let icon = tray_icon::Icon::from_rgba(image.into_raw(), width, height).map_err(|e| format!("Invalid icon: {e}"))?;
+
+    let menu = Menu::new();
+    menu.append_items(&[
+        &MenuItem::with_id(SHOW_ID, tr("tray-show-hide"), true, None),
+        &MenuItem::with_id(MUTE_ID, tr("tray-mute"), true, None),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id(QUIT_ID, tr("tray-quit"), true, None),
+    ])
+    .map_err(|e| format!("Failed to build tray menu: {e}"))?;
+
+    TrayIconBuilder::new()
+        .with_icon(icon)
+        .with_tooltip("Kaspeak")
+        .with_menu(Box::new(menu))
+        .build()
+        .map_err(|e| format!("Failed to create tray icon: {e}"))
+}
+
+pub fn is_tray_available() -> bool {
+    TRAY_AVAILABLE.load(Ordering::SeqCst)
+}
+
+/// Забирает следующее действие из трея; GUI опрашивает на каждом Tick
+pub fn poll_tray_action() -> Option<TrayAction> {
+    if let Ok(event) = MenuEvent::receiver().try_recv() {
+        return match event.id.as_ref() {
+            SHOW_ID => Some(TrayAction::ToggleWindow),
+            MUTE_ID => Some(TrayAction::MuteMicrophone),
+            QUIT_ID => Some(TrayAction::Quit),
+            _ => None,
+        };
+    }
+    while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+        if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+            return Some(TrayAction::ToggleWindow);
+        }
+    }
+    None
+}
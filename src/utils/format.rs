@@ -0,0 +1,314 @@
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::app_state::APP_STATE;
+use crate::constants::{AUTO_DENOMINATION_SOMPI_BELOW, KAS_DECIMALS, SOMPI_PER_KAS};
+use crate::utils::i18n::{tr, tr_args};
+
+/// Локаль для отображения чисел и времени
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FormatLocale {
+    #[default]
+    English,
+    Russian,
+}
+
+impl FormatLocale {
+    pub const ALL: [FormatLocale; 2] = [FormatLocale::English, FormatLocale::Russian];
+
+    fn decimal_separator(&self) -> char {
+        match self {
+            FormatLocale::English => '.',
+            FormatLocale::Russian => ',',
+        }
+    }
+
+    fn group_separator(&self) -> char {
+        match self {
+            FormatLocale::English => ',',
+            FormatLocale::Russian => '\u{a0}',
+        }
+    }
+
+    /// 12-часовой формат по умолчанию для локали
+    fn uses_12_hour(&self) -> bool {
+        match self {
+            FormatLocale::English => true,
+            FormatLocale::Russian => false,
+        }
+    }
+
+    fn date_pattern(&self) -> &'static str {
+        match self {
+            FormatLocale::English => "%m/%d/%Y",
+            FormatLocale::Russian => "%d.%m.%Y",
+        }
+    }
+}
+
+impl fmt::Display for FormatLocale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatLocale::English => write!(f, "English (1,234.5 / 12h)"),
+            FormatLocale::Russian => write!(f, "Русский (1 234,5 / 24ч)"),
+        }
+    }
+}
+
+/// Формат часов: как принято в локали или принудительно 12/24-часовой
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ClockFormat {
+    #[default]
+    Locale,
+    Hour12,
+    Hour24,
+}
+
+impl ClockFormat {
+    pub const ALL: [ClockFormat; 3] = [ClockFormat::Locale, ClockFormat::Hour12, ClockFormat::Hour24];
+
+    fn is_12_hour(self, locale: FormatLocale) -> bool {
+        match self {
+            ClockFormat::Locale => locale.uses_12_hour(),
+            ClockFormat::Hour12 => true,
+            ClockFormat::Hour24 => false,
+        }
+    }
+
+    fn time_pattern(self, locale: FormatLocale) -> &'static str {
+        if self.is_12_hour(locale) {
+            "%I:%M %p"
+        } else {
+            "%H:%M"
+        }
+    }
+
+    fn time_with_seconds_pattern(self, locale: FormatLocale) -> &'static str {
+        if self.is_12_hour(locale) {
+            "%I:%M:%S %p"
+        } else {
+            "%H:%M:%S"
+        }
+    }
+}
+
+impl fmt::Display for ClockFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClockFormat::Locale => write!(f, "{}", tr("clock-format-locale")),
+            ClockFormat::Hour12 => write!(f, "{}", tr("clock-format-12h")),
+            ClockFormat::Hour24 => write!(f, "{}", tr("clock-format-24h")),
+        }
+    }
+}
+
+/// Единица, в которой показываются и вводятся суммы
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Denomination {
+    #[default]
+    Kas,
+    Sompi,
+    /// Мелкие суммы (комиссии) в сомпи, остальные в KAS; вводятся в KAS
+    Auto,
+}
+
+impl Denomination {
+    pub const ALL: [Denomination; 3] = [Denomination::Kas, Denomination::Sompi, Denomination::Auto];
+
+    /// Единица полей ввода: у «Auto» она не должна меняться от введённого значения
+    pub fn input_unit(self) -> Denomination {
+        match self {
+            Denomination::Auto => Denomination::Kas,
+            other => other,
+        }
+    }
+
+    /// Единица, в которой показывается конкретная сумма
+    fn for_amount(self, sompi: u64) -> Denomination {
+        match self {
+            Denomination::Auto if sompi != 0 && sompi < AUTO_DENOMINATION_SOMPI_BELOW => Denomination::Sompi,
+            Denomination::Auto => Denomination::Kas,
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Denomination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Denomination::Kas => write!(f, "{}", APP_STATE.get_network().ticker()),
+            Denomination::Sompi => write!(f, "sompi"),
+            Denomination::Auto => write!(f, "{}", tr_args("denomination-auto", &[("ticker", &APP_STATE.get_network().ticker())])),
+        }
+    }
+}
+
+/// Текущая локаль из настроек
+pub fn current_locale() -> FormatLocale {
+    APP_STATE.get_locale().unwrap_or_default()
+}
+
+/// Текущий формат часов из настроек
+pub fn current_clock_format() -> ClockFormat {
+    APP_STATE.get_clock_format().unwrap_or_default()
+}
+
+/// Текущая единица сумм из настроек
+pub fn current_denomination() -> Denomination {
+    APP_STATE.get_denomination().unwrap_or_default()
+}
+
+/// Расставляет разделители групп разрядов в строке из цифр
+fn group_digits(digits: &str, separator: char) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(ch);
+    }
+    grouped
+}
+
+/// Целое число с разделителями разрядов: 1,234,567 / 1 234 567
+pub fn format_integer_in(value: u64, locale: FormatLocale) -> String {
+    group_digits(&value.to_string(), locale.group_separator())
+}
+
+/// Дробное число с заданным количеством знаков после запятой
+pub fn format_decimal_in(value: f64, decimals: usize, locale: FormatLocale) -> String {
+    let raw = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = raw.split_once('.').unwrap_or((raw.as_str(), ""));
+
+    let mut formatted = String::new();
+    if value < 0.0 && raw.chars().any(|c| c != '0' && c != '.') {
+        formatted.push('-');
+    }
+    formatted.push_str(&group_digits(int_part, locale.group_separator()));
+    if !frac_part.is_empty() {
+        formatted.push(locale.decimal_separator());
+        formatted.push_str(frac_part);
+    }
+    formatted
+}
+
+/// Сумма с единицей: «1,234.567 TKAS» / «123,456,700,000 sompi»; в режиме «Auto» единица зависит от суммы.
+/// `kas_decimals` — сколько знаков после запятой показывать в KAS
+pub fn format_amount_in(sompi: u64, kas_decimals: usize, denomination: Denomination, locale: FormatLocale) -> String {
+    match denomination.for_amount(sompi) {
+        Denomination::Sompi => format!("{} {}", format_integer_in(sompi, locale), Denomination::Sompi),
+        unit => format!("{} {}", format_decimal_in(sompi_to_kas(sompi), kas_decimals, locale), unit),
+    }
+}
+
+/// Сомпи в KAS — для сравнений и вычислений, не для показа
+pub fn sompi_to_kas(sompi: u64) -> f64 {
+    sompi as f64 / SOMPI_PER_KAS as f64
+}
+
+/// Сумма для журнала: всегда KAS с 8 знаками, без разделителей разрядов и независимо от настроек
+pub fn format_log_amount(sompi: u64) -> String {
+    format!("{:.*} {}", KAS_DECIMALS, sompi_to_kas(sompi), APP_STATE.get_network().ticker())
+}
+
+/// Значение для поля ввода: без разделителей разрядов и единицы, KAS — без хвостовых нулей
+pub fn amount_to_input(sompi: u64, denomination: Denomination) -> String {
+    match denomination.input_unit() {
+        Denomination::Kas => {
+            let frac = format!("{:0width$}", sompi % SOMPI_PER_KAS, width = KAS_DECIMALS);
+            let frac = frac.trim_end_matches('0');
+            if frac.is_empty() {
+                (sompi / SOMPI_PER_KAS).to_string()
+            } else {
+                format!("{}.{}", sompi / SOMPI_PER_KAS, frac)
+            }
+        }
+        _ => sompi.to_string(),
+    }
+}
+
+/// Разбор введённой суммы в сомпи. Для KAS допускается «.» или «,» и не больше 8 знаков после запятой.
+/// Пустая строка — ноль, незавершённый ввод вида «1.» допустим
+pub fn parse_amount(input: &str, denomination: Denomination) -> Option<u64> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Some(0);
+    }
+    match denomination.input_unit() {
+        Denomination::Sompi => input.chars().all(|c| c.is_ascii_digit()).then(|| input.parse().ok()).flatten(),
+        _ => {
+            let (int_part, frac_part) = input.split_once(['.', ',']).unwrap_or((input, ""));
+            let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+            if !all_digits(int_part) || !all_digits(frac_part) || frac_part.len() > KAS_DECIMALS {
+                return None;
+            }
+            let whole: u64 = if int_part.is_empty() { 0 } else { int_part.parse().ok()? };
+            let frac: u64 = format!("{:0<width$}", frac_part, width = KAS_DECIMALS).parse().ok()?;
+            whole.checked_mul(SOMPI_PER_KAS)?.checked_add(frac)
+        }
+    }
+}
+
+/// Время сообщения (часы и минуты)
+pub fn format_time_in(time: &DateTime<Local>, locale: FormatLocale, clock: ClockFormat) -> String {
+    time.format(clock.time_pattern(locale)).to_string()
+}
+
+/// Дата без времени (разделители дней в чате)
+pub fn format_date_in(date: &NaiveDate, locale: FormatLocale) -> String {
+    date.format(locale.date_pattern()).to_string()
+}
+
+/// Полная дата и время (для экспорта и подсказок)
+pub fn format_date_time_in(time: &DateTime<Local>, locale: FormatLocale, clock: ClockFormat) -> String {
+    format!("{} {}", time.format(locale.date_pattern()), time.format(clock.time_with_seconds_pattern(locale)))
+}
+
+/// Расхождение часов: «+2h 5m» — часы отправителя спешат, «-40s» — отстают
+pub fn format_skew(skew_secs: i64) -> String {
+    let sign = if skew_secs < 0 { '-' } else { '+' };
+    let secs = skew_secs.unsigned_abs();
+    match secs {
+        0..=59 => format!("{sign}{secs}s"),
+        60..=3_599 => format!("{sign}{}m {}s", secs / 60, secs % 60),
+        3_600..=86_399 => format!("{sign}{}h {}m", secs / 3_600, secs % 3_600 / 60),
+        _ => format!("{sign}{}d {}h", secs / 86_400, secs % 86_400 / 3_600),
+    }
+}
+
+/// Сколько прошло, в самой крупной подходящей единице: «40s», «5m», «2h», «3d» (единицы — из каталога языка)
+pub fn format_elapsed(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (key, value) = match secs {
+        0..=59 => ("elapsed-seconds", secs),
+        60..=3_599 => ("elapsed-minutes", secs / 60),
+        3_600..=86_399 => ("elapsed-hours", secs / 3_600),
+        _ => ("elapsed-days", secs / 86_400),
+    };
+    tr_args(key, &[("value", &value)])
+}
+
+pub fn format_integer(value: u64) -> String {
+    format_integer_in(value, current_locale())
+}
+
+pub fn format_decimal(value: f64, decimals: usize) -> String {
+    format_decimal_in(value, decimals, current_locale())
+}
+
+pub fn format_amount(sompi: u64, kas_decimals: usize) -> String {
+    format_amount_in(sompi, kas_decimals, current_denomination(), current_locale())
+}
+
+pub fn format_time(time: &DateTime<Local>) -> String {
+    format_time_in(time, current_locale(), current_clock_format())
+}
+
+pub fn format_date(date: &NaiveDate) -> String {
+    format_date_in(date, current_locale())
+}
+
+pub fn format_date_time(time: &DateTime<Local>) -> String {
+    format_date_time_in(time, current_locale(), current_clock_format())
+}
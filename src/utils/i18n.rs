@@ -0,0 +1,106 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::app_state::APP_STATE;
+use crate::constants::{LOCALE_EN_INLINED, LOCALE_RU_INLINED};
+
+/// Язык интерфейса
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UiLanguage {
+    #[default]
+    English,
+    Russian,
+}
+
+impl UiLanguage {
+    pub const ALL: [UiLanguage; 2] = [UiLanguage::English, UiLanguage::Russian];
+
+    /// Язык системы по LC_ALL / LC_MESSAGES / LANG; используется для новых настроек
+    pub fn from_env() -> UiLanguage {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| if value.to_lowercase().starts_with("ru") { UiLanguage::Russian } else { UiLanguage::English })
+            .unwrap_or_default()
+    }
+
+    fn catalog(&self) -> &'static Catalog {
+        match self {
+            UiLanguage::English => &EN_CATALOG,
+            UiLanguage::Russian => &RU_CATALOG,
+        }
+    }
+}
+
+impl fmt::Display for UiLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UiLanguage::English => write!(f, "English"),
+            UiLanguage::Russian => write!(f, "Русский"),
+        }
+    }
+}
+
+type Catalog = HashMap<&'static str, String>;
+
+lazy_static! {
+    static ref EN_CATALOG: Catalog = parse_catalog(LOCALE_EN_INLINED);
+    static ref RU_CATALOG: Catalog = parse_catalog(LOCALE_RU_INLINED);
+}
+
+/// Разбирает каталог в духе Fluent: `ключ = значение`, `#` — комментарий,
+/// строка с отступом продолжает предыдущее значение с новой строки
+fn parse_catalog(source: &'static str) -> Catalog {
+    let mut catalog = Catalog::new();
+    let mut last_key = None;
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            match last_key.and_then(|key| catalog.get_mut(key)) {
+                Some(value) => {
+                    let value: &mut String = value;
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(trimmed);
+                }
+                None => log::warn!("Locale catalog: continuation without a key: {}", trimmed),
+            }
+            continue;
+        }
+        match line.split_once('=') {
+            Some((key, value)) => {
+                let key = key.trim();
+                catalog.insert(key, value.trim().to_string());
+                last_key = Some(key);
+            }
+            None => log::warn!("Locale catalog: malformed line: {}", trimmed),
+        }
+    }
+    catalog
+}
+
+/// Текущий язык интерфейса из настроек
+pub fn current_language() -> UiLanguage {
+    APP_STATE.get_language().unwrap_or_default()
+}
+
+/// Строка интерфейса на текущем языке. Нет перевода — английская строка, нет и её — сам ключ
+pub fn tr(key: &'static str) -> &'static str {
+    current_language().catalog().get(key).or_else(|| EN_CATALOG.get(key)).map(String::as_str).unwrap_or(key)
+}
+
+/// Строка интерфейса с подстановками `{ $имя }`
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut result = tr(key).to_string();
+    for (name, value) in args {
+        result = result.replace(&format!("{{ ${name} }}"), &value.to_string());
+    }
+    result
+}
@@ -0,0 +1,46 @@
+use sha2::{Digest, Sha256};
+
+use crate::constants::{IDENTICON_CELL_PX, IDENTICON_GRID};
+
+/// Identicon для отправителя без аватара: сетка IDENTICON_GRID × IDENTICON_GRID, симметричная
+/// по горизонтали, цвет и рисунок берутся из SHA-256 от `seed` (ключ идентичности или имя).
+/// Возвращает сторону изображения в пикселях и RGBA-данные
+pub fn identicon_rgba(seed: &str) -> (u32, Vec<u8>) {
+    let hash = Sha256::digest(seed.as_bytes());
+    let hue = u16::from_be_bytes([hash[0], hash[1]]) as f32 / u16::MAX as f32 * 360.0;
+    let [r, g, b] = hsl_to_rgb(hue, 0.55, 0.55);
+    // Фон того же оттенка, полупрозрачный: заметен и на светлой, и на тёмной теме
+    let foreground = [r, g, b, 255];
+    let background = [r, g, b, 48];
+
+    let half = IDENTICON_GRID.div_ceil(2);
+    let side = IDENTICON_GRID as u32 * IDENTICON_CELL_PX;
+    let mut rgba = Vec::with_capacity((side * side * 4) as usize);
+    for y in 0..side {
+        let row = (y / IDENTICON_CELL_PX) as usize;
+        for x in 0..side {
+            let column = (x / IDENTICON_CELL_PX) as usize;
+            let mirrored = column.min(IDENTICON_GRID - 1 - column);
+            let bit = row * half + mirrored;
+            let filled = hash[2 + bit / 8] & (1 << (bit % 8)) != 0;
+            rgba.extend_from_slice(if filled { &foreground } else { &background });
+        }
+    }
+    (side, rgba)
+}
+
+fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> [u8; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    [r, g, b].map(|channel| ((channel + m) * 255.0).round() as u8)
+}
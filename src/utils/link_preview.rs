@@ -0,0 +1,205 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::blocking::{Client, Response};
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use std::io::Read;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+use std::time::Duration;
+
+use crate::constants::{LINK_PREVIEW_MAX_BYTES, LINK_PREVIEW_MAX_REDIRECTS, LINK_PREVIEW_TIMEOUT_SECS, MAX_PREVIEW_DESCRIPTION_CHARS};
+
+lazy_static! {
+    static ref URL_RE: Regex = Regex::new(r#"https?://[^\s<>"']+"#).unwrap();
+    static ref TITLE_RE: Regex = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap();
+    static ref META_RE: Regex = Regex::new(r"(?is)<meta\s[^>]*>").unwrap();
+    static ref ATTR_RE: Regex = Regex::new(r#"(?is)([a-z:]+)\s*=\s*["']([^"']*)["']"#).unwrap();
+}
+
+/// Заголовок и описание страницы для карточки под сообщением
+#[derive(Debug, Clone)]
+pub struct LinkPreview {
+    pub url: String,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+/// Первая http(s)-ссылка в тексте
+pub fn extract_first_url(text: &str) -> Option<String> {
+    URL_RE.find(text).map(|m| trim_url(m.as_str()).to_string())
+}
+
+/// Делит текст на куски: обычный текст и ссылки (`true`). Вместе куски дают исходный текст
+pub fn split_links(text: &str) -> Vec<(&str, bool)> {
+    let mut segments = Vec::new();
+    let mut rest_start = 0;
+    for m in URL_RE.find_iter(text) {
+        let url = trim_url(m.as_str());
+        if m.start() > rest_start {
+            segments.push((&text[rest_start..m.start()], false));
+        }
+        segments.push((url, true));
+        rest_start = m.start() + url.len();
+    }
+    if rest_start < text.len() {
+        segments.push((&text[rest_start..], false));
+    }
+    segments
+}
+
+/// Знаки препинания в конце обычно относятся к предложению, а не к ссылке
+fn trim_url(url: &str) -> &str {
+    url.trim_end_matches(['.', ',', ')', '!', '?'])
+}
+
+/// Загружает страницу и достаёт из неё заголовок и описание.
+/// Ссылку прислал собеседник, поэтому ходим только на публичные адреса, в том числе после редиректов.
+/// Читается не больше `LINK_PREVIEW_MAX_BYTES`: метаданные почти всегда в начале документа
+pub fn fetch_preview(url: &str) -> Result<LinkPreview, String> {
+    let mut target = Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
+    for _ in 0..=LINK_PREVIEW_MAX_REDIRECTS {
+        let response = request_public(&target)?;
+        if !response.status().is_redirection() {
+            return read_preview(url, response);
+        }
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| format!("Redirect {} without location", response.status()))?;
+        target = target.join(location).map_err(|e| format!("Invalid redirect location: {e}"))?;
+    }
+    Err("Too many redirects".to_string())
+}
+
+/// Запрос без автоматических редиректов к заранее проверенному адресу хоста: клиент не резолвит
+/// имя повторно, так что второй DNS-ответ не уведёт запрос в локальную сеть
+fn request_public(url: &Url) -> Result<Response, String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Unsupported scheme {}", url.scheme()));
+    }
+    let host = url.host_str().ok_or_else(|| "URL has no host".to_string())?;
+    let port = url.port_or_known_default().ok_or_else(|| "URL has no port".to_string())?;
+    // IPv6-адрес в URL записан в скобках
+    let literal = host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>().ok();
+    let addrs: Vec<SocketAddr> = match literal {
+        Some(ip) => vec![SocketAddr::new(ip, port)],
+        None => (host, port).to_socket_addrs().map_err(|e| format!("Failed to resolve {host}: {e}"))?.collect(),
+    };
+    if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(format!("Refusing to fetch {host}: {} is not a public address", addr.ip()));
+    }
+    let addr = *addrs.first().ok_or_else(|| format!("No addresses for {host}"))?;
+
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(LINK_PREVIEW_TIMEOUT_SECS))
+        .user_agent(concat!("Kaspeak/", env!("CARGO_PKG_VERSION")))
+        .redirect(Policy::none());
+    if literal.is_none() {
+        builder = builder.resolve(host, addr);
+    }
+    let client = builder.build().map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+    client.get(url.clone()).send().map_err(|e| format!("Request failed: {e}"))
+}
+
+fn read_preview(url: &str, response: Response) -> Result<LinkPreview, String> {
+    if !response.status().is_success() {
+        return Err(format!("Unexpected status {}", response.status()));
+    }
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("text/html"));
+    if !is_html {
+        return Err("Not an HTML page".to_string());
+    }
+
+    let mut body = Vec::new();
+    response.take(LINK_PREVIEW_MAX_BYTES).read_to_end(&mut body).map_err(|e| format!("Failed to read body: {e}"))?;
+    parse_html(url, &String::from_utf8_lossy(&body)).ok_or_else(|| "No title found".to_string())
+}
+
+/// Адрес в интернете, а не loopback, частная или link-local сеть и прочие служебные диапазоны
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0
+        // 100.64.0.0/10 — адреса за NAT провайдера
+        || (a == 100 && (64..128).contains(&b))
+        // 198.18.0.0/15 — тестовые сети, 240.0.0.0/4 — зарезервировано
+        || (a == 198 && (b == 18 || b == 19))
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7 — уникальные локальные, fe80::/10 — link-local
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80)
+}
+
+/// Разбор HTML: og:title/<title> и og:description/description
+fn parse_html(url: &str, html: &str) -> Option<LinkPreview> {
+    let mut og_title = None;
+    let mut description = None;
+    for meta in META_RE.find_iter(html) {
+        let mut key = None;
+        let mut content = None;
+        for attr in ATTR_RE.captures_iter(meta.as_str()) {
+            match attr[1].to_lowercase().as_str() {
+                "property" | "name" => key = Some(attr[2].to_lowercase()),
+                "content" => content = Some(decode_entities(&attr[2])),
+                _ => {}
+            }
+        }
+        match (key.as_deref(), content) {
+            (Some("og:title"), Some(content)) => og_title = Some(content),
+            (Some("og:description"), Some(content)) => description = Some(content),
+            (Some("description"), Some(content)) if description.is_none() => description = Some(content),
+            _ => {}
+        }
+    }
+
+    let title = og_title.or_else(|| TITLE_RE.captures(html).map(|caps| decode_entities(&caps[1])))?;
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    if title.is_empty() {
+        return None;
+    }
+    let description = description
+        .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|d| !d.is_empty())
+        .map(|d| truncate_chars(&d, MAX_PREVIEW_DESCRIPTION_CHARS));
+    Some(LinkPreview { url: url.to_string(), title, description })
+}
+
+/// Только самые частые HTML-сущности
+fn decode_entities(text: &str) -> String {
+    text.replace("&amp;", "&").replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&#39;", "'")
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text.to_string(),
+    }
+}
@@ -0,0 +1,294 @@
+pub(crate) mod format;
+pub(crate) mod i18n;
+pub(crate) mod identicon;
+pub(crate) mod link_preview;
+pub(crate) mod rpc;
+pub(crate) mod store;
+
+use rodio::source::SineWave;
+use rodio::{Decoder, OutputStream, Sink, Source};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use kaspa_wallet_core::prelude::*;
+use kaspa_wrpc_client::prelude::*;
+
+use crate::app_state::APP_STATE;
+use crate::constants::{
+    ADJECTIVES, ANNOUNCEMENT_TONE_HZ, ANNOUNCEMENT_TONE_MS, ANNOUNCEMENT_TONE_VOLUME, EMOJIS, MENTION_TONES_HZ, MENTION_TONE_MS,
+    NOTIFICATION_SOUND_FILE_INLINED, NOUNS, PERMALINK_PREFIX, PROFILE_ARG,
+};
+use cpal::traits::{DeviceTrait, HostTrait};
+use cpal::Device;
+use kaspa_wallet_core::rpc::ConnectStrategy::Fallback;
+
+/// Собирает список доступных устройств ввода и возвращает кортеж: (список названий, выбранное устройство).
+/// Выбирается устройство `preferred`, если оно подключено, иначе устройство по умолчанию.
+pub fn gather_audio_devices(preferred: Option<&str>) -> (Vec<String>, Option<Device>) {
+    let host = cpal::default_host();
+
+    let available_input_devices = match host.input_devices() {
+        Ok(devices) => devices.map(|d| d.name().unwrap_or_else(|_| "Unknown".to_string())).collect(),
+        Err(e) => {
+            log::error!("Error while retrieving input device list: {:?}", e);
+            vec![]
+        }
+    };
+
+    let preferred_device = preferred.and_then(|name| {
+        let device = host.input_devices().ok()?.find(|d| d.name().ok().as_deref() == Some(name));
+        if device.is_none() {
+            log::warn!("Saved input device '{}' is not available, using the default one", name);
+        }
+        device
+    });
+    let selected_input_device = preferred_device.or_else(|| host.default_input_device());
+    (available_input_devices, selected_input_device)
+}
+
+/// Набор элементов, привязанных к DAA score блока, в котором они встретились.
+/// Хранятся только элементы не старше `window` единиц от самого большого увиденного score,
+/// поэтому объём памяти зависит от реальной нагрузки сети, а не от фиксированного лимита.
+pub struct DaaWindowSet<T> {
+    scores: HashMap<T, u64>,
+    by_score: BTreeMap<u64, Vec<T>>,
+    window: u64,
+    highest_score: u64,
+}
+
+impl<T: std::hash::Hash + Eq + Clone> DaaWindowSet<T> {
+    /// Создает пустой набор с окном в `window` единиц DAA score.
+    pub fn new(window: u64) -> Self {
+        Self { scores: HashMap::new(), by_score: BTreeMap::new(), window, highest_score: 0 }
+    }
+
+    /// Меняет размер окна; при уменьшении лишние элементы удаляются сразу.
+    pub fn set_window(&mut self, window: u64) {
+        if self.window != window {
+            self.window = window;
+            self.prune();
+        }
+    }
+
+    /// Вставляет элемент, встреченный в блоке с указанным DAA score.
+    /// Возвращает `false`, если элемент уже есть в окне.
+    pub fn insert(&mut self, value: T, daa_score: u64) -> bool {
+        if self.scores.contains_key(&value) {
+            return false;
+        }
+        self.scores.insert(value.clone(), daa_score);
+        self.by_score.entry(daa_score).or_default().push(value);
+        if daa_score > self.highest_score {
+            self.highest_score = daa_score;
+            self.prune();
+        }
+        true
+    }
+
+    /// Проверяет, содержит ли набор данный элемент.
+    pub fn contains(&self, value: &T) -> bool {
+        self.scores.contains_key(value)
+    }
+
+    /// Все элементы с их DAA score, от старых к новым: для сохранения на диск.
+    pub fn entries(&self) -> Vec<(T, u64)> {
+        self.by_score.iter().flat_map(|(score, values)| values.iter().map(|value| (value.clone(), *score))).collect()
+    }
+
+    /// Восстанавливает сохранённые элементы; вышедшие за окно сразу отбрасываются.
+    pub fn extend(&mut self, entries: impl IntoIterator<Item = (T, u64)>) {
+        for (value, daa_score) in entries {
+            self.insert(value, daa_score);
+        }
+    }
+
+    /// Удаляет элементы, вышедшие за окно.
+    fn prune(&mut self) {
+        let cutoff = self.highest_score.saturating_sub(self.window);
+        let kept = self.by_score.split_off(&cutoff);
+        let expired = std::mem::replace(&mut self.by_score, kept);
+        let mut removed = 0;
+        for value in expired.into_values().flatten() {
+            self.scores.remove(&value);
+            removed += 1;
+        }
+        if removed > 0 {
+            log::debug!("Forgot {} entries older than DAA score {}", removed, cutoff);
+        }
+    }
+}
+
+/// Инициализирует RPC-клиент Kaspa.
+pub fn bootstrap_rpc_client(network_id: NetworkId, url: Option<String>) -> Arc<KaspaRpcClient> {
+    let (resolver, url) = if let Some(url) = url { (None, Some(url)) } else { (Some(Resolver::default()), None) };
+
+    let client =
+        Arc::new(KaspaRpcClient::new_with_args(WrpcEncoding::Borsh, url.as_deref(), resolver, Some(network_id), None).unwrap());
+
+    client
+}
+
+pub fn shorten_address(full: &str) -> String {
+    if full == "Empty" || full == "Error" {
+        return full.to_string();
+    }
+    // Пробуем удалить префикс сети
+    let prefix = APP_STATE.get_network().address_prefix();
+    match full.strip_prefix(prefix) {
+        Some(rest) if rest.len() > 10 => {
+            let first5 = &rest[..5];
+            let last5 = &rest[rest.len() - 5..];
+            format!("{prefix}{first5}...{last5}")
+        }
+        // Адрес другой сети или без префикса
+        _ => full.to_string(),
+    }
+}
+
+/// Сокращает hex-ключ для отображения: первые 8 и последние 6 символов
+pub fn shorten_key(key: &str) -> String {
+    if key.len() <= 16 || !key.is_ascii() {
+        return key.to_string();
+    }
+    format!("{}…{}", &key[..8], &key[key.len() - 6..])
+}
+
+/// Ссылка на сообщение по идентификатору транзакции
+pub fn build_permalink(tx_id: &str) -> String {
+    format!("{PERMALINK_PREFIX}{tx_id}")
+}
+
+/// Извлекает идентификатор транзакции из ссылки вида kaspeak://msg/<txid>
+pub fn parse_permalink(link: &str) -> Option<String> {
+    let tx_id = link.trim().strip_prefix(PERMALINK_PREFIX)?.trim_end_matches('/');
+    if tx_id.len() == 64 && tx_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(tx_id.to_lowercase())
+    } else {
+        None
+    }
+}
+
+/// Генерация "человеко-понятного" имени пользователя из входной строки (обычно это мнемоника).
+pub fn generate_username(input: &str) -> String {
+    // Хэшируем входную строку с помощью SHA256
+    let mut hasher = Sha256::new();
+    hasher.update(input.as_bytes());
+    let hash_result = hasher.finalize();
+    let bytes = hash_result.as_slice();
+
+    // Убедимся, что хэш содержит достаточно байтов
+    if bytes.len() < 18 {
+        panic!("Insufficient bytes in the hash to generate a user name.");
+    }
+
+    // Извлекаем первые 8 байт для прилагательного
+    let adj_bytes: [u8; 8] = bytes[0..8].try_into().unwrap();
+    let adj_num = u64::from_be_bytes(adj_bytes);
+    let adj_index = (adj_num as usize) % ADJECTIVES.len();
+    let adjective = ADJECTIVES[adj_index];
+
+    // Извлекаем следующие 8 байт для существительного
+    let noun_bytes: [u8; 8] = bytes[8..16].try_into().unwrap();
+    let noun_num = u64::from_be_bytes(noun_bytes);
+    let noun_index = (noun_num as usize) % NOUNS.len();
+    let noun = NOUNS[noun_index];
+
+    // Извлекаем 1 байт для эмодзи
+    let emoji_byte = bytes[16];
+    let emoji_index = (emoji_byte as usize) % EMOJIS.len();
+    let emoji = EMOJIS[emoji_index];
+
+    // Формируем имя пользователя
+    format!("{}{} {}", adjective, noun, emoji)
+}
+
+/// Запускает новый экземпляр приложения с выбранным профилем
+pub fn spawn_new_instance(profile: &str) -> std::io::Result<()> {
+    let exe = std::env::current_exe()?;
+    std::process::Command::new(exe).args([PROFILE_ARG, profile]).spawn().map(|_| ())
+}
+
+pub fn play_notification_sound() -> Result<(), Box<dyn std::error::Error>> {
+    // Получаем устройство вывода по умолчанию
+    let (_stream, stream_handle) = OutputStream::try_default()?;
+
+    // Создаём `Sink`
+    let sink = Sink::try_new(&stream_handle)?;
+
+    let beep_source = Decoder::new_wav(Cursor::new(NOTIFICATION_SOUND_FILE_INLINED))?;
+
+    // Добавляем источник звука в Sink
+    sink.append(beep_source);
+
+    sink.sleep_until_end();
+
+    Ok(())
+}
+
+/// Короткий тихий сигнал о том, что кто-то начал говорить
+pub fn play_announcement_tone() -> Result<(), Box<dyn std::error::Error>> {
+    let (_stream, stream_handle) = OutputStream::try_default()?;
+    let sink = Sink::try_new(&stream_handle)?;
+
+    let tone = SineWave::new(ANNOUNCEMENT_TONE_HZ)
+        .take_duration(Duration::from_millis(ANNOUNCEMENT_TONE_MS))
+        .fade_in(Duration::from_millis(10))
+        .amplify(ANNOUNCEMENT_TONE_VOLUME);
+    sink.append(tone);
+    sink.sleep_until_end();
+
+    Ok(())
+}
+
+/// Сигнал об упоминании: два коротких восходящих тона
+pub fn play_mention_tone() -> Result<(), Box<dyn std::error::Error>> {
+    let (_stream, stream_handle) = OutputStream::try_default()?;
+    let sink = Sink::try_new(&stream_handle)?;
+
+    for hz in MENTION_TONES_HZ {
+        let tone = SineWave::new(hz)
+            .take_duration(Duration::from_millis(MENTION_TONE_MS))
+            .fade_in(Duration::from_millis(10))
+            .amplify(ANNOUNCEMENT_TONE_VOLUME);
+        sink.append(tone);
+    }
+    sink.sleep_until_end();
+
+    Ok(())
+}
+
+pub async fn try_connect_to_node(kaspa_rpc_client: Arc<KaspaRpcClient>, node_url: Option<String>) {
+    // Опции для подключения
+    let connect_timeout = APP_STATE.get_rpc_policy().unwrap_or_default().connect_timeout();
+    let options = ConnectOptions {
+        block_async_connect: true,
+        strategy: Fallback,
+        url: node_url.clone(),
+        connect_timeout: Some(connect_timeout),
+        ..Default::default()
+    };
+
+    // Начало RPC подключения
+    let started = std::time::Instant::now();
+    match kaspa_rpc_client.connect(Some(options)).await {
+        // Резолвер сообщает выбранную ноду только после подключения
+        Ok(_) => APP_STATE.set_active_node(kaspa_rpc_client.url()),
+        // Ошибка по истечении таймаута — нода не ответила, а не отказала
+        Err(e) if started.elapsed() >= connect_timeout => {
+            log::error!("Timed out after {} s while connecting to node '{:?}': {}", connect_timeout.as_secs(), node_url, e);
+            APP_STATE.record_rpc_timeout("connect", connect_timeout);
+        }
+        Err(e) => log::error!("Error while connecting to node '{:?}': {}", node_url, e),
+    }
+}
+
+pub fn parse_3bytes_to_u32(bytes: &[u8]) -> u32 {
+    ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | (bytes[2] as u32)
+}
+
+pub fn u32_to_3bytes(value: u32) -> [u8; 3] {
+    [((value >> 16) & 0xFF) as u8, ((value >> 8) & 0xFF) as u8, (value & 0xFF) as u8]
+}
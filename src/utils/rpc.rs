@@ -0,0 +1,54 @@
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::app_state::APP_STATE;
+use crate::constants::RPC_RETRY_DELAY_MS;
+
+/// Ошибка запроса к ноде: таймаут отделён от прочих, чтобы GUI и журнал показывали его отдельно
+#[derive(Debug)]
+pub enum RpcCallError {
+    Timeout { what: String, timeout: Duration },
+    Failed { what: String, error: String },
+}
+
+impl fmt::Display for RpcCallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcCallError::Timeout { what, timeout } => {
+                write!(f, "Node did not answer the {} request within {} s", what, timeout.as_secs())
+            }
+            RpcCallError::Failed { what, error } => write!(f, "The {} request failed: {}", what, error),
+        }
+    }
+}
+
+/// Выполняет запрос на чтение по настройкам `RpcPolicy`: каждая попытка ограничена таймаутом,
+/// неудачные повторяются до `request_retries` раз. Таймаут последней попытки попадает в уведомления.
+/// `what` — что запрашиваем, для журнала и уведомления («fee estimate»)
+pub async fn call_with_policy<T, E, F, Fut>(what: &str, mut call: F) -> Result<T, RpcCallError>
+where
+    E: fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let policy = APP_STATE.get_rpc_policy().unwrap_or_default();
+    let timeout = policy.request_timeout();
+    let mut attempt = 0;
+    loop {
+        let error = match tokio::time::timeout(timeout, call()).await {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(err)) => RpcCallError::Failed { what: what.to_string(), error: err.to_string() },
+            Err(_) => RpcCallError::Timeout { what: what.to_string(), timeout },
+        };
+        if attempt >= policy.request_retries {
+            if matches!(error, RpcCallError::Timeout { .. }) {
+                APP_STATE.record_rpc_timeout(what, timeout);
+            }
+            return Err(error);
+        }
+        attempt += 1;
+        log::warn!("{}, retrying ({}/{})", error, attempt, policy.request_retries);
+        tokio::time::sleep(Duration::from_millis(RPC_RETRY_DELAY_MS)).await;
+    }
+}
@@ -0,0 +1,27 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Читает кэш из JSON-файла. Отсутствующий файл — не ошибка, а пустой кэш.
+/// Для несекретных данных; секреты хранятся в зашифрованных настройках
+pub fn load_json<T: DeserializeOwned>(path: &str) -> Result<Option<T>, String> {
+    let path = Path::new(path);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = fs::read(path).map_err(|e| format!("Error reading file {:?}: {}", path, e))?;
+    serde_json::from_slice(&data).map(Some).map_err(|e| format!("Error parsing file {:?}: {}", path, e))
+}
+
+/// Сохраняет кэш в JSON через временный файл, чтобы падение не оставило файл записанным наполовину
+pub fn save_json<T: Serialize>(path: &str, value: &T) -> Result<(), String> {
+    let path = Path::new(path);
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|e| format!("Error creating directory {:?}: {}", dir, e))?;
+    }
+    let data = serde_json::to_vec(value).map_err(|e| format!("Error serializing {:?}: {}", path, e))?;
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, data).map_err(|e| format!("Error writing file {:?}: {}", tmp_path, e))?;
+    fs::rename(&tmp_path, path).map_err(|e| format!("Error replacing file {:?}: {}", path, e))
+}